@@ -3,16 +3,31 @@ from importlib.metadata import version
 from . import extra_types
 from ._internal import (
     Batch,
+    BatchResult,
+    BatchRetryPolicy,
     BatchType,
+    BatchWriter,
+    BoundStatement,
     Consistency,
     ExecutionProfile,
     InlineBatch,
+    LastQuery,
+    LazyValue,
+    Migrations,
+    PartialBoundStatement,
     PreparedQuery,
     Query,
     QueryResult,
+    RequestInfo,
+    RetryPolicy,
     Scylla,
     SerialConsistency,
+    SpeculativeExecution,
     SSLVerifyMode,
+    StartupRetryPolicy,
+    TimestampGenerator,
+    Writer,
+    last_query,
 )
 
 __version__ = version("scyllapy")
@@ -24,11 +39,26 @@ __all__ = [
     "Query",
     "SerialConsistency",
     "PreparedQuery",
+    "BoundStatement",
+    "PartialBoundStatement",
     "Batch",
+    "BatchResult",
+    "BatchRetryPolicy",
     "BatchType",
+    "BatchWriter",
     "QueryResult",
     "SSLVerifyMode",
     "extra_types",
     "InlineBatch",
     "ExecutionProfile",
+    "RetryPolicy",
+    "SpeculativeExecution",
+    "TimestampGenerator",
+    "RequestInfo",
+    "Migrations",
+    "LastQuery",
+    "LazyValue",
+    "Writer",
+    "StartupRetryPolicy",
+    "last_query",
 ]
@@ -1,15 +1,20 @@
+import contextvars
+import os
 from typing import (
     Any,
     Callable,
     Generic,
     Iterable,
+    Iterator,
     Literal,
     Optional,
+    TypedDict,
     TypeVar,
     overload,
 )
 
 from scyllapy._internal.load_balancing import LoadBalancingPolicy
+from scyllapy._internal.query_builder import Delete, Insert, Update
 
 _T = TypeVar("_T")
 _T2 = TypeVar("_T2")
@@ -26,6 +31,36 @@ class SSLVerifyMode:
     PEER: SSLVerifyMode
     FAIL_IF_NO_PEER_CERT: SSLVerifyMode
 
+class KeyspaceInfo(TypedDict):
+    name: str
+    strategy: str
+    replication: dict[str, str]
+    tables: list[str]
+    views: list[str]
+    user_types: list[str]
+
+class ColumnInfo(TypedDict):
+    name: str
+    type: str
+    kind: Literal["partition_key", "clustering", "static", "regular"]
+
+class TableInfo(TypedDict):
+    keyspace: str
+    name: str
+    partition_key: list[str]
+    clustering_key: list[str]
+    partitioner: str | None
+    columns: list[ColumnInfo]
+
+class FieldInfo(TypedDict):
+    name: str
+    type: str
+
+class UserTypeInfo(TypedDict):
+    keyspace: str
+    name: str
+    fields: list[FieldInfo]
+
 class Scylla:
     """
     Scylla class.
@@ -42,6 +77,7 @@ class Scylla:
         *,
         username: str | None = None,
         password: str | None = None,
+        credentials: Callable[[], tuple[str, str]] | None = None,
         keyspace: str | None = None,
         ssl_cert: str | None = None,
         ssl_key: str | None = None,
@@ -56,7 +92,25 @@ class Scylla:
         tcp_keepalive_interval: int | None = None,
         tcp_nodelay: bool | None = None,
         disallow_shard_aware_port: bool | None = None,
+        compression: Literal["lz4", "snappy"] | None = None,
+        startup_retry_policy: StartupRetryPolicy | None = None,
         default_execution_profile: ExecutionProfile | None = None,
+        default_read_profile: ExecutionProfile | None = None,
+        default_write_profile: ExecutionProfile | None = None,
+        query_context_in_errors: bool = False,
+        emit_server_warnings: bool = False,
+        tracer: Any | None = None,
+        collect_latency_histograms: bool = False,
+        collect_statement_stats: bool = False,
+        log_queries: bool = False,
+        log_sampling: int = 1,
+        track_last_query: bool = False,
+        lazy_collections: bool = False,
+        profiling: bool = False,
+        coalesce_reads: bool = False,
+        result_cache_size: int | None = None,
+        result_cache_max_bytes: int | None = None,
+        result_cache_ttl: float | None = None,
     ) -> None:
         """
         Configure cluster for later use.
@@ -65,6 +119,11 @@ class Scylla:
             ["192.168.1.1:9042", "my_keyspace.node:9042"]
         :param username: Plain text auth username.
         :param password: Plain text auth password.
+        :param credentials: Callable, which returns username and password.
+            It's called each time a connection is established,
+            so credentials can be rotated. It's called in a separate
+            thread, so it should be synchronous. Cannot be used with
+            `username` and `password`.
         :param ssl_cert: Certficiate string to use for connection.
             Should be PEM encoded x509 certificate string.
         :param ssl_key: Key string to use for connection.
@@ -89,19 +148,96 @@ class Scylla:
         :param tcp_nodelay: sets TCP nodelay flag.
         :param disallow_shard_aware_port: If true, prevents the driver from connecting
             to the shard-aware port, even if the node supports it.
+        :param compression: Compress CQL frames with `lz4` or `snappy`.
+            Frames aren't compressed by default.
+        :param startup_retry_policy: Retry `startup` with backoff,
+            while the cluster is not reachable. Only connection
+            errors are retried.
+        :param default_read_profile: Profile for SELECT statements
+            that don't have their own profile.
+        :param default_write_profile: Profile for INSERT, UPDATE, DELETE statements
+            and batches that don't have their own profile.
+        :param query_context_in_errors: Attach text of the query and
+            types of its parameters to database errors. Values of
+            parameters are never included.
+        :param emit_server_warnings: Emit warnings returned by the server
+            as python warnings of `ScyllaPyServerWarning` category.
+        :param tracer: OpenTelemetry tracer. If passed, spans are created
+            for all queries and batches.
+        :param collect_latency_histograms: Record latencies of requests,
+            which are returned by `latency_histograms`.
+        :param collect_statement_stats: Collect statistics of statements,
+            which are returned by `statement_stats`.
+        :param log_queries: Log finished queries and batches
+            to `scyllapy.queries` logger with structured fields.
+        :param log_sampling: Log only one of this number of successful
+            queries. Failed queries are always logged.
+        :param track_last_query: Store tracing id and statement hash
+            of the last query in `last_query` context variable.
+        :param lazy_collections: Return lists, sets, maps and UDTs
+            as `LazyValue`, which is decoded on first access.
+        :param profiling: Record timings of request processing stages,
+            which are returned by `profile_report`.
+        :param coalesce_reads: Send only one of identical SELECT statements
+            executed concurrently, other callers get the same result.
+            Statements with different keyspace, consistency,
+            serial consistency, timeout or page size aren't identical.
+        :param result_cache_size: Maximum number of cached results of
+            SELECT statements. Results aren't cached, if it's not set.
+        :param result_cache_max_bytes: Maximum total size of cached
+            results in bytes. Size of a result is the size of its response.
+        :param result_cache_ttl: For how many seconds results are cached,
+            if statement doesn't have its own TTL. If it's not set,
+            only statements with TTL set by `set_cache_ttl` are cached.
         """
     async def startup(self) -> None:
         """Initialize the custer."""
     async def shutdown(self) -> None:
         """Shutdown the cluster."""
     async def prepare(self, query: str | Query) -> PreparedQuery: ...
+    async def warmup(
+        self,
+        statements: Iterable[str | Query] | None = None,
+    ) -> list[PreparedQuery]:
+        """
+        Warm up connections to all nodes.
+
+        Metadata of the cluster is fetched and every node receives
+        a few cheap requests for each of its shards.
+        Passed statements are prepared at the same time
+        and returned in the same order.
+        """
+    async def prepare_many(
+        self,
+        queries: Iterable[str | Query],
+    ) -> list[PreparedQuery]: ...
+    async def execute_prepared_batch(
+        self,
+        prepared: PreparedQuery,
+        params: Iterable[Iterable[Any] | dict[str, Any]],
+        batch_type: BatchType = ...,
+        **request_params: Any,
+    ) -> BatchResult:
+        """
+        Execute the same prepared query with many sets of parameters.
+
+        All parameter sets are sent in a single batch.
+        """
+    async def get_replicas(
+        self,
+        query: PreparedQuery,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+    ) -> list[str]:
+        """Get addresses of nodes that own the partition."""
     @overload
     async def execute(  # type: ignore
         self,
-        query: str | Query | PreparedQuery,
+        query: str | Query | PreparedQuery | BoundStatement | PartialBoundStatement,
         params: Iterable[Any] | dict[str, Any] | None = None,
         *,
         paged: Literal[False] = False,
+        routing_token: int | None = None,
+        keyspace: str | None = None,
     ) -> QueryResult:
         """
         Execute a query.
@@ -122,20 +258,53 @@ class Scylla:
         :param as_class: DTO class to use for parsing rows
             (Can be pydantic model or dataclass).
         :param paged: Whether to use paging. Default if false.
+        :param routing_token: token of the partition,
+            query is sent to its replicas.
+        :param keyspace: keyspace, which qualifies the table
+            of the query. Prepared statements cannot be qualified.
         """
     @overload
     async def execute(
         self,
-        query: str | Query | PreparedQuery,
+        query: str | Query | PreparedQuery | BoundStatement | PartialBoundStatement,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+        *,
+        paged: Literal[True] = ...,
+        routing_token: int | None = None,
+        keyspace: str | None = None,
+    ) -> IterableQueryResult[dict[str, Any]]: ...
+    async def register_statement(self, name: str, query: str | Query) -> PreparedQuery:
+        """
+        Prepare a statement and register it by name.
+
+        Statement with the same name is replaced.
+        """
+    @overload
+    async def execute_named(  # type: ignore
+        self,
+        name: str,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+        *,
+        paged: Literal[False] = False,
+    ) -> QueryResult:
+        """Execute a statement registered with `register_statement`."""
+    @overload
+    async def execute_named(
+        self,
+        name: str,
         params: Iterable[Any] | dict[str, Any] | None = None,
         *,
         paged: Literal[True] = ...,
     ) -> IterableQueryResult[dict[str, Any]]: ...
+    def registered_statements(self) -> list[str]:
+        """Get sorted names of registered statements."""
     async def batch(
         self,
         batch: Batch | InlineBatch,
         params: Optional[Iterable[Iterable[Any] | dict[str, Any]]] = None,
-    ) -> QueryResult:
+        *,
+        keyspace: str | None = None,
+    ) -> BatchResult:
         """
         Execute a batch statement.
 
@@ -146,10 +315,635 @@ class Scylla:
 
         It may speed up you application.
         """
+    async def execute_batches(
+        self,
+        batches: Iterable[Batch | InlineBatch],
+        *,
+        concurrency: int | None = None,
+    ) -> list[BatchResult | Exception]:
+        """
+        Execute multiple batches concurrently.
+
+        At most `concurrency` batches are executed at the same time.
+        Results are returned in the same order as batches.
+        If a batch has failed, the exception is returned in its place.
+        Each batch is a separate request for listeners,
+        statistics, histograms and query logs.
+        """
+    async def execute_many(
+        self,
+        query: str | Query | PreparedQuery,
+        params: Iterable[Iterable[Any] | dict[str, Any]],
+        *,
+        concurrency: int | None = None,
+    ) -> list[QueryResult | Exception]:
+        """
+        Execute one query with many sets of parameters.
+
+        All parameters are parsed at once and statements
+        are sent concurrently, at most `concurrency` at the same time.
+        Results are returned in the same order as parameters.
+        If an execution has failed, the exception is returned in its place.
+        Each execution is a separate request for listeners,
+        statistics, histograms and query logs.
+        """
+    async def execute_lwt(
+        self,
+        query: str | Query | PreparedQuery,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+        *,
+        retries: int = 3,
+    ) -> QueryResult:
+        """
+        Execute a conditional statement.
+
+        Statements, which were rejected because coordinator
+        is overloaded or bootstrapping, are retried with backoff.
+        Timeouts leave the outcome unknown, so they're not retried.
+        Retries are attempts of one request for listeners,
+        statistics, histograms and query logs.
+        """
+    async def compare_and_set(
+        self,
+        table: str,
+        key: dict[str, Any],
+        expected: dict[str, Any],
+        new: dict[str, Any] | Callable[[dict[str, Any]], dict[str, Any]],
+        *,
+        retries: int = 3,
+        timeout_retries: int = 3,
+        serial_consistency: SerialConsistency | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> bool:
+        """
+        Update a row, if its columns have expected values.
+
+        If `new` is a callable, it's called with expected values.
+        When the update isn't applied, it's called again with
+        current values of the row, up to `retries` times.
+
+        After a timeout the row is read at serial consistency
+        to find out whether the update was applied,
+        up to `timeout_retries` times.
+        Each statement is a separate request for listeners,
+        statistics, histograms and query logs.
+
+        :return: whether the update was applied.
+        """
+    def batch_writer(
+        self,
+        batch_type: BatchType = ...,
+        *,
+        max_batch_size: int = 100,
+        flush_interval: float = 1.0,
+        buffer_size: int = 1000,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> BatchWriter:
+        """
+        Create a writer that groups statements into batches.
+
+        Batches are sent when they reach `max_batch_size` statements,
+        or when `flush_interval` seconds pass since the last flush.
+        `buffer_size` limits the number of statements waiting to be sent.
+        """
+    def writer(
+        self,
+        *,
+        writes_per_second: float | None = None,
+        concurrency: int = 64,
+        buffer_size: int = 1000,
+    ) -> Writer:
+        """
+        Create a writer that sends statements in background.
+
+        At most `concurrency` statements are executed at once and
+        at most `writes_per_second` statements are sent per second.
+        `buffer_size` limits the number of statements waiting in the queue.
+        Writes are reported to listeners and statistics
+        and mirrored to the shadow cluster like other requests.
+        """
     async def use_keyspace(self, keyspace: str) -> None:
         """Change current keyspace for all connections."""
     async def get_keyspace(self) -> str | None:
         """Get current keyspace."""
+    async def get_ttl(
+        self,
+        table: str,
+        key: dict[str, Any],
+        columns: list[str],
+    ) -> dict[str, int | None] | None:
+        """
+        Get remaining TTL of columns in seconds.
+
+        Row is found by values of primary key columns.
+        If the row doesn't exist, None is returned.
+        Table and column names are case-sensitive.
+        """
+    async def get_writetime(
+        self,
+        table: str,
+        key: dict[str, Any],
+        columns: list[str],
+    ) -> dict[str, int | None] | None:
+        """
+        Get write time of columns in microseconds.
+
+        Row is found by values of primary key columns.
+        If the row doesn't exist, None is returned.
+        Table and column names are case-sensitive.
+        """
+    async def get_functions(self, keyspace: str | None = None) -> list[dict[str, Any]]:
+        """
+        Get user defined functions of the keyspace.
+
+        If keyspace isn't passed, keyspace of the session is used.
+        """
+    async def get_aggregates(
+        self,
+        keyspace: str | None = None,
+    ) -> list[dict[str, Any]]:
+        """
+        Get user defined aggregates of the keyspace.
+
+        If keyspace isn't passed, keyspace of the session is used.
+        """
+    async def get_keyspaces(self, *, refresh: bool = False) -> list[KeyspaceInfo]:
+        """
+        Get all keyspaces of the cluster.
+
+        Keyspaces are taken from the cluster metadata of the driver,
+        which is refreshed periodically. If `refresh` is True,
+        metadata is refreshed first, which reads the whole schema.
+        Each keyspace has `name`, `strategy`, `replication` options
+        and names of its `tables`, `views` and `user_types`.
+        """
+    async def get_tables(
+        self,
+        keyspace: str | None = None,
+        *,
+        refresh: bool = False,
+    ) -> list[TableInfo]:
+        """
+        Get tables of the keyspace.
+
+        If keyspace isn't passed, keyspace of the session is used.
+        If `refresh` is True, cluster metadata is refreshed first.
+        Each table has `keyspace`, `name`, `partition_key`,
+        `clustering_key`, `partitioner` and `columns`.
+        """
+    async def get_columns(
+        self,
+        keyspace: str | None,
+        table: str,
+        *,
+        refresh: bool = False,
+    ) -> list[ColumnInfo]:
+        """
+        Get columns of the table.
+
+        If keyspace is None, keyspace of the session is used.
+        If `refresh` is True, cluster metadata is refreshed first.
+        Each column has `name`, CQL `type` and `kind`, which is one of
+        `partition_key`, `clustering`, `static` and `regular`.
+        """
+    async def get_user_types(
+        self,
+        keyspace: str | None = None,
+        *,
+        refresh: bool = False,
+    ) -> list[UserTypeInfo]:
+        """
+        Get user defined types of the keyspace.
+
+        If keyspace isn't passed, keyspace of the session is used.
+        If `refresh` is True, cluster metadata is refreshed first.
+        Each type has `keyspace`, `name` and `fields` with their CQL types.
+        """
+    async def describe_table(self, table: str, *, refresh: bool = False) -> str:
+        """
+        Get CREATE TABLE statement of the table.
+
+        If `refresh` is True, cluster metadata is refreshed first.
+
+        Table can be prefixed with keyspace, otherwise
+        keyspace of the session is used. Names are parsed as in CQL,
+        so `'"MyKs"."Users"'` is a quoted keyspace and table.
+        """
+    async def describe_keyspace(
+        self,
+        keyspace: str | None = None,
+        *,
+        refresh: bool = False,
+    ) -> str:
+        """
+        Get statements, which create the keyspace.
+
+        If `refresh` is True, cluster metadata is refreshed first.
+
+        CREATE KEYSPACE statement is followed by statements
+        of user defined types and tables of the keyspace.
+        Each table is followed by its secondary indexes
+        and materialized views. If keyspace isn't passed, keyspace of the session is used.
+        """
+    def latency_histograms(self) -> dict[str, dict[str, Any]]:
+        """
+        Get latency histograms of requests by statement kind.
+
+        Keys are `SELECT`, `INSERT`, `UPDATE`, `DELETE`, `BATCH`
+        and `OTHER` for all other statements. Each histogram has
+        `count`, `total` and `max` latencies and `buckets`
+        as a list of upper bounds with numbers of requests.
+        All latencies are in milliseconds. It's empty if histograms
+        aren't enabled with `collect_latency_histograms`.
+        """
+    def reset_latency_histograms(self) -> None:
+        """Remove all recorded latencies."""
+    def latency_stats(self) -> list[dict[str, Any]] | None:
+        """
+        Get latencies of nodes measured by latency awareness.
+
+        Statistics are taken from the load balancing policy
+        of the default execution profile. Each dict has `host_id`,
+        `address`, `datacenter`, `average_latency` in milliseconds,
+        `measurements`, `slowdown` compared to the fastest node
+        and `penalized`, which is true if node is excluded
+        from query plans for being slow.
+        Returns `None` if policy doesn't have latency awareness.
+        """
+    def profile_report(self) -> dict[str, dict[str, Any]]:
+        """
+        Get timings of request processing stages.
+
+        Stages are `bind`, `network`, `decode` and `conversion`.
+        Timings have the same format as latency histograms.
+        It's empty if profiling isn't enabled.
+        """
+    def reset_profile_report(self) -> None:
+        """Remove all recorded timings of stages."""
+    def migrations(
+        self,
+        path: str | os.PathLike[str],
+        table: str = "schema_migrations",
+    ) -> Migrations:
+        """
+        Get runner of migrations from the directory.
+
+        Migration files are named with version and name,
+        for example `0001_create_users.cql`. CQL migrations
+        can be rolled back with `.down.cql` files, python migrations
+        have `up` and `down` async functions, which accept the session.
+
+        :param path: directory with migration files.
+        :param table: table with applied migrations.
+        """
+    async def copy_from(
+        self,
+        path: str | os.PathLike[str],
+        table: str,
+        *,
+        format: Literal["csv", "parquet"] = "csv",
+        delimiter: str = ",",
+        concurrency: int | None = None,
+    ) -> int:
+        """
+        Load rows from CSV or parquet file into the table.
+
+        Columns are matched by names from the CSV header
+        or parquet schema. Rows are written with prepared inserts,
+        which are executed concurrently.
+
+        :param path: path to the file.
+        :param table: table to load rows into.
+        :param format: format of the file.
+        :param delimiter: delimiter of CSV fields.
+        :param concurrency: maximum number of concurrent inserts, 64 by default.
+        :return: number of loaded rows.
+        """
+    async def copy_to(
+        self,
+        path: str | os.PathLike[str],
+        table_or_select: str,
+        *,
+        format: Literal["csv", "parquet"] = "csv",
+        delimiter: str = ",",
+        concurrency: int | None = None,
+    ) -> int:
+        """
+        Unload rows of the table or a select statement into CSV or parquet file.
+
+        Tables are split by token ranges, which are scanned in parallel,
+        so order of rows in the file is not defined.
+        Select statements are executed as is.
+
+        :param path: path to the file.
+        :param table_or_select: table, optionally with keyspace, or select statement.
+        :param format: format of the file.
+        :param delimiter: delimiter of CSV fields.
+        :param concurrency: maximum number of concurrently scanned ranges,
+            16 by default.
+        :return: number of unloaded rows.
+        """
+    async def copy_table(
+        self,
+        destination: Scylla,
+        table: str,
+        *,
+        token_ranges: list[tuple[int, int]] | None = None,
+        concurrency: int | None = None,
+    ) -> int:
+        """
+        Copy rows of the table into another cluster.
+
+        The table is split by token ranges, which are scanned in parallel.
+        Rows are written into the table with the same name in `destination`,
+        rows of the same partition are grouped into unlogged batches.
+        Write timestamps and TTLs of cells are kept. Non-frozen collections
+        get the latest timestamp of the row without TTL.
+        Tables with counters cannot be copied.
+
+        :param destination: session of the cluster to copy rows into.
+        :param table: table, optionally with keyspace.
+        :param token_ranges: ranges of tokens `(start, end]` to copy,
+            the whole ring is copied by default.
+        :param concurrency: maximum number of concurrently copied ranges,
+            16 by default.
+        :return: number of copied rows.
+        """
+    async def trace_timeline(self, trace_id: str) -> dict[str, Any]:
+        """
+        Get timeline of a traced request.
+
+        Tracing info is fetched from `system_traces` tables.
+        Timeline has `request`, `coordinator`, `started_at`, `duration`
+        in microseconds, `nodes` with events of each node sorted
+        by elapsed microseconds and all `events` in recorded order.
+        Each event has `node`, `activity`, `elapsed` and `thread`.
+
+        :param trace_id: trace id of the query result.
+        """
+    def statement_stats(self) -> list[dict[str, Any]]:
+        """
+        Get statistics of statements.
+
+        Statements are grouped by normalized text, where literals
+        are replaced with `?`. Each dict has `statement`, `calls`, `errors`,
+        `error_rate`, `total_latency` and `p50`, `p95`, `p99` latency quantiles
+        in milliseconds. Statements with the highest total latency are first.
+        It's empty if statistics aren't enabled with `collect_statement_stats`.
+        """
+    def reset_statement_stats(self) -> None:
+        """Remove all statement statistics."""
+    async def node_stats(self) -> list[dict[str, Any]]:
+        """
+        Get statistics of nodes in the cluster.
+
+        Each dict has `address`, `host_id`, `datacenter`, `rack`, `is_up`,
+        `configured_pool_size`, `in_flight`, `requests`, `errors`
+        and `average_latency` in milliseconds, which is `None` if node
+        has no finished requests. Pool size is calculated from pool
+        settings and number of shards, it's not a number of opened connections.
+        Retries and speculative executions are counted for nodes
+        that have received them.
+        """
+    async def connection_info(self) -> dict[str, Any]:
+        """
+        Get features of connections to the cluster.
+
+        The dict has `requested_compression`, `shard_aware_port` and `nodes`.
+        Protocol version isn't reported, because the driver doesn't expose
+        what was negotiated. Compression is the one passed to the session,
+        connections silently fall back to no compression if nodes don't support it.
+        Each node has `address`, `host_id`, `datacenter`, `rack`,
+        `is_up`, `shard_aware`, `shards` and `release_version`,
+        which is `None` if it cannot be requested.
+        """
+    def attach_shadow(
+        self,
+        shadow: Scylla,
+        *,
+        read_sampling: float = 0.0,
+        max_in_flight: int = 1024,
+    ) -> None:
+        """
+        Mirror requests to another cluster.
+
+        Inserts, updates, deletes and batches are mirrored,
+        selects are mirrored with probability of `read_sampling`.
+        Requests are mirrored after they succeed, with the same
+        consistency, profile and timestamp. Timestamps come from
+        the configured generator, with SERVER each cluster assigns its own.
+        Errors of mirrored requests are only logged and counted.
+        If `max_in_flight` mirrored requests are pending,
+        new ones are dropped and counted.
+        """
+    def detach_shadow(self) -> None:
+        """Stop mirroring requests."""
+    def shadow_stats(self) -> dict[str, Any] | None:
+        """
+        Get counters of mirrored requests.
+
+        The dict has `writes`, `reads`, `errors`, `dropped` and `last_error`.
+        If shadow cluster isn't attached, None is returned.
+        """
+    def coalesced_reads(self) -> int | None:
+        """
+        Get number of reads, which got results of identical reads.
+
+        If reads aren't coalesced, None is returned.
+        """
+    def set_cache_ttl(self, statement: str, ttl: float | None) -> None:
+        """
+        Set for how many seconds results of the statement are cached.
+
+        Zero TTL disables caching of the statement,
+        None makes it use the default TTL.
+        """
+    def invalidate_cache(self, statement: str | None = None) -> None:
+        """
+        Remove cached results of the statement.
+
+        If statement is not passed, all cached results are removed.
+        """
+    def cache_stats(self) -> dict[str, int | None] | None:
+        """
+        Get counters of the result cache.
+
+        The dict has `hits`, `misses`, `size`, `max_size`,
+        `bytes` and `max_bytes`.
+        If the cache is disabled, None is returned.
+        """
+    async def await_healthy(self, timeout: float, *, min_nodes_up: int = 1) -> None:
+        """
+        Wait until the cluster is ready to serve requests.
+
+        Cluster is healthy, when the session is started,
+        metadata can be fetched through the control connection
+        and at least `min_nodes_up` nodes are up.
+
+        :param timeout: maximum time to wait in seconds.
+        :param min_nodes_up: minimum number of nodes, which are up.
+        :raises ScyllaPyTimeoutError: if cluster isn't healthy in time.
+        """
+    def on_request_start(
+        self,
+        callback: Callable[[RequestInfo], Any],
+    ) -> Callable[[RequestInfo], Any]:
+        """
+        Register a callback, which is called before each request.
+
+        The callback is returned, so this method can be used as a decorator.
+        """
+    def on_request_end(
+        self,
+        callback: Callable[[RequestInfo, float], Any],
+    ) -> Callable[[RequestInfo, float], Any]:
+        """
+        Register a callback, which is called after each successful request.
+
+        Callback receives duration of the request in milliseconds.
+        """
+    def on_request_error(
+        self,
+        callback: Callable[[RequestInfo, Exception], Any],
+    ) -> Callable[[RequestInfo, Exception], Any]:
+        """
+        Register a callback, which is called after each failed request.
+
+        Callback receives the exception, which is raised.
+        """
+
+class RetryPolicy:
+    """
+    Retry policy for execution profiles.
+
+    DEFAULT retries only when it's safe, FALLTHROUGH never retries
+    and DOWNGRADING_CONSISTENCY retries with lower consistency
+    if not enough replicas are alive.
+    """
+
+    DEFAULT: RetryPolicy
+    FALLTHROUGH: RetryPolicy
+    DOWNGRADING_CONSISTENCY: RetryPolicy
+
+class SpeculativeExecution:
+    """
+    Policy of speculative execution.
+
+    Speculative executions send the same request to other nodes,
+    if the first one is slow. Only idempotent statements
+    are executed speculatively.
+    """
+
+    @classmethod
+    def simple(
+        cls,
+        *,
+        max_retry_count: int,
+        retry_interval: int,
+    ) -> SpeculativeExecution:
+        """
+        Send speculative executions with constant delay.
+
+        :param retry_interval: delay between executions in milliseconds.
+        """
+    @classmethod
+    def percentile(
+        cls,
+        *,
+        max_retry_count: int,
+        percentile: float,
+    ) -> SpeculativeExecution:
+        """Send speculative execution when request is slower than percentile."""
+
+class TimestampGenerator:
+    """
+    Generator of timestamps for queries.
+
+    With SERVER timestamps are assigned by the database.
+    With MONOTONIC timestamps are generated on the client
+    and every next timestamp is greater than the previous one.
+    """
+
+    SERVER: TimestampGenerator
+    MONOTONIC: TimestampGenerator
+
+class Migrations:
+    """
+    Runner of schema migrations.
+
+    Applied migrations are recorded in a table,
+    so each migration is applied only once.
+    Schema agreement is awaited after each statement.
+    """
+
+    async def apply(
+        self,
+        target: int | None = None,
+        *,
+        resume: bool = False,
+    ) -> list[int]:
+        """
+        Apply migrations, which weren't applied yet.
+
+        Each migration is claimed before it's started, so concurrent
+        runners don't apply it twice. Progress of CQL migrations
+        is recorded after each statement.
+
+        :param target: apply migrations only up to this version.
+        :param resume: continue migrations, which were started,
+            but not finished. CQL migrations are continued from the first
+            statement, which isn't applied, python migrations are run again.
+        :return: versions of applied migrations.
+        """
+    async def rollback(self, steps: int = 1) -> list[int]:
+        """
+        Roll back the latest applied migrations.
+
+        :param steps: number of migrations to roll back.
+        :return: versions of rolled back migrations.
+        """
+    async def status(self) -> list[dict[str, Any]]:
+        """
+        Get status of migrations.
+
+        Each dict has `version`, `name`, `applied`, `applied_at`
+        and `applied_statements`, which is a number of applied statements
+        of CQL migration or `None` if migration wasn't started.
+        """
+
+class RequestInfo:
+    """
+    Information about a request, passed to request listeners.
+
+    Kind is one of `SELECT`, `INSERT`, `UPDATE`, `DELETE`, `BATCH`
+    and `OTHER` for all other statements, like in latency histograms.
+    Coordinator is the address of the node, which has received
+    the last attempt. It's not known before the request is sent.
+    """
+
+    kind: str
+    statement: str
+    coordinator: str | None
+
+class LastQuery:
+    """
+    Tracing id and statement hash of the last finished query.
+
+    Tracing id is set only for traced queries.
+    """
+
+    trace_id: str | None
+    statement_hash: str | None
+
+    def __init__(self) -> None: ...
+
+last_query: contextvars.ContextVar[LastQuery | None]
 
 class ExecutionProfile:
     def __init__(
@@ -159,10 +953,27 @@ class ExecutionProfile:
         serial_consistency: SerialConsistency | None = None,
         request_timeout: int | None = None,
         load_balancing_policy: LoadBalancingPolicy | None = None,
+        retry_policy: RetryPolicy | None = None,
+        speculative_execution: SpeculativeExecution | None = None,
+        timestamp_generator: TimestampGenerator = ...,
     ) -> None: ...
+    def derive(self, **overrides: Any) -> ExecutionProfile:
+        """
+        Create a copy of the profile with some settings changed.
+
+        Overrides have the same names as parameters of the constructor.
+        Passing `None` for optional settings resets them.
+        """
+    def with_consistency(self, consistency: Consistency) -> ExecutionProfile: ...
+    def with_request_timeout(self, request_timeout: int | None) -> ExecutionProfile: ...
+    def with_load_balancing(
+        self,
+        load_balancing_policy: LoadBalancingPolicy,
+    ) -> ExecutionProfile: ...
 
 class QueryResult:
     trace_id: str | None
+    columns: list[str]
 
     @overload
     def all(self, as_class: Literal[None] = None) -> list[dict[str, Any]]: ...
@@ -176,6 +987,36 @@ class QueryResult:
     def scalar(self) -> Any | None: ...
     def __len__(self) -> int: ...
 
+class BatchResult(QueryResult):
+    """
+    Result of a batch.
+
+    Besides rows, it gives access to results
+    of conditional statements.
+    """
+
+    applied: bool
+    conditions: list[dict[str, Any]]
+
+class LazyValue:
+    """
+    List, set, map or UDT decoded on first access.
+
+    It supports iteration, indexing, `in` and comparison,
+    decoding the value the first time it's used.
+    Other attributes, like `keys` or `items` of maps,
+    are taken from the decoded value. It can be passed
+    as a parameter of queries.
+    """
+
+    def load(self) -> Any:
+        """Decode the value."""
+    def __getattr__(self, name: str) -> Any: ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Iterator[Any]: ...
+    def __getitem__(self, key: Any) -> Any: ...
+    def __contains__(self, item: Any) -> bool: ...
+
 class IterableQueryResult(Generic[_T]):
     def as_cls(
         self: IterableQueryResult[_T],
@@ -212,6 +1053,7 @@ class Query:
         is_idempotent: bool | None = None,
         tracing: bool | None = None,
         profile: ExecutionProfile | None = None,
+        routing_token: int | None = None,
     ) -> None: ...
     def with_consistency(self, consistency: Consistency | None) -> Query: ...
     def with_serial_consistency(
@@ -223,6 +1065,12 @@ class Query:
     def with_is_idempotent(self, is_idempotent: bool | None) -> Query: ...
     def with_tracing(self, tracing: bool | None) -> Query: ...
     def with_profile(self, profile: ExecutionProfile | None) -> Query: ...
+    def with_routing_token(self, routing_token: int | None) -> Query:
+        """
+        Route the query to replicas of the token.
+
+        The driver computes tokens only for prepared queries.
+        """
 
 class BatchType:
     """Possible BatchTypes."""
@@ -231,37 +1079,201 @@ class BatchType:
     LOGGED: BatchType
     UNLOGGED: BatchType
 
+class StartupRetryPolicy:
+    """
+    Policy of connecting to the cluster, which is not reachable yet.
+
+    It's used only by `startup`. Attempts, which failed because
+    of connection errors, are retried with exponential backoff.
+    Delays are in seconds. If `max_attempts` is None,
+    attempts never stop.
+    """
+
+    initial_delay: float
+    max_delay: float
+    max_attempts: int | None
+
+    def __init__(
+        self,
+        initial_delay: float = 0.5,
+        max_delay: float = 10.0,
+        max_attempts: int | None = 10,
+    ) -> None: ...
+
+class BatchRetryPolicy:
+    """
+    Retry policy for idempotent batches.
+
+    Batches are retried on timeouts with exponential backoff.
+    Delays are in seconds.
+    """
+
+    max_retries: int
+    base_delay: float
+    max_delay: float
+    jitter: bool
+
+    def __init__(
+        self,
+        max_retries: int = 3,
+        base_delay: float = 0.1,
+        max_delay: float = 1.0,
+        jitter: bool = True,
+    ) -> None: ...
+
+class BatchWriter:
+    """
+    Writer that groups statements into batches.
+
+    Errors of batches sent in background are raised
+    by the next call to `flush` or `close`.
+    """
+
+    async def add(
+        self,
+        query: Query | PreparedQuery | BoundStatement | str,
+        values: list[Any] | dict[str, Any] | None = None,
+    ) -> None:
+        """Add statement, waiting if the buffer is full."""
+    async def flush(self) -> None:
+        """Send all buffered statements."""
+    async def close(self) -> None:
+        """Send all buffered statements and stop the writer."""
+    async def __aenter__(self) -> BatchWriter: ...
+    async def __aexit__(self, *args: Any) -> None: ...
+
+class Writer:
+    """
+    Writer that sends statements in background with limited rate.
+
+    Errors of statements sent in background are raised
+    by the next call to `flush` or `close`.
+    """
+
+    async def add(
+        self,
+        query: Query | PreparedQuery | BoundStatement | str,
+        values: list[Any] | dict[str, Any] | None = None,
+    ) -> None:
+        """Add statement to the queue, waiting if the queue is full."""
+    async def flush(self) -> None:
+        """Wait until all queued statements are sent."""
+    async def close(self) -> None:
+        """Send all queued statements and stop the writer."""
+    async def __aenter__(self) -> Writer: ...
+    async def __aexit__(self, *args: Any) -> None: ...
+
 class Batch:
     """Class for batching queries together."""
 
+    @property
+    def statements(self) -> list[str]:
+        """Texts of added statements."""
     def __init__(
         self,
         batch_type: BatchType = ...,
+        *,
+        ttl: int | None = None,
+        retry_policy: BatchRetryPolicy | None = None,
+        max_statements: int | None = None,
+        max_size: int | None = None,
         consistency: Consistency | None = None,
         serial_consistency: SerialConsistency | None = None,
         request_timeout: int | None = None,
         timestamp: int | None = None,
         is_idempotent: bool | None = None,
         tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
     ) -> None: ...
-    def add_query(self, query: Query | PreparedQuery | str) -> None: ...
+    def add_query(
+        self,
+        query: Query | PreparedQuery | BoundStatement | str | Insert | Update | Delete,
+    ) -> None:
+        """
+        Add query to the batch.
+
+        Query builders are added with their values.
+        Parameters passed to `execute` are used only
+        for statements without values.
+
+        If batch has `ttl`, it's added to inserts and updates
+        without their own TTL. Prepared inserts and updates
+        must have their own TTL.
+        """
+    async def execute(
+        self,
+        scylla: Scylla,
+        params: Optional[Iterable[Iterable[Any] | dict[str, Any]]] = None,
+    ) -> BatchResult: ...
 
 class InlineBatch:
+    @property
+    def statements(self) -> list[str]:
+        """Texts of added statements."""
+    @property
+    def params(self) -> list[Any]:
+        """
+        Python values, which statements were added with.
+
+        Values of bound statements, query builders
+        and unpickled batches are `None`.
+        """
     def __init__(
         self,
         batch_type: BatchType = ...,
+        *,
+        ttl: int | None = None,
+        retry_policy: BatchRetryPolicy | None = None,
+        max_statements: int | None = None,
+        max_size: int | None = None,
         consistency: Consistency | None = None,
         serial_consistency: SerialConsistency | None = None,
         request_timeout: int | None = None,
         timestamp: int | None = None,
         is_idempotent: bool | None = None,
         tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
     ) -> None: ...
     def add_query(
         self,
-        query: Query | PreparedQuery | str,
-        values: list[Any] | None = None,
-    ) -> None: ...
+        query: Query | PreparedQuery | BoundStatement | str,
+        values: list[Any] | dict[str, Any] | None = None,
+    ) -> None:
+        """
+        Add query to the batch.
+
+        Values can be passed as a dict only for prepared queries.
+        If batch has `ttl`, it's added to inserts and updates
+        without their own TTL. Prepared inserts and updates
+        must have their own TTL.
+        """
+    @classmethod
+    def from_statements(
+        cls,
+        statements: Iterable[
+            tuple[
+                Query | PreparedQuery | BoundStatement | str,
+                list[Any] | dict[str, Any] | None,
+            ]
+        ],
+        batch_type: BatchType = ...,
+        *,
+        ttl: int | None = None,
+        retry_policy: BatchRetryPolicy | None = None,
+        max_statements: int | None = None,
+        max_size: int | None = None,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> InlineBatch:
+        """Create batch from pairs of statements and values."""
+    def extend(self, other: InlineBatch) -> None:
+        """Append statements and values of another batch."""
+    async def execute(self, scylla: Scylla) -> BatchResult: ...
 
 class Consistency:
     """Consistency for query."""
@@ -286,3 +1298,69 @@ class SerialConsistency:
 
 class PreparedQuery:
     """Class that represents prepared statement."""
+
+    @property
+    def query(self) -> str:
+        """Text of the prepared statement."""
+    @property
+    def id(self) -> bytes:
+        """Id of the statement assigned by the server."""
+    def with_consistency(self, consistency: Consistency) -> PreparedQuery: ...
+    def with_serial_consistency(
+        self,
+        serial_consistency: SerialConsistency | None,
+    ) -> PreparedQuery: ...
+    def with_request_timeout(self, request_timeout: int | None) -> PreparedQuery: ...
+    def with_timestamp(self, timestamp: int | None) -> PreparedQuery: ...
+    def with_tracing(self, tracing: bool) -> PreparedQuery: ...
+    def with_profile(self, profile: ExecutionProfile | None) -> PreparedQuery: ...
+    def set_is_idempotent(self, is_idempotent: bool) -> None: ...
+    def set_page_size(self, page_size: int | None) -> None:
+        """Set page size. If `None` is passed, paging is disabled."""
+    def compute_token(
+        self,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+    ) -> int | None:
+        """
+        Calculate token of the partition key.
+
+        Returns `None` if partition key is not bound by the query.
+        """
+    def bind(
+        self,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+    ) -> BoundStatement:
+        """
+        Bind parameters to the query.
+
+        Parameters are serialized once, so bound statement
+        can be executed many times without parsing them again.
+        """
+    def bind_partial(self, **kwargs: Any) -> PartialBoundStatement:
+        """
+        Bind some of parameters to the query.
+
+        Remaining parameters are passed on execution,
+        either as a dict or as a list in the order they appear in the query.
+        """
+    @property
+    def is_idempotent(self) -> bool: ...
+    @property
+    def page_size(self) -> int | None: ...
+
+class BoundStatement:
+    """Prepared query with bound parameters."""
+
+    @property
+    def query(self) -> PreparedQuery: ...
+
+class PartialBoundStatement:
+    """Prepared query with some of parameters bound."""
+
+    @property
+    def query(self) -> PreparedQuery: ...
+    def bind(
+        self,
+        params: Iterable[Any] | dict[str, Any] | None = None,
+    ) -> BoundStatement:
+        """Bind remaining parameters."""
@@ -1,3 +1,36 @@
+import asyncio
+
+from scyllapy._internal import Consistency
+
+class ErrorCode:
+    """
+    Error codes of CQL protocol.
+
+    RATE_LIMIT_REACHED is a Scylla extension,
+    OTHER is used for unknown codes.
+    """
+
+    SERVER_ERROR: ErrorCode
+    PROTOCOL_ERROR: ErrorCode
+    AUTHENTICATION_ERROR: ErrorCode
+    UNAVAILABLE: ErrorCode
+    OVERLOADED: ErrorCode
+    IS_BOOTSTRAPPING: ErrorCode
+    TRUNCATE_ERROR: ErrorCode
+    WRITE_TIMEOUT: ErrorCode
+    READ_TIMEOUT: ErrorCode
+    READ_FAILURE: ErrorCode
+    FUNCTION_FAILURE: ErrorCode
+    WRITE_FAILURE: ErrorCode
+    SYNTAX_ERROR: ErrorCode
+    UNAUTHORIZED: ErrorCode
+    INVALID: ErrorCode
+    CONFIG_ERROR: ErrorCode
+    ALREADY_EXISTS: ErrorCode
+    UNPREPARED: ErrorCode
+    RATE_LIMIT_REACHED: ErrorCode
+    OTHER: ErrorCode
+
 class ScyllaPyBaseError(Exception):
     """Base scyllapy exception."""
 
@@ -16,8 +49,33 @@ class ScyllaPyDBError(ScyllaPyBaseError):
 
     This exception can be thrown when
     the database returns an error.
+
+    Errors returned by the database have `code`.
+    Timeouts and unavailability errors have details
+    in attributes. Attributes that don't apply are None.
+
+    If session has `query_context_in_errors` enabled,
+    `query` and `params` describe the failed query.
+
+    Errors of executed queries have number of `attempts`,
+    including retries and speculative executions,
+    and `attempt_errors` with errors of failed attempts.
     """
 
+    code: ErrorCode | None
+    query: str | None
+    params: str | None
+    attempts: int | None
+    attempt_errors: list[str] | None
+
+    consistency: Consistency | None
+    required: int | None
+    alive: int | None
+    received: int | None
+    data_present: bool | None
+    write_type: str | None
+    failures: int | None
+
 class ScyllaPySessionError(ScyllaPyDBError):
     """
     Error related to database session.
@@ -27,6 +85,25 @@ class ScyllaPySessionError(ScyllaPyDBError):
     or if it was closed by some reason.
     """
 
+class ScyllaPySyntaxError(ScyllaPyDBError):
+    """
+    Error in the statement.
+
+    This exception is thrown when the database
+    rejects the statement as syntactically incorrect
+    or invalid, for example if the table doesn't exist.
+    """
+
+class ScyllaPyTimeoutError(ScyllaPyDBError, asyncio.TimeoutError):
+    """
+    Request to the database has timed out.
+
+    It's thrown for both client-side request timeouts
+    and read or write timeouts reported by the server.
+    It's also a subclass of `asyncio.TimeoutError`,
+    so it can be handled as any other timeout.
+    """
+
 class ScyllaPyMappingError(ScyllaPyBaseError):
     """
     Exception that occurs during mapping results back to python.
@@ -47,3 +124,19 @@ class ScyllaPyQueryBuiderError(ScyllaPyBaseError):
     such situations we introduced another type of error,
     that is thrown before query is executed.
     """
+
+class ScyllaPyMigrationError(ScyllaPyBaseError):
+    """
+    Error that is thrown if migrations cannot be loaded or run.
+
+    For example, if migration files have the same version,
+    or migration doesn't have statements to roll it back.
+    """
+
+class ScyllaPyServerWarning(UserWarning):
+    """
+    Warning returned by the database.
+
+    Warnings are emitted only if session
+    has `emit_server_warnings` enabled.
+    """
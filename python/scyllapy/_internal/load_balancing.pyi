@@ -1,3 +1,5 @@
+from typing import Any, Protocol
+
 class LatencyAwareness:
     def __init__(
         self,
@@ -23,7 +25,37 @@ class LatencyAwareness:
         over time.
     """
 
-class LoadBalancingPolicy:
+class ReplicaOrdering:
+    """
+    Order of replicas in token-aware routing.
+
+    SHUFFLED tries replicas in random order,
+    PRIMARY_FIRST tries them in the order of the token ring,
+    RACK_LOCAL_FIRST tries replicas from `prefer_rack` first.
+    """
+
+    SHUFFLED: ReplicaOrdering
+    PRIMARY_FIRST: ReplicaOrdering
+    RACK_LOCAL_FIRST: ReplicaOrdering
+
+class CustomPolicy(Protocol):
+    """
+    Load balancing policy implemented in python.
+
+    Query info contains `consistency`, `keyspace`, `token` and `is_confirmed_lwt`.
+    Every node is described by `host_id`, `address`, `datacenter`, `rack`
+    and `is_down` keys. Policy returns indices of nodes in the list.
+    It can also define `fallback` method, which returns list of indices
+    of nodes to try if the picked one fails.
+    """
+
+    def pick(
+        self,
+        query: dict[str, Any],
+        nodes: list[dict[str, Any]],
+    ) -> int | None: ...
+
+
     """
     Load balancing policy.
 
@@ -40,6 +72,10 @@ class LoadBalancingPolicy:
         prefer_datacenter: str | None = None,
         permit_dc_failover: bool | None = None,
         shuffling_replicas: bool | None = None,
+        latency_awareness: LatencyAwareness | None = None,
+        avoid_datacenters: list[str] | None = None,
+        avoid_racks: list[str] | None = None,
+        replica_ordering: ReplicaOrdering | None = None,
     ) -> LoadBalancingPolicy: ...
     """
     Construct load balancing policy.
@@ -51,6 +87,42 @@ class LoadBalancingPolicy:
     :param prefer_datacenter: Name of the datacenter to prefer.
     :param permit_dc_failover: Whether to allow datacenter failover.
     :param shuffling_replicas: Whether to shuffle replicas.
+    :param latency_awareness: Latency awareness settings.
+    :param avoid_datacenters: Names of datacenters, which nodes are never used.
+    :param avoid_racks: Names of racks, which nodes are never used.
+    :param replica_ordering: Order of replicas, overrides `shuffling_replicas`.
+        RACK_LOCAL_FIRST requires `prefer_rack` to be set.
+    """
+
+    @classmethod
+    def round_robin(cls) -> LoadBalancingPolicy: ...
+    """
+    Construct round-robin load balancing policy.
+
+    This policy is not token aware and sends queries to all nodes in turn.
+    It's useful when nodes are behind a proxy or in test environments.
+    """
+
+    @classmethod
+    def custom(cls, policy: CustomPolicy) -> LoadBalancingPolicy: ...
+    """
+    Construct load balancing policy implemented in python.
+
+    Policy is called for every query, so it should be fast.
+    If it raises an error, all nodes are tried in their order.
+    """
+
+    def latency_stats(self) -> list[dict[str, Any]] | None: ...
+    """
+    Latencies of nodes measured by latency awareness.
+
+    Every item has `host_id`, `address`, `datacenter`,
+    `average_latency` in milliseconds, number of `measurements`,
+    `slowdown` compared to the fastest node and `penalized`.
+    Nodes with average latency higher than the fastest one
+    by exclusion threshold are penalized by latency awareness.
+
+    Returns None if policy was built without latency awareness.
     """
 
     async def with_latency_awareness(
@@ -1,6 +1,7 @@
-from typing import Any, Literal, overload
+from typing import Any, Callable, Literal, TypeVar, overload
 
 from scyllapy._internal import (
+    BatchType,
     Consistency,
     ExecutionProfile,
     InlineBatch,
@@ -10,12 +11,26 @@ from scyllapy._internal import (
     SerialConsistency,
 )
 
+_T = TypeVar("_T")
+
 class Select:
-    def __init__(self, table: str) -> None: ...
+    table: str
+    keyspace: str | None
+    selected_columns: list[str] | None
+    where_clauses: list[str]
+    bound_values_count: int
+
+    def __init__(self, table: str, keyspace: str | None = None) -> None: ...
     def only(self, *columns: str) -> Select: ...
     def where(self, clause: str, params: list[Any] | None = None) -> Select: ...
     def group_by(self, group: str) -> Select: ...
     def order_by(self, order: str, desc: bool = False) -> Select: ...
+    def order_by_ann(
+        self,
+        column: str,
+        query_vector: list[float],
+        limit: int,
+    ) -> Select: ...
     def per_partition_limit(self, per_partition_limit: int) -> Select: ...
     def limit(self, limit: int) -> Select: ...
     def allow_filtering(self) -> Select: ...
@@ -38,6 +53,13 @@ class Select:
         scylla: Scylla,
         *,
         paged: Literal[False] = False,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
     ) -> QueryResult: ...
     @overload
     async def execute(
@@ -45,17 +67,83 @@ class Select:
         scylla: Scylla,
         *,
         paged: Literal[True] = True,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
     ) -> IterableQueryResult[dict[str, Any]]: ...
     @overload
-    async def execute(self, scylla: Scylla, *, paged: bool = False) -> Any: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        paged: bool = False,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> Any: ...
+    @overload
+    def execute_stream(
+        self,
+        scylla: Scylla,
+        *,
+        as_class: Literal[None] = None,
+        page_size: int | None = None,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> IterableQueryResult[dict[str, Any]]: ...
+    @overload
+    def execute_stream(
+        self,
+        scylla: Scylla,
+        *,
+        as_class: Callable[..., _T],
+        page_size: int | None = None,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> IterableQueryResult[_T]: ...
 
 class Insert:
-    def __init__(self, table: str) -> None: ...
+    table: str
+    keyspace: str | None
+    column_names: list[str]
+    bound_values_count: int
+
+    def __init__(self, table: str, keyspace: str | None = None) -> None: ...
     def if_not_exists(self) -> Insert: ...
     def set(self, name: str, value: Any) -> Insert: ...
+    def from_model(self, model: Any) -> Insert: ...
+    def columns(self, *names: str) -> Insert: ...
+    def values(self, *values: Any) -> Insert: ...
+    def many(
+        self,
+        rows: list[dict[str, Any]],
+        *,
+        chunk_size: int = 100,
+        batch_type: BatchType = ...,
+    ) -> list[InlineBatch]: ...
     def timeout(self, timeout: int | str) -> Insert: ...
     def timestamp(self, timestamp: int) -> Insert: ...
     def ttl(self, ttl: int) -> Insert: ...
+    def using_ttl_marker(self, ttl: int | None = None) -> Insert: ...
+    def using_timestamp_marker(self, timestamp: int | None = None) -> Insert: ...
     def request_params(
         self,
         consistency: Consistency | None = None,
@@ -67,10 +155,27 @@ class Insert:
         profile: ExecutionProfile | None = None,
     ) -> Insert: ...
     def add_to_batch(self, batch: InlineBatch) -> None: ...
-    async def execute(self, scylla: Scylla) -> QueryResult: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
 
 class Delete:
-    def __init__(self, table: str) -> None: ...
+    table: str
+    keyspace: str | None
+    selected_columns: list[str] | None
+    where_clauses: list[str]
+    bound_values_count: int
+
+    def __init__(self, table: str, keyspace: str | None = None) -> None: ...
     def cols(self, *cols: str) -> Delete: ...
     def where(self, clause: str, values: list[Any] | None = None) -> Delete: ...
     def timeout(self, timeout: int | str) -> Delete: ...
@@ -88,10 +193,27 @@ class Delete:
         profile: ExecutionProfile | None = None,
     ) -> Delete: ...
     def add_to_batch(self, batch: InlineBatch) -> None: ...
-    async def execute(self, scylla: Scylla) -> QueryResult: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
 
 class Update:
-    def __init__(self, table: str) -> None: ...
+    table: str
+    keyspace: str | None
+    column_names: list[str]
+    where_clauses: list[str]
+    bound_values_count: int
+
+    def __init__(self, table: str, keyspace: str | None = None) -> None: ...
     def set(self, name: str, value: Any) -> Update: ...
     def inc(self, column: str, value: Any) -> Update: ...
     def dec(self, column: str, value: Any) -> Update: ...
@@ -99,6 +221,8 @@ class Update:
     def timeout(self, timeout: int | str) -> Update: ...
     def timestamp(self, timestamp: int) -> Update: ...
     def ttl(self, ttl: int) -> Update: ...
+    def using_ttl_marker(self, ttl: int | None = None) -> Update: ...
+    def using_timestamp_marker(self, timestamp: int | None = None) -> Update: ...
     def request_params(
         self,
         consistency: Consistency | None = None,
@@ -112,4 +236,292 @@ class Update:
     def if_exists(self) -> Update: ...
     def if_(self, clause: str, values: list[Any] | None = None) -> Update: ...
     def add_to_batch(self, batch: InlineBatch) -> None: ...
-    async def execute(self, scylla: Scylla) -> QueryResult: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class CreateType:
+    def __init__(self, name: str) -> None: ...
+    def if_not_exists(self) -> CreateType: ...
+    def field(self, name: str, cql_type: str) -> CreateType: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> CreateType: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class CreateTable:
+    def __init__(self, name: str) -> None: ...
+    def if_not_exists(self) -> CreateTable: ...
+    def column(self, name: str, cql_type: str) -> CreateTable: ...
+    def partition_key(self, *columns: str) -> CreateTable: ...
+    def clustering_key(self, column: str, desc: bool = False) -> CreateTable: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> CreateTable: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class CreateFunction:
+    def __init__(self, name: str) -> None: ...
+    def or_replace(self) -> CreateFunction: ...
+    def if_not_exists(self) -> CreateFunction: ...
+    def argument(self, name: str, cql_type: str) -> CreateFunction: ...
+    def called_on_null_input(self, called: bool = True) -> CreateFunction: ...
+    def returns(self, cql_type: str) -> CreateFunction: ...
+    def language(self, language: str) -> CreateFunction: ...
+    def body(self, body: str) -> CreateFunction: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> CreateFunction: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class CreateAggregate:
+    def __init__(self, name: str) -> None: ...
+    def or_replace(self) -> CreateAggregate: ...
+    def if_not_exists(self) -> CreateAggregate: ...
+    def argument(self, cql_type: str) -> CreateAggregate: ...
+    def state_function(self, name: str) -> CreateAggregate: ...
+    def state_type(self, cql_type: str) -> CreateAggregate: ...
+    def final_function(self, name: str) -> CreateAggregate: ...
+    def reduce_function(self, name: str) -> CreateAggregate: ...
+    def initial_condition(self, literal: str) -> CreateAggregate: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> CreateAggregate: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropTable:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropTable: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropTable: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropKeyspace:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropKeyspace: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropKeyspace: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropIndex:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropIndex: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropIndex: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropType:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropType: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropType: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropFunction:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropFunction: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropFunction: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
+
+class DropAggregate:
+    def __init__(self, name: str) -> None: ...
+    def if_exists(self) -> DropAggregate: ...
+    def request_params(
+        self,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> DropAggregate: ...
+    async def execute(
+        self,
+        scylla: Scylla,
+        *,
+        consistency: Consistency | None = None,
+        serial_consistency: SerialConsistency | None = None,
+        request_timeout: int | None = None,
+        timestamp: int | None = None,
+        is_idempotent: bool | None = None,
+        tracing: bool | None = None,
+        profile: ExecutionProfile | None = None,
+    ) -> QueryResult: ...
@@ -0,0 +1,352 @@
+"""
+DB-API 2.0 (PEP 249) interface.
+
+This module wraps asynchronous session into
+blocking connections and cursors, so tools
+that only understand DB-API can talk to Scylla.
+"""
+import asyncio
+import threading
+from collections import deque
+from typing import (
+    Any,
+    Awaitable,
+    Callable,
+    Deque,
+    Dict,
+    Iterable,
+    Iterator,
+    List,
+    Optional,
+    Sequence,
+    Tuple,
+    TypeVar,
+    Union,
+)
+
+from ._internal import QueryResult, Scylla
+from ._internal.exceptions import (
+    ScyllaPyBaseError,
+    ScyllaPyBindingError,
+    ScyllaPySessionError,
+    ScyllaPySyntaxError,
+    ScyllaPyTimeoutError,
+)
+
+apilevel = "2.0"
+threadsafety = 1
+paramstyle = "qmark"
+
+_T = TypeVar("_T")
+Parameters = Union[Sequence[Any], Dict[str, Any]]
+Description = Tuple[str, None, None, None, None, None, None]
+
+
+class Warning(Exception):  # noqa: N818
+    """Important warnings, like data truncations."""
+
+
+class Error(Exception):
+    """Base class for all DB-API errors."""
+
+
+class InterfaceError(Error):
+    """Errors related to the interface rather than the database."""
+
+
+class DatabaseError(Error):
+    """Errors related to the database."""
+
+
+class DataError(DatabaseError):
+    """Errors caused by problems with the processed data."""
+
+
+class OperationalError(DatabaseError):
+    """Errors related to the database operation, like timeouts."""
+
+
+class IntegrityError(DatabaseError):
+    """Relational integrity of the database is affected."""
+
+
+class InternalError(DatabaseError):
+    """Internal error of the database."""
+
+
+class ProgrammingError(DatabaseError):
+    """Errors in the query or its parameters."""
+
+
+class NotSupportedError(DatabaseError):
+    """Requested method or API is not supported."""
+
+
+def _convert_error(err: ScyllaPyBaseError) -> Error:
+    if isinstance(err, (ScyllaPySyntaxError, ScyllaPyBindingError)):
+        return ProgrammingError(str(err))
+    if isinstance(err, ScyllaPyTimeoutError):
+        return OperationalError(str(err))
+    if isinstance(err, ScyllaPySessionError):
+        return InterfaceError(str(err))
+    return DatabaseError(str(err))
+
+
+class _LoopThread:
+    """Event loop that runs coroutines in a background thread."""
+
+    def __init__(self) -> None:
+        self._loop = asyncio.new_event_loop()
+        self._thread = threading.Thread(
+            target=self._loop.run_forever,
+            name="scyllapy-dbapi",
+            daemon=True,
+        )
+        self._thread.start()
+
+    def run(
+        self,
+        func: Callable[..., Awaitable[_T]],
+        *args: Any,
+        **kwargs: Any,
+    ) -> _T:
+        """
+        Call async function inside of the loop and wait for its result.
+
+        Function is called in the loop's thread, because
+        awaitables of the session are bound to the running loop.
+        """
+
+        async def call() -> _T:
+            return await func(*args, **kwargs)
+
+        try:
+            return asyncio.run_coroutine_threadsafe(call(), self._loop).result()
+        except ScyllaPyBaseError as exc:
+            raise _convert_error(exc) from exc
+
+    def stop(self) -> None:
+        """Stop the loop and wait for the thread to finish."""
+        self._loop.call_soon_threadsafe(self._loop.stop)
+        self._thread.join()
+        self._loop.close()
+
+
+class Connection:
+    """
+    DB-API connection.
+
+    Each connection owns a session and an event loop
+    running in a separate thread.
+    """
+
+    def __init__(self, contact_points: List[str], **kwargs: Any) -> None:
+        self._runner = _LoopThread()
+        self._session = Scylla(contact_points, **kwargs)
+        self._closed = False
+        try:
+            self._runner.run(self._session.startup)
+        except BaseException:
+            self._runner.stop()
+            raise
+
+    @property
+    def closed(self) -> bool:
+        """Whether the connection is closed."""
+        return self._closed
+
+    def _check(self) -> None:
+        if self._closed:
+            raise InterfaceError("Connection is closed.")
+
+    def _run(
+        self,
+        func: Callable[..., Awaitable[_T]],
+        *args: Any,
+        **kwargs: Any,
+    ) -> _T:
+        self._check()
+        return self._runner.run(func, *args, **kwargs)
+
+    def cursor(self) -> "Cursor":
+        """Create a new cursor."""
+        self._check()
+        return Cursor(self)
+
+    def commit(self) -> None:
+        """
+        Commit the transaction.
+
+        Scylla doesn't have transactions, every
+        statement is applied right away, so this is a no-op.
+        """
+        self._check()
+
+    def rollback(self) -> None:
+        """Rollbacks are not supported by Scylla."""
+        raise NotSupportedError("Scylla doesn't support transactions.")
+
+    def close(self) -> None:
+        """Shutdown the session and stop the event loop."""
+        if self._closed:
+            return
+        try:
+            self._runner.run(self._session.shutdown)
+        finally:
+            self._closed = True
+            self._runner.stop()
+
+    def __enter__(self) -> "Connection":
+        return self
+
+    def __exit__(self, *_args: object) -> None:
+        self.close()
+
+
+class Cursor:
+    """DB-API cursor."""
+
+    def __init__(self, connection: Connection) -> None:
+        self.connection = connection
+        self.arraysize = 1
+        self.description: Optional[List[Description]] = None
+        self.rowcount = -1
+        self._rows: Deque[Tuple[Any, ...]] = deque()
+        self._closed = False
+
+    def _check(self) -> None:
+        if self._closed:
+            raise InterfaceError("Cursor is closed.")
+
+    def _reset(self) -> None:
+        self.description = None
+        self.rowcount = -1
+        self._rows.clear()
+
+    def _set_result(self, result: QueryResult) -> None:
+        columns = result.columns
+        if not columns:
+            return
+        self.description = [
+            (name, None, None, None, None, None, None) for name in columns
+        ]
+        self._rows.extend(tuple(row.values()) for row in result.all())
+        self.rowcount = len(self._rows)
+
+    def execute(
+        self,
+        operation: str,
+        parameters: Optional[Parameters] = None,
+    ) -> "Cursor":
+        """
+        Execute a query.
+
+        Parameters are bound using `?` placeholders or,
+        if a dict is passed, using named placeholders.
+        """
+        self._check()
+        self._reset()
+        result = self.connection._run(  # noqa: SLF001
+            self.connection._session.execute,  # noqa: SLF001
+            operation,
+            parameters,
+        )
+        self._set_result(result)
+        return self
+
+    def executemany(
+        self,
+        operation: str,
+        seq_of_parameters: Iterable[Parameters],
+    ) -> "Cursor":
+        """
+        Execute a query once per each set of parameters.
+
+        Query is prepared once and then executed
+        concurrently for every set of parameters.
+        """
+        self._check()
+        self._reset()
+        session = self.connection._session  # noqa: SLF001
+
+        async def execute_many() -> List[Union[QueryResult, Exception]]:
+            prepared = await session.prepare(operation)
+            return await session.execute_many(prepared, list(seq_of_parameters))
+
+        results = self.connection._run(execute_many)  # noqa: SLF001
+        for result in results:
+            if isinstance(result, ScyllaPyBaseError):
+                raise _convert_error(result) from result
+            if isinstance(result, Exception):
+                raise DatabaseError(str(result)) from result
+        return self
+
+    def fetchone(self) -> Optional[Tuple[Any, ...]]:
+        """Fetch next row or None if there are no more rows."""
+        self._check()
+        if not self._rows:
+            return None
+        return self._rows.popleft()
+
+    def fetchmany(self, size: Optional[int] = None) -> List[Tuple[Any, ...]]:
+        """Fetch up to `size` rows, by default `arraysize` rows are fetched."""
+        self._check()
+        if size is None:
+            size = self.arraysize
+        return [self._rows.popleft() for _ in range(min(size, len(self._rows)))]
+
+    def fetchall(self) -> List[Tuple[Any, ...]]:
+        """Fetch all remaining rows."""
+        self._check()
+        rows = list(self._rows)
+        self._rows.clear()
+        return rows
+
+    def setinputsizes(self, sizes: Any) -> None:
+        """Does nothing, required by the specification."""
+
+    def setoutputsize(self, size: Any, column: Optional[int] = None) -> None:
+        """Does nothing, required by the specification."""
+
+    def close(self) -> None:
+        """Close the cursor."""
+        self._closed = True
+        self._rows.clear()
+
+    def __iter__(self) -> Iterator[Tuple[Any, ...]]:
+        return iter(self.fetchone, None)
+
+    def __enter__(self) -> "Cursor":
+        return self
+
+    def __exit__(self, *_args: object) -> None:
+        self.close()
+
+
+def connect(contact_points: List[str], **kwargs: Any) -> Connection:
+    """
+    Connect to the cluster.
+
+    All keyword arguments are passed to the `Scylla` session.
+    """
+    return Connection(contact_points, **kwargs)
+
+
+__all__ = [
+    "apilevel",
+    "threadsafety",
+    "paramstyle",
+    "connect",
+    "Connection",
+    "Cursor",
+    "Warning",
+    "Error",
+    "InterfaceError",
+    "DatabaseError",
+    "DataError",
+    "OperationalError",
+    "IntegrityError",
+    "InternalError",
+    "ProgrammingError",
+    "NotSupportedError",
+]
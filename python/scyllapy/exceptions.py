@@ -1,17 +1,27 @@
 from ._internal.exceptions import (
+    ErrorCode,
     ScyllaPyBaseError,
     ScyllaPyBindingError,
     ScyllaPyDBError,
     ScyllaPyMappingError,
+    ScyllaPyMigrationError,
     ScyllaPyQueryBuiderError,
+    ScyllaPyServerWarning,
     ScyllaPySessionError,
+    ScyllaPySyntaxError,
+    ScyllaPyTimeoutError,
 )
 
 __all__ = (
+    "ErrorCode",
     "ScyllaPyBaseError",
     "ScyllaPyDBError",
     "ScyllaPySessionError",
+    "ScyllaPySyntaxError",
+    "ScyllaPyTimeoutError",
     "ScyllaPyMappingError",
     "ScyllaPyQueryBuiderError",
     "ScyllaPyBindingError",
+    "ScyllaPyMigrationError",
+    "ScyllaPyServerWarning",
 )
@@ -1,3 +1,7 @@
-from ._internal.load_balancing import LatencyAwareness, LoadBalancingPolicy
+from ._internal.load_balancing import (
+    LatencyAwareness,
+    LoadBalancingPolicy,
+    ReplicaOrdering,
+)
 
-__all__ = ("LatencyAwareness", "LoadBalancingPolicy")
+__all__ = ("LatencyAwareness", "LoadBalancingPolicy", "ReplicaOrdering")
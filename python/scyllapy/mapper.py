@@ -0,0 +1,249 @@
+"""
+Table mapper.
+
+Models are declared as dataclasses, which inherit `Model`.
+`Mapper` binds model to a session and provides
+`save`, `get`, `filter` and `delete` methods.
+"""
+import dataclasses
+from typing import (
+    Any,
+    Callable,
+    ClassVar,
+    Dict,
+    Generic,
+    List,
+    Optional,
+    Sequence,
+    Tuple,
+    Type,
+    TypeVar,
+    Union,
+    get_args,
+    get_origin,
+    get_type_hints,
+)
+
+from ._internal import PreparedQuery, Scylla
+from ._internal.query_builder import Select
+from .extra_types import ScyllaPyUDT
+
+_M = TypeVar("_M", bound="Model")
+_Converter = Callable[[Any], Any]
+
+
+class Model:
+    """
+    Base class for table models.
+
+    Models must be dataclasses. Field names are used as column names.
+
+    ```python
+    @dataclass
+    class User(Model, table="users", primary_key=["id"]):
+        id: int
+        name: str
+    ```
+    """
+
+    __table__: ClassVar[str]
+    __primary_key__: ClassVar[Tuple[str, ...]]
+
+    def __init_subclass__(
+        cls,
+        table: Optional[str] = None,
+        primary_key: Sequence[str] = (),
+        **kwargs: Any,
+    ) -> None:
+        super().__init_subclass__(**kwargs)
+        if table is not None:
+            cls.__table__ = table
+        if primary_key:
+            cls.__primary_key__ = tuple(primary_key)
+
+
+def _identity(value: Any) -> Any:
+    return value
+
+
+def _convert(converter: _Converter, value: Any) -> Any:
+    if value is None:
+        return None
+    return converter(value)
+
+
+def _udt_converter(udt: Type[Any]) -> _Converter:
+    converters = {
+        name: _type_converter(hint) for name, hint in get_type_hints(udt).items()
+    }
+
+    def convert(value: Any) -> Any:
+        if not isinstance(value, dict):
+            return value
+        return udt(
+            **{
+                name: _convert(converters.get(name, _identity), field_value)
+                for name, field_value in value.items()
+            },
+        )
+
+    return convert
+
+
+def _type_converter(hint: Any) -> _Converter:  # noqa: PLR0911
+    """
+    Build a function which converts returned value to the annotated type.
+
+    Only UDTs need conversion, because they are returned as dicts.
+    Values of other types are returned as is.
+    """
+    if isinstance(hint, type) and issubclass(hint, ScyllaPyUDT):
+        return _udt_converter(hint)
+    origin = get_origin(hint)
+    args = get_args(hint)
+    if origin is None or not args:
+        return _identity
+    if origin is Union:
+        not_none = [arg for arg in args if arg is not type(None)]
+        if len(not_none) != 1:
+            return _identity
+        return _type_converter(not_none[0])
+    if origin is dict:
+        key_conv = _type_converter(args[0])
+        val_conv = _type_converter(args[1])
+        if key_conv is _identity and val_conv is _identity:
+            return _identity
+        return lambda value: {key_conv(k): val_conv(v) for k, v in value.items()}
+    if origin in (list, set, frozenset, tuple):
+        if origin is tuple and (len(args) != 2 or args[1] is not Ellipsis):
+            item_convs = [_type_converter(arg) for arg in args]
+            return lambda value: tuple(
+                conv(item) for conv, item in zip(item_convs, value)
+            )
+        item_conv = _type_converter(args[0])
+        if item_conv is _identity:
+            return _identity
+        return lambda value: origin(item_conv(item) for item in value)
+    return _identity
+
+
+class Mapper(Generic[_M]):
+    """
+    Mapper of a model to its table.
+
+    Inserts, lookups by primary key and deletes
+    are executed as prepared statements, which are
+    prepared once per mapper. Filtering uses query builder.
+    """
+
+    def __init__(self, scylla: Scylla, model: Type[_M]) -> None:
+        if not dataclasses.is_dataclass(model):
+            raise TypeError(f"Model {model.__name__} is not a dataclass.")
+        if not getattr(model, "__table__", None):
+            raise TypeError(f"Model {model.__name__} has no table.")
+        if not getattr(model, "__primary_key__", None):
+            raise TypeError(f"Model {model.__name__} has no primary key.")
+        self.scylla = scylla
+        self.model = model
+        self.columns = [field.name for field in dataclasses.fields(model)]
+        unknown = set(model.__primary_key__) - set(self.columns)
+        if unknown:
+            raise TypeError(
+                f"Primary key columns {sorted(unknown)} "
+                f"are not fields of {model.__name__}.",
+            )
+        hints = get_type_hints(model)
+        self._converters = {
+            name: _type_converter(hints[name]) for name in self.columns
+        }
+        self._statements: Dict[str, PreparedQuery] = {}
+
+    async def _prepared(self, query: str) -> PreparedQuery:
+        prepared = self._statements.get(query)
+        if prepared is None:
+            prepared = await self.scylla.prepare(query)
+            self._statements[query] = prepared
+        return prepared
+
+    def _pk_clause(self) -> str:
+        return " AND ".join(f"{name} = ?" for name in self.model.__primary_key__)
+
+    def _load(self, row: Dict[str, Any]) -> _M:
+        return self.model(
+            **{
+                name: _convert(self._converters.get(name, _identity), value)
+                for name, value in row.items()
+            },
+        )
+
+    async def save(self, obj: _M) -> None:
+        """Insert or overwrite a row with values of the model."""
+        prepared = await self._prepared(
+            f"INSERT INTO {self.model.__table__} ({', '.join(self.columns)}) "
+            f"VALUES ({', '.join('?' for _ in self.columns)})",
+        )
+        await self.scylla.execute(
+            prepared,
+            [getattr(obj, name) for name in self.columns],
+        )
+
+    async def get(self, *pk: Any) -> Optional[_M]:
+        """
+        Find a model by its primary key.
+
+        Values of the primary key are passed in
+        the same order as they were declared in the model.
+        """
+        if len(pk) != len(self.model.__primary_key__):
+            raise ValueError(
+                f"Expected {len(self.model.__primary_key__)} "
+                f"primary key values, got {len(pk)}.",
+            )
+        prepared = await self._prepared(
+            f"SELECT {', '.join(self.columns)} FROM {self.model.__table__} "
+            f"WHERE {self._pk_clause()}",
+        )
+        row = (await self.scylla.execute(prepared, list(pk))).first()
+        if row is None:
+            return None
+        return self._load(row)
+
+    async def filter(
+        self,
+        *,
+        limit: Optional[int] = None,
+        allow_filtering: bool = False,
+        **conditions: Any,
+    ) -> List[_M]:
+        """
+        Find models with columns equal to given values.
+
+        :param limit: maximum number of returned models.
+        :param allow_filtering: add ALLOW FILTERING to the query.
+        :param conditions: column names and their values.
+        """
+        unknown = set(conditions) - set(self.columns)
+        if unknown:
+            raise ValueError(f"Unknown columns: {sorted(unknown)}.")
+        query = Select(self.model.__table__).only(*self.columns)
+        for name, value in conditions.items():
+            query = query.where(f"{name} = ?", [value])
+        if limit is not None:
+            query = query.limit(limit)
+        if allow_filtering:
+            query = query.allow_filtering()
+        result = await query.execute(self.scylla)
+        return [self._load(row) for row in result.all()]
+
+    async def delete(self, obj: _M) -> None:
+        """Delete a row of the model by its primary key."""
+        prepared = await self._prepared(
+            f"DELETE FROM {self.model.__table__} WHERE {self._pk_clause()}",
+        )
+        await self.scylla.execute(
+            prepared,
+            [getattr(obj, name) for name in self.model.__primary_key__],
+        )
+
+
+__all__ = ["Model", "Mapper"]
@@ -1,3 +1,33 @@
-from ._internal.query_builder import Delete, Insert, Select, Update
+from ._internal.query_builder import (
+    CreateAggregate,
+    CreateFunction,
+    CreateTable,
+    CreateType,
+    Delete,
+    DropAggregate,
+    DropFunction,
+    DropIndex,
+    DropKeyspace,
+    DropTable,
+    DropType,
+    Insert,
+    Select,
+    Update,
+)
 
-__all__ = ["Select", "Delete", "Insert", "Update"]
+__all__ = [
+    "Select",
+    "Delete",
+    "Insert",
+    "Update",
+    "CreateType",
+    "CreateTable",
+    "CreateFunction",
+    "CreateAggregate",
+    "DropTable",
+    "DropKeyspace",
+    "DropIndex",
+    "DropType",
+    "DropFunction",
+    "DropAggregate",
+]
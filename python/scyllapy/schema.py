@@ -0,0 +1,247 @@
+"""
+Schema generation from dataclasses.
+
+Types of columns are derived from annotations of dataclass fields.
+Keys are marked with `partition_key` and `clustering_key` fields.
+"""
+import dataclasses
+import datetime
+import decimal
+import ipaddress
+import uuid
+from typing import (
+    Any,
+    Dict,
+    List,
+    Optional,
+    Set,
+    Type,
+    Union,
+    get_args,
+    get_origin,
+    get_type_hints,
+)
+
+from ._internal import Scylla
+from ._internal.extra_types import BigInt, Counter, Double, SmallInt, TinyInt
+from ._internal.query_builder import CreateTable, CreateType
+from .extra_types import ScyllaPyUDT
+
+_SIMPLE_TYPES: Dict[Any, str] = {
+    str: "text",
+    int: "int",
+    bool: "boolean",
+    float: "float",
+    bytes: "blob",
+    BigInt: "bigint",
+    SmallInt: "smallint",
+    TinyInt: "tinyint",
+    Double: "double",
+    Counter: "counter",
+    uuid.UUID: "uuid",
+    datetime.datetime: "timestamp",
+    datetime.date: "date",
+    datetime.time: "time",
+    datetime.timedelta: "duration",
+    decimal.Decimal: "decimal",
+    ipaddress.IPv4Address: "inet",
+    ipaddress.IPv6Address: "inet",
+}
+
+_META_KEY = "scyllapy"
+
+
+def column(
+    *,
+    cql_type: Optional[str] = None,
+    partition_key: bool = False,
+    clustering_key: bool = False,
+    desc: bool = False,
+    **kwargs: Any,
+) -> Any:
+    """
+    Declare a dataclass field with schema options.
+
+    :param cql_type: type of the column, overrides the derived type.
+    :param partition_key: the column is a part of the partition key.
+    :param clustering_key: the column is a clustering column.
+    :param desc: clustering column is sorted in descending order.
+    :param kwargs: other arguments of `dataclasses.field`.
+    """
+    metadata = dict(kwargs.pop("metadata", None) or {})
+    metadata[_META_KEY] = {
+        "cql_type": cql_type,
+        "partition_key": partition_key,
+        "clustering_key": clustering_key,
+        "desc": desc,
+    }
+    return dataclasses.field(metadata=metadata, **kwargs)
+
+
+def partition_key(**kwargs: Any) -> Any:
+    """Declare a field, which is a part of the partition key."""
+    return column(partition_key=True, **kwargs)
+
+
+def clustering_key(*, desc: bool = False, **kwargs: Any) -> Any:
+    """Declare a clustering column."""
+    return column(clustering_key=True, desc=desc, **kwargs)
+
+
+def _options(field: "dataclasses.Field[Any]") -> Dict[str, Any]:
+    return field.metadata.get(_META_KEY, {})
+
+
+def type_name(udt: Type[Any]) -> str:
+    """
+    Get name of the UDT.
+
+    It's taken from `__type_name__` attribute,
+    or it's a lowercased name of the class.
+    """
+    return getattr(udt, "__type_name__", None) or udt.__name__.lower()
+
+
+def table_name(model: Type[Any]) -> str:
+    """
+    Get name of the table.
+
+    It's taken from `__table__` attribute,
+    or it's a lowercased name of the class.
+    """
+    return getattr(model, "__table__", None) or model.__name__.lower()
+
+
+def _is_udt(hint: Any) -> bool:
+    return isinstance(hint, type) and issubclass(hint, ScyllaPyUDT)
+
+
+def cql_type(hint: Any, *, frozen: bool = False) -> str:  # noqa: PLR0911
+    """
+    Convert python annotation to CQL type.
+
+    UDTs and collections inside of other collections are frozen.
+
+    :param hint: python type annotation.
+    :param frozen: whether collections and UDTs should be frozen.
+    """
+    if hint in _SIMPLE_TYPES:
+        return _SIMPLE_TYPES[hint]
+    if _is_udt(hint):
+        return f"frozen<{type_name(hint)}>"
+    origin = get_origin(hint)
+    args = get_args(hint)
+    if origin is Union:
+        not_none = [arg for arg in args if arg is not type(None)]
+        if len(not_none) == 1:
+            return cql_type(not_none[0], frozen=frozen)
+    elif origin in (list, set, frozenset) and args:
+        name = "list" if origin is list else "set"
+        cql = f"{name}<{cql_type(args[0], frozen=True)}>"
+        return f"frozen<{cql}>" if frozen else cql
+    elif origin is dict and args:
+        key, value = (cql_type(arg, frozen=True) for arg in args)
+        cql = f"map<{key}, {value}>"
+        return f"frozen<{cql}>" if frozen else cql
+    elif origin is tuple and args:
+        return f"tuple<{', '.join(cql_type(arg, frozen=True) for arg in args)}>"
+    raise TypeError(f"Cannot find CQL type for {hint}.")
+
+
+def _udts(hint: Any, found: List[Type[Any]]) -> None:
+    """Collect UDTs of the annotation, dependencies go first."""
+    if _is_udt(hint):
+        if hint in found:
+            return
+        for field_hint in get_type_hints(hint).values():
+            _udts(field_hint, found)
+        found.append(hint)
+        return
+    for arg in get_args(hint):
+        _udts(arg, found)
+
+
+def _column_type(field: "dataclasses.Field[Any]", hint: Any) -> str:
+    return _options(field).get("cql_type") or cql_type(hint)
+
+
+def create_types(model: Type[Any]) -> List[CreateType]:
+    """
+    Build `CREATE TYPE` statements for all UDTs of the model.
+
+    Types are ordered so that nested types are created first.
+    """
+    found: List[Type[Any]] = []
+    for hint in get_type_hints(model).values():
+        _udts(hint, found)
+    statements = []
+    for udt in found:
+        hints = get_type_hints(udt)
+        statement = CreateType(type_name(udt)).if_not_exists()
+        for field in dataclasses.fields(udt):
+            field_type = _column_type(field, hints[field.name])
+            statement = statement.field(field.name, field_type)
+        statements.append(statement)
+    return statements
+
+
+def create_table(model: Type[Any]) -> CreateTable:
+    """
+    Build `CREATE TABLE` statement for the model.
+
+    If no fields are marked as keys, but the model has
+    `__primary_key__`, its first column is the partition key
+    and the rest are clustering columns.
+    """
+    hints = get_type_hints(model)
+    fields = dataclasses.fields(model)
+    statement = CreateTable(table_name(model)).if_not_exists()
+    for field in fields:
+        column_type = _column_type(field, hints[field.name])
+        statement = statement.column(field.name, column_type)
+    partition = [
+        field.name for field in fields if _options(field).get("partition_key")
+    ]
+    clustering = [
+        (field.name, _options(field).get("desc", False))
+        for field in fields
+        if _options(field).get("clustering_key")
+    ]
+    if not partition:
+        primary_key = list(getattr(model, "__primary_key__", ()))
+        if not primary_key:
+            raise TypeError(f"Model {model.__name__} has no partition key.")
+        partition = primary_key[:1]
+        clustering = [(name, False) for name in primary_key[1:]]
+    statement = statement.partition_key(*partition)
+    for name, desc in clustering:
+        statement = statement.clustering_key(name, desc)
+    return statement
+
+
+async def create_schema(scylla: Scylla, *models: Type[Any]) -> None:
+    """
+    Create types and tables for models.
+
+    Objects which already exist are left untouched.
+    """
+    created: Set[str] = set()
+    for model in models:
+        for statement in create_types(model):
+            if str(statement) not in created:
+                created.add(str(statement))
+                await statement.execute(scylla)
+        await create_table(model).execute(scylla)
+
+
+__all__ = [
+    "column",
+    "partition_key",
+    "clustering_key",
+    "cql_type",
+    "type_name",
+    "table_name",
+    "create_types",
+    "create_table",
+    "create_schema",
+]
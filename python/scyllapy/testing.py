@@ -0,0 +1,627 @@
+"""
+Utilities for testing applications without a running cluster.
+
+`MockScylla` has the same `execute`, `prepare` and `batch` methods
+as `Scylla`, but tables are stored in memory. Only basic CQL
+is supported: creating, dropping and truncating tables,
+inserts, updates, deletes and selects with simple conditions.
+"""
+import operator
+import re
+from typing import (
+    Any,
+    Callable,
+    Dict,
+    Iterable,
+    Iterator,
+    List,
+    Optional,
+    Sequence,
+    Tuple,
+    Union,
+)
+
+from ._internal import Batch, InlineBatch, Query
+from ._internal.exceptions import (
+    ScyllaPyBindingError,
+    ScyllaPyDBError,
+    ScyllaPySyntaxError,
+)
+
+Params = Union[Sequence[Any], Dict[str, Any], None]
+_Resolver = Callable[[Any], Any]
+
+_TOKEN_RE = re.compile(
+    r"""
+    \s*(?:
+        (?P<string>'(?:[^']|'')*')
+        |(?P<number>-?\d+(?:\.\d+)?(?:[eE][-+]?\d+)?)
+        |(?P<quoted>"(?:[^"]|"")+")
+        |(?P<marker>\?|:\w+)
+        |(?P<ident>\w+)
+        |(?P<op><=|>=|!=|[(),=<>*;.:\[\]{}])
+    )
+    """,
+    re.VERBOSE,
+)
+
+_COMPARATORS: Dict[str, Callable[[Any, Any], bool]] = {
+    "=": operator.eq,
+    "!=": operator.ne,
+    "<": operator.lt,
+    "<=": operator.le,
+    ">": operator.gt,
+    ">=": operator.ge,
+}
+
+_NO_OPS = {"KEYSPACE", "TYPE", "INDEX", "MATERIALIZED", "FUNCTION", "AGGREGATE"}
+
+
+class _Token:
+    __slots__ = ("kind", "text")
+
+    def __init__(self, kind: str, text: str) -> None:
+        self.kind = kind
+        self.text = text
+
+    @property
+    def upper(self) -> str:
+        return self.text.upper() if self.kind == "ident" else ""
+
+
+class _Marker:
+    """Bind marker, which is resolved when the statement is executed."""
+
+    __slots__ = ("name",)
+
+    def __init__(self, name: Optional[str]) -> None:
+        self.name = name
+
+
+class _Parser:
+    """Recursive descent parser over tokens of a single statement."""
+
+    def __init__(self, query: str) -> None:
+        self.tokens: List[_Token] = []
+        pos = 0
+        query = query.strip().rstrip(";")
+        while pos < len(query):
+            match = _TOKEN_RE.match(query, pos)
+            if match is None or match.end() == pos:
+                if query[pos:].strip() == "":
+                    break
+                raise ScyllaPySyntaxError(f"Cannot parse query near: {query[pos:]}")
+            kind = match.lastgroup or ""
+            self.tokens.append(_Token(kind, match.group(kind)))
+            pos = match.end()
+        self.pos = 0
+
+    def peek(self, offset: int = 0) -> Optional[_Token]:
+        if self.pos + offset < len(self.tokens):
+            return self.tokens[self.pos + offset]
+        return None
+
+    def next(self) -> _Token:
+        token = self.peek()
+        if token is None:
+            raise ScyllaPySyntaxError("Unexpected end of query.")
+        self.pos += 1
+        return token
+
+    def accept(self, *keywords: str) -> bool:
+        """Consume keywords if they're next in the query."""
+        for offset, keyword in enumerate(keywords):
+            token = self.peek(offset)
+            if token is None or (token.upper or token.text) != keyword:
+                return False
+        self.pos += len(keywords)
+        return True
+
+    def expect(self, *keywords: str) -> None:
+        if not self.accept(*keywords):
+            token = self.peek()
+            found = token.text if token else "end of query"
+            raise ScyllaPySyntaxError(f"Expected {' '.join(keywords)}, found {found}.")
+
+    def end(self) -> None:
+        """Check that the whole query is parsed."""
+        token = self.peek()
+        if token is not None:
+            raise ScyllaPySyntaxError(f"Unsupported clause near {token.text}.")
+
+    def skip_rest(self) -> None:
+        """Skip the rest of the query, which doesn't affect tables in memory."""
+        self.pos = len(self.tokens)
+
+    def name(self) -> str:
+        """Parse identifier. Unquoted identifiers are case-insensitive."""
+        token = self.next()
+        if token.kind == "quoted":
+            return token.text[1:-1].replace('""', '"')
+        if token.kind != "ident":
+            raise ScyllaPySyntaxError(f"Expected identifier, found {token.text}.")
+        return token.text.lower()
+
+    def table_name(self) -> str:
+        """Parse table name, keyspace is ignored."""
+        name = self.name()
+        if self.accept("."):
+            name = self.name()
+        return name
+
+    def names(self) -> List[str]:
+        self.expect("(")
+        names = [self.name()]
+        while self.accept(","):
+            names.append(self.name())
+        self.expect(")")
+        return names
+
+    def value(self) -> Any:  # noqa: PLR0911
+        token = self.next()
+        if token.kind == "marker":
+            if token.text == "?":
+                return _Marker(None)
+            return _Marker(token.text[1:].lower())
+        if token.kind == "string":
+            return token.text[1:-1].replace("''", "'")
+        if token.kind == "number":
+            if re.fullmatch(r"-?\d+", token.text):
+                return int(token.text)
+            return float(token.text)
+        if token.upper in ("TRUE", "FALSE"):
+            return token.upper == "TRUE"
+        if token.upper == "NULL":
+            return None
+        if token.text in ("[", "{", "("):
+            return self._collection(token.text)
+        raise ScyllaPySyntaxError(f"Unexpected value {token.text}.")
+
+    def _collection(self, opening: str) -> Any:
+        closing = {"[": "]", "{": "}", "(": ")"}[opening]
+        items: List[Any] = []
+        pairs: Dict[Any, Any] = {}
+        while not self.accept(closing):
+            item = self.value()
+            if opening == "{" and self.accept(":"):
+                pairs[item] = self.value()
+            else:
+                items.append(item)
+            self.accept(",")
+        if opening == "[":
+            return items
+        if opening == "(":
+            return tuple(items)
+        return pairs or set(items)
+
+    def table_definition(self) -> Tuple[List[str], List[str]]:
+        """Parse columns and primary key of a table."""
+        columns: List[str] = []
+        primary_key: List[str] = []
+        self.expect("(")
+        while not self.accept(")"):
+            if self.accept("PRIMARY", "KEY"):
+                self.expect("(")
+                token = self.peek()
+                if token is not None and token.text == "(":
+                    primary_key.extend(self.names())
+                else:
+                    primary_key.append(self.name())
+                while self.accept(","):
+                    primary_key.append(self.name())
+                self.expect(")")
+            else:
+                columns.append(self.name())
+                self.skip_type()
+                if self.accept("PRIMARY", "KEY"):
+                    primary_key.append(columns[-1])
+            self.accept(",")
+        return columns, primary_key
+
+    def skip_type(self) -> None:
+        """Skip column type, which may have nested brackets."""
+        depth = 0
+        while True:
+            token = self.peek()
+            if token is None:
+                return
+            if depth == 0 and (token.text in (",", ")") or token.upper == "PRIMARY"):
+                return
+            if token.text == "<":
+                depth += 1
+            elif token.text == ">":
+                depth -= 1
+            self.pos += 1
+
+
+class _Condition:
+    __slots__ = ("column", "op", "value")
+
+    def __init__(self, column: str, op: str, value: Any) -> None:
+        self.column = column
+        self.op = op
+        self.value = value
+
+
+class _Table:
+    def __init__(self, columns: List[str], primary_key: List[str]) -> None:
+        self.columns = columns
+        self.primary_key = primary_key
+        self.rows: Dict[Tuple[Any, ...], Dict[str, Any]] = {}
+
+    def check_columns(self, columns: Iterable[str]) -> None:
+        for column in columns:
+            if column not in self.columns:
+                raise ScyllaPyDBError(f"Undefined column name {column}.")
+
+    def key(self, values: Dict[str, Any]) -> Tuple[Any, ...]:
+        try:
+            return tuple(values[column] for column in self.primary_key)
+        except KeyError as exc:
+            raise ScyllaPyDBError(f"Missing primary key column {exc}.") from exc
+
+
+class MockQueryResult:
+    """In-memory counterpart of `QueryResult`."""
+
+    trace_id: Optional[str] = None
+
+    def __init__(
+        self,
+        columns: Optional[List[str]] = None,
+        rows: Optional[List[Dict[str, Any]]] = None,
+    ) -> None:
+        self.columns = columns or []
+        self.rows = rows or []
+
+    def all(self, as_class: Optional[Callable[..., Any]] = None) -> List[Any]:
+        """Get all rows."""
+        if as_class is None:
+            return [dict(row) for row in self.rows]
+        return [as_class(**row) for row in self.rows]
+
+    def first(self, as_class: Optional[Callable[..., Any]] = None) -> Any:
+        """Get the first row or None."""
+        if not self.rows:
+            return None
+        if as_class is None:
+            return dict(self.rows[0])
+        return as_class(**self.rows[0])
+
+    def scalars(self) -> List[Any]:
+        """Get values of the first column."""
+        return [next(iter(row.values()), None) for row in self.rows]
+
+    def scalar(self) -> Any:
+        """Get value of the first column of the first row."""
+        if not self.rows:
+            return None
+        return next(iter(self.rows[0].values()), None)
+
+    def __len__(self) -> int:
+        return len(self.rows)
+
+    def __iter__(self) -> Iterator[Dict[str, Any]]:
+        return iter(self.all())
+
+    def __repr__(self) -> str:
+        return f"MockQueryResult({self.rows})"
+
+
+class MockPreparedQuery:
+    """In-memory counterpart of `PreparedQuery`."""
+
+    def __init__(self, query: str) -> None:
+        self.query = query
+
+    def __repr__(self) -> str:
+        return f"MockPreparedQuery({self.query!r})"
+
+
+class MockScylla:
+    """
+    In-memory replacement of `Scylla` session for unit tests.
+
+    ```python
+    scylla = MockScylla()
+    await scylla.execute("CREATE TABLE users (id INT PRIMARY KEY, name TEXT)")
+    await scylla.execute("INSERT INTO users (id, name) VALUES (?, ?)", [1, "a"])
+    ```
+    """
+
+    def __init__(self, *_args: Any, **_kwargs: Any) -> None:
+        self.tables: Dict[str, _Table] = {}
+        self.queries: List[Tuple[str, Params]] = []
+
+    async def startup(self) -> None:
+        """Does nothing, there's nothing to connect to."""
+
+    async def shutdown(self) -> None:
+        """Does nothing, there's nothing to disconnect from."""
+
+    async def prepare(self, query: Union[str, Query]) -> MockPreparedQuery:
+        """Check syntax of the query and return it as prepared."""
+        text = query.query if isinstance(query, Query) else query
+        _Parser(text)
+        return MockPreparedQuery(text)
+
+    async def execute(
+        self,
+        query: Union[str, Query, MockPreparedQuery],
+        params: Params = None,
+        **_kwargs: Any,
+    ) -> MockQueryResult:
+        """Execute a query against tables in memory."""
+        if isinstance(query, (Query, MockPreparedQuery)):
+            query = query.query
+        self.queries.append((query, params))
+        return self._execute(query, params)
+
+    async def batch(
+        self,
+        batch: Union[Batch, InlineBatch],
+        params: Optional[Iterable[Params]] = None,
+    ) -> None:
+        """
+        Execute statements of the batch one by one.
+
+        If `params` aren't passed, values which statements
+        were added to `InlineBatch` with are used.
+        """
+        statements = batch.statements
+        if params is not None:
+            values = list(params)
+        elif isinstance(batch, InlineBatch):
+            values = batch.params
+        else:
+            values = [None] * len(statements)
+        if len(values) != len(statements):
+            raise ScyllaPyBindingError(
+                f"Batch has {len(statements)} statements, "
+                f"but {len(values)} sets of parameters were passed.",
+            )
+        for statement, statement_params in zip(statements, values):
+            self.queries.append((statement, statement_params))
+            self._execute(statement, statement_params)
+
+    def _execute(self, query: str, params: Params) -> MockQueryResult:
+        parser = _Parser(query)
+        resolve = self._resolver(params)
+        handlers: Dict[str, Callable[[_Parser, _Resolver], Any]] = {
+            "CREATE": self._create,
+            "DROP": self._drop,
+            "TRUNCATE": self._truncate,
+            "INSERT": self._insert,
+            "UPDATE": self._update,
+            "DELETE": self._delete,
+            "SELECT": self._select,
+            "USE": lambda parser, _resolve: parser.skip_rest(),
+        }
+        keyword = parser.next().upper
+        handler = handlers.get(keyword)
+        if handler is None:
+            raise ScyllaPySyntaxError(f"Unsupported statement {keyword}.")
+        result = handler(parser, resolve)
+        return result if isinstance(result, MockQueryResult) else MockQueryResult()
+
+    @staticmethod
+    def _resolver(params: Params) -> _Resolver:
+        """Build a function which replaces bind markers with values."""
+        positional = iter(params) if isinstance(params, (list, tuple)) else None
+        named = (
+            {str(key).lower(): value for key, value in params.items()}
+            if isinstance(params, dict)
+            else None
+        )
+
+        def resolve(value: Any) -> Any:
+            if isinstance(value, list):
+                return [resolve(item) for item in value]
+            if isinstance(value, tuple):
+                return tuple(resolve(item) for item in value)
+            if not isinstance(value, _Marker):
+                return value
+            if value.name is not None and named is not None:
+                if value.name not in named:
+                    raise ScyllaPyBindingError(f"Missing parameter {value.name}.")
+                return named[value.name]
+            if positional is None:
+                raise ScyllaPyBindingError("Query has markers, but no parameters.")
+            try:
+                return next(positional)
+            except StopIteration as exc:
+                raise ScyllaPyBindingError("Not enough parameters.") from exc
+
+        return resolve
+
+    def _table(self, name: str) -> _Table:
+        table = self.tables.get(name)
+        if table is None:
+            raise ScyllaPyDBError(f"Table {name} does not exist.")
+        return table
+
+    def _create(self, parser: _Parser, _resolve: _Resolver) -> None:
+        token = parser.next()
+        if token.upper in _NO_OPS:
+            parser.skip_rest()
+            return
+        if token.upper != "TABLE":
+            raise ScyllaPySyntaxError(f"Unsupported statement CREATE {token.text}.")
+        if_not_exists = parser.accept("IF", "NOT", "EXISTS")
+        name = parser.table_name()
+        columns, primary_key = parser.table_definition()
+        if parser.accept("WITH"):
+            # Table options don't affect tables in memory.
+            parser.skip_rest()
+        parser.end()
+        if not primary_key:
+            raise ScyllaPySyntaxError(f"Table {name} has no primary key.")
+        if name in self.tables:
+            if if_not_exists:
+                return
+            raise ScyllaPyDBError(f"Table {name} already exists.")
+        self.tables[name] = _Table(columns, primary_key)
+
+    def _drop(self, parser: _Parser, _resolve: _Resolver) -> None:
+        token = parser.next()
+        if token.upper in _NO_OPS:
+            parser.skip_rest()
+            return
+        if token.upper != "TABLE":
+            raise ScyllaPySyntaxError(f"Unsupported statement DROP {token.text}.")
+        if_exists = parser.accept("IF", "EXISTS")
+        name = parser.table_name()
+        parser.end()
+        if name not in self.tables and not if_exists:
+            raise ScyllaPyDBError(f"Table {name} does not exist.")
+        self.tables.pop(name, None)
+
+    def _truncate(self, parser: _Parser, _resolve: _Resolver) -> None:
+        parser.accept("TABLE")
+        table = self._table(parser.table_name())
+        parser.end()
+        table.rows.clear()
+
+    @staticmethod
+    def _skip_using(parser: _Parser, resolve: _Resolver) -> None:
+        """Skip TTL and timestamp, but consume their parameters."""
+        if parser.accept("USING"):
+            parser.next()
+            resolve(parser.value())
+            while parser.accept("AND"):
+                parser.next()
+                resolve(parser.value())
+
+    def _insert(self, parser: _Parser, resolve: _Resolver) -> MockQueryResult:
+        parser.expect("INTO")
+        table = self._table(parser.table_name())
+        columns = parser.names()
+        table.check_columns(columns)
+        parser.expect("VALUES")
+        parser.expect("(")
+        values = [parser.value()]
+        while parser.accept(","):
+            values.append(parser.value())
+        parser.expect(")")
+        if len(values) != len(columns):
+            raise ScyllaPySyntaxError("Number of columns and values doesn't match.")
+        row = {column: resolve(value) for column, value in zip(columns, values)}
+        if_not_exists = parser.accept("IF", "NOT", "EXISTS")
+        self._skip_using(parser, resolve)
+        parser.end()
+        key = table.key(row)
+        if if_not_exists:
+            applied = key not in table.rows
+            if applied:
+                table.rows[key] = row
+            return MockQueryResult(["[applied]"], [{"[applied]": applied}])
+        table.rows.setdefault(key, {}).update(row)
+        return MockQueryResult()
+
+    def _where(
+        self,
+        parser: _Parser,
+        resolve: _Resolver,
+        table: _Table,
+    ) -> List[_Condition]:
+        conditions: List[_Condition] = []
+        if not parser.accept("WHERE"):
+            return conditions
+        while True:
+            column = parser.name()
+            table.check_columns([column])
+            if parser.accept("IN"):
+                conditions.append(_Condition(column, "IN", resolve(parser.value())))
+            else:
+                op = parser.next().text
+                if op not in _COMPARATORS:
+                    raise ScyllaPySyntaxError(f"Unsupported operator {op}.")
+                conditions.append(_Condition(column, op, resolve(parser.value())))
+            if not parser.accept("AND"):
+                return conditions
+
+    @staticmethod
+    def _matches(row: Dict[str, Any], conditions: List[_Condition]) -> bool:
+        for condition in conditions:
+            value = row.get(condition.column)
+            if condition.op == "IN":
+                matches = value in condition.value
+            else:
+                compare = _COMPARATORS[condition.op]
+                matches = value is not None and compare(value, condition.value)
+            if not matches:
+                return False
+        return True
+
+    def _update(self, parser: _Parser, resolve: _Resolver) -> None:
+        table = self._table(parser.table_name())
+        self._skip_using(parser, resolve)
+        parser.expect("SET")
+        values: Dict[str, Any] = {}
+        while True:
+            column = parser.name()
+            parser.expect("=")
+            values[column] = parser.value()
+            if not parser.accept(","):
+                break
+        table.check_columns(values)
+        values = {column: resolve(value) for column, value in values.items()}
+        conditions = self._where(parser, resolve, table)
+        parser.end()
+        key_values = {
+            condition.column: condition.value
+            for condition in conditions
+            if condition.op == "="
+        }
+        row = table.rows.setdefault(table.key(key_values), dict(key_values))
+        row.update(values)
+
+    def _delete(self, parser: _Parser, resolve: _Resolver) -> None:
+        columns: List[str] = []
+        while not parser.accept("FROM"):
+            columns.append(parser.name())
+            parser.accept(",")
+        table = self._table(parser.table_name())
+        self._skip_using(parser, resolve)
+        conditions = self._where(parser, resolve, table)
+        parser.end()
+        for key, row in list(table.rows.items()):
+            if not self._matches(row, conditions):
+                continue
+            if columns:
+                for column in columns:
+                    row[column] = None
+            else:
+                del table.rows[key]
+
+    def _select(self, parser: _Parser, resolve: _Resolver) -> MockQueryResult:
+        count = False
+        columns: List[str] = []
+        if parser.accept("COUNT", "(", "*", ")"):
+            count = True
+        elif not parser.accept("*"):
+            columns.append(parser.name())
+            while parser.accept(","):
+                columns.append(parser.name())
+        parser.expect("FROM")
+        table = self._table(parser.table_name())
+        table.check_columns(columns)
+        conditions = self._where(parser, resolve, table)
+        limit = None
+        if parser.accept("LIMIT"):
+            limit = resolve(parser.value())
+        parser.accept("ALLOW", "FILTERING")
+        parser.end()
+        rows = [row for row in table.rows.values() if self._matches(row, conditions)]
+        if limit is not None:
+            rows = rows[:limit]
+        if count:
+            return MockQueryResult(["count"], [{"count": len(rows)}])
+        columns = columns or table.columns
+        return MockQueryResult(
+            columns,
+            [{column: row.get(column) for column in columns} for row in rows],
+        )
+
+
+__all__ = ["MockScylla", "MockQueryResult", "MockPreparedQuery"]
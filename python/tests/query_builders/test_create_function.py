@@ -0,0 +1,52 @@
+import pytest
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyQueryBuiderError
+from scyllapy.query_builder import CreateAggregate, CreateFunction, DropFunction
+
+
+def test_create_function() -> None:
+    query = (
+        CreateFunction("add")
+        .or_replace()
+        .argument("acc", "int")
+        .argument("val", "int")
+        .called_on_null_input()
+        .returns("int")
+        .body("return acc + val -- it's a sum")
+    )
+    assert str(query) == (
+        "CREATE OR REPLACE FUNCTION add(acc int, val int) CALLED ON NULL INPUT "
+        "RETURNS int LANGUAGE lua AS 'return acc + val -- it''s a sum'"
+    )
+
+
+def test_create_function_without_body() -> None:
+    with pytest.raises(ScyllaPyQueryBuiderError):
+        str(CreateFunction("add").returns("int"))
+
+
+def test_create_aggregate() -> None:
+    query = (
+        CreateAggregate("total")
+        .if_not_exists()
+        .argument("int")
+        .state_function("add")
+        .state_type("int")
+        .final_function("finish")
+        .initial_condition("0")
+    )
+    assert str(query) == (
+        "CREATE AGGREGATE IF NOT EXISTS total(int) "
+        "SFUNC add STYPE int FINALFUNC finish INITCOND 0"
+    )
+
+
+def test_drop_function() -> None:
+    assert str(DropFunction("add").if_exists()) == "DROP FUNCTION IF EXISTS add"
+
+
+@pytest.mark.anyio
+async def test_get_functions(scylla: Scylla, keyspace: str) -> None:
+    assert await scylla.get_functions() == []
+    assert await scylla.get_aggregates(keyspace) == []
@@ -0,0 +1,25 @@
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyQueryBuiderError
+from scyllapy.query_builder import CreateType
+
+
+@pytest.mark.anyio
+async def test_create_type(scylla: Scylla) -> None:
+    type_name = random_string(4)
+    query = (
+        CreateType(type_name).if_not_exists().field("id", "int").field("name", "text")
+    )
+    assert str(query) == f"CREATE TYPE IF NOT EXISTS {type_name} (id int, name text)"
+    await query.execute(scylla)
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, udt FROZEN<{type_name}>)",
+    )
+
+
+def test_create_type_without_fields() -> None:
+    with pytest.raises(ScyllaPyQueryBuiderError):
+        str(CreateType("test"))
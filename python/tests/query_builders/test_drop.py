@@ -0,0 +1,23 @@
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyDBError
+from scyllapy.query_builder import DropTable, DropType
+
+
+@pytest.mark.anyio
+async def test_drop_table(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    await DropTable(table_name).execute(scylla)
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.execute(f"SELECT * FROM {table_name}")
+
+
+@pytest.mark.anyio
+async def test_drop_if_exists(scylla: Scylla) -> None:
+    type_name = random_string(4)
+    query = DropType(type_name).if_exists()
+    assert str(query) == f"DROP TYPE IF EXISTS {type_name}"
+    await query.execute(scylla)
@@ -1,7 +1,11 @@
+import dataclasses
+from typing import Any, Dict
+
 import pytest
 from tests.utils import random_string
 
-from scyllapy import Scylla
+from scyllapy import InlineBatch, Scylla
+from scyllapy.exceptions import ScyllaPyQueryBuiderError
 from scyllapy.query_builder import Insert
 
 
@@ -42,3 +46,98 @@ async def test_insert_request_params(scylla: Scylla) -> None:
         .execute(scylla)
     )
     assert res.trace_id
+
+
+@pytest.mark.anyio
+async def test_insert_ttl_marker(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    query = Insert(table_name).set("id", 1).set("name", "random").using_ttl_marker(100)
+    assert str(query).endswith("USING TTL ?")
+    await query.execute(scylla)
+    res = await scylla.execute(f"SELECT TTL(name) AS ttl FROM {table_name}")
+    assert 0 < res.scalar() <= 100
+
+
+@pytest.mark.anyio
+async def test_insert_positional_values(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    await Insert(table_name).columns("id", "name").values(1, "random").execute(scylla)
+    result = await scylla.execute(f"SELECT * FROM {table_name}")
+    assert result.all() == [{"id": 1, "name": "random"}]
+
+
+@pytest.mark.anyio
+async def test_insert_multiple_rows(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    query = Insert(table_name).columns("id", "name").values(1, "a").values(2, "b")
+    with pytest.raises(ScyllaPyQueryBuiderError):
+        await query.execute(scylla)
+    batch = InlineBatch()
+    query.add_to_batch(batch)
+    await scylla.batch(batch)
+    result = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(result.scalars()) == {1, 2}
+
+
+def test_insert_values_count_mismatch() -> None:
+    with pytest.raises(ScyllaPyQueryBuiderError):
+        Insert("table").columns("id", "name").values(1)
+
+
+@pytest.mark.anyio
+async def test_insert_from_dataclass(scylla: Scylla) -> None:
+    @dataclasses.dataclass
+    class User:
+        id: int
+        name: str
+
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    await Insert(table_name).from_model(User(id=1, name="random")).execute(scylla)
+    result = await scylla.execute(f"SELECT * FROM {table_name}")
+    assert result.all() == [{"id": 1, "name": "random"}]
+
+
+@pytest.mark.anyio
+async def test_insert_from_scylla_dump(scylla: Scylla) -> None:
+    class User:
+        def __init__(self, user_id: int) -> None:
+            self.user_id = user_id
+
+        def __scylla_dump__(self) -> Dict[str, Any]:
+            return {"id": self.user_id, "name": f"user{self.user_id}"}
+
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    await Insert(table_name).from_model(User(2)).execute(scylla)
+    result = await scylla.execute(f"SELECT * FROM {table_name}")
+    assert result.all() == [{"id": 2, "name": "user2"}]
+
+
+@pytest.mark.anyio
+async def test_insert_execute_params(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    res = (
+        await Insert(table_name)
+        .set("id", 1)
+        .set("name", "random")
+        .execute(scylla, tracing=True)
+    )
+    assert res.trace_id
+
+
+@pytest.mark.anyio
+async def test_insert_many(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    rows = [{"id": i, "name": f"user{i}"} for i in range(10)]
+    batches = Insert(table_name).many(rows, chunk_size=3)
+    assert len(batches) == 4
+    for batch in batches:
+        await scylla.batch(batch)
+    result = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(result.scalars()) == set(range(10))
@@ -120,3 +120,48 @@ async def test_order_by(scylla: Scylla) -> None:
     )
     ids = [row["iid"] for row in res.all()]
     assert ids == list(range(10))
+
+
+def test_select_introspection() -> None:
+    query = (
+        Select("users")
+        .only("id", "name")
+        .where("id = ?", [1])
+        .where("name = ?", ["a"])
+    )
+    assert query.table == "users"
+    assert query.selected_columns == ["id", "name"]
+    assert query.where_clauses == ["id = ?", "name = ?"]
+    assert query.bound_values_count == 2
+
+
+@pytest.mark.anyio
+async def test_select_keyspace(keyspace: str, scylla_url: str) -> None:
+    table_name = random_string(4)
+    table = f"{keyspace}.{table_name}"
+    unbound = Scylla(contact_points=[scylla_url])
+    await unbound.startup()
+    try:
+        await unbound.execute(f"CREATE TABLE {table} (id INT PRIMARY KEY)")
+        await unbound.execute(f"INSERT INTO {table}(id) VALUES (?)", [1])
+
+        query = Select(table_name, keyspace=keyspace)
+        assert query.keyspace == keyspace
+        assert str(query) == f"SELECT * FROM {table}"
+        res = await query.execute(unbound)
+        assert res.all() == [{"id": 1}]
+    finally:
+        await unbound.shutdown()
+
+
+def test_select_order_by_ann() -> None:
+    query = (
+        Select("items")
+        .where("category = ?", ["books"])
+        .order_by_ann("embedding", [0.1, 0.2, 0.3], limit=5)
+    )
+    assert str(query) == (
+        "SELECT * FROM items WHERE category = ? "
+        "ORDER BY embedding ANN OF ? LIMIT 5"
+    )
+    assert query.bound_values_count == 2
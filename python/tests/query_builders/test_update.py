@@ -44,3 +44,33 @@ async def test_if_exists(scylla: Scylla) -> None:
     ).execute(scylla)
     res = await scylla.execute(f"SELECT * FROM {table_name}")
     assert res.all() == []
+
+
+@pytest.mark.anyio
+async def test_timestamp_marker(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    query = (
+        Update(table_name)
+        .set("name", "meme")
+        .where("id = ?", [1])
+        .using_timestamp_marker(1000)
+    )
+    assert "USING TIMESTAMP ?" in str(query)
+    await query.execute(scylla)
+    res = await scylla.execute(f"SELECT WRITETIME(name) AS wt FROM {table_name}")
+    assert res.scalar() == 1000
+
+
+def test_update_introspection() -> None:
+    query = (
+        Update("users")
+        .set("name", "meme")
+        .inc("counter", 1)
+        .where("id = ?", [1])
+        .if_("name = ?", ["old"])
+    )
+    assert query.table == "users"
+    assert query.column_names == ["name", "counter"]
+    assert query.where_clauses == ["id = ?"]
+    assert query.bound_values_count == 4
@@ -1,7 +1,29 @@
+import copy
+import pickle
+import time
+
 import pytest
 from tests.utils import random_string
 
-from scyllapy import Batch, Scylla
+from scyllapy import (
+    Batch,
+    BatchResult,
+    BatchRetryPolicy,
+    BatchType,
+    Consistency,
+    ExecutionProfile,
+    InlineBatch,
+    RequestInfo,
+    Scylla,
+)
+from scyllapy.exceptions import (
+    ScyllaPyBaseError,
+    ScyllaPyBindingError,
+    ScyllaPyDBError,
+    ScyllaPyServerWarning,
+    ScyllaPySessionError,
+)
+from scyllapy.query_builder import Insert, Update
 
 
 @pytest.mark.anyio
@@ -18,3 +40,428 @@ async def test_batches(scylla: Scylla) -> None:
 
     res = await scylla.execute(f"SELECT id FROM {table_name}")
     assert set(res.scalars()) == set(range(num_queries))
+
+
+@pytest.mark.anyio
+async def test_batch_conditions(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name}(id INT, name TEXT, PRIMARY KEY (id))",
+    )
+    await scylla.execute(f"INSERT INTO {table_name}(id, name) VALUES (1, 'first')")
+
+    batch = Batch(batch_type=BatchType.LOGGED)
+    batch.add_query(f"UPDATE {table_name} SET name = ? WHERE id = ? IF name = ?")
+    res = await scylla.batch(batch, [["second", 1, "unknown"]])
+    assert not res.applied
+    assert res.conditions == [{"id": 1, "name": "first"}]
+
+    res = await scylla.batch(batch, [["second", 1, "first"]])
+    assert res.applied
+
+
+@pytest.mark.anyio
+async def test_batch_execute(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    batch = InlineBatch()
+    for i in range(5):
+        batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [i])
+    await batch.execute(scylla)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(res.scalars()) == set(range(5))
+
+
+@pytest.mark.anyio
+async def test_execute_batches(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    batches = []
+    for i in range(5):
+        batch = InlineBatch()
+        batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [i])
+        batches.append(batch)
+    failing = InlineBatch()
+    failing.add_query(f"INSERT INTO {table_name}(unknown) VALUES (?)", [1])
+    batches.append(failing)
+
+    results = await scylla.execute_batches(batches, concurrency=2)
+    assert all(isinstance(res, BatchResult) for res in results[:5])
+    assert isinstance(results[5], ScyllaPyDBError)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(res.scalars()) == set(range(5))
+
+
+@pytest.mark.anyio
+async def test_execute_batches_instrumentation(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, collect_statement_stats=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+        ended: list[RequestInfo] = []
+        failed: list[Exception] = []
+        scylla.on_request_end(lambda info, _: ended.append(info))
+        scylla.on_request_error(lambda _, error: failed.append(error))
+
+        query = f"INSERT INTO {table_name}(id) VALUES (?)"
+        batch = InlineBatch()
+        batch.add_query(query, [1])
+        failing = InlineBatch()
+        failing.add_query(f"INSERT INTO {table_name}(unknown) VALUES (?)", [1])
+        results = await scylla.execute_batches([batch, failing])
+
+        assert [info.statement for info in ended] == [query]
+        assert ended[0].coordinator is not None
+        assert [type(error) for error in failed] == [type(results[1])]
+        stats = {stat["statement"]: stat for stat in scylla.statement_stats()}
+        assert stats[query]["calls"] == 1
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_batch_extend(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    first = InlineBatch()
+    first.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+    second = InlineBatch()
+    second.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [2])
+    first.extend(second)
+    await scylla.batch(first)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(res.scalars()) == {1, 2}
+
+
+@pytest.mark.anyio
+async def test_batch_from_statements(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    query = f"INSERT INTO {table_name}(id) VALUES (?)"
+    batch = InlineBatch.from_statements([(query, [i]) for i in range(3)])
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(res.scalars()) == {0, 1, 2}
+
+
+@pytest.mark.anyio
+async def test_inline_batch_prepared_types(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name}(id INT PRIMARY KEY, small SMALLINT, big BIGINT)",
+    )
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, small, big) VALUES (?, ?, ?)",
+    )
+    batch = InlineBatch()
+    batch.add_query(prepared, [1, 2, 3])
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT * FROM {table_name}")
+    assert res.all() == [{"id": 1, "small": 2, "big": 3}]
+
+
+@pytest.mark.anyio
+async def test_batch_default_ttl_clauses(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name}(id INT, ck INT, ttl INT, PRIMARY KEY (id, ck))",
+    )
+
+    # Conditional batches cannot have custom timestamps.
+    conditional = InlineBatch(ttl=100)
+    conditional.add_query(
+        f"INSERT INTO {table_name}(id, ck, ttl) VALUES (?, ?, ?) IF NOT EXISTS",
+        [1, 1, 1],
+    )
+    batch = InlineBatch(ttl=100)
+    batch.add_query(
+        f"UPDATE {table_name} USING TIMESTAMP 10 SET ttl = ? WHERE id = ? AND ck = ?",
+        [2, 1, 2],
+    )
+    assert conditional.statements + batch.statements == [
+        f"INSERT INTO {table_name}(id, ck, ttl) VALUES (?, ?, ?) "
+        "IF NOT EXISTS USING TTL 100",
+        f"UPDATE {table_name} USING TTL 100 AND TIMESTAMP 10 "
+        "SET ttl = ? WHERE id = ? AND ck = ?",
+    ]
+    await scylla.batch(conditional)
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT TTL(ttl) AS remaining FROM {table_name}")
+    assert len(res.all()) == 2
+    assert all(0 < row["remaining"] <= 100 for row in res.all())
+
+
+@pytest.mark.anyio
+async def test_batch_default_ttl_prepared(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+    insert = await scylla.prepare(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)")
+    insert_ttl = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (?, ?) USING TTL 1000",
+    )
+
+    batch = InlineBatch(ttl=100)
+    with pytest.raises(ValueError):
+        batch.add_query(insert, [1, 1])
+    batch.add_query(insert_ttl, [2, 2])
+    assert len(batch.statements) == 1
+
+
+@pytest.mark.anyio
+async def test_batch_default_ttl(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+
+    batch = InlineBatch(ttl=100)
+    batch.add_query(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", [1, 1])
+    batch.add_query(f"UPDATE {table_name} SET val = ? WHERE id = ?", [2, 2])
+    batch.add_query(
+        f"INSERT INTO {table_name}(id, val) VALUES (?, ?) USING TTL 1000",
+        [3, 3],
+    )
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT id, TTL(val) AS ttl FROM {table_name}")
+    ttls = {row["id"]: row["ttl"] for row in res.all()}
+    assert 0 < ttls[1] <= 100
+    assert 0 < ttls[2] <= 100
+    assert 100 < ttls[3] <= 1000
+
+
+@pytest.mark.anyio
+async def test_batch_pickle(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+
+    batch = InlineBatch(BatchType.LOGGED, consistency=Consistency.QUORUM)
+    for i in range(3):
+        batch.add_query(
+            f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+            [i, str(i)],
+        )
+    restored = pickle.loads(pickle.dumps(batch))
+    await scylla.batch(copy.deepcopy(restored))
+
+    res = await scylla.execute(f"SELECT id, val FROM {table_name}")
+    assert sorted(res.all(), key=lambda row: row["id"]) == [
+        {"id": i, "val": str(i)} for i in range(3)
+    ]
+
+
+def test_batch_pickle_profile() -> None:
+    batch = Batch(profile=ExecutionProfile())
+    with pytest.raises(TypeError):
+        pickle.dumps(batch)
+
+
+@pytest.mark.anyio
+async def test_batch_retry_policy(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+
+    batch = InlineBatch(
+        is_idempotent=True,
+        retry_policy=BatchRetryPolicy(max_retries=2, base_delay=0.01),
+    )
+    batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.scalars() == [1]
+
+
+def test_batch_retry_policy_not_idempotent() -> None:
+    with pytest.raises(ValueError):
+        InlineBatch(retry_policy=BatchRetryPolicy())
+    with pytest.raises(ValueError):
+        BatchRetryPolicy(base_delay=-1)
+
+
+@pytest.mark.anyio
+async def test_inline_batch_named_params(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (:id, :val)",
+    )
+    batch = InlineBatch()
+    batch.add_query(prepared, {"val": "one", "id": 1})
+    batch.add_query(prepared, {"VAL": "two", "Id": 2})
+    with pytest.raises(ScyllaPyBindingError):
+        batch.add_query(prepared, {"id": 2})
+    with pytest.raises(ScyllaPyBindingError):
+        batch.add_query(f"INSERT INTO {table_name}(id) VALUES (:id)", {"id": 3})
+    await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT id, val FROM {table_name}")
+    assert sorted(res.all(), key=lambda row: row["id"]) == [
+        {"id": 1, "val": "one"},
+        {"id": 2, "val": "two"},
+    ]
+
+
+@pytest.mark.anyio
+async def test_batch_query_builders(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+
+    batch = Batch()
+    batch.add_query(Insert(table_name).set("id", 1).set("val", 1))
+    batch.add_query(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)")
+    batch.add_query(Update(table_name).set("val", 3).where("id = ?", [3]))
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.batch(batch, [[2, 2], [4, 4]])
+    await scylla.batch(batch, [[2, 2]])
+
+    res = await scylla.execute(f"SELECT id, val FROM {table_name}")
+    assert sorted(res.all(), key=lambda row: row["id"]) == [
+        {"id": i, "val": i} for i in range(1, 4)
+    ]
+
+
+@pytest.mark.anyio
+async def test_batch_limits(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    query = f"INSERT INTO {table_name}(id, val) VALUES (?, ?)"
+
+    batch = InlineBatch(max_statements=2)
+    for i in range(3):
+        batch.add_query(query, [i, "val"])
+    with pytest.raises(ScyllaPyBaseError, match=".*3 statements.*"):
+        await scylla.batch(batch)
+
+    batch = InlineBatch(max_size=1024)
+    batch.add_query(query, [1, "a" * 2048])
+    with pytest.raises(ScyllaPyBaseError, match=".*bytes.*"):
+        await scylla.batch(batch)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.all() == []
+
+
+@pytest.mark.anyio
+async def test_batch_writer(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+
+    async with scylla.batch_writer(max_batch_size=3, buffer_size=2) as writer:
+        for i in range(10):
+            await writer.add(f"INSERT INTO {table_name}(id) VALUES (?)", [i])
+        await writer.flush()
+        res = await scylla.execute(f"SELECT id FROM {table_name}")
+        assert sorted(res.scalars()) == list(range(10))
+
+
+@pytest.mark.anyio
+async def test_batch_writer_errors(scylla: Scylla) -> None:
+    writer = scylla.batch_writer()
+    await writer.add("INSERT INTO not_existing_table(id) VALUES (?)", [1])
+    with pytest.raises(ScyllaPyDBError):
+        await writer.close()
+    with pytest.raises(ScyllaPySessionError):
+        await writer.add("INSERT INTO not_existing_table(id) VALUES (?)", [1])
+
+
+@pytest.mark.anyio
+async def test_writer(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    prepared = await scylla.prepare(f"INSERT INTO {table_name}(id) VALUES (?)")
+
+    start = time.monotonic()
+    async with scylla.writer(
+        writes_per_second=50,
+        concurrency=2,
+        buffer_size=2,
+    ) as writer:
+        for i in range(10):
+            await writer.add(prepared, [i])
+        await writer.flush()
+        res = await scylla.execute(f"SELECT id FROM {table_name}")
+        assert sorted(res.scalars()) == list(range(10))
+    assert time.monotonic() - start >= 0.18
+
+
+@pytest.mark.anyio
+async def test_writer_listeners(scylla_url: str) -> None:
+    events = []
+    scylla = Scylla([scylla_url])
+    scylla.on_request_end(lambda request, _: events.append(request))
+    await scylla.startup()
+    try:
+        async with scylla.writer() as writer:
+            await writer.add("SELECT * FROM system.local")
+    finally:
+        await scylla.shutdown()
+
+    (request,) = events
+    assert request.kind == "SELECT"
+    assert request.statement == "SELECT * FROM system.local"
+    assert request.coordinator is not None
+
+
+@pytest.mark.anyio
+async def test_writer_errors(scylla: Scylla) -> None:
+    writer = scylla.writer()
+    await writer.add("INSERT INTO not_existing_table(id) VALUES (?)", [1])
+    with pytest.raises(ScyllaPyDBError):
+        await writer.flush()
+    await writer.close()
+    with pytest.raises(ScyllaPySessionError):
+        await writer.add("INSERT INTO not_existing_table(id) VALUES (?)", [1])
+
+
+@pytest.mark.anyio
+async def test_execute_prepared_batch(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (:id, :val)",
+    )
+
+    await scylla.execute_prepared_batch(
+        prepared,
+        [[1, "one"], {"id": 2, "val": "two"}],
+        BatchType.LOGGED,
+    )
+    res = await scylla.execute(f"SELECT id, val FROM {table_name}")
+    assert sorted(res.all(), key=lambda row: row["id"]) == [
+        {"id": 1, "val": "one"},
+        {"id": 2, "val": "two"},
+    ]
+
+
+@pytest.mark.anyio
+async def test_server_warnings(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        contact_points=[scylla_url],
+        keyspace=keyspace,
+        emit_server_warnings=True,
+    )
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+        batch = InlineBatch()
+        # Batches larger than 5KB make the server return a warning.
+        for i in range(10):
+            batch.add_query(
+                f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+                [i, "a" * 1024],
+            )
+        with pytest.warns(ScyllaPyServerWarning):
+            await scylla.batch(batch)
+    finally:
+        await scylla.shutdown()
@@ -1,5 +1,6 @@
 import datetime
 import ipaddress
+import os
 import random
 import uuid
 from decimal import Decimal
@@ -10,6 +11,7 @@ from dateutil.relativedelta import relativedelta
 from tests.utils import random_string
 
 from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyBindingError
 
 
 @pytest.mark.anyio
@@ -88,6 +90,24 @@ async def test_collections(
     assert rows[0] == {"id": 1, "coll": cast_func(test_val)}
 
 
+@pytest.mark.anyio
+async def test_big_parameters(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, data BLOB, tags LIST<TEXT>)",
+    )
+    data = os.urandom(64 * 1024)
+    tags = [random_string(10) for _ in range(10_000)]
+
+    await scylla.execute(
+        f"INSERT INTO {table_name}(id, data, tags) VALUES (?, ?, ?)",
+        [1, data, tags],
+    )
+
+    res = await scylla.execute(f"SELECT * FROM {table_name}")
+    assert res.first() == {"id": 1, "data": data, "tags": tags}
+
+
 @pytest.mark.anyio
 async def test_named_parameters(scylla: Scylla) -> None:
     table_name = random_string(4)
@@ -159,3 +179,22 @@ async def test_cases(scylla: Scylla) -> None:
         f"INSERT INTO {table_name}(id, name) VALUES (:Id, :NaMe)",
         {"Id": 1, "NaMe": 2},
     )
+
+
+@pytest.mark.anyio
+async def test_binding_error_context(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, val TEXT)")
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+    )
+    with pytest.raises(
+        ScyllaPyBindingError,
+        match=".*parameter 1 \\(`val`\\) of type object, expected CQL type Text.*",
+    ):
+        await scylla.execute(prepared, [1, object()])
+    with pytest.raises(ScyllaPyBindingError, match=".*parameter `val` of type object.*"):
+        await scylla.execute(
+            f"INSERT INTO {table_name}(id, val) VALUES (:id, :val)",
+            {"id": 1, "val": object()},
+        )
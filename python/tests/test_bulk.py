@@ -0,0 +1,175 @@
+import csv
+from pathlib import Path
+
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyBaseError, ScyllaPyBindingError
+
+
+@pytest.mark.anyio
+async def test_copy_from_csv(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT, score DOUBLE)",
+    )
+    path = tmp_path / "rows.csv"
+    path.write_text("id;name;score\n1;first;1.5\n2;;\n3;third;2\n")
+
+    loaded = await scylla.copy_from(path, table_name, delimiter=";", concurrency=2)
+
+    assert loaded == 3
+    res = await scylla.execute(f"SELECT id, name, score FROM {table_name}")
+    assert sorted(res.all(), key=lambda row: row["id"]) == [
+        {"id": 1, "name": "first", "score": 1.5},
+        {"id": 2, "name": "", "score": None},
+        {"id": 3, "name": "third", "score": 2.0},
+    ]
+
+
+@pytest.mark.anyio
+async def test_copy_from_invalid_value(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    path = tmp_path / "rows.csv"
+    path.write_text("id\nnot_a_number\n")
+
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.copy_from(path, table_name)
+
+
+@pytest.mark.anyio
+async def test_copy_from_unknown_format(scylla: Scylla, tmp_path: Path) -> None:
+    with pytest.raises(ValueError):
+        await scylla.copy_from(tmp_path / "rows.json", "table", format="json")
+
+
+@pytest.mark.anyio
+@pytest.mark.parametrize("file_format", ["csv", "parquet"])
+async def test_copy_to(scylla: Scylla, tmp_path: Path, file_format: str) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT, score DOUBLE)",
+    )
+    for row_id in range(100):
+        await scylla.execute(
+            f"INSERT INTO {table_name}(id, name, score) VALUES (?, ?, ?)",
+            [row_id, f"name{row_id}", row_id / 2],
+        )
+    path = tmp_path / f"rows.{file_format}"
+
+    unloaded = await scylla.copy_to(path, table_name, format=file_format)
+
+    assert unloaded == 100
+    copy_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {copy_name} (id INT PRIMARY KEY, name TEXT, score DOUBLE)",
+    )
+    assert await scylla.copy_from(path, copy_name, format=file_format) == 100
+    original = await scylla.execute(f"SELECT * FROM {table_name}")
+    copied = await scylla.execute(f"SELECT * FROM {copy_name}")
+    assert sorted(copied.all(), key=lambda row: row["id"]) == sorted(
+        original.all(),
+        key=lambda row: row["id"],
+    )
+
+
+@pytest.mark.anyio
+async def test_copy_to_udt(scylla: Scylla, tmp_path: Path) -> None:
+    type_name = random_string(4)
+    table_name = random_string(4)
+    await scylla.execute(f'CREATE TYPE {type_name} ("Name" TEXT, age INT)')
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, info FROZEN<{type_name}>)",
+    )
+    await scylla.execute(
+        f"INSERT INTO {table_name}(id, info) VALUES (1, {{\"Name\": 'first'}})",
+    )
+    path = tmp_path / "rows.csv"
+
+    await scylla.copy_to(path, f"SELECT info FROM {table_name}")
+
+    with path.open(newline="") as rows_file:
+        assert list(csv.reader(rows_file)) == [
+            ["info"],
+            ["{\"Name\": 'first', \"age\": null}"],
+        ]
+
+
+@pytest.mark.anyio
+async def test_copy_to_select(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    await scylla.execute(f"INSERT INTO {table_name}(id, name) VALUES (1, 'first')")
+    path = tmp_path / "rows.csv"
+
+    unloaded = await scylla.copy_to(path, f"SELECT name FROM {table_name}")
+
+    assert unloaded == 1
+    assert path.read_text() == "name\nfirst\n"
+
+
+@pytest.mark.anyio
+async def test_copy_table(scylla: Scylla, scylla_url: str) -> None:
+    dest_keyspace = random_string(5)
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE KEYSPACE {dest_keyspace} WITH replication = "
+        "{'class': 'SimpleStrategy', 'replication_factor': 1}",
+    )
+    create_table = (
+        f"CREATE TABLE {{}}.{table_name} "
+        "(id INT, pos INT, name TEXT, tags LIST<TEXT>, PRIMARY KEY (id, pos))"
+    )
+    await scylla.execute(create_table.format(await scylla.get_keyspace()))
+    await scylla.execute(create_table.format(dest_keyspace))
+    for row_id in range(10):
+        for pos in range(row_id):
+            await scylla.execute(
+                f"INSERT INTO {table_name}(id, pos, tags) VALUES (?, ?, ?)",
+                [row_id, pos, [str(pos)]],
+            )
+    await scylla.execute(
+        f"UPDATE {table_name} USING TIMESTAMP 1000 AND TTL 3600 "
+        "SET name = 'old' WHERE id = 1 AND pos = 0",
+    )
+    destination = Scylla([scylla_url], keyspace=dest_keyspace)
+    await destination.startup()
+    try:
+        copied = await scylla.copy_table(destination, table_name, concurrency=2)
+
+        assert copied == 45
+        original = await scylla.execute(f"SELECT * FROM {table_name}")
+        res = await destination.execute(f"SELECT * FROM {table_name}")
+        assert sorted(res.all(), key=lambda row: (row["id"], row["pos"])) == sorted(
+            original.all(),
+            key=lambda row: (row["id"], row["pos"]),
+        )
+        cell = (
+            f"SELECT WRITETIME(name) AS written, TTL(name) AS ttl FROM {table_name} "
+            "WHERE id = 1 AND pos = 0"
+        )
+        copied_cell = (await destination.execute(cell)).first()
+        assert copied_cell is not None
+        assert copied_cell["written"] == 1000
+        assert 0 < copied_cell["ttl"] <= 3600
+        empty_range = await scylla.copy_table(
+            destination,
+            table_name,
+            token_ranges=[(0, 0)],
+        )
+        assert empty_range == 0
+    finally:
+        await destination.execute(f"DROP KEYSPACE {dest_keyspace}")
+        await destination.shutdown()
+
+
+@pytest.mark.anyio
+async def test_copy_table_counters(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, hits COUNTER)",
+    )
+    with pytest.raises(ScyllaPyBaseError, match="counter"):
+        await scylla.copy_table(scylla, table_name)
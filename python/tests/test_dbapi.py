@@ -0,0 +1,40 @@
+import pytest
+from tests.utils import random_string
+
+from scyllapy import dbapi
+
+
+@pytest.mark.anyio
+async def test_dbapi(scylla_url: str, keyspace: str) -> None:
+    table_name = random_string(4)
+    with dbapi.connect([scylla_url], keyspace=keyspace) as conn:
+        cursor = conn.cursor()
+        cursor.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+        assert cursor.description is None
+        cursor.executemany(
+            f"INSERT INTO {table_name}(id, name) VALUES (?, ?)",
+            [(1, "a"), (2, "b"), (3, "c")],
+        )
+        conn.commit()
+        cursor.execute(f"SELECT id, name FROM {table_name} WHERE id = ?", (2,))
+        assert cursor.description is not None
+        assert [column[0] for column in cursor.description] == ["id", "name"]
+        assert cursor.rowcount == 1
+        assert cursor.fetchone() == (2, "b")
+        assert cursor.fetchone() is None
+
+        cursor.execute(f"SELECT id FROM {table_name}")
+        assert len(cursor.fetchmany(2)) == 2
+        assert sorted(cursor.fetchall()) in ([(1,)], [(2,)], [(3,)])
+
+
+@pytest.mark.anyio
+async def test_dbapi_errors(scylla_url: str, keyspace: str) -> None:
+    with dbapi.connect([scylla_url], keyspace=keyspace) as conn:
+        cursor = conn.cursor()
+        with pytest.raises(dbapi.ProgrammingError):
+            cursor.execute("SELEC 1")
+        with pytest.raises(dbapi.NotSupportedError):
+            conn.rollback()
+    with pytest.raises(dbapi.InterfaceError):
+        conn.cursor()
@@ -0,0 +1,47 @@
+from dataclasses import dataclass
+from typing import List, Optional
+
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.extra_types import ScyllaPyUDT
+from scyllapy.mapper import Mapper, Model
+
+
+@dataclass
+class Address(ScyllaPyUDT):
+    city: str
+    street: str
+
+
+@dataclass
+class User(Model, primary_key=["id"]):
+    id: int
+    name: str
+    tags: List[str]
+    address: Optional[Address]
+
+
+@pytest.mark.anyio
+async def test_mapper(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    type_name = f"address{table_name}"
+    await scylla.execute(f"CREATE TYPE {type_name} (city text, street text)")
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT, "
+        f"tags LIST<TEXT>, address frozen<{type_name}>)",
+    )
+    User.__table__ = table_name
+    users = Mapper(scylla, User)
+
+    user = User(id=1, name="meme", tags=["a", "b"], address=Address("c", "s"))
+    await users.save(user)
+    await users.save(User(id=2, name="other", tags=[], address=None))
+
+    assert await users.get(1) == user
+    assert await users.get(3) is None
+    assert await users.filter(name="meme", allow_filtering=True) == [user]
+
+    await users.delete(user)
+    assert await users.get(1) is None
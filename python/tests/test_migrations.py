@@ -0,0 +1,98 @@
+from pathlib import Path
+
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPyDBError, ScyllaPyMigrationError
+
+PY_MIGRATION = """
+async def up(scylla):
+    await scylla.execute("INSERT INTO {table}(id) VALUES (1)")
+
+
+async def down(scylla):
+    await scylla.execute("DELETE FROM {table} WHERE id = 1")
+"""
+
+
+@pytest.mark.anyio
+async def test_migrations(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(6)
+    (tmp_path / "0001_create.cql").write_text(
+        "-- Table for tests; with a comment.\n"
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, val TEXT);\n"
+        f"ALTER TABLE {table_name} ADD extra TEXT;",
+    )
+    (tmp_path / "0001_create.down.cql").write_text(f"DROP TABLE {table_name};")
+    (tmp_path / "0002_fill.py").write_text(PY_MIGRATION.format(table=table_name))
+    migrations = scylla.migrations(tmp_path, table=f"migrations_{table_name}")
+
+    assert await migrations.apply(target=1) == [1]
+    assert await migrations.apply() == [2]
+    assert await migrations.apply() == []
+    res = await scylla.execute(f"SELECT id, extra FROM {table_name}")
+    assert res.all() == [{"id": 1, "extra": None}]
+
+    status = await migrations.status()
+    assert [(row["version"], row["name"], row["applied"]) for row in status] == [
+        (1, "create", True),
+        (2, "fill", True),
+    ]
+    assert all(row["applied_at"] is not None for row in status)
+
+    assert await migrations.rollback() == [2]
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.all() == []
+    assert await migrations.rollback() == [1]
+    status = await migrations.status()
+    assert [row["applied"] for row in status] == [False, False]
+
+
+@pytest.mark.anyio
+async def test_migrations_without_down(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(6)
+    (tmp_path / "0001_create.cql").write_text(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY);",
+    )
+    migrations = scylla.migrations(tmp_path, table=f"migrations_{table_name}")
+    await migrations.apply()
+    with pytest.raises(ScyllaPyMigrationError):
+        await migrations.rollback()
+
+
+@pytest.mark.anyio
+async def test_migrations_duplicate_versions(scylla: Scylla, tmp_path: Path) -> None:
+    (tmp_path / "0001_first.cql").write_text("SELECT * FROM system.local;")
+    (tmp_path / "0001_second.cql").write_text("SELECT * FROM system.local;")
+    with pytest.raises(ScyllaPyMigrationError):
+        await scylla.migrations(tmp_path).apply()
+
+
+@pytest.mark.anyio
+async def test_migrations_resume(scylla: Scylla, tmp_path: Path) -> None:
+    table_name = random_string(6)
+    migration = tmp_path / "0001_create.cql"
+    migration.write_text(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY);\n"
+        f"INSERT INTO {table_name}(id, unknown) VALUES (1, 1);",
+    )
+    migrations = scylla.migrations(tmp_path, table=f"migrations_{table_name}")
+    with pytest.raises(ScyllaPyDBError):
+        await migrations.apply()
+    (status,) = await migrations.status()
+    assert not status["applied"]
+    assert status["applied_statements"] == 1
+
+    migration.write_text(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY);\n"
+        f"INSERT INTO {table_name}(id) VALUES (1);",
+    )
+    with pytest.raises(ScyllaPyMigrationError):
+        await migrations.apply()
+    assert await migrations.apply(resume=True) == [1]
+    (status,) = await migrations.status()
+    assert status["applied"]
+    assert status["applied_statements"] == 2
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.scalars() == [1]
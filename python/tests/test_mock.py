@@ -0,0 +1,79 @@
+import pytest
+
+from scyllapy import Batch, InlineBatch
+from scyllapy.exceptions import ScyllaPyDBError, ScyllaPySyntaxError
+from scyllapy.testing import MockScylla
+
+
+@pytest.mark.anyio
+async def test_mock_scylla() -> None:
+    scylla = MockScylla()
+    await scylla.execute(
+        "CREATE TABLE users (id INT, c INT, name TEXT, PRIMARY KEY (id, c))",
+    )
+    await scylla.execute(
+        "INSERT INTO users (id, c, name) VALUES (?, ?, ?)",
+        [1, 1, "one"],
+    )
+    prepared = await scylla.prepare("INSERT INTO users (id, c, name) VALUES (?, ?, ?)")
+    await scylla.execute(prepared, [1, 2, "two"])
+    await scylla.execute(
+        "UPDATE users SET name = :name WHERE id = :id AND c = :c",
+        {"id": 2, "c": 1, "name": "three"},
+    )
+
+    res = await scylla.execute("SELECT c, name FROM users WHERE id = ?", [1])
+    assert res.all() == [{"c": 1, "name": "one"}, {"c": 2, "name": "two"}]
+    res = await scylla.execute("SELECT COUNT(*) FROM users")
+    assert res.scalar() == 3
+
+    batch = Batch()
+    batch.add_query("DELETE FROM users WHERE id = ?")
+    batch.add_query("INSERT INTO users (id, c, name) VALUES (?, ?, ?)")
+    await scylla.batch(batch, [[1], [3, 1, "four"]])
+    res = await scylla.execute("SELECT name FROM users")
+    assert res.scalars() == ["three", "four"]
+
+    await scylla.execute("DROP TABLE users")
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.execute("SELECT * FROM users")
+
+
+@pytest.mark.anyio
+async def test_mock_unsupported_clauses() -> None:
+    scylla = MockScylla()
+    await scylla.execute(
+        "CREATE TABLE users (id INT, c INT, PRIMARY KEY (id, c)) "
+        "WITH CLUSTERING ORDER BY (c DESC)",
+    )
+    await scylla.execute("INSERT INTO users (id, c) VALUES (1, 1)")
+    with pytest.raises(ScyllaPySyntaxError):
+        await scylla.execute("SELECT * FROM users WHERE id = 1 ORDER BY c DESC")
+    with pytest.raises(ScyllaPySyntaxError):
+        await scylla.execute("DELETE FROM users WHERE id = 1 AND c = 1 IF EXISTS")
+    res = await scylla.execute("SELECT COUNT(*) FROM users")
+    assert res.scalar() == 1
+
+
+@pytest.mark.anyio
+async def test_mock_inline_batch() -> None:
+    scylla = MockScylla()
+    await scylla.execute("CREATE TABLE users (id INT PRIMARY KEY, name TEXT)")
+    batch = InlineBatch()
+    batch.add_query("INSERT INTO users (id, name) VALUES (?, ?)", [1, "one"])
+    batch.add_query("INSERT INTO users (id, name) VALUES (?, ?)", [2, "two"])
+    await scylla.batch(batch)
+    res = await scylla.execute("SELECT name FROM users")
+    assert sorted(res.scalars()) == ["one", "two"]
+
+
+@pytest.mark.anyio
+async def test_mock_named_values_case() -> None:
+    scylla = MockScylla()
+    await scylla.execute("CREATE TABLE users (id INT PRIMARY KEY, name TEXT)")
+    await scylla.execute(
+        "INSERT INTO users (id, name) VALUES (:userId, :userName)",
+        {"userId": 1, "userName": "one"},
+    )
+    res = await scylla.execute("SELECT name FROM users WHERE id = ?", [1])
+    assert res.scalar() == "one"
@@ -78,3 +78,43 @@ async def test_paged_select_qb(scylla: Scylla) -> None:
     async for row in res:
         assert row["id"] in vals
         assert row["val"] == -row["id"]
+
+
+@pytest.mark.anyio
+async def test_select_qb_stream(scylla: Scylla) -> None:
+    @dataclass
+    class TestDTO:
+        id: int
+        val: int
+
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE IF NOT EXISTS {table_name} (id INT PRIMARY KEY, val INT)",
+    )
+    vals = list(range(10))
+    for i in vals:
+        await scylla.execute(
+            f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+            [i, -i],
+        )
+    stream = Select(table_name).execute_stream(scylla, as_class=TestDTO, page_size=3)
+    ids = []
+    async for row in stream:
+        assert row.val == -row.id
+        ids.append(row.id)
+    assert sorted(ids) == vals
+
+
+@pytest.mark.anyio
+async def test_many_rows(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    for i in range(2500):
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [i])
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}", paged=True)
+    ids = [row["id"] async for row in res]
+
+    assert sorted(ids) == list(range(2500))
+    all_rows = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert sorted(all_rows.scalars()) == list(range(2500))
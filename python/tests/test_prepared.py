@@ -1,7 +1,12 @@
 import pytest
 from tests.utils import random_string
 
-from scyllapy import Scylla
+from scyllapy import Consistency, ExecutionProfile, InlineBatch, Query, Scylla
+from scyllapy.exceptions import (
+    ScyllaPyBindingError,
+    ScyllaPyDBError,
+    ScyllaPySessionError,
+)
 
 
 @pytest.mark.anyio
@@ -16,3 +21,218 @@ async def test_prepared(scylla: Scylla) -> None:
     prepared_res = await scylla.execute(prepared)
 
     assert res.all() == prepared_res.all()
+
+
+@pytest.mark.anyio
+async def test_prepared_request_params(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    prepared = await scylla.prepare(f"SELECT * FROM {table_name} WHERE id = ?")
+    with pytest.raises(ScyllaPyDBError, match=".*only supported for writes.*"):
+        await scylla.execute(prepared.with_consistency(Consistency.ANY), [1])
+    profile = ExecutionProfile(consistency=Consistency.ANY)
+    with pytest.raises(ScyllaPyDBError, match=".*only supported for writes.*"):
+        await scylla.execute(prepared.with_profile(profile), [1])
+
+    await scylla.execute(prepared.with_tracing(True).with_request_timeout(5), [1])
+
+
+@pytest.mark.anyio
+async def test_prepared_setters(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+    for i in range(10):
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [i])
+
+    prepared = await scylla.prepare(f"SELECT id FROM {table_name}")
+    assert not prepared.is_idempotent
+    prepared.set_is_idempotent(True)
+    assert prepared.is_idempotent
+
+    prepared.set_page_size(3)
+    assert prepared.page_size == 3
+    with pytest.raises(ValueError):
+        prepared.set_page_size(0)
+    res = await scylla.execute(prepared, paged=True)
+    assert sorted([row["id"] async for row in res]) == list(range(10))
+
+
+@pytest.mark.anyio
+async def test_prepare_many(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT, PRIMARY KEY (id))")
+
+    insert, select = await scylla.prepare_many(
+        [
+            f"INSERT INTO {table_name}(id) VALUES (?)",
+            f"SELECT id FROM {table_name}",
+        ],
+    )
+    await scylla.execute(insert, [1])
+    res = await scylla.execute(select)
+    assert res.all() == [{"id": 1}]
+
+
+@pytest.mark.anyio
+async def test_prepare_many_error(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.prepare_many(["SELECT * FROM not_existing_table"])
+
+
+@pytest.mark.anyio
+async def test_prepared_named_params_validation(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (:id, :val)",
+    )
+
+    with pytest.raises(ScyllaPyBindingError, match=".*missing parameters: val.*"):
+        await scylla.execute(prepared, {"id": 1})
+    with pytest.raises(ScyllaPyBindingError, match=".*unknown parameters: value.*"):
+        await scylla.execute(prepared, {"id": 1, "val": "a", "value": "b"})
+
+    await scylla.execute(prepared, {"id": 1, "val": "a"})
+    res = await scylla.execute(f"SELECT id, val FROM {table_name}")
+    assert res.all() == [{"id": 1, "val": "a"}]
+
+
+@pytest.mark.anyio
+async def test_prepared_compute_token(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    await scylla.execute(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", [1, "a"])
+    prepared = await scylla.prepare(f"SELECT * FROM {table_name} WHERE id = :id")
+
+    token = prepared.compute_token([1])
+    assert token is not None
+    assert prepared.compute_token({"id": 1}) == token
+    res = await scylla.execute(
+        f"SELECT token(id) AS token FROM {table_name} WHERE id = ?",
+        [1],
+    )
+    assert res.scalar() == token
+
+    replicas = await scylla.get_replicas(prepared, [1])
+    assert replicas
+
+
+@pytest.mark.anyio
+async def test_routing_token(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    await scylla.execute(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", [1, "a"])
+    prepared = await scylla.prepare(f"SELECT val FROM {table_name} WHERE id = ?")
+    token = prepared.compute_token([1])
+
+    query = f"SELECT val FROM {table_name} WHERE id = 1"
+    res = await scylla.execute(query, routing_token=token)
+    assert res.scalar() == "a"
+    res = await scylla.execute(Query(query, routing_token=token))
+    assert res.scalar() == "a"
+    res = await scylla.execute(Query(query).with_routing_token(token), paged=True)
+    assert [row async for row in res] == [{"val": "a"}]
+
+
+@pytest.mark.anyio
+async def test_bound_statement(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val TEXT)")
+    insert = await scylla.prepare(
+        f"INSERT INTO {table_name}(id, val) VALUES (:id, :val)",
+    )
+
+    await scylla.execute(insert.bind({"id": 1, "val": "one"}))
+    batch = InlineBatch()
+    batch.add_query(insert.bind([2, "two"]))
+    await scylla.batch(batch)
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.execute(insert.bind([3, "three"]), [3, "three"])
+
+    select = (await scylla.prepare(f"SELECT val FROM {table_name} WHERE id = ?")).bind(
+        [2],
+    )
+    assert select.query
+    res = await scylla.execute(select)
+    assert res.scalar() == "two"
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert sorted(row["id"] for row in res.all()) == [1, 2]
+
+
+@pytest.mark.anyio
+async def test_prepared_after_schema_change(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    prepared = await scylla.prepare(f"INSERT INTO {table_name}(id) VALUES (?)")
+
+    await scylla.execute(f"DROP TABLE {table_name}")
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    await scylla.execute(prepared, [1])
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.all() == [{"id": 1}]
+
+
+@pytest.mark.anyio
+async def test_prepared_text(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = f"SELECT id FROM {table_name}"
+    prepared = await scylla.prepare(query)
+
+    assert prepared.query == query
+    assert str(prepared) == query
+    assert prepared.id
+    assert prepared.id.hex() in repr(prepared)
+    assert query in repr(prepared)
+
+
+@pytest.mark.anyio
+async def test_bind_partial(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name}(tenant INT, id INT, PRIMARY KEY (tenant, id))",
+    )
+    prepared = await scylla.prepare(
+        f"INSERT INTO {table_name}(tenant, id) VALUES (:tenant, :id)",
+    )
+    with pytest.raises(ScyllaPyBindingError):
+        prepared.bind_partial(unknown=1)
+
+    partial = prepared.bind_partial(Tenant=1)
+    await scylla.execute(partial, [1])
+    await scylla.execute(partial, {"ID": 2})
+    await scylla.execute(partial.bind([3]))
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.execute(partial, {"tenant": 2, "id": 4})
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.execute(partial, {"TENANT": 2, "id": 4})
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.execute(partial, [4, 5])
+    with pytest.raises(ScyllaPyBindingError):
+        await scylla.execute(partial)
+
+    res = await scylla.execute(f"SELECT tenant, id FROM {table_name}")
+    assert sorted((row["tenant"], row["id"]) for row in res.all()) == [
+        (1, 1),
+        (1, 2),
+        (1, 3),
+    ]
+
+
+@pytest.mark.anyio
+async def test_named_statements(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    insert = f"INSERT INTO {table_name}(id) VALUES (?)"
+    await scylla.register_statement("insert", insert)
+    await scylla.register_statement("select", f"SELECT id FROM {table_name}")
+    assert scylla.registered_statements() == ["insert", "select"]
+
+    await scylla.execute_named("insert", [1])
+    res = await scylla.execute_named("select")
+    assert res.scalars() == [1]
+
+    with pytest.raises(ScyllaPySessionError):
+        await scylla.execute_named("unknown")
@@ -1,8 +1,24 @@
+from typing import Any
+
 import pytest
 from tests.utils import random_string
 
-from scyllapy import Consistency, ExecutionProfile, Query, Scylla
+from scyllapy import (
+    Consistency,
+    ExecutionProfile,
+    InlineBatch,
+    Query,
+    RetryPolicy,
+    Scylla,
+    SpeculativeExecution,
+    TimestampGenerator,
+)
 from scyllapy.exceptions import ScyllaPyDBError
+from scyllapy.load_balancing import (
+    LatencyAwareness,
+    LoadBalancingPolicy,
+    ReplicaOrdering,
+)
 
 
 @pytest.mark.anyio
@@ -15,3 +31,318 @@ async def test_wrong_consistency(scylla: Scylla) -> None:
         await scylla.execute(query, [1])
 
     await scylla.execute(query.with_profile(None), [1])
+
+
+@pytest.mark.anyio
+async def test_batch_profile(scylla: Scylla) -> None:
+    profile = ExecutionProfile(consistency=Consistency.SERIAL)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    batch = InlineBatch(profile=profile)
+    batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.batch(batch)
+
+    batch = InlineBatch()
+    batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+    await scylla.batch(batch)
+
+
+@pytest.mark.anyio
+@pytest.mark.parametrize(
+    "retry_policy",
+    [
+        RetryPolicy.DEFAULT,
+        RetryPolicy.FALLTHROUGH,
+        RetryPolicy.DOWNGRADING_CONSISTENCY,
+    ],
+)
+async def test_retry_policy(scylla: Scylla, retry_policy: RetryPolicy) -> None:
+    profile = ExecutionProfile(retry_policy=retry_policy)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"SELECT * FROM {table_name}", profile=profile)
+    res = await scylla.execute(query)
+    assert res.all() == []
+
+
+@pytest.mark.anyio
+@pytest.mark.parametrize(
+    "speculative_execution",
+    [
+        SpeculativeExecution.simple(max_retry_count=2, retry_interval=10),
+        SpeculativeExecution.percentile(max_retry_count=1, percentile=99.0),
+    ],
+)
+async def test_speculative_execution(
+    scylla: Scylla,
+    speculative_execution: SpeculativeExecution,
+) -> None:
+    profile = ExecutionProfile(speculative_execution=speculative_execution)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"SELECT * FROM {table_name}", profile=profile, is_idempotent=True)
+    res = await scylla.execute(query)
+    assert res.all() == []
+
+
+def test_speculative_execution_percentile() -> None:
+    with pytest.raises(ValueError):
+        SpeculativeExecution.percentile(max_retry_count=1, percentile=0)
+    with pytest.raises(ValueError):
+        SpeculativeExecution.percentile(max_retry_count=1, percentile=101)
+
+
+@pytest.mark.anyio
+async def test_round_robin(scylla: Scylla) -> None:
+    profile = ExecutionProfile(load_balancing_policy=LoadBalancingPolicy.round_robin())
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"INSERT INTO {table_name}(id) VALUES (?)", profile=profile)
+    for i in range(5):
+        await scylla.execute(query, [i])
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert len(res.all()) == 5
+
+
+@pytest.mark.anyio
+async def test_custom_policy(scylla: Scylla) -> None:
+    picked = []
+
+    class FirstNodePolicy:
+        def pick(self, query: dict[str, Any], nodes: list[dict[str, Any]]) -> int:
+            picked.append(nodes[0]["address"])
+            return 0
+
+        def fallback(
+            self,
+            query: dict[str, Any],
+            nodes: list[dict[str, Any]],
+        ) -> list[int]:
+            return list(range(len(nodes)))
+
+    policy = LoadBalancingPolicy.custom(FirstNodePolicy())
+    profile = ExecutionProfile(load_balancing_policy=policy)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"SELECT * FROM {table_name}", profile=profile)
+    res = await scylla.execute(query)
+    assert res.all() == []
+    assert picked
+
+
+def test_custom_policy_without_pick() -> None:
+    with pytest.raises(TypeError):
+        LoadBalancingPolicy.custom(object())
+
+
+@pytest.mark.anyio
+async def test_latency_stats(scylla: Scylla) -> None:
+    policy = await LoadBalancingPolicy.build(latency_awareness=LatencyAwareness())
+    profile = ExecutionProfile(load_balancing_policy=policy)
+    for _ in range(3):
+        await scylla.execute(
+            Query("SELECT * FROM system.local", profile=profile),
+        )
+    stats = policy.latency_stats()
+    assert stats
+    assert sum(node["measurements"] for node in stats) == 3
+    assert all(node["average_latency"] > 0 for node in stats)
+    # Nodes aren't penalized until they have enough measurements.
+    assert not any(node["penalized"] for node in stats)
+
+
+@pytest.mark.anyio
+async def test_session_latency_stats(scylla_url: str, keyspace: str) -> None:
+    policy = await LoadBalancingPolicy.build(
+        latency_awareness=LatencyAwareness(minimum_measurements=1),
+    )
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        default_execution_profile=ExecutionProfile(load_balancing_policy=policy),
+    )
+    await scylla.startup()
+    try:
+        for _ in range(3):
+            await scylla.execute("SELECT * FROM system.local")
+        stats = scylla.latency_stats()
+        assert stats
+        assert min(node["slowdown"] for node in stats) == 1.0
+    finally:
+        await scylla.shutdown()
+    assert Scylla([scylla_url]).latency_stats() is None
+
+
+@pytest.mark.anyio
+async def test_latency_stats_without_awareness() -> None:
+    policy = await LoadBalancingPolicy.build()
+    assert policy.latency_stats() is None
+
+
+@pytest.mark.anyio
+@pytest.mark.parametrize(
+    "ordering",
+    [
+        ReplicaOrdering.SHUFFLED,
+        ReplicaOrdering.PRIMARY_FIRST,
+        ReplicaOrdering.RACK_LOCAL_FIRST,
+    ],
+)
+async def test_replica_ordering(scylla: Scylla, ordering: ReplicaOrdering) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    local = await scylla.execute("SELECT rack FROM system.local")
+    policy = await LoadBalancingPolicy.build(
+        prefer_rack=local.scalar(),
+        replica_ordering=ordering,
+    )
+    profile = ExecutionProfile(load_balancing_policy=policy)
+    prepared = await scylla.prepare(f"INSERT INTO {table_name}(id) VALUES (?)")
+    await scylla.execute(prepared.with_profile(profile), [1])
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert res.all() == [{"id": 1}]
+
+
+@pytest.mark.anyio
+async def test_rack_local_first_requires_rack() -> None:
+    with pytest.raises(ValueError):
+        await LoadBalancingPolicy.build(
+            replica_ordering=ReplicaOrdering.RACK_LOCAL_FIRST,
+        )
+
+
+@pytest.mark.anyio
+async def test_avoid_datacenters(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+
+    policy = await LoadBalancingPolicy.build(avoid_datacenters=["not_existing"])
+    profile = ExecutionProfile(load_balancing_policy=policy)
+    res = await scylla.execute(Query(f"SELECT * FROM {table_name}", profile=profile))
+    assert res.all() == []
+
+    local = await scylla.execute("SELECT data_center FROM system.local")
+    policy = await LoadBalancingPolicy.build(avoid_datacenters=[local.scalar()])
+    profile = ExecutionProfile(load_balancing_policy=policy)
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.execute(Query(f"SELECT * FROM {table_name}", profile=profile))
+
+
+@pytest.mark.anyio
+async def test_derive_profile(scylla: Scylla) -> None:
+    base = ExecutionProfile(consistency=Consistency.ANY, request_timeout=10)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"SELECT * FROM {table_name} WHERE id = ?", profile=base.derive())
+    with pytest.raises(ScyllaPyDBError, match=".*only supported for writes.*"):
+        await scylla.execute(query, [1])
+
+    derived = base.derive(consistency=Consistency.ONE, request_timeout=None)
+    await scylla.execute(query.with_profile(derived), [1])
+
+
+def test_derive_profile_unknown() -> None:
+    with pytest.raises(TypeError):
+        ExecutionProfile().derive(unknown=1)
+
+
+@pytest.mark.anyio
+async def test_profile_chaining(scylla: Scylla) -> None:
+    base = ExecutionProfile().with_consistency(Consistency.ANY).with_request_timeout(10)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+    query = Query(f"SELECT * FROM {table_name} WHERE id = ?", profile=base)
+    with pytest.raises(ScyllaPyDBError, match=".*only supported for writes.*"):
+        await scylla.execute(query, [1])
+
+    chained = base.with_consistency(Consistency.ONE).with_load_balancing(
+        LoadBalancingPolicy.round_robin(),
+    )
+    await scylla.execute(query.with_profile(chained), [1])
+
+
+@pytest.mark.anyio
+async def test_timestamp_generator(scylla: Scylla) -> None:
+    profile = ExecutionProfile(timestamp_generator=TimestampGenerator.MONOTONIC)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+    query = Query(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", profile=profile)
+    await scylla.execute(query, [1, 1])
+    await scylla.execute(query, [1, 2])
+
+    res = await scylla.execute(f"SELECT val, WRITETIME(val) AS ts FROM {table_name}")
+    row = res.first()
+    assert row is not None
+    assert row["val"] == 2
+
+    # Explicit timestamp takes precedence over generated one.
+    query = Query(
+        f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+        profile=profile,
+        timestamp=row["ts"] + 1000,
+    )
+    await scylla.execute(query, [1, 3])
+    res = await scylla.execute(f"SELECT WRITETIME(val) AS ts FROM {table_name}")
+    assert res.scalar() == row["ts"] + 1000
+
+
+@pytest.mark.anyio
+async def test_timestamp_generator_prepared(scylla: Scylla) -> None:
+    profile = ExecutionProfile(timestamp_generator=TimestampGenerator.MONOTONIC)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+    query = Query(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", profile=profile)
+    prepared = await scylla.prepare(query)
+    # Writes with equal timestamps are resolved by value,
+    # so the smaller value wins only with a newer timestamp.
+    await scylla.execute(prepared, [1, 2])
+    await scylla.execute(prepared, [1, 1])
+    res = await scylla.execute(f"SELECT val FROM {table_name}")
+    assert res.scalar() == 1
+
+
+@pytest.mark.anyio
+async def test_timestamp_generator_default_profile(
+    scylla_url: str,
+    keyspace: str,
+) -> None:
+    profile = ExecutionProfile(timestamp_generator=TimestampGenerator.MONOTONIC)
+    scylla = Scylla([scylla_url], keyspace=keyspace, default_write_profile=profile)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY, val INT)")
+        prepared = await scylla.prepare(
+            f"INSERT INTO {table_name}(id, val) VALUES (?, ?)",
+        )
+        await scylla.execute(prepared, [1, 2])
+        await scylla.execute(prepared, [1, 1])
+        res = await scylla.execute(f"SELECT val FROM {table_name}")
+        assert res.scalar() == 1
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_read_write_profiles(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        default_read_profile=ExecutionProfile(consistency=Consistency.ANY),
+        default_write_profile=ExecutionProfile(consistency=Consistency.ONE),
+    )
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name}(id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        with pytest.raises(ScyllaPyDBError, match=".*only supported for writes.*"):
+            await scylla.execute(f"select * FROM {table_name}")
+
+        profile = ExecutionProfile(consistency=Consistency.ONE)
+        query = Query(f"SELECT * FROM {table_name}", profile=profile)
+        res = await scylla.execute(query)
+        assert res.all() == [{"id": 1}]
+    finally:
+        await scylla.shutdown()
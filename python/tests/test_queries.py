@@ -1,9 +1,37 @@
+import asyncio
+import datetime
+import logging
+import os
+import threading
+import time
+import uuid
 from dataclasses import dataclass
+from typing import Tuple
 
 import pytest
 from tests.utils import random_string
 
-from scyllapy import Scylla
+from scyllapy import (
+    Consistency,
+    ExecutionProfile,
+    InlineBatch,
+    LastQuery,
+    LazyValue,
+    Query,
+    RequestInfo,
+    Scylla,
+    SerialConsistency,
+    StartupRetryPolicy,
+    TimestampGenerator,
+    last_query,
+)
+from scyllapy.exceptions import (
+    ErrorCode,
+    ScyllaPyDBError,
+    ScyllaPySessionError,
+    ScyllaPySyntaxError,
+    ScyllaPyTimeoutError,
+)
 
 
 @pytest.mark.anyio
@@ -59,3 +87,964 @@ async def test_udt_as_dataclass(scylla: Scylla) -> None:
     assert res.all(as_class=TestDTO) == [
         TestDTO(id=1, udt_col=UDTType(id=1, name="test")),
     ]
+
+
+@pytest.mark.anyio
+async def test_unavailable_error_attrs(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    query = Query(
+        f"INSERT INTO {table_name}(id) VALUES (?)",
+        consistency=Consistency.THREE,
+    )
+    with pytest.raises(ScyllaPyDBError) as exc_info:
+        await scylla.execute(query, [1])
+
+    assert exc_info.value.code == ErrorCode.UNAVAILABLE
+    assert exc_info.value.consistency == Consistency.THREE
+    assert exc_info.value.required == 3
+    assert exc_info.value.alive == 1
+    assert exc_info.value.write_type is None
+
+
+@pytest.mark.anyio
+async def test_db_error_attrs_default(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPyDBError) as exc_info:
+        await scylla.execute("SELECT * FROM not_existing_table")
+
+    assert exc_info.value.consistency is None
+    assert exc_info.value.required is None
+
+
+@pytest.mark.anyio
+async def test_query_context_in_errors(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        contact_points=[scylla_url],
+        keyspace=keyspace,
+        query_context_in_errors=True,
+    )
+    await scylla.startup()
+    try:
+        query = "SELECT * FROM not_existing_table WHERE id = ?"
+        with pytest.raises(ScyllaPyDBError) as exc_info:
+            await scylla.execute(query, [1])
+        assert exc_info.value.query == query
+        assert exc_info.value.params == "1 values"
+        assert query in str(exc_info.value)
+
+        results = await scylla.execute_many(query, [[1], [2]])
+        assert all(isinstance(res, ScyllaPyDBError) for res in results)
+        assert [res.query for res in results] == [query, query]
+
+        batch = InlineBatch()
+        batch.add_query("INSERT INTO not_existing_table(id) VALUES (?)", [1])
+        with pytest.raises(ScyllaPyDBError) as exc_info:
+            await scylla.batch(batch)
+        assert exc_info.value.query == "INSERT INTO not_existing_table(id) VALUES (?)"
+        assert exc_info.value.params == "1 values"
+
+        results = await scylla.execute_batches([batch])
+        assert isinstance(results[0], ScyllaPyDBError)
+        assert results[0].query == "INSERT INTO not_existing_table(id) VALUES (?)"
+        assert results[0].attempts >= 1
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_syntax_error(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPySyntaxError):
+        await scylla.execute("SELEC * FROM system.local")
+    with pytest.raises(ScyllaPySyntaxError):
+        await scylla.execute("SELECT * FROM not_existing_table")
+
+
+@pytest.mark.anyio
+async def test_attempts_in_errors(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPyDBError) as exc_info:
+        await scylla.execute("SELECT * FROM not_existing_table")
+
+    assert exc_info.value.attempts == 1
+    assert exc_info.value.attempt_errors is not None
+    assert len(exc_info.value.attempt_errors) == 1
+
+
+def test_timeout_error_hierarchy() -> None:
+    assert issubclass(ScyllaPyTimeoutError, ScyllaPyDBError)
+    assert issubclass(ScyllaPyTimeoutError, asyncio.TimeoutError)
+
+
+@pytest.mark.anyio
+async def test_error_code(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPyDBError) as exc_info:
+        await scylla.execute("SELEC * FROM system.local")
+    assert exc_info.value.code == ErrorCode.SYNTAX_ERROR
+
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    with pytest.raises(ScyllaPyDBError) as exc_info:
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    assert exc_info.value.code == ErrorCode.ALREADY_EXISTS
+    assert int(exc_info.value.code) == 0x2400
+
+
+@pytest.mark.anyio
+async def test_opentelemetry_spans(scylla_url: str, keyspace: str) -> None:
+    pytest.importorskip("opentelemetry.sdk")
+    from opentelemetry.sdk.trace import TracerProvider
+    from opentelemetry.sdk.trace.export import SimpleSpanProcessor
+    from opentelemetry.sdk.trace.export.in_memory_span_exporter import (
+        InMemorySpanExporter,
+    )
+    from opentelemetry.trace import StatusCode
+
+    exporter = InMemorySpanExporter()
+    provider = TracerProvider()
+    provider.add_span_processor(SimpleSpanProcessor(exporter))
+    scylla = Scylla(
+        contact_points=[scylla_url],
+        keyspace=keyspace,
+        tracer=provider.get_tracer("scyllapy"),
+    )
+    await scylla.startup()
+    try:
+        await scylla.execute("SELECT * FROM system.local")
+        with pytest.raises(ScyllaPyDBError):
+            await scylla.execute("SELECT * FROM not_existing_table")
+    finally:
+        await scylla.shutdown()
+
+    success, failure = exporter.get_finished_spans()
+    assert success.name == "SELECT"
+    assert success.attributes["db.system"] == "cassandra"
+    assert success.attributes["db.statement"] == "SELECT * FROM system.local"
+    assert success.attributes["db.name"] == keyspace
+    assert "net.peer.name" in success.attributes
+    assert failure.status.status_code == StatusCode.ERROR
+
+
+@pytest.mark.anyio
+async def test_latency_histograms(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, collect_latency_histograms=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        await scylla.execute(f"SELECT id FROM {table_name}")
+        await scylla.execute(f"select id FROM {table_name}")
+
+        histograms = scylla.latency_histograms()
+        assert set(histograms) == {"OTHER", "INSERT", "SELECT"}
+        select = histograms["SELECT"]
+        assert select["count"] == 2
+        assert sum(count for _, count in select["buckets"]) == 2
+        assert select["buckets"][-1][0] == float("inf")
+        assert 0 < select["max"] <= select["total"]
+
+        scylla.reset_latency_histograms()
+        assert scylla.latency_histograms() == {}
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_latency_histograms_disabled(scylla: Scylla) -> None:
+    await scylla.execute("SELECT * FROM system.local")
+    assert scylla.latency_histograms() == {}
+
+
+@pytest.mark.anyio
+async def test_request_listeners(scylla_url: str) -> None:
+    events = []
+    scylla = Scylla(contact_points=[scylla_url])
+    scylla.on_request_start(lambda request: events.append(("start", request)))
+    scylla.on_request_end(lambda request, _: events.append(("end", request)))
+    scylla.on_request_error(lambda request, _: events.append(("error", request)))
+    await scylla.startup()
+    try:
+        await scylla.execute("SELECT * FROM system.local")
+        with pytest.raises(ScyllaPyDBError):
+            await scylla.execute("SELECT * FROM not_existing_table")
+    finally:
+        await scylla.shutdown()
+
+    assert [event for event, _ in events] == ["start", "end", "start", "error"]
+    start, end = events[0][1], events[1][1]
+    assert start.kind == "SELECT"
+    assert start.statement == "SELECT * FROM system.local"
+    assert start.coordinator is None
+    assert end.coordinator is not None
+
+
+@pytest.mark.anyio
+async def test_node_stats(scylla: Scylla) -> None:
+    await scylla.execute("SELECT * FROM system.local")
+    with pytest.raises(ScyllaPyDBError):
+        await scylla.execute("SELECT * FROM not_existing_table")
+
+    (node,) = await scylla.node_stats()
+    assert node["is_up"]
+    assert node["configured_pool_size"] > 0
+    assert node["in_flight"] == 0
+    assert node["requests"] >= 2
+    assert node["errors"] >= 1
+    assert node["average_latency"] > 0
+
+
+@pytest.mark.anyio
+async def test_connection_info(scylla: Scylla) -> None:
+    info = await scylla.connection_info()
+    assert info["requested_compression"] is None
+    assert info["shard_aware_port"]
+    (node,) = info["nodes"]
+    assert node["is_up"]
+    assert node["shard_aware"]
+    assert node["shards"] >= 1
+    assert node["release_version"]
+
+
+@pytest.mark.anyio
+@pytest.mark.parametrize("compression", ["lz4", "snappy"])
+async def test_compression(scylla_url: str, compression: str) -> None:
+    scylla = Scylla([scylla_url], compression=compression)
+    await scylla.startup()
+    try:
+        res = await scylla.execute("SELECT * FROM system.local")
+        assert len(res.all()) == 1
+        info = await scylla.connection_info()
+        assert info["requested_compression"] == compression
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_credentials_callback(scylla_url: str) -> None:
+    with pytest.raises(ScyllaPySessionError):
+        await Scylla(
+            [scylla_url],
+            username="cassandra",
+            password="cassandra",
+            credentials=lambda: ("cassandra", "cassandra"),
+        ).startup()
+
+
+@pytest.mark.anyio
+@pytest.mark.skipif(
+    "SCYLLA_AUTH_URL" not in os.environ,
+    reason="Cluster with password authentication is not configured.",
+)
+async def test_credentials_callback_auth() -> None:
+    calls = []
+
+    def credentials() -> Tuple[str, str]:
+        calls.append(threading.get_ident())
+        return "cassandra", "cassandra"
+
+    scylla = Scylla([os.environ["SCYLLA_AUTH_URL"]], credentials=credentials)
+    await scylla.startup()
+    try:
+        res = await scylla.execute("SELECT * FROM system.local")
+        assert len(res.all()) == 1
+    finally:
+        await scylla.shutdown()
+    assert calls
+    assert threading.get_ident() not in calls
+
+    async def async_credentials() -> Tuple[str, str]:
+        return "cassandra", "cassandra"
+
+    with pytest.raises(ScyllaPySessionError, match="awaitable"):
+        await Scylla(
+            [os.environ["SCYLLA_AUTH_URL"]],
+            credentials=async_credentials,
+        ).startup()
+
+
+@pytest.mark.anyio
+async def test_startup_retry_policy(scylla_url: str) -> None:
+    policy = StartupRetryPolicy(initial_delay=0.05, max_delay=0.1, max_attempts=3)
+    scylla = Scylla([scylla_url], startup_retry_policy=policy)
+    await scylla.startup()
+    await scylla.shutdown()
+
+    start = time.monotonic()
+    with pytest.raises(ScyllaPySessionError):
+        await Scylla(["127.0.0.1:1"], startup_retry_policy=policy).startup()
+    assert time.monotonic() - start >= 0.15
+
+    # Configuration errors aren't retried.
+    policy = StartupRetryPolicy(initial_delay=10, max_attempts=None)
+    start = time.monotonic()
+    with pytest.raises(ScyllaPySessionError):
+        await Scylla(
+            [scylla_url],
+            keyspace=random_string(5),
+            startup_retry_policy=policy,
+        ).startup()
+    assert time.monotonic() - start < 10
+
+
+def test_startup_retry_policy_validation() -> None:
+    with pytest.raises(ValueError):
+        StartupRetryPolicy(initial_delay=-1)
+    with pytest.raises(ValueError):
+        StartupRetryPolicy(max_attempts=0)
+    assert StartupRetryPolicy(max_attempts=None).max_attempts is None
+
+
+def test_unknown_compression(scylla_url: str) -> None:
+    with pytest.raises(ValueError):
+        Scylla([scylla_url], compression="gzip")  # type: ignore
+
+
+@pytest.mark.anyio
+async def test_await_healthy(scylla: Scylla) -> None:
+    await scylla.await_healthy(timeout=5)
+
+    with pytest.raises(ScyllaPyTimeoutError):
+        await scylla.await_healthy(timeout=0.2, min_nodes_up=100)
+
+
+@pytest.mark.anyio
+async def test_await_healthy_not_started(scylla_url: str) -> None:
+    scylla = Scylla([scylla_url])
+
+    with pytest.raises(asyncio.TimeoutError):
+        await scylla.await_healthy(timeout=0.2)
+
+
+@pytest.mark.anyio
+async def test_statement_stats(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, collect_statement_stats=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(
+            f"CREATE TABLE {table_name} (id INT PRIMARY KEY, val TEXT)",
+        )
+        scylla.reset_statement_stats()
+        await scylla.execute(f"INSERT INTO {table_name}(id, val) VALUES (1, 'it''s')")
+        await scylla.execute(f"INSERT  INTO {table_name}(id, val)\nVALUES (2, 'two')")
+        with pytest.raises(ScyllaPyDBError):
+            await scylla.execute(f"INSERT INTO {table_name}(id, val) VALUES (3, 4)")
+
+        (stats,) = scylla.statement_stats()
+        assert stats["statement"] == f"INSERT INTO {table_name}(id, val) VALUES (?, ?)"
+        assert stats["calls"] == 3
+        assert stats["errors"] == 1
+        assert stats["error_rate"] == pytest.approx(1 / 3)
+        assert 0 < stats["p50"] <= stats["p95"] <= stats["p99"]
+
+        scylla.reset_statement_stats()
+        assert scylla.statement_stats() == []
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_statement_stats_disabled(scylla: Scylla) -> None:
+    await scylla.execute("SELECT * FROM system.local")
+    assert scylla.statement_stats() == []
+
+
+@pytest.mark.anyio
+async def test_query_logs(
+    scylla_url: str,
+    caplog: pytest.LogCaptureFixture,
+) -> None:
+    scylla = Scylla(
+        contact_points=[scylla_url],
+        log_queries=True,
+        log_sampling=2,
+        default_read_profile=ExecutionProfile(consistency=Consistency.ONE),
+    )
+    await scylla.startup()
+    try:
+        with caplog.at_level(logging.DEBUG, logger="scyllapy.queries"):
+            for _ in range(4):
+                await scylla.execute("SELECT * FROM system.local WHERE key = 'local'")
+            with pytest.raises(ScyllaPyDBError):
+                await scylla.execute("SELECT * FROM not_existing_table")
+            await scylla.execute(
+                Query("SELECT * FROM system.local", consistency=Consistency.QUORUM),
+            )
+    finally:
+        await scylla.shutdown()
+
+    records = [record for record in caplog.records if record.name == "scyllapy.queries"]
+    assert [record.levelno for record in records] == [
+        logging.DEBUG,
+        logging.DEBUG,
+        logging.WARNING,
+        logging.DEBUG,
+    ]
+    success = records[0]
+    assert success.statement == "SELECT * FROM system.local WHERE key = ?"
+    assert len(success.statement_hash) == 16
+    assert success.statement_hash == records[1].statement_hash
+    assert success.duration > 0
+    assert success.node is not None
+    assert success.consistency == "ONE"
+    assert records[2].error is not None
+    assert records[3].consistency == "QUORUM"
+
+
+@pytest.mark.anyio
+async def test_trace_timeline(scylla: Scylla) -> None:
+    res = await scylla.execute(Query("SELECT * FROM system.local", tracing=True))
+    assert res.trace_id is not None
+
+    timeline = await scylla.trace_timeline(res.trace_id)
+    assert timeline["trace_id"] == res.trace_id
+    assert timeline["nodes"]
+    assert len(timeline["events"]) == sum(
+        len(node["events"]) for node in timeline["nodes"]
+    )
+    for node in timeline["nodes"]:
+        elapsed = [event["elapsed"] for event in node["events"]]
+        assert elapsed == sorted(elapsed)
+
+
+@pytest.mark.anyio
+async def test_trace_timeline_invalid_id(scylla: Scylla) -> None:
+    with pytest.raises(ValueError):
+        await scylla.trace_timeline("not-a-uuid")
+
+
+@pytest.mark.anyio
+async def test_last_query(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, track_last_query=True)
+    await scylla.startup()
+    try:
+        res = await scylla.execute(Query("SELECT * FROM system.local", tracing=True))
+        query = last_query.get()
+        assert query is not None
+        assert query.trace_id == res.trace_id
+        assert query.statement_hash is not None
+
+        holder = LastQuery()
+        last_query.set(holder)
+        statement = "SELECT * FROM system.local WHERE key = 'local'"
+        await asyncio.gather(scylla.execute(statement))
+        assert holder.trace_id is None
+        assert holder.statement_hash not in (None, query.statement_hash)
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_execute_many_instrumentation(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        collect_latency_histograms=True,
+        collect_statement_stats=True,
+    )
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        ended: list[RequestInfo] = []
+        failed: list[Exception] = []
+        scylla.on_request_end(lambda info, _: ended.append(info))
+        scylla.on_request_error(lambda _, error: failed.append(error))
+
+        query = f"SELECT id FROM {table_name} WHERE id = ?"
+        results = await scylla.execute_many(query, [[1], [2], ["invalid"]])
+
+        assert [info.statement for info in ended] == [query, query]
+        assert all(info.coordinator is not None for info in ended)
+        assert isinstance(results[2], ScyllaPyDBError)
+        assert [type(error) for error in failed] == [type(results[2])]
+        assert scylla.latency_histograms()["SELECT"]["count"] == 3
+        stats = {stat["statement"]: stat for stat in scylla.statement_stats()}
+        assert stats[query]["calls"] == 3
+        assert stats[query]["errors"] == 1
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_execute_many(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT)")
+    prepared = await scylla.prepare(f"INSERT INTO {table_name}(id, name) VALUES (?, ?)")
+
+    params = [(i, str(i)) for i in range(20)]
+    results = await scylla.execute_many(prepared, params, concurrency=4)
+    assert len(results) == len(params)
+    assert not any(isinstance(res, Exception) for res in results)
+
+    res = await scylla.execute(f"SELECT id FROM {table_name}")
+    assert set(res.scalars()) == set(range(20))
+
+    query = f"SELECT name FROM {table_name} WHERE id = ?"
+    results = await scylla.execute_many(query, [[1], ["invalid"]])
+    assert results[0].scalar() == "1"
+    assert isinstance(results[1], ScyllaPyDBError)
+
+
+@pytest.mark.anyio
+async def test_big_result(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id UUID PRIMARY KEY, day DATE)")
+    prepared = await scylla.prepare(f"INSERT INTO {table_name}(id, day) VALUES (?, ?)")
+    params = [(uuid.uuid4(), datetime.date(2023, 1, i % 28 + 1)) for i in range(6000)]
+    await scylla.execute_many(prepared, params)
+
+    res = await scylla.execute(f"SELECT id, day FROM {table_name}")
+    assert sorted((row["id"], row["day"]) for row in res.all()) == sorted(params)
+
+
+@pytest.mark.anyio
+async def test_lazy_collections(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, lazy_collections=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(
+            f"CREATE TABLE {table_name} (id INT PRIMARY KEY, "
+            "tags LIST<TEXT>, attrs MAP<TEXT, INT>)",
+        )
+        await scylla.execute(
+            f"INSERT INTO {table_name}(id, tags, attrs) VALUES (?, ?, ?)",
+            [1, ["a", "b"], {"x": 1}],
+        )
+        row = (await scylla.execute(f"SELECT * FROM {table_name}")).first()
+        assert isinstance(row["tags"], LazyValue)
+        assert len(row["tags"]) == 2
+        assert row["tags"] == ["a", "b"]
+        assert "b" in row["tags"]
+        assert row["attrs"]["x"] == 1
+        assert list(row["attrs"].items()) == [("x", 1)]
+        assert row["attrs"].get("y") is None
+        assert row["attrs"].load() == {"x": 1}
+
+        await scylla.execute(
+            f"INSERT INTO {table_name}(id, tags, attrs) VALUES (?, ?, ?)",
+            [2, row["tags"], row["attrs"]],
+        )
+        res = await scylla.execute(f"SELECT * FROM {table_name} WHERE id = 2")
+        copied = res.first()
+        assert copied["tags"] == ["a", "b"]
+        assert copied["attrs"] == {"x": 1}
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_coalesce_reads(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, coalesce_reads=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        select = f"SELECT id FROM {table_name} WHERE id = ?"
+
+        results = await asyncio.gather(
+            *(scylla.execute(select, [1]) for _ in range(20)),
+            scylla.execute(select, [2]),
+        )
+
+        assert [res.scalars() for res in results] == [[1]] * 20 + [[]]
+        coalesced = scylla.coalesced_reads()
+        assert coalesced is not None
+        assert 0 < coalesced < 20
+
+        before = scylla.coalesced_reads()
+        await asyncio.gather(
+            scylla.execute(Query(select, consistency=Consistency.ONE), [1]),
+            scylla.execute(Query(select, consistency=Consistency.QUORUM), [1]),
+        )
+        assert scylla.coalesced_reads() == before
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_coalesce_reads_disabled(scylla: Scylla) -> None:
+    await scylla.execute("SELECT * FROM system.local")
+    assert scylla.coalesced_reads() is None
+
+
+@pytest.mark.anyio
+async def test_result_cache(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, result_cache_size=2)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        select = f"SELECT id FROM {table_name}"
+        scylla.set_cache_ttl(select, 60)
+
+        assert (await scylla.execute(select)).scalars() == [1]
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [2])
+        assert (await scylla.execute(select)).scalars() == [1]
+        stats = scylla.cache_stats()
+        assert stats is not None
+        assert stats.pop("bytes") > 0
+        assert stats == {
+            "hits": 1,
+            "misses": 1,
+            "size": 1,
+            "max_size": 2,
+            "max_bytes": None,
+        }
+
+        scylla.invalidate_cache(select)
+        assert sorted((await scylla.execute(select)).scalars()) == [1, 2]
+
+        scylla.set_cache_ttl(select, 0)
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [3])
+        assert sorted((await scylla.execute(select)).scalars()) == [1, 2, 3]
+        with pytest.raises(ValueError):
+            scylla.set_cache_ttl(select, -1)
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_result_cache_disabled(scylla: Scylla) -> None:
+    assert scylla.cache_stats() is None
+    with pytest.raises(ScyllaPySessionError):
+        scylla.set_cache_ttl("SELECT * FROM system.local", 1)
+
+
+@pytest.mark.anyio
+async def test_profile_report(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, profiling=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        res = await scylla.execute(f"SELECT id FROM {table_name}")
+        assert res.scalars() == [1]
+
+        report = scylla.profile_report()
+        assert set(report) == {"bind", "network", "conversion"}
+        assert report["network"]["count"] == 3
+        assert report["conversion"]["count"] == 1
+
+        scylla.reset_profile_report()
+        assert scylla.profile_report() == {}
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_profile_report_disabled(scylla: Scylla) -> None:
+    await scylla.execute("SELECT * FROM system.local")
+    assert scylla.profile_report() == {}
+
+
+@pytest.mark.anyio
+async def test_warmup(scylla: Scylla) -> None:
+    assert await scylla.warmup() == []
+
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    insert, select = await scylla.warmup(
+        [
+            f"INSERT INTO {table_name}(id) VALUES (?)",
+            f"SELECT id FROM {table_name}",
+        ],
+    )
+    await scylla.execute(insert, [1])
+    res = await scylla.execute(select)
+    assert res.scalars() == [1]
+
+
+@pytest.mark.anyio
+async def test_execute_lwt(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+    query = f"INSERT INTO {table_name}(id) VALUES (?) IF NOT EXISTS"
+    res = await scylla.execute_lwt(query, [1])
+    assert res.first()["[applied]"]
+    res = await scylla.execute_lwt(query, [1])
+    assert not res.first()["[applied]"]
+
+
+@pytest.mark.anyio
+async def test_result_cache_eviction(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        result_cache_size=2,
+        result_cache_ttl=60,
+        collect_latency_histograms=True,
+    )
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        select = f"SELECT id FROM {table_name} WHERE id = ?"
+        await scylla.execute(select, [1])
+        await scylla.execute(select, [2])
+        # The first result is used, so the second one is evicted.
+        await scylla.execute(select, [1])
+        await scylla.execute(select, [3])
+        await scylla.execute(select, [1])
+        stats = scylla.cache_stats()
+        assert stats is not None
+        assert (stats["hits"], stats["misses"], stats["size"]) == (2, 3, 2)
+
+        # Hits don't reach the cluster, so they aren't recorded.
+        assert scylla.latency_histograms()["SELECT"]["count"] == 3
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_result_cache_keyspaces(scylla_url: str, keyspace: str) -> None:
+    other_keyspace = random_string(6)
+    table_name = random_string(4)
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        result_cache_size=10,
+        result_cache_ttl=60,
+    )
+    await scylla.startup()
+    try:
+        await scylla.execute(
+            f"CREATE KEYSPACE {other_keyspace} WITH replication = "
+            "{'class': 'SimpleStrategy', 'replication_factor': 1}",
+        )
+        for value, table_keyspace in enumerate([keyspace, other_keyspace]):
+            await scylla.execute(
+                f"CREATE TABLE {table_keyspace}.{table_name} (id INT PRIMARY KEY)",
+            )
+            await scylla.execute(
+                f"INSERT INTO {table_keyspace}.{table_name}(id) VALUES (?)",
+                [value],
+            )
+        select = f"SELECT id FROM {table_name}"
+        assert (await scylla.execute(select)).scalars() == [0]
+        await scylla.use_keyspace(other_keyspace)
+        assert (await scylla.execute(select)).scalars() == [1]
+    finally:
+        await scylla.execute(f"DROP KEYSPACE IF EXISTS {other_keyspace}")
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_result_cache_max_bytes(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla(
+        [scylla_url],
+        keyspace=keyspace,
+        result_cache_size=10,
+        result_cache_max_bytes=1,
+        result_cache_ttl=60,
+    )
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY)")
+        await scylla.execute(f"INSERT INTO {table_name}(id) VALUES (?)", [1])
+        select = f"SELECT id FROM {table_name}"
+        await scylla.execute(select)
+        await scylla.execute(select)
+        stats = scylla.cache_stats()
+        assert stats is not None
+        assert (stats["hits"], stats["size"], stats["bytes"]) == (0, 0, 0)
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_compare_and_set_quoted(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f'CREATE TABLE {table_name} ("userId" INT PRIMARY KEY, "Value" INT)',
+    )
+    await scylla.execute(
+        f'INSERT INTO {table_name}("userId", "Value") VALUES (?, ?)',
+        [1, 10],
+    )
+
+    applied = await scylla.compare_and_set(
+        table_name,
+        key={"userId": 1},
+        expected={"Value": 10},
+        new={"Value": 11},
+        serial_consistency=SerialConsistency.SERIAL,
+        profile=ExecutionProfile(consistency=Consistency.QUORUM),
+    )
+    assert applied
+    res = await scylla.execute(
+        f'SELECT "Value" FROM {table_name} WHERE "userId" = ?',
+        [1],
+    )
+    assert res.scalar() == 11
+
+
+@pytest.mark.anyio
+async def test_lwt_instrumentation(scylla_url: str, keyspace: str) -> None:
+    scylla = Scylla([scylla_url], keyspace=keyspace, query_context_in_errors=True)
+    await scylla.startup()
+    try:
+        table_name = random_string(4)
+        await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, val INT)")
+        ended: list[RequestInfo] = []
+        scylla.on_request_end(lambda info, _: ended.append(info))
+
+        query = f"INSERT INTO {table_name}(id, val) VALUES (?, ?) IF NOT EXISTS"
+        await scylla.execute_lwt(query, [1, 10])
+        key = {"id": 1}
+        assert await scylla.compare_and_set(table_name, key, {"val": 10}, {"val": 11})
+        assert len(ended) == 2
+        assert ended[0].statement == query
+        assert all(info.coordinator is not None for info in ended)
+
+        with pytest.raises(ScyllaPyDBError) as exc_info:
+            await scylla.execute_lwt(
+                f"UPDATE {table_name} SET val = 1 WHERE id = 1 IF val = 'a'",
+            )
+        assert exc_info.value.query is not None
+    finally:
+        await scylla.shutdown()
+
+
+@pytest.mark.anyio
+async def test_compare_and_set(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TABLE {table_name} (id INT PRIMARY KEY, val INT)")
+    await scylla.execute(f"INSERT INTO {table_name}(id, val) VALUES (?, ?)", [1, 10])
+
+    key = {"id": 1}
+    assert not await scylla.compare_and_set(table_name, key, {"val": 1}, {"val": 2})
+    assert await scylla.compare_and_set(table_name, key, {"val": 10}, {"val": 11})
+
+    # Stale expected value is refreshed from the row.
+    applied = await scylla.compare_and_set(
+        table_name,
+        key=key,
+        expected={"val": 10},
+        new=lambda current: {"val": current["val"] + 1},
+    )
+    assert applied
+    res = await scylla.execute(f"SELECT val FROM {table_name} WHERE id = ?", [1])
+    assert res.scalar() == 12
+
+
+@pytest.mark.anyio
+async def test_ttl_quoted(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f'CREATE TABLE {table_name} ("userId" INT PRIMARY KEY, "Name" TEXT)',
+    )
+    await scylla.execute(
+        f'INSERT INTO {table_name}("userId", "Name") VALUES (?, ?) USING TTL 100',
+        [1, "user"],
+    )
+
+    for _ in range(2):
+        ttl = await scylla.get_ttl(table_name, {"userId": 1}, ["Name"])
+        assert ttl is not None
+        assert 0 < ttl["Name"] <= 100
+
+
+@pytest.mark.anyio
+async def test_ttl_and_writetime(scylla: Scylla) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id INT PRIMARY KEY, name TEXT, email TEXT)",
+    )
+    await scylla.execute(
+        f"INSERT INTO {table_name}(id, name) VALUES (?, ?) "
+        "USING TTL 100 AND TIMESTAMP 10",
+        [1, "user"],
+    )
+
+    ttl = await scylla.get_ttl(table_name, {"id": 1}, ["name", "email"])
+    assert ttl is not None
+    assert 0 < ttl["name"] <= 100
+    assert ttl["email"] is None
+    writetime = await scylla.get_writetime(table_name, {"id": 1}, ["name"])
+    assert writetime == {"name": 10}
+    assert await scylla.get_ttl(table_name, {"id": 2}, ["name"]) is None
+
+
+@pytest.mark.anyio
+async def test_execute_keyspace(keyspace: str, scylla_url: str) -> None:
+    table_name = random_string(4)
+    unbound = Scylla(contact_points=[scylla_url])
+    await unbound.startup()
+    try:
+        await unbound.execute(
+            f"CREATE TABLE {keyspace}.{table_name} (id INT PRIMARY KEY)",
+        )
+        await unbound.execute(
+            f"INSERT INTO {table_name}(id) VALUES (?)",
+            [1],
+            keyspace=keyspace,
+        )
+        batch = InlineBatch()
+        batch.add_query(f"INSERT INTO {table_name}(id) VALUES (?)", [2])
+        await unbound.batch(batch, keyspace=keyspace)
+        res = await unbound.execute(
+            f"SELECT id FROM {table_name}",
+            keyspace=keyspace,
+        )
+        assert sorted(res.scalars()) == [1, 2]
+
+        prepared = await unbound.prepare(f"SELECT id FROM {keyspace}.{table_name}")
+        with pytest.raises(ValueError):
+            await unbound.execute(prepared, keyspace=keyspace)
+    finally:
+        await unbound.shutdown()
+
+
+@pytest.mark.anyio
+async def test_shadow(scylla: Scylla, scylla_url: str) -> None:
+    shadow_keyspace = random_string(5)
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE KEYSPACE {shadow_keyspace} WITH replication = "
+        "{'class': 'SimpleStrategy', 'replication_factor': 1}",
+    )
+    create_table = f"CREATE TABLE {{}}.{table_name} (id INT PRIMARY KEY, name TEXT)"
+    await scylla.execute(create_table.format(await scylla.get_keyspace()))
+    await scylla.execute(create_table.format(shadow_keyspace))
+    shadow = Scylla([scylla_url], keyspace=shadow_keyspace)
+    await shadow.startup()
+    try:
+        assert scylla.shadow_stats() is None
+        scylla.attach_shadow(shadow)
+        profile = ExecutionProfile(timestamp_generator=TimestampGenerator.MONOTONIC)
+        await scylla.execute(
+            Query(f"INSERT INTO {table_name}(id, name) VALUES (?, ?)", profile=profile),
+            [1, "a"],
+        )
+        await scylla.execute(f"SELECT * FROM {table_name}")
+
+        for _ in range(50):
+            res = await shadow.execute(f"SELECT id FROM {table_name}")
+            if res.all():
+                break
+            await asyncio.sleep(0.1)
+        assert res.scalars() == [1]
+        writetime = f"SELECT WRITETIME(name) FROM {table_name}"
+        primary_writetime = (await scylla.execute(writetime)).scalars()
+        assert (await shadow.execute(writetime)).scalars() == primary_writetime
+        with pytest.raises(ScyllaPyDBError):
+            await scylla.execute(f"INSERT INTO {table_name}(id) VALUES ('str')")
+        assert scylla.shadow_stats() == {
+            "writes": 1,
+            "reads": 0,
+            "errors": 0,
+            "dropped": 0,
+            "last_error": None,
+        }
+        scylla.detach_shadow()
+        assert scylla.shadow_stats() is None
+    finally:
+        scylla.detach_shadow()
+        await shadow.execute(f"DROP KEYSPACE {shadow_keyspace}")
+        await shadow.shutdown()
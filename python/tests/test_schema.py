@@ -0,0 +1,229 @@
+import datetime
+import uuid
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Set
+
+import pytest
+from tests.utils import random_string
+
+from scyllapy import Scylla
+from scyllapy.exceptions import ScyllaPySessionError
+from scyllapy.extra_types import BigInt, ScyllaPyUDT
+from scyllapy.schema import (
+    clustering_key,
+    create_schema,
+    create_table,
+    create_types,
+    partition_key,
+)
+
+
+@dataclass
+class Coords(ScyllaPyUDT):
+    lat: float
+    lon: float
+
+
+@dataclass
+class Place(ScyllaPyUDT):
+    name: str
+    coords: Coords
+
+
+@dataclass
+class Visit:
+    user_id: uuid.UUID = partition_key()
+    day: datetime.date = partition_key()
+    at: datetime.datetime = clustering_key(desc=True)
+    views: BigInt = 0
+    place: Optional[Place] = None
+    tags: Set[str] = field(default_factory=set)
+    scores: Optional[Dict[str, List[int]]] = None
+
+
+def test_create_table_query() -> None:
+    assert str(create_table(Visit)) == (
+        "CREATE TABLE IF NOT EXISTS visit (user_id uuid, day date, "
+        "at timestamp, views bigint, place frozen<place>, tags set<text>, "
+        "scores map<text, frozen<list<int>>>, "
+        "PRIMARY KEY ((user_id, day), at)) "
+        "WITH CLUSTERING ORDER BY (at DESC)"
+    )
+    assert [str(query) for query in create_types(Visit)] == [
+        "CREATE TYPE IF NOT EXISTS coords (lat float, lon float)",
+        "CREATE TYPE IF NOT EXISTS place (name text, coords frozen<coords>)",
+    ]
+
+
+@pytest.mark.anyio
+async def test_create_schema(scylla: Scylla) -> None:
+    table_name = random_string(4)
+
+    @dataclass
+    class Model:
+        __table__ = table_name
+        __primary_key__ = ("id", "created")
+
+        id: int
+        created: datetime.datetime
+        place: Place
+
+    await create_schema(scylla, Model)
+    await scylla.execute(
+        f"INSERT INTO {table_name} (id, created, place) VALUES (?, ?, ?)",
+        [1, datetime.datetime.now(), Place("home", Coords(1.5, 2.5))],
+    )
+    res = await scylla.execute(f"SELECT place FROM {table_name}")
+    assert res.scalars() == [
+        {"name": "home", "coords": {"lat": 1.5, "lon": 2.5}},
+    ]
+
+
+@pytest.mark.anyio
+async def test_schema_metadata(scylla: Scylla, keyspace: str) -> None:
+    type_name = random_string(4)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TYPE {type_name} (lat float, tags list<text>)")
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id int, at timestamp, score bigint, "
+        f"place frozen<{type_name}>, PRIMARY KEY (id, at))",
+    )
+
+    keyspaces = {
+        info["name"]: info for info in await scylla.get_keyspaces(refresh=True)
+    }
+    assert keyspaces[keyspace]["strategy"] == "SimpleStrategy"
+    assert keyspaces[keyspace]["replication"] == {"replication_factor": "1"}
+    assert table_name in keyspaces[keyspace]["tables"]
+    assert type_name in keyspaces[keyspace]["user_types"]
+
+    tables = {info["name"]: info for info in await scylla.get_tables()}
+    assert tables[table_name]["keyspace"] == keyspace
+    assert tables[table_name]["partition_key"] == ["id"]
+    assert tables[table_name]["clustering_key"] == ["at"]
+    assert await scylla.get_columns(keyspace, table_name) == [
+        {"name": "id", "type": "int", "kind": "partition_key"},
+        {"name": "at", "type": "timestamp", "kind": "clustering"},
+        {"name": "place", "type": f"frozen<{type_name}>", "kind": "regular"},
+        {"name": "score", "type": "bigint", "kind": "regular"},
+    ]
+
+    user_types = {info["name"]: info for info in await scylla.get_user_types()}
+    assert user_types[type_name]["fields"] == [
+        {"name": "lat", "type": "float"},
+        {"name": "tags", "type": "list<text>"},
+    ]
+
+
+@pytest.mark.anyio
+async def test_schema_metadata_unknown(scylla: Scylla) -> None:
+    with pytest.raises(ScyllaPySessionError):
+        await scylla.get_tables(random_string(6))
+    with pytest.raises(ScyllaPySessionError):
+        await scylla.get_columns(None, random_string(6))
+
+
+@pytest.mark.anyio
+async def test_describe_table(scylla: Scylla, keyspace: str) -> None:
+    table_name = random_string(4)
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id int, bucket int, at timestamp, "
+        "note text static, score bigint, PRIMARY KEY ((id, bucket), at)) "
+        "WITH CLUSTERING ORDER BY (at DESC) AND comment = 'it''s a table'",
+    )
+
+    statement = await scylla.describe_table(table_name, refresh=True)
+    assert statement.startswith(
+        f"CREATE TABLE {keyspace}.{table_name} (\n"
+        "    id int,\n"
+        "    bucket int,\n"
+        "    at timestamp,\n"
+        "    note text static,\n"
+        "    score bigint,\n"
+        "    PRIMARY KEY ((id, bucket), at)\n"
+        ") WITH CLUSTERING ORDER BY (at DESC)\n",
+    )
+    assert "    AND comment = 'it''s a table'\n" in statement
+    assert statement == await scylla.describe_table(f"{keyspace}.{table_name}")
+
+    await scylla.execute(f"DROP TABLE {table_name}")
+    await scylla.execute(statement)
+    assert await scylla.describe_table(table_name, refresh=True) == statement
+
+
+@pytest.mark.anyio
+async def test_describe_keyspace(scylla: Scylla, keyspace: str) -> None:
+    type_name = random_string(4)
+    table_name = random_string(4)
+    await scylla.execute(f"CREATE TYPE {type_name} (name text)")
+    await scylla.execute(
+        f"CREATE TABLE {table_name} (id int PRIMARY KEY, owner frozen<{type_name}>)",
+    )
+
+    statements = await scylla.describe_keyspace(refresh=True)
+    assert statements.startswith(f"CREATE KEYSPACE {keyspace} WITH replication = ")
+    assert "'replication_factor': '1'" in statements
+    create_type = f"CREATE TYPE {keyspace}.{type_name} (\n    name text\n);"
+    create_table = await scylla.describe_table(table_name)
+    assert create_type in statements
+    assert create_table in statements
+    assert statements.index(create_type) < statements.index(create_table)
+
+
+@pytest.mark.anyio
+async def test_describe_keyspace_round_trip(scylla: Scylla) -> None:
+    source, target = random_string(6), random_string(6)
+    await scylla.execute(
+        f"CREATE KEYSPACE {source} WITH replication = "
+        "{'class': 'SimpleStrategy', 'replication_factor': 1}",
+    )
+    try:
+        await scylla.execute(f"CREATE TYPE {source}.place (name text)")
+        await scylla.execute(
+            f"CREATE TABLE {source}.users (id int, name text, email text, "
+            "place frozen<place>, PRIMARY KEY (id, name)) "
+            "WITH CLUSTERING ORDER BY (name DESC) "
+            "AND comment = 'users' AND default_time_to_live = 60",
+        )
+        await scylla.execute(f"CREATE INDEX users_email ON {source}.users (email)")
+        await scylla.execute(
+            f"CREATE INDEX users_local ON {source}.users ((id), email)",
+        )
+        await scylla.execute(
+            f"CREATE MATERIALIZED VIEW {source}.users_by_name AS "
+            f"SELECT id, name FROM {source}.users "
+            "WHERE id IS NOT NULL AND name IS NOT NULL "
+            "PRIMARY KEY (name, id)",
+        )
+
+        statements = await scylla.describe_keyspace(source, refresh=True)
+        assert f"CREATE INDEX users_email ON {source}.users (email);" in statements
+        assert f"CREATE INDEX users_local ON {source}.users ((id), email);" in (
+            statements
+        )
+        assert f"CREATE MATERIALIZED VIEW {source}.users_by_name AS" in statements
+        assert "users_email_index" not in statements
+
+        recreated = statements.replace(source, target)
+        for statement in recreated.split(";\n\n"):
+            await scylla.execute(statement.rstrip(";"))
+        assert await scylla.describe_keyspace(target, refresh=True) == recreated
+    finally:
+        await scylla.execute(f"DROP KEYSPACE IF EXISTS {source}")
+        await scylla.execute(f"DROP KEYSPACE IF EXISTS {target}")
+
+
+@pytest.mark.anyio
+async def test_describe_quoted_table(scylla: Scylla, keyspace: str) -> None:
+    table_name = f"My{random_string(4)}"
+    await scylla.execute(f'CREATE TABLE "{table_name}" (id int PRIMARY KEY)')
+    statement = await scylla.describe_table(
+        f'{keyspace}."{table_name}"',
+        refresh=True,
+    )
+    assert statement.startswith(f'CREATE TABLE {keyspace}."{table_name}" (\n')
+    assert statement == await scylla.describe_table(f'"{table_name}"')
+    with pytest.raises(ScyllaPySessionError):
+        await scylla.describe_table(f"{keyspace}.{table_name}")
+    with pytest.raises(ScyllaPySessionError):
+        await scylla.describe_table(f'{keyspace}."{table_name}')
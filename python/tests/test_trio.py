@@ -0,0 +1,21 @@
+import pytest
+
+from scyllapy import Scylla
+
+trio = pytest.importorskip("trio")
+
+
+def test_trio(scylla_url: str) -> None:
+    async def run() -> None:
+        scylla = Scylla([scylla_url])
+        await scylla.startup()
+        try:
+            res = await scylla.execute("SELECT key FROM system.local")
+            assert res.scalars() == ["local"]
+            prepared = await scylla.prepare("SELECT key FROM system.local")
+            res = await scylla.execute(prepared)
+            assert res.scalars() == ["local"]
+        finally:
+            await scylla.shutdown()
+
+    trio.run(run)
@@ -0,0 +1,52 @@
+use async_trait::async_trait;
+use pyo3::{Py, PyAny, Python};
+use scylla::authentication::{
+    AuthError, AuthenticatorProvider, AuthenticatorSession, PlainTextAuthenticator,
+};
+
+/// Authenticator, which gets credentials from python callable.
+///
+/// The callable is called each time a connection is established,
+/// so credentials can be rotated without recreating the session.
+/// It runs on a blocking thread, so a slow callable
+/// doesn't stall other connections.
+#[derive(Debug)]
+pub struct CredentialsCallback {
+    callback: Py<PyAny>,
+}
+
+impl CredentialsCallback {
+    #[must_use]
+    pub fn new(callback: Py<PyAny>) -> Self {
+        Self { callback }
+    }
+
+    /// Call the callable and extract username and password.
+    fn credentials(callback: &Py<PyAny>) -> Result<(String, String), String> {
+        Python::with_gil(|py| {
+            let credentials = callback.call0(py).map_err(|err| err.to_string())?;
+            if credentials.as_ref(py).hasattr("__await__").unwrap_or(false) {
+                return Err("the callable should return a tuple, not an awaitable".into());
+            }
+            credentials.extract(py).map_err(|err| err.to_string())
+        })
+    }
+}
+
+#[async_trait]
+impl AuthenticatorProvider for CredentialsCallback {
+    async fn start_authentication_session(
+        &self,
+        authenticator_name: &str,
+    ) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        let callback = self.callback.clone();
+        let (username, password) =
+            tokio::task::spawn_blocking(move || Self::credentials(&callback))
+                .await
+                .unwrap_or_else(|err| Err(err.to_string()))
+                .map_err(|err| format!("Cannot get credentials: {err}"))?;
+        PlainTextAuthenticator::new(username, password)
+            .start_authentication_session(authenticator_name)
+            .await
+    }
+}
@@ -0,0 +1,205 @@
+use std::{mem, num::NonZeroUsize, sync::Arc, time::Duration};
+
+use pyo3::{pyclass, pymethods, Py, PyAny, Python};
+use scylla::{
+    batch::{Batch, BatchStatement},
+    frame::value::LegacySerializedValues,
+    Session,
+};
+use tokio::{
+    sync::{mpsc, RwLock},
+    time::Instant,
+};
+
+use crate::{
+    batches::parse_batch_values,
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    inputs::BatchQueryInput,
+    utils::scyllapy_future,
+    writer::{WriterCommand, WriterHandle},
+};
+
+/// Statement with its values.
+type Statement = (BatchStatement, LegacySerializedValues);
+
+/// Settings of the batch writer.
+#[derive(Clone, Copy, Debug)]
+pub struct BatchWriterConfig {
+    pub max_batch_size: NonZeroUsize,
+    pub flush_interval: Duration,
+    pub buffer_size: NonZeroUsize,
+}
+
+/// Writer that groups statements into batches.
+///
+/// Statements are buffered and sent in batches,
+/// when batch reaches `max_batch_size` statements,
+/// or when `flush_interval` passes since the last flush.
+///
+/// If the internal buffer is full, adding new statements
+/// waits until previous batches are sent.
+#[pyclass(name = "BatchWriter")]
+pub struct ScyllaPyBatchWriter {
+    handle: WriterHandle<Statement>,
+}
+
+impl ScyllaPyBatchWriter {
+    /// Start new writer.
+    ///
+    /// All batches are created from the template batch,
+    /// so they have the same type and parameters.
+    #[must_use]
+    pub fn start(
+        session: Arc<RwLock<Option<Session>>>,
+        template: Batch,
+        config: BatchWriterConfig,
+    ) -> Self {
+        Self {
+            handle: WriterHandle::spawn("Batch writer", config.buffer_size, |receiver| {
+                run_writer(session, template, config, receiver)
+            }),
+        }
+    }
+}
+
+/// Send a batch with buffered statements.
+async fn flush_batch(
+    session: &RwLock<Option<Session>>,
+    template: &Batch,
+    statements: &mut Vec<BatchStatement>,
+    values: &mut Vec<LegacySerializedValues>,
+) -> ScyllaPyResult<()> {
+    if statements.is_empty() {
+        return Ok(());
+    }
+    let mut batch = template.clone();
+    batch.statements = mem::take(statements);
+    let values = mem::take(values);
+    let session_guard = session.read().await;
+    let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+        "Session is not initialized.".into(),
+    ))?;
+    session.batch(&batch, values).await?;
+    Ok(())
+}
+
+/// Main loop of the writer.
+///
+/// Errors of batches sent in background are kept
+/// and returned on the next flush or when writer stops.
+async fn run_writer(
+    session: Arc<RwLock<Option<Session>>>,
+    template: Batch,
+    config: BatchWriterConfig,
+    mut receiver: mpsc::Receiver<WriterCommand<Statement>>,
+) -> ScyllaPyResult<()> {
+    let mut statements = Vec::with_capacity(config.max_batch_size.get());
+    let mut values = Vec::with_capacity(config.max_batch_size.get());
+    let mut error = None;
+    let mut deadline = Instant::now() + config.flush_interval;
+    loop {
+        let command = match tokio::time::timeout_at(deadline, receiver.recv()).await {
+            Ok(Some(command)) => Some(command),
+            // All senders are dropped, so writer is closed.
+            Ok(None) => break,
+            // Flush interval has passed.
+            Err(_) => None,
+        };
+        match command {
+            Some(WriterCommand::Add((statement, statement_values))) => {
+                statements.push(statement);
+                values.push(statement_values);
+                if statements.len() < config.max_batch_size.get() {
+                    continue;
+                }
+                if let Err(err) =
+                    flush_batch(&session, &template, &mut statements, &mut values).await
+                {
+                    error.get_or_insert(err);
+                }
+            }
+            Some(WriterCommand::Flush(reply)) => {
+                let res = flush_batch(&session, &template, &mut statements, &mut values).await;
+                reply.send(error.take().map_or(res, Err)).ok();
+            }
+            None => {
+                if let Err(err) =
+                    flush_batch(&session, &template, &mut statements, &mut values).await
+                {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+        deadline = Instant::now() + config.flush_interval;
+    }
+    flush_batch(&session, &template, &mut statements, &mut values).await?;
+    error.map_or(Ok(()), Err)
+}
+
+#[pymethods]
+impl ScyllaPyBatchWriter {
+    /// Add statement to the writer.
+    ///
+    /// Values are parsed the same way
+    /// as in `InlineBatch.add_query`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if values are incorrect,
+    /// or if writer is closed.
+    #[pyo3(signature = (query, values = None))]
+    pub fn add<'a>(
+        &self,
+        py: Python<'a>,
+        query: BatchQueryInput,
+        values: Option<&'a PyAny>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let query_values = parse_batch_values(py, &query, values)?;
+        self.handle
+            .add(py, (BatchStatement::from(query), query_values))
+    }
+
+    /// Send all buffered statements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending fails,
+    /// or if any batch sent in background has failed.
+    pub fn flush<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        self.handle.flush(py)
+    }
+
+    /// Send all buffered statements and stop the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sending fails,
+    /// or if any batch sent in background has failed.
+    pub fn close<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        self.handle.close(py)
+    }
+
+    /// Start using writer as a context manager.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if future cannot be created.
+    pub fn __aenter__<'a>(slf: Py<Self>, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        scyllapy_future(py, async move { Ok(slf) })
+    }
+
+    /// Close writer when leaving context manager.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors from `close`.
+    pub fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &PyAny,
+        _exc: &PyAny,
+        _traceback: &PyAny,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        self.close(py)
+    }
+}
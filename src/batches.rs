@@ -1,15 +1,28 @@
-use pyo3::{pyclass, pymethods, types::PyDict, PyAny};
+use std::{collections::HashMap, future::Future, time::Duration};
+
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyBytes, PyDict, PyList, PyType},
+    FromPyObject, IntoPy, Py, PyAny, PyResult, Python,
+};
 use scylla::{
     batch::{Batch, BatchStatement, BatchType},
-    frame::value::LegacySerializedValues,
+    frame::{response::result::ColumnSpec, value::LegacySerializedValues},
+    query::Query,
+    transport::errors::{DbError, QueryError},
 };
 
 use crate::{
-    exceptions::rust_err::ScyllaPyResult, inputs::BatchQueryInput, queries::ScyllaPyRequestParams,
-    utils::parse_python_query_params,
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    inputs::{BatchInput, BatchQueryInput, BatchStatementInput},
+    queries::ScyllaPyRequestParams,
+    query_builder::utils::{prepared_keyspace_error, qualify_statement},
+    scylla_cls::Scylla,
+    utils::{check_named_params, parse_python_query_params, reduce_enum_variant},
 };
 
-#[pyclass(name = "BatchType")]
+#[pyclass(name = "BatchType", module = "scyllapy._internal")]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ScyllaPyBatchType {
     COUNTER,
@@ -17,19 +30,561 @@ pub enum ScyllaPyBatchType {
     UNLOGGED,
 }
 
-#[pyclass(name = "Batch")]
+#[pyclass(name = "Batch", module = "scyllapy._internal")]
 #[derive(Clone)]
 pub struct ScyllaPyBatch {
     inner: Batch,
     request_params: ScyllaPyRequestParams,
+    ttl: Option<i32>,
+    retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+    limits: BatchLimits,
+    // Values of statements added from query builders.
+    values: Vec<Option<LegacySerializedValues>>,
 }
 
-#[pyclass(name = "InlineBatch")]
+#[pyclass(name = "InlineBatch", module = "scyllapy._internal")]
 #[derive(Clone)]
 pub struct ScyllaPyInlineBatch {
     inner: Batch,
     request_params: ScyllaPyRequestParams,
+    ttl: Option<i32>,
+    retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+    limits: BatchLimits,
     values: Vec<LegacySerializedValues>,
+    // Python values, which statements were added with.
+    params: Vec<Option<Py<PyAny>>>,
+}
+
+/// Limits for batch size.
+///
+/// Checked before batch is sent,
+/// so too large batches are not sent to the server.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BatchLimits {
+    pub max_statements: Option<usize>,
+    pub max_size: Option<usize>,
+}
+
+impl BatchLimits {
+    /// Check that batch fits into limits.
+    ///
+    /// Size of the batch is calculated as size
+    /// of statements and their serialized values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if batch exceeds limits.
+    pub fn check(&self, batch: &Batch, values: &[LegacySerializedValues]) -> ScyllaPyResult<()> {
+        if let Some(max_statements) = self.max_statements {
+            let statements = batch.statements.len();
+            if statements > max_statements {
+                return Err(ScyllaPyError::BatchSizeError(format!(
+                    "batch has {statements} statements, but only {max_statements} are allowed"
+                )));
+            }
+        }
+        if let Some(max_size) = self.max_size {
+            let statements_size = batch
+                .statements
+                .iter()
+                .map(|statement| match statement {
+                    BatchStatement::Query(query) => query.contents.len(),
+                    BatchStatement::PreparedStatement(prepared) => prepared.get_id().len(),
+                })
+                .sum::<usize>();
+            let size = statements_size
+                + values
+                    .iter()
+                    .map(LegacySerializedValues::size)
+                    .sum::<usize>();
+            if size > max_size {
+                return Err(ScyllaPyError::BatchSizeError(format!(
+                    "batch size is {size} bytes, but only {max_size} bytes are allowed"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Retry policy for idempotent batches.
+///
+/// Whole batch is retried on timeouts
+/// with exponential backoff, capped by `max_delay`.
+/// If `jitter` is enabled, random part of the delay is used.
+#[pyclass(name = "BatchRetryPolicy", module = "scyllapy._internal")]
+#[derive(Clone, Copy, Debug)]
+pub struct ScyllaPyBatchRetryPolicy {
+    #[pyo3(get)]
+    max_retries: u32,
+    #[pyo3(get)]
+    base_delay: f64,
+    #[pyo3(get)]
+    max_delay: f64,
+    #[pyo3(get)]
+    jitter: bool,
+}
+
+impl ScyllaPyBatchRetryPolicy {
+    /// Delay before the retry with the given number.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = (self.base_delay * 2f64.powf(f64::from(attempt))).min(self.max_delay);
+        if !self.jitter {
+            return Duration::from_secs_f64(delay);
+        }
+        Duration::from_secs_f64(delay * rand::random::<f64>())
+    }
+
+    /// Run the request, retrying it on timeouts.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error, if all retries failed,
+    /// or the first error that is not a timeout.
+    pub async fn run<T, F, Fut>(&self, request: F) -> Result<T, QueryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, QueryError>>,
+    {
+        self.run_if(is_timeout, request).await
+    }
+
+    /// Run the request, retrying errors for which `retryable` returns true.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error, if all retries failed,
+    /// or the first error that cannot be retried.
+    pub async fn run_if<T, F, Fut>(
+        &self,
+        retryable: fn(&QueryError) -> bool,
+        mut request: F,
+    ) -> Result<T, QueryError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, QueryError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request().await {
+                Err(err) if attempt < self.max_retries && retryable(&err) => {
+                    tokio::time::sleep(self.delay(attempt)).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl ScyllaPyBatchRetryPolicy {
+    /// Create new retry policy.
+    ///
+    /// Delays are in seconds.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if delays are negative.
+    #[new]
+    #[pyo3(signature = (max_retries = 3, base_delay = 0.1, max_delay = 1.0, jitter = true))]
+    pub fn py_new(
+        max_retries: u32,
+        base_delay: f64,
+        max_delay: f64,
+        jitter: bool,
+    ) -> PyResult<Self> {
+        for delay in [base_delay, max_delay] {
+            if !delay.is_finite() || delay < 0.0 {
+                return Err(PyValueError::new_err(
+                    "Delays should be non-negative numbers.",
+                ));
+            }
+        }
+        Ok(Self {
+            max_retries,
+            base_delay,
+            max_delay,
+            jitter,
+        })
+    }
+
+    /// Pickle retry policy.
+    #[must_use]
+    pub fn __reduce__<'a>(&self, py: Python<'a>) -> (&'a PyType, (u32, f64, f64, bool)) {
+        (
+            py.get_type::<Self>(),
+            (
+                self.max_retries,
+                self.base_delay,
+                self.max_delay,
+                self.jitter,
+            ),
+        )
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// Parse values of the statement for batches.
+///
+/// If query is prepared, values are parsed
+/// according to its column specs. Also, for prepared
+/// queries values can be passed as a dict.
+///
+/// # Errors
+///
+/// Will result in an error, if
+/// values are incorrect.
+pub fn parse_batch_values(
+    py: Python<'_>,
+    query: &BatchQueryInput,
+    values: Option<&PyAny>,
+) -> ScyllaPyResult<LegacySerializedValues> {
+    let col_spec = match query {
+        BatchQueryInput::PreparedQuery(prepared) => {
+            Some(prepared.inner.get_prepared_metadata().col_specs.as_slice())
+        }
+        BatchQueryInput::BoundStatement(bound) => {
+            if values.is_some() {
+                return Err(bound_values_error());
+            }
+            return Ok(bound.values.clone());
+        }
+        _ => None,
+    };
+    let mut values = values;
+    if let Some(named_values) = values.and_then(|vals| vals.downcast::<PyDict>().ok()) {
+        let Some(col_spec) = col_spec else {
+            return Err(ScyllaPyError::BindingError(
+                "Named parameters in batches are supported only for prepared queries".into(),
+            ));
+        };
+        values = Some(named_to_positional(py, named_values, col_spec)?);
+    }
+    parse_python_query_params(values, false, col_spec)
+}
+
+/// Order named values according to column specs.
+///
+/// Batches don't support named values,
+/// so we resolve names using prepared statement's metadata.
+/// Names are lowercased, as in named values of queries.
+///
+/// # Errors
+///
+/// If names don't match parameters of the query.
+pub fn named_to_positional<'a>(
+    py: Python<'a>,
+    named_values: &'a PyDict,
+    col_spec: &[ColumnSpec],
+) -> ScyllaPyResult<&'a PyAny> {
+    let named_values = named_values
+        .iter()
+        .map(|(name, value)| Ok((name.extract::<&str>()?.to_lowercase(), value)))
+        .collect::<PyResult<HashMap<_, _>>>()?;
+    check_named_params(named_values.keys().map(String::as_str), col_spec)?;
+    let mut positional = Vec::with_capacity(col_spec.len());
+    for spec in col_spec {
+        let value = named_values.get(&spec.name).ok_or_else(|| {
+            ScyllaPyError::BindingError(format!("Value for parameter `{}` is missing", spec.name))
+        })?;
+        positional.push(*value);
+    }
+    Ok(PyList::new(py, positional).as_ref())
+}
+
+/// Error for values passed along with bound statement.
+#[must_use]
+pub fn bound_values_error() -> ScyllaPyError {
+    ScyllaPyError::BindingError("Bound statement already has values".into())
+}
+
+/// Check whether error is caused by timeout.
+#[must_use]
+pub fn is_timeout(err: &QueryError) -> bool {
+    matches!(
+        err,
+        QueryError::TimeoutError
+            | QueryError::RequestTimeout(_)
+            | QueryError::DbError(
+                DbError::ReadTimeout { .. } | DbError::WriteTimeout { .. },
+                _
+            )
+    )
+}
+
+/// Only idempotent batches can be retried.
+fn check_retry_policy(
+    retry_policy: Option<&ScyllaPyBatchRetryPolicy>,
+    request_params: &ScyllaPyRequestParams,
+) -> ScyllaPyResult<()> {
+    if retry_policy.is_some() && request_params.is_idempotent != Some(true) {
+        return Err(PyValueError::new_err(
+            "Retry policy can be used only with idempotent batches.",
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Words of the statement with their positions.
+///
+/// Only top-level words are returned, so words inside
+/// literals, comments, quoted names, brackets and bind markers are skipped.
+#[must_use]
+pub fn top_level_words(query: &str) -> Vec<(usize, &str)> {
+    let bytes = query.as_bytes();
+    let is_word = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let skip_until = |from: usize, end: &str| {
+        query[from..]
+            .find(end)
+            .map_or(bytes.len(), |pos| from + pos + end.len())
+    };
+    let mut words = Vec::new();
+    let mut depth = 0usize;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        match bytes[idx] {
+            quote @ (b'\'' | b'"') => {
+                idx += 1;
+                // Quotes are escaped by doubling them.
+                while idx < bytes.len() {
+                    if bytes[idx] == quote {
+                        if bytes.get(idx + 1) != Some(&quote) {
+                            break;
+                        }
+                        idx += 1;
+                    }
+                    idx += 1;
+                }
+                idx += 1;
+            }
+            b'$' if bytes.get(idx + 1) == Some(&b'$') => idx = skip_until(idx + 2, "$$"),
+            b'-' if bytes.get(idx + 1) == Some(&b'-') => idx = skip_until(idx, "\n"),
+            b'/' if bytes.get(idx + 1) == Some(&b'/') => idx = skip_until(idx, "\n"),
+            b'/' if bytes.get(idx + 1) == Some(&b'*') => idx = skip_until(idx + 2, "*/"),
+            b'(' | b'[' | b'{' => {
+                depth += 1;
+                idx += 1;
+            }
+            b')' | b']' | b'}' => {
+                depth = depth.saturating_sub(1);
+                idx += 1;
+            }
+            b':' => {
+                idx += 1;
+                while idx < bytes.len() && is_word(bytes[idx]) {
+                    idx += 1;
+                }
+            }
+            byte if is_word(byte) => {
+                let start = idx;
+                while idx < bytes.len() && is_word(bytes[idx]) {
+                    idx += 1;
+                }
+                if depth == 0 {
+                    words.push((start, &query[start..idx]));
+                }
+            }
+            _ => idx += 1,
+        }
+    }
+    words
+}
+
+/// Add TTL to the statement, if it doesn't have one.
+///
+/// Only `INSERT` and `UPDATE` statements are changed,
+/// because other statements cannot have TTL.
+/// TTL is added to the `USING` clause, which goes
+/// before `SET` in updates and at the end of inserts.
+/// Returns `None` if statement stays the same.
+///
+/// # Errors
+///
+/// Returns an error if place for TTL cannot be found.
+fn add_default_ttl(query: &str, ttl: i32) -> ScyllaPyResult<Option<String>> {
+    let words = top_level_words(query);
+    let keyword = |idx: usize, name: &str| {
+        words
+            .get(idx)
+            .is_some_and(|(_, word)| word.eq_ignore_ascii_case(name))
+    };
+    let is_insert = keyword(0, "INSERT");
+    if !is_insert && !keyword(0, "UPDATE") {
+        return Ok(None);
+    }
+    let set_idx = words
+        .iter()
+        .position(|(_, word)| word.eq_ignore_ascii_case("SET"));
+    if !is_insert && set_idx.is_none() {
+        return Err(PyValueError::new_err(format!(
+            "Cannot find where to add TTL in statement `{query}`."
+        ))
+        .into());
+    }
+    let using_idx = (0..set_idx.unwrap_or(words.len())).find(|idx| keyword(*idx, "USING"));
+    if let Some(using_idx) = using_idx {
+        // Clause has only `TTL`, `TIMESTAMP`, `AND` and values.
+        let clause_end = if is_insert {
+            words.len()
+        } else {
+            set_idx.unwrap_or(words.len())
+        };
+        if (using_idx + 1..clause_end).any(|idx| keyword(idx, "TTL")) {
+            return Ok(None);
+        }
+        let (start, word) = words[using_idx];
+        let (head, tail) = query.split_at(start + word.len());
+        return Ok(Some(format!("{head} TTL {ttl} AND{tail}")));
+    }
+    if is_insert {
+        let query = query.trim_end();
+        let query = query.strip_suffix(';').unwrap_or(query);
+        return Ok(Some(format!("{query} USING TTL {ttl}")));
+    }
+    let (start, _) = words[set_idx.unwrap_or_default()];
+    let (head, tail) = query.split_at(start);
+    Ok(Some(format!("{head}USING TTL {ttl} {tail}")))
+}
+
+/// State of pickled batch.
+#[derive(FromPyObject)]
+pub struct BatchState {
+    #[pyo3(item)]
+    statements: Vec<String>,
+    #[pyo3(item)]
+    ttl: Option<i32>,
+    #[pyo3(item)]
+    params: Py<PyDict>,
+    #[pyo3(item)]
+    retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+    #[pyo3(item)]
+    max_statements: Option<usize>,
+    #[pyo3(item)]
+    max_size: Option<usize>,
+    #[pyo3(item)]
+    values: Vec<Option<(Vec<u8>, bool)>>,
+}
+
+impl BatchState {
+    /// Restore serialized values of statements.
+    fn load_values(&self) -> ScyllaPyResult<Vec<Option<LegacySerializedValues>>> {
+        self.values
+            .iter()
+            .map(|query_values| {
+                query_values
+                    .as_ref()
+                    .map(|(values, has_names)| {
+                        LegacySerializedValues::new_from_frame(&mut values.as_slice(), *has_names)
+                            .map_err(|err| ScyllaPyError::BindingError(err.to_string()))
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+}
+
+/// Texts of all statements in the batch.
+pub fn statement_texts(batch: &Batch) -> Vec<String> {
+    batch
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            BatchStatement::Query(query) => query.contents.clone(),
+            BatchStatement::PreparedStatement(prepared) => prepared.get_statement().to_string(),
+        })
+        .collect()
+}
+
+/// Build state of the batch for pickling.
+fn batch_state<'a>(
+    py: Python<'a>,
+    batch: &Batch,
+    ttl: Option<i32>,
+    request_params: &ScyllaPyRequestParams,
+    retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+    limits: BatchLimits,
+    values: &[Option<&LegacySerializedValues>],
+) -> ScyllaPyResult<&'a PyDict> {
+    let statements = statement_texts(batch);
+    let values = values
+        .iter()
+        .map(|query_values| {
+            query_values.map(|query_values| {
+                let mut buf = Vec::new();
+                query_values.write_to_request(&mut buf);
+                (PyBytes::new(py, &buf), query_values.has_names())
+            })
+        })
+        .collect::<Vec<_>>();
+    let state = PyDict::new(py);
+    state.set_item("statements", statements)?;
+    state.set_item("ttl", ttl)?;
+    state.set_item("params", request_params.to_dict(py)?)?;
+    state.set_item(
+        "retry_policy",
+        retry_policy.map(|policy| policy.into_py(py)),
+    )?;
+    state.set_item("max_statements", limits.max_statements)?;
+    state.set_item("max_size", limits.max_size)?;
+    state.set_item("values", values)?;
+    Ok(state)
+}
+
+/// Qualify tables of batch statements with keyspace.
+///
+/// # Errors
+///
+/// Returns an error for prepared statements, because their
+/// text cannot be changed, and for statements without table.
+pub fn qualify_batch(batch: &mut Batch, keyspace: &str) -> ScyllaPyResult<()> {
+    for statement in &mut batch.statements {
+        match statement {
+            BatchStatement::Query(query) => {
+                query.contents = qualify_statement(&query.contents, keyspace)?;
+            }
+            BatchStatement::PreparedStatement(prepared) => {
+                return Err(prepared_keyspace_error(prepared.get_statement()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Apply default TTL of the batch to the statement.
+///
+/// # Errors
+///
+/// Returns an error for prepared inserts and updates
+/// without their own TTL, because their text cannot be changed.
+fn apply_default_ttl(statement: &mut BatchStatement, ttl: Option<i32>) -> ScyllaPyResult<()> {
+    let Some(ttl) = ttl else {
+        return Ok(());
+    };
+    match statement {
+        BatchStatement::Query(query) => {
+            if let Some(contents) = add_default_ttl(&query.contents, ttl)? {
+                query.contents = contents;
+            }
+        }
+        BatchStatement::PreparedStatement(prepared) => {
+            if add_default_ttl(prepared.get_statement(), ttl)?.is_some() {
+                return Err(PyValueError::new_err(format!(
+                    "Default TTL cannot be added to prepared statement `{}`. \
+                    Add `USING TTL` to its text instead.",
+                    prepared.get_statement()
+                ))
+                .into());
+            }
+        }
+    }
+    Ok(())
 }
 
 impl From<ScyllaPyBatch> for Batch {
@@ -51,6 +606,14 @@ impl From<ScyllaPyInlineBatch> for (Batch, Vec<LegacySerializedValues>) {
 impl ScyllaPyBatch {
     /// Create new batch.
     ///
+    /// If `ttl` is set, it's added to all
+    /// inserts and updates that don't have their own TTL,
+    /// when they're added to the batch. Prepared statements
+    /// cannot be changed, so they must have their own TTL.
+    ///
+    /// `max_statements` and `max_size` limit
+    /// the number of statements and size of the batch in bytes.
+    ///
     /// # Errors
     ///
     /// Can return an error in case if
@@ -58,28 +621,239 @@ impl ScyllaPyBatch {
     #[new]
     #[pyo3(signature = (
         batch_type = ScyllaPyBatchType::UNLOGGED,
+        *,
+        ttl = None,
+        retry_policy = None,
+        max_statements = None,
+        max_size = None,
         **params
     ))]
-    pub fn py_new(batch_type: ScyllaPyBatchType, params: Option<&PyDict>) -> ScyllaPyResult<Self> {
+    pub fn py_new(
+        batch_type: ScyllaPyBatchType,
+        ttl: Option<i32>,
+        retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+        max_statements: Option<usize>,
+        max_size: Option<usize>,
+        params: Option<&PyDict>,
+    ) -> ScyllaPyResult<Self> {
+        let request_params = ScyllaPyRequestParams::from_dict(params)?;
+        check_retry_policy(retry_policy.as_ref(), &request_params)?;
         Ok(Self {
             inner: Batch::new(batch_type.into()),
-            request_params: ScyllaPyRequestParams::from_dict(params)?,
+            request_params,
+            ttl,
+            retry_policy,
+            limits: BatchLimits {
+                max_statements,
+                max_size,
+            },
+            values: vec![],
         })
     }
 
-    pub fn add_query(&mut self, query: BatchQueryInput) {
-        self.inner.append_statement(query);
+    /// Add query to batch.
+    ///
+    /// Query builders and bound statements
+    /// are added along with their values.
+    /// Parameters passed to `execute` are used only
+    /// for statements without values, in the same order.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if query builder
+    /// cannot build a query.
+    pub fn add_query(&mut self, query: BatchStatementInput) -> ScyllaPyResult<()> {
+        let statements = match query {
+            BatchStatementInput::Query(BatchQueryInput::BoundStatement(bound)) => vec![(
+                BatchStatement::PreparedStatement(bound.prepared.into()),
+                Some(bound.values),
+            )],
+            BatchStatementInput::Query(query) => vec![(BatchStatement::from(query), None)],
+            BatchStatementInput::Builder(builder) => builder
+                .batch_statements()?
+                .into_iter()
+                .map(|(query, values)| (BatchStatement::Query(query), Some(values)))
+                .collect(),
+        };
+        for (mut statement, values) in statements {
+            apply_default_ttl(&mut statement, self.ttl)?;
+            self.inner.append_statement(statement);
+            self.values.push(values);
+        }
+        Ok(())
+    }
+
+    /// Texts of added statements.
+    #[getter]
+    #[must_use]
+    pub fn statements(&self) -> Vec<String> {
+        statement_texts(&self.inner)
+    }
+
+    /// Execute the batch.
+    ///
+    /// It's the same as calling `batch` method of `Scylla`.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors from `Scylla.batch`.
+    #[pyo3(signature = (scylla, params = None))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<Vec<&'a PyAny>>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        scylla.batch(py, BatchInput::Batch(self.clone()), params, None)
+    }
+
+    /// Pickle batch.
+    ///
+    /// Prepared statements are pickled as
+    /// their query text, so unpickled batch sends
+    /// them unprepared. Parameters of statements are
+    /// dropped, only parameters of the batch are kept.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if batch has
+    /// execution profile, because profiles cannot be pickled.
+    pub fn __reduce__<'a>(
+        &self,
+        py: Python<'a>,
+    ) -> ScyllaPyResult<(&'a PyType, (ScyllaPyBatchType,), &'a PyDict)> {
+        let state = batch_state(
+            py,
+            &self.inner,
+            self.ttl,
+            &self.request_params,
+            self.retry_policy,
+            self.limits,
+            &self.values.iter().map(Option::as_ref).collect::<Vec<_>>(),
+        )?;
+        Ok((
+            py.get_type::<Self>(),
+            (self.inner.get_type().into(),),
+            state,
+        ))
+    }
+
+    /// Restore pickled batch.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if state is incorrect.
+    pub fn __setstate__(&mut self, py: Python<'_>, state: BatchState) -> ScyllaPyResult<()> {
+        self.inner.statements = state
+            .statements
+            .into_iter()
+            .map(|statement| BatchStatement::Query(Query::new(statement)))
+            .collect();
+        self.ttl = state.ttl;
+        self.request_params = ScyllaPyRequestParams::from_dict(Some(state.params.as_ref(py)))?;
+        self.retry_policy = state.retry_policy;
+        self.limits = BatchLimits {
+            max_statements: state.max_statements,
+            max_size: state.max_size,
+        };
+        self.values = state.load_values()?;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
+
+impl ScyllaPyBatch {
+    #[must_use]
+    pub fn retry_policy(&self) -> Option<ScyllaPyBatchRetryPolicy> {
+        self.retry_policy
+    }
+
+    #[must_use]
+    pub fn limits(&self) -> BatchLimits {
+        self.limits
+    }
+
+    /// Combine passed parameters with values of query builders.
+    ///
+    /// Passed parameters are used for statements
+    /// without values, in the same order.
+    /// If no parameters are passed, such statements
+    /// are executed without values.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if number of passed parameters
+    /// doesn't match number of statements without values.
+    pub fn into_batch_with_values(
+        mut self,
+        params: Vec<LegacySerializedValues>,
+    ) -> ScyllaPyResult<(Batch, Vec<LegacySerializedValues>)> {
+        let expected = self.values.iter().filter(|values| values.is_none()).count();
+        if !params.is_empty() && params.len() != expected {
+            return Err(ScyllaPyError::BindingError(format!(
+                "Batch has {expected} statements without values, but {} parameters were passed",
+                params.len()
+            )));
+        }
+        let mut params = params.into_iter();
+        let values = std::mem::take(&mut self.values)
+            .into_iter()
+            .map(|query_values| {
+                query_values
+                    .unwrap_or_else(|| params.next().unwrap_or_else(LegacySerializedValues::new))
+            })
+            .collect();
+        Ok((self.into(), values))
     }
 }
 
 impl ScyllaPyInlineBatch {
+    #[must_use]
+    pub fn retry_policy(&self) -> Option<ScyllaPyBatchRetryPolicy> {
+        self.retry_policy
+    }
+
+    #[must_use]
+    pub fn limits(&self) -> BatchLimits {
+        self.limits
+    }
+
+    /// Add statement with its values.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if default TTL
+    /// cannot be added to the statement.
     pub fn add_query_inner(
         &mut self,
         query: impl Into<BatchStatement>,
         values: impl Into<LegacySerializedValues>,
-    ) {
-        self.inner.append_statement(query);
-        self.values.push(values.into());
+    ) -> ScyllaPyResult<()> {
+        self.add_statement(query.into(), values.into(), None)
+    }
+
+    /// Add statement with its values and python values,
+    /// which it was added with.
+    fn add_statement(
+        &mut self,
+        mut statement: BatchStatement,
+        values: LegacySerializedValues,
+        params: Option<Py<PyAny>>,
+    ) -> ScyllaPyResult<()> {
+        apply_default_ttl(&mut statement, self.ttl)?;
+        self.inner.append_statement(statement);
+        self.values.push(values);
+        self.params.push(params);
+        Ok(())
     }
 }
 
@@ -87,6 +861,14 @@ impl ScyllaPyInlineBatch {
 impl ScyllaPyInlineBatch {
     /// Create new batch.
     ///
+    /// If `ttl` is set, it's added to all
+    /// inserts and updates that don't have their own TTL,
+    /// when they're added to the batch. Prepared statements
+    /// cannot be changed, so they must have their own TTL.
+    ///
+    /// `max_statements` and `max_size` limit
+    /// the number of statements and size of the batch in bytes.
+    ///
     /// # Errors
     ///
     /// Can return an error in case if
@@ -94,22 +876,93 @@ impl ScyllaPyInlineBatch {
     #[new]
     #[pyo3(signature = (
         batch_type = ScyllaPyBatchType::UNLOGGED,
+        *,
+        ttl = None,
+        retry_policy = None,
+        max_statements = None,
+        max_size = None,
         **params
     ))]
-    pub fn py_new(batch_type: ScyllaPyBatchType, params: Option<&PyDict>) -> ScyllaPyResult<Self> {
+    pub fn py_new(
+        batch_type: ScyllaPyBatchType,
+        ttl: Option<i32>,
+        retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+        max_statements: Option<usize>,
+        max_size: Option<usize>,
+        params: Option<&PyDict>,
+    ) -> ScyllaPyResult<Self> {
+        let request_params = ScyllaPyRequestParams::from_dict(params)?;
+        check_retry_policy(retry_policy.as_ref(), &request_params)?;
         Ok(Self {
             inner: Batch::new(batch_type.into()),
-            request_params: ScyllaPyRequestParams::from_dict(params)?,
+            request_params,
+            ttl,
+            retry_policy,
+            limits: BatchLimits {
+                max_statements,
+                max_size,
+            },
             values: vec![],
+            params: vec![],
         })
     }
 
+    /// Create batch from statements.
+    ///
+    /// This function takes a list of pairs
+    /// of statements and their values.
+    ///
+    /// # Errors
+    ///
+    /// Can return an error in case if
+    /// wrong type for parameters were passed,
+    /// or values are incorrect.
+    #[classmethod]
+    #[pyo3(signature = (
+        statements,
+        batch_type = ScyllaPyBatchType::UNLOGGED,
+        *,
+        ttl = None,
+        retry_policy = None,
+        max_statements = None,
+        max_size = None,
+        **params
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_statements(
+        cls: &PyType,
+        statements: Vec<(BatchQueryInput, Option<&PyAny>)>,
+        batch_type: ScyllaPyBatchType,
+        ttl: Option<i32>,
+        retry_policy: Option<ScyllaPyBatchRetryPolicy>,
+        max_statements: Option<usize>,
+        max_size: Option<usize>,
+        params: Option<&PyDict>,
+    ) -> ScyllaPyResult<Self> {
+        let mut batch = Self::py_new(
+            batch_type,
+            ttl,
+            retry_policy,
+            max_statements,
+            max_size,
+            params,
+        )?;
+        for (query, values) in statements {
+            batch.add_query(cls.py(), query, values)?;
+        }
+        Ok(batch)
+    }
+
     /// Add query to batch.
     ///
     /// This function appends query to batch.
     /// along with values, so you don't need to
     /// pass values in execute.
     ///
+    /// If query is prepared, values are parsed
+    /// according to its column specs. Also, for prepared
+    /// queries values can be passed as a dict.
+    ///
     /// # Errors
     ///
     /// Will result in an error, if
@@ -117,18 +970,150 @@ impl ScyllaPyInlineBatch {
     #[pyo3(signature = (query, values = None))]
     pub fn add_query(
         &mut self,
+        py: Python<'_>,
         query: BatchQueryInput,
         values: Option<&PyAny>,
     ) -> ScyllaPyResult<()> {
-        self.inner.append_statement(query);
-        if let Some(passed_params) = values {
-            self.values
-                .push(parse_python_query_params(Some(passed_params), false, None)?);
-        } else {
-            self.values.push(LegacySerializedValues::new());
+        let query_values = parse_batch_values(py, &query, values)?;
+        self.add_statement(query.into(), query_values, values.map(Into::into))
+    }
+
+    /// Extend batch with another batch.
+    ///
+    /// Statements and values of another batch
+    /// are appended to the current one.
+    /// Type and parameters of the current batch stay the same,
+    /// so its default TTL is added to appended statements.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if default TTL
+    /// cannot be added to statements.
+    pub fn extend(&mut self, other: ScyllaPyInlineBatch) -> ScyllaPyResult<()> {
+        let statements = other.inner.statements.into_iter().zip(other.values);
+        for ((statement, values), params) in statements.zip(other.params) {
+            self.add_statement(statement, values, params)?;
         }
         Ok(())
     }
+
+    /// Texts of added statements.
+    #[getter]
+    #[must_use]
+    pub fn statements(&self) -> Vec<String> {
+        statement_texts(&self.inner)
+    }
+
+    /// Python values, which statements were added with.
+    ///
+    /// Values of bound statements, query builders
+    /// and unpickled batches are `None`.
+    #[getter]
+    #[must_use]
+    pub fn params(&self) -> Vec<Option<Py<PyAny>>> {
+        self.params.clone()
+    }
+
+    /// Execute the batch.
+    ///
+    /// It's the same as calling `batch` method of `Scylla`.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors from `Scylla.batch`.
+    pub fn execute<'a>(&'a self, py: Python<'a>, scylla: &'a Scylla) -> ScyllaPyResult<&'a PyAny> {
+        scylla.batch(py, BatchInput::InlineBatch(self.clone()), None, None)
+    }
+
+    /// Pickle batch.
+    ///
+    /// Prepared statements are pickled as
+    /// their query text, so unpickled batch sends
+    /// them unprepared. Parameters of statements are
+    /// dropped, only parameters of the batch are kept.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if batch has
+    /// execution profile, because profiles cannot be pickled.
+    pub fn __reduce__<'a>(
+        &self,
+        py: Python<'a>,
+    ) -> ScyllaPyResult<(&'a PyType, (ScyllaPyBatchType,), &'a PyDict)> {
+        let state = batch_state(
+            py,
+            &self.inner,
+            self.ttl,
+            &self.request_params,
+            self.retry_policy,
+            self.limits,
+            &self.values.iter().map(Some).collect::<Vec<_>>(),
+        )?;
+        Ok((
+            py.get_type::<Self>(),
+            (self.inner.get_type().into(),),
+            state,
+        ))
+    }
+
+    /// Restore pickled batch.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if state is incorrect.
+    pub fn __setstate__(&mut self, py: Python<'_>, state: BatchState) -> ScyllaPyResult<()> {
+        self.inner.statements = state
+            .statements
+            .into_iter()
+            .map(|statement| BatchStatement::Query(Query::new(statement)))
+            .collect();
+        self.ttl = state.ttl;
+        self.request_params = ScyllaPyRequestParams::from_dict(Some(state.params.as_ref(py)))?;
+        self.retry_policy = state.retry_policy;
+        self.limits = BatchLimits {
+            max_statements: state.max_statements,
+            max_size: state.max_size,
+        };
+        self.values = state
+            .load_values()?
+            .into_iter()
+            .map(|query_values| query_values.unwrap_or_else(LegacySerializedValues::new))
+            .collect();
+        self.params = self.values.iter().map(|_| None).collect();
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
+
+#[pymethods]
+impl ScyllaPyBatchType {
+    /// Pickle batch type by its name.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if builtins cannot be imported.
+    pub fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        reduce_enum_variant::<Self>(py, format!("{self:?}"))
+    }
+}
+
+impl From<BatchType> for ScyllaPyBatchType {
+    fn from(value: BatchType) -> Self {
+        match value {
+            BatchType::Counter => Self::COUNTER,
+            BatchType::Logged => Self::LOGGED,
+            BatchType::Unlogged => Self::UNLOGGED,
+        }
+    }
 }
 
 impl From<ScyllaPyBatchType> for BatchType {
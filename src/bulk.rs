@@ -0,0 +1,1185 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    net::IpAddr,
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+use futures::{StreamExt, TryStreamExt};
+#[cfg(feature = "parquet")]
+use parquet::{
+    basic::{ConvertedType, Repetition, Type as PhysicalType},
+    data_type::{
+        BoolType, ByteArray, ByteArrayType, DataType, DoubleType, FloatType, Int32Type, Int64Type,
+    },
+    file::{
+        properties::WriterProperties,
+        reader::{FileReader, SerializedFileReader},
+        writer::{SerializedColumnWriter, SerializedFileWriter},
+    },
+    record::{reader::RowIter, Field},
+    schema::types::Type,
+};
+use pyo3::{exceptions::PyValueError, FromPyObject, PyAny, PyResult};
+use scylla::{
+    batch::{Batch, BatchType},
+    execution_profile::ExecutionProfileHandle,
+    frame::{
+        response::result::{ColumnSpec, ColumnType, CqlValue, Row},
+        value::{CqlDate, CqlTime, LegacySerializedValues, MaybeUnset},
+    },
+    prepared_statement::PreparedStatement,
+    query::Query,
+    transport::{
+        iterator::RowIterator,
+        topology::{ColumnKind, CqlType, NativeType, Table},
+    },
+    Session,
+};
+
+use crate::{
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    prepared_queries::execute_prepared,
+    utils::{quote_identifier, quote_table, ScyllaPyCQLDTO},
+};
+
+/// How many rows are inserted concurrently by default.
+pub const DEFAULT_COPY_CONCURRENCY: usize = 64;
+/// How many token ranges are scanned concurrently by default.
+pub const DEFAULT_SCAN_CONCURRENCY: usize = 16;
+/// How many rows are sent to the writer at once.
+const COPY_CHUNK_SIZE: usize = 1024;
+/// How many rows of a partition are written in a single batch.
+const COPY_BATCH_SIZE: usize = 100;
+/// How many rows are written in a single row group of parquet file.
+#[cfg(feature = "parquet")]
+const PARQUET_ROW_GROUP_SIZE: usize = 16384;
+/// Unix epoch in days, as it's stored in CQL dates.
+const CQL_DATE_EPOCH: i64 = 1 << 31;
+
+/// Format of files for loading and unloading data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileFormat {
+    Csv,
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl<'a> FromPyObject<'a> for FileFormat {
+    fn extract(ob: &'a PyAny) -> PyResult<Self> {
+        match ob.extract::<&str>()? {
+            "csv" => Ok(Self::Csv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(Self::Parquet),
+            #[cfg(not(feature = "parquet"))]
+            "parquet" => Err(PyValueError::new_err(
+                "Parquet support is not enabled. Build scyllapy with parquet feature.",
+            )),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown file format: {other}. Supported formats are csv and parquet."
+            ))),
+        }
+    }
+}
+
+/// Decode hex blob, as it's written by cqlsh.
+fn decode_hex(text: &str) -> Option<Vec<u8>> {
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+    if digits.len() % 2 != 0 {
+        return None;
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(digits.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_timestamp(text: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    if let Ok(millis) = text.parse::<i64>() {
+        return chrono::DateTime::from_timestamp_millis(millis);
+    }
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Some(timestamp.with_timezone(&chrono::Utc));
+    }
+    if let Ok(timestamp) = chrono::DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%z") {
+        return Some(timestamp.with_timezone(&chrono::Utc));
+    }
+    chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|timestamp| timestamp.and_utc())
+}
+
+/// Convert text value from a file to CQL value.
+///
+/// Empty values are nulls, except for text columns.
+///
+/// # Errors
+///
+/// If text cannot be parsed as the column type.
+pub fn text_to_value(text: &str, column_type: &ColumnType) -> ScyllaPyResult<ScyllaPyCQLDTO> {
+    let invalid =
+        || ScyllaPyError::BindingError(format!("Cannot parse {text:?} as {column_type:?}"));
+    if matches!(column_type, ColumnType::Ascii | ColumnType::Text) {
+        return Ok(ScyllaPyCQLDTO::String(text.to_string()));
+    }
+    if text.is_empty() {
+        return Ok(ScyllaPyCQLDTO::Null);
+    }
+    Ok(match column_type {
+        ColumnType::Boolean => match text.to_ascii_lowercase().as_str() {
+            "true" | "1" => ScyllaPyCQLDTO::Bool(true),
+            "false" | "0" => ScyllaPyCQLDTO::Bool(false),
+            _ => return Err(invalid()),
+        },
+        ColumnType::TinyInt => ScyllaPyCQLDTO::TinyInt(text.parse().map_err(|_| invalid())?),
+        ColumnType::SmallInt => ScyllaPyCQLDTO::SmallInt(text.parse().map_err(|_| invalid())?),
+        ColumnType::Int => ScyllaPyCQLDTO::Int(text.parse().map_err(|_| invalid())?),
+        ColumnType::BigInt => ScyllaPyCQLDTO::BigInt(text.parse().map_err(|_| invalid())?),
+        ColumnType::Counter => ScyllaPyCQLDTO::Counter(text.parse().map_err(|_| invalid())?),
+        ColumnType::Float => {
+            ScyllaPyCQLDTO::Float(eq_float::F32(text.parse().map_err(|_| invalid())?))
+        }
+        ColumnType::Double => {
+            ScyllaPyCQLDTO::Double(eq_float::F64(text.parse().map_err(|_| invalid())?))
+        }
+        ColumnType::Decimal => ScyllaPyCQLDTO::Decimal(
+            bigdecimal_04::BigDecimal::from_str(text).map_err(|_| invalid())?,
+        ),
+        ColumnType::Uuid | ColumnType::Timeuuid => {
+            ScyllaPyCQLDTO::Uuid(uuid::Uuid::parse_str(text)?)
+        }
+        ColumnType::Inet => ScyllaPyCQLDTO::Inet(IpAddr::from_str(text)?),
+        ColumnType::Date => ScyllaPyCQLDTO::Date(chrono::NaiveDate::from_str(text)?),
+        ColumnType::Time => ScyllaPyCQLDTO::Time(chrono::NaiveTime::from_str(text)?),
+        ColumnType::Timestamp => {
+            ScyllaPyCQLDTO::Timestamp(parse_timestamp(text).ok_or_else(invalid)?)
+        }
+        ColumnType::Blob => ScyllaPyCQLDTO::Bytes(decode_hex(text).ok_or_else(invalid)?),
+        _ => {
+            return Err(ScyllaPyError::BindingError(format!(
+                "Cannot load values of type {column_type:?} from text"
+            )))
+        }
+    })
+}
+
+/// Convert field of parquet file to CQL value.
+///
+/// Strings are parsed the same way as values of CSV files,
+/// so they can be loaded into columns of any supported type.
+///
+/// # Errors
+///
+/// If field cannot be converted to the column type.
+#[cfg(feature = "parquet")]
+pub fn field_to_value(field: &Field, column_type: &ColumnType) -> ScyllaPyResult<ScyllaPyCQLDTO> {
+    let integer = match field {
+        Field::Byte(value) => Some(i64::from(*value)),
+        Field::Short(value) => Some(i64::from(*value)),
+        Field::Int(value) => Some(i64::from(*value)),
+        Field::Long(value) => Some(*value),
+        Field::UByte(value) => Some(i64::from(*value)),
+        Field::UShort(value) => Some(i64::from(*value)),
+        Field::UInt(value) => Some(i64::from(*value)),
+        _ => None,
+    };
+    let invalid =
+        || ScyllaPyError::BindingError(format!("Cannot convert {field} to {column_type:?}"));
+    Ok(match (column_type, field) {
+        (_, Field::Null) => ScyllaPyCQLDTO::Null,
+        (_, Field::Str(text)) => text_to_value(text, column_type)?,
+        (ColumnType::Boolean, Field::Bool(value)) => ScyllaPyCQLDTO::Bool(*value),
+        (ColumnType::TinyInt, _) => ScyllaPyCQLDTO::TinyInt(
+            integer
+                .and_then(|value| value.try_into().ok())
+                .ok_or_else(invalid)?,
+        ),
+        (ColumnType::SmallInt, _) => ScyllaPyCQLDTO::SmallInt(
+            integer
+                .and_then(|value| value.try_into().ok())
+                .ok_or_else(invalid)?,
+        ),
+        (ColumnType::Int, _) => ScyllaPyCQLDTO::Int(
+            integer
+                .and_then(|value| value.try_into().ok())
+                .ok_or_else(invalid)?,
+        ),
+        (ColumnType::BigInt, _) => ScyllaPyCQLDTO::BigInt(integer.ok_or_else(invalid)?),
+        (ColumnType::Counter, _) => ScyllaPyCQLDTO::Counter(integer.ok_or_else(invalid)?),
+        (ColumnType::Float, Field::Float(value)) => ScyllaPyCQLDTO::Float(eq_float::F32(*value)),
+        (ColumnType::Double, Field::Float(value)) => {
+            ScyllaPyCQLDTO::Double(eq_float::F64(f64::from(*value)))
+        }
+        (ColumnType::Double, Field::Double(value)) => ScyllaPyCQLDTO::Double(eq_float::F64(*value)),
+        (ColumnType::Blob, Field::Bytes(bytes)) => ScyllaPyCQLDTO::Bytes(bytes.data().to_vec()),
+        (ColumnType::Uuid | ColumnType::Timeuuid, Field::Bytes(bytes)) => {
+            ScyllaPyCQLDTO::Uuid(uuid::Uuid::from_slice(bytes.data()).map_err(|_| invalid())?)
+        }
+        (ColumnType::Date, Field::Date(days)) => ScyllaPyCQLDTO::Date(
+            chrono::NaiveDate::default()
+                .checked_add_signed(chrono::Duration::days(i64::from(*days)))
+                .ok_or_else(invalid)?,
+        ),
+        (ColumnType::Timestamp, Field::TimestampMillis(millis)) => ScyllaPyCQLDTO::Timestamp(
+            chrono::DateTime::from_timestamp_millis(*millis).ok_or_else(invalid)?,
+        ),
+        (ColumnType::Timestamp, Field::TimestampMicros(micros)) => ScyllaPyCQLDTO::Timestamp(
+            chrono::DateTime::from_timestamp_micros(*micros).ok_or_else(invalid)?,
+        ),
+        _ => return Err(invalid()),
+    })
+}
+
+/// Serialize values of a single row.
+///
+/// Rows of the file usually have similar sizes,
+/// so the size of the previous row is used
+/// as initial capacity of the buffer.
+fn serialize_row(
+    values: impl Iterator<Item = ScyllaPyResult<ScyllaPyCQLDTO>>,
+    capacity: usize,
+) -> ScyllaPyResult<LegacySerializedValues> {
+    let mut serialized = LegacySerializedValues::with_capacity(capacity);
+    for value in values {
+        serialized.add_value(&value?)?;
+    }
+    Ok(serialized)
+}
+
+/// Reader of rows from CSV or parquet file.
+enum RowReader {
+    Csv(csv::Reader<File>),
+    #[cfg(feature = "parquet")]
+    Parquet(RowIter<'static>),
+}
+
+impl RowReader {
+    /// Open the file and read names of its columns.
+    ///
+    /// CSV files must have a header with names of columns.
+    fn open(path: &Path, format: FileFormat, delimiter: u8) -> ScyllaPyResult<(Vec<String>, Self)> {
+        let copy_error = |err: &dyn std::error::Error| {
+            ScyllaPyError::CopyError(format!("{}: {err}", path.display()))
+        };
+        match format {
+            FileFormat::Csv => {
+                let mut reader = csv::ReaderBuilder::new()
+                    .delimiter(delimiter)
+                    .from_path(path)
+                    .map_err(|err| copy_error(&err))?;
+                let columns = reader
+                    .headers()
+                    .map_err(|err| copy_error(&err))?
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                Ok((columns, Self::Csv(reader)))
+            }
+            #[cfg(feature = "parquet")]
+            FileFormat::Parquet => {
+                let file = File::open(path).map_err(|err| copy_error(&err))?;
+                let reader = SerializedFileReader::new(file).map_err(|err| copy_error(&err))?;
+                let columns = reader
+                    .metadata()
+                    .file_metadata()
+                    .schema_descr()
+                    .root_schema()
+                    .get_fields()
+                    .iter()
+                    .map(|field| field.name().to_string())
+                    .collect();
+                Ok((
+                    columns,
+                    Self::Parquet(RowIter::from_file_into(Box::new(reader))),
+                ))
+            }
+        }
+    }
+
+    /// Read values of the next row.
+    ///
+    /// Values are converted to types of columns,
+    /// which have the same order as columns of the file.
+    fn next_values(
+        &mut self,
+        columns: &[ColumnSpec],
+        capacity: usize,
+    ) -> Option<ScyllaPyResult<LegacySerializedValues>> {
+        match self {
+            Self::Csv(reader) => {
+                let mut record = csv::StringRecord::new();
+                match reader.read_record(&mut record) {
+                    Ok(false) => None,
+                    Ok(true) => Some(serialize_row(
+                        record
+                            .iter()
+                            .zip(columns)
+                            .map(|(text, column)| text_to_value(text, &column.typ)),
+                        capacity,
+                    )),
+                    Err(err) => Some(Err(ScyllaPyError::CopyError(err.to_string()))),
+                }
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet(rows) => rows.next().map(|row| {
+                let row = row.map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+                serialize_row(
+                    row.get_column_iter()
+                        .zip(columns)
+                        .map(|((_, field), column)| field_to_value(field, &column.typ)),
+                    capacity,
+                )
+            }),
+        }
+    }
+}
+
+/// Load rows from the file into the table.
+///
+/// Names of columns are taken from the file.
+/// File is read in a separate thread, while rows
+/// are inserted with prepared statement, with at most
+/// `concurrency` inserts in flight.
+///
+/// Returns the number of inserted rows.
+///
+/// # Errors
+///
+/// If file cannot be read, values cannot be converted
+/// to types of columns or any insert fails.
+pub async fn copy_from(
+    session: &Session,
+    path: &Path,
+    table: &str,
+    format: FileFormat,
+    delimiter: u8,
+    concurrency: usize,
+    profile: Option<ExecutionProfileHandle>,
+) -> ScyllaPyResult<u64> {
+    let file_path = path.to_path_buf();
+    let (columns, mut reader) =
+        tokio::task::spawn_blocking(move || RowReader::open(&file_path, format, delimiter))
+            .await
+            .map_err(|err| ScyllaPyError::CopyError(err.to_string()))??;
+    let markers = vec!["?"; columns.len()].join(", ");
+    let column_names = columns
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut prepared = session
+        .prepare(format!(
+            "INSERT INTO {} ({column_names}) VALUES ({markers})",
+            quote_table(table)
+        ))
+        .await?;
+    prepared.set_execution_profile_handle(profile);
+    let specs = prepared.get_variable_col_specs().to_vec();
+    let (sender, mut receiver) = tokio::sync::mpsc::channel(concurrency);
+    let producer = tokio::task::spawn_blocking(move || {
+        let mut capacity = 0;
+        while let Some(values) = reader.next_values(&specs, capacity) {
+            let failed = values.is_err();
+            if let Ok(values) = &values {
+                capacity = values.size();
+            }
+            // Receiver is closed if inserts have failed.
+            if sender.blocking_send(values).is_err() || failed {
+                break;
+            }
+        }
+    });
+    let prepared = &prepared;
+    let inserted = futures::stream::poll_fn(move |cx| receiver.poll_recv(cx))
+        .map(|values| async move {
+            execute_prepared(session, prepared, values?, None).await?;
+            Ok::<_, ScyllaPyError>(())
+        })
+        .buffer_unordered(concurrency)
+        .try_fold(0, |inserted, ()| async move { Ok(inserted + 1) })
+        .await;
+    producer
+        .await
+        .map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+    inserted
+}
+
+fn join_values(values: impl Iterator<Item = String>) -> String {
+    values.collect::<Vec<_>>().join(", ")
+}
+
+/// Convert CQL value to text, which can be loaded with `copy_from`.
+///
+/// Collections, tuples and user defined types are written
+/// as CQL literals, so nested text values are quoted.
+#[must_use]
+pub fn value_to_text(value: &CqlValue, nested: bool) -> String {
+    let optional = |value: Option<&CqlValue>| {
+        value.map_or_else(|| "null".to_string(), |value| value_to_text(value, true))
+    };
+    match value {
+        CqlValue::Ascii(text) | CqlValue::Text(text) if nested => {
+            format!("'{}'", text.replace('\'', "''"))
+        }
+        CqlValue::Ascii(text) | CqlValue::Text(text) => text.clone(),
+        CqlValue::Boolean(value) => value.to_string(),
+        CqlValue::Blob(bytes) => bytes.iter().fold("0x".to_string(), |mut hex, byte| {
+            hex.push_str(&format!("{byte:02x}"));
+            hex
+        }),
+        CqlValue::Counter(counter) => counter.0.to_string(),
+        CqlValue::Decimal(decimal) => bigdecimal_04::BigDecimal::from(decimal.clone()).to_string(),
+        CqlValue::Varint(varint) => {
+            bigdecimal_04::num_bigint::BigInt::from(varint.clone()).to_string()
+        }
+        CqlValue::Date(CqlDate(days)) => chrono::NaiveDate::default()
+            .checked_add_signed(chrono::Duration::days(i64::from(*days) - CQL_DATE_EPOCH))
+            .map_or_else(|| days.to_string(), |date| date.to_string()),
+        CqlValue::Time(CqlTime(nanos)) => u32::try_from(nanos / 1_000_000_000)
+            .ok()
+            .zip(u32::try_from(nanos % 1_000_000_000).ok())
+            .and_then(|(secs, nanos)| {
+                chrono::NaiveTime::from_num_seconds_from_midnight_opt(secs, nanos)
+            })
+            .map_or_else(|| nanos.to_string(), |time| time.to_string()),
+        CqlValue::Timestamp(timestamp) => chrono::DateTime::from_timestamp_millis(timestamp.0)
+            .map_or_else(|| timestamp.0.to_string(), |time| time.to_rfc3339()),
+        CqlValue::Duration(duration) => format!(
+            "{}mo{}d{}ns",
+            duration.months, duration.days, duration.nanoseconds
+        ),
+        CqlValue::Double(value) => value.to_string(),
+        CqlValue::Float(value) => value.to_string(),
+        CqlValue::Int(value) => value.to_string(),
+        CqlValue::BigInt(value) => value.to_string(),
+        CqlValue::SmallInt(value) => value.to_string(),
+        CqlValue::TinyInt(value) => value.to_string(),
+        CqlValue::Inet(addr) => addr.to_string(),
+        CqlValue::Uuid(uuid) => uuid.to_string(),
+        CqlValue::Timeuuid(uuid) => uuid.as_ref().to_string(),
+        CqlValue::Empty => String::new(),
+        CqlValue::List(values) => format!(
+            "[{}]",
+            join_values(values.iter().map(|value| value_to_text(value, true)))
+        ),
+        CqlValue::Set(values) => format!(
+            "{{{}}}",
+            join_values(values.iter().map(|value| value_to_text(value, true)))
+        ),
+        CqlValue::Map(values) => format!(
+            "{{{}}}",
+            join_values(values.iter().map(|(key, value)| format!(
+                "{}: {}",
+                value_to_text(key, true),
+                value_to_text(value, true)
+            )))
+        ),
+        CqlValue::Tuple(values) => format!(
+            "({})",
+            join_values(values.iter().map(|value| optional(value.as_ref())))
+        ),
+        CqlValue::UserDefinedType { fields, .. } => format!(
+            "{{{}}}",
+            join_values(fields.iter().map(|(name, value)| format!(
+                "{}: {}",
+                quote_identifier(name),
+                optional(value.as_ref())
+            )))
+        ),
+    }
+}
+
+/// Physical type of parquet column for the CQL column.
+///
+/// Types without parquet counterpart are written as text,
+/// the same way as in CSV files.
+#[cfg(feature = "parquet")]
+#[derive(Clone, Copy)]
+enum ParquetColumn {
+    Boolean,
+    Int32,
+    Int64,
+    Float,
+    Double,
+    Date,
+    Timestamp,
+    Blob,
+    Text,
+}
+
+#[cfg(feature = "parquet")]
+impl ParquetColumn {
+    fn new(column_type: &ColumnType) -> Self {
+        match column_type {
+            ColumnType::Boolean => Self::Boolean,
+            ColumnType::TinyInt | ColumnType::SmallInt | ColumnType::Int => Self::Int32,
+            ColumnType::BigInt | ColumnType::Counter => Self::Int64,
+            ColumnType::Float => Self::Float,
+            ColumnType::Double => Self::Double,
+            ColumnType::Date => Self::Date,
+            ColumnType::Timestamp => Self::Timestamp,
+            ColumnType::Blob => Self::Blob,
+            _ => Self::Text,
+        }
+    }
+
+    fn schema(spec: &ColumnSpec) -> parquet::errors::Result<Arc<Type>> {
+        let (physical_type, converted_type) = match (Self::new(&spec.typ), &spec.typ) {
+            (Self::Boolean, _) => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+            (Self::Int32, ColumnType::TinyInt) => (PhysicalType::INT32, ConvertedType::INT_8),
+            (Self::Int32, ColumnType::SmallInt) => (PhysicalType::INT32, ConvertedType::INT_16),
+            (Self::Int32, _) => (PhysicalType::INT32, ConvertedType::NONE),
+            (Self::Int64, _) => (PhysicalType::INT64, ConvertedType::NONE),
+            (Self::Float, _) => (PhysicalType::FLOAT, ConvertedType::NONE),
+            (Self::Double, _) => (PhysicalType::DOUBLE, ConvertedType::NONE),
+            (Self::Date, _) => (PhysicalType::INT32, ConvertedType::DATE),
+            (Self::Timestamp, _) => (PhysicalType::INT64, ConvertedType::TIMESTAMP_MILLIS),
+            (Self::Blob, _) => (PhysicalType::BYTE_ARRAY, ConvertedType::NONE),
+            (Self::Text, _) => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+        };
+        Ok(Arc::new(
+            Type::primitive_type_builder(&spec.name, physical_type)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_converted_type(converted_type)
+                .build()?,
+        ))
+    }
+
+    /// Write values of the column in a row group.
+    fn write(
+        self,
+        column: &mut SerializedColumnWriter<'_>,
+        values: impl Iterator<Item = Option<&CqlValue>>,
+    ) -> parquet::errors::Result<()> {
+        match self {
+            Self::Boolean => {
+                write_values::<BoolType>(column, values.map(|value| value?.as_boolean()))
+            }
+            Self::Int32 => write_values::<Int32Type>(
+                column,
+                values.map(|value| match value? {
+                    CqlValue::TinyInt(value) => Some(i32::from(*value)),
+                    CqlValue::SmallInt(value) => Some(i32::from(*value)),
+                    value => value.as_int(),
+                }),
+            ),
+            Self::Int64 => write_values::<Int64Type>(
+                column,
+                values.map(|value| match value? {
+                    CqlValue::Counter(counter) => Some(counter.0),
+                    value => value.as_bigint(),
+                }),
+            ),
+            Self::Float => write_values::<FloatType>(column, values.map(|value| value?.as_float())),
+            Self::Double => {
+                write_values::<DoubleType>(column, values.map(|value| value?.as_double()))
+            }
+            Self::Date => write_values::<Int32Type>(
+                column,
+                values.map(|value| match value? {
+                    CqlValue::Date(CqlDate(days)) => {
+                        i32::try_from(i64::from(*days) - CQL_DATE_EPOCH).ok()
+                    }
+                    _ => None,
+                }),
+            ),
+            Self::Timestamp => write_values::<Int64Type>(
+                column,
+                values.map(|value| value?.as_cql_timestamp().map(|timestamp| timestamp.0)),
+            ),
+            Self::Blob => write_values::<ByteArrayType>(
+                column,
+                values.map(|value| value?.as_blob().map(|bytes| ByteArray::from(bytes.clone()))),
+            ),
+            Self::Text => write_values::<ByteArrayType>(
+                column,
+                values.map(|value| Some(ByteArray::from(value_to_text(value?, false).as_str()))),
+            ),
+        }
+    }
+}
+
+/// Write optional values of a column, nulls are marked by definition levels.
+#[cfg(feature = "parquet")]
+fn write_values<T: DataType>(
+    column: &mut SerializedColumnWriter<'_>,
+    values: impl Iterator<Item = Option<T::T>>,
+) -> parquet::errors::Result<()> {
+    let values = values.collect::<Vec<_>>();
+    let levels = values
+        .iter()
+        .map(|value| i16::from(value.is_some()))
+        .collect::<Vec<_>>();
+    let values = values.into_iter().flatten().collect::<Vec<_>>();
+    column
+        .typed::<T>()
+        .write_batch(&values, Some(&levels), None)?;
+    Ok(())
+}
+
+/// Writer of rows to CSV or parquet file.
+enum RowWriter {
+    Csv(csv::Writer<File>),
+    #[cfg(feature = "parquet")]
+    Parquet {
+        writer: SerializedFileWriter<File>,
+        columns: Vec<ParquetColumn>,
+        rows: Vec<Row>,
+    },
+}
+
+impl RowWriter {
+    /// Create the file and write names of columns.
+    fn create(
+        path: &Path,
+        format: FileFormat,
+        delimiter: u8,
+        specs: &[ColumnSpec],
+    ) -> ScyllaPyResult<Self> {
+        let copy_error = |err: &dyn std::error::Error| {
+            ScyllaPyError::CopyError(format!("{}: {err}", path.display()))
+        };
+        match format {
+            FileFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new()
+                    .delimiter(delimiter)
+                    .from_path(path)
+                    .map_err(|err| copy_error(&err))?;
+                writer
+                    .write_record(specs.iter().map(|spec| spec.name.as_str()))
+                    .map_err(|err| copy_error(&err))?;
+                Ok(Self::Csv(writer))
+            }
+            #[cfg(feature = "parquet")]
+            FileFormat::Parquet => {
+                let fields = specs
+                    .iter()
+                    .map(ParquetColumn::schema)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|err| copy_error(&err))?;
+                let schema = Type::group_type_builder("schema")
+                    .with_fields(fields)
+                    .build()
+                    .map_err(|err| copy_error(&err))?;
+                let file = File::create(path).map_err(|err| copy_error(&err))?;
+                let writer = SerializedFileWriter::new(
+                    file,
+                    Arc::new(schema),
+                    Arc::new(WriterProperties::builder().build()),
+                )
+                .map_err(|err| copy_error(&err))?;
+                Ok(Self::Parquet {
+                    writer,
+                    columns: specs
+                        .iter()
+                        .map(|spec| ParquetColumn::new(&spec.typ))
+                        .collect(),
+                    rows: Vec::new(),
+                })
+            }
+        }
+    }
+
+    /// Write rows to the file.
+    ///
+    /// Rows of parquet files are buffered until
+    /// there are enough rows for a row group.
+    fn write(&mut self, new_rows: Vec<Row>) -> ScyllaPyResult<()> {
+        match self {
+            Self::Csv(writer) => {
+                for row in new_rows {
+                    writer
+                        .write_record(row.columns.iter().map(|value| {
+                            value
+                                .as_ref()
+                                .map(|value| value_to_text(value, false))
+                                .unwrap_or_default()
+                        }))
+                        .map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+                }
+                Ok(())
+            }
+            #[cfg(feature = "parquet")]
+            Self::Parquet {
+                writer,
+                columns,
+                rows,
+            } => {
+                rows.extend(new_rows);
+                if rows.len() >= PARQUET_ROW_GROUP_SIZE {
+                    write_row_group(writer, columns, rows)
+                        .map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+                    rows.clear();
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Write buffered rows and close the file.
+    fn finish(self) -> ScyllaPyResult<()> {
+        match self {
+            Self::Csv(mut writer) => writer
+                .flush()
+                .map_err(|err| ScyllaPyError::CopyError(err.to_string())),
+            #[cfg(feature = "parquet")]
+            Self::Parquet {
+                mut writer,
+                columns,
+                rows,
+            } => {
+                if !rows.is_empty() {
+                    write_row_group(&mut writer, &columns, &rows)
+                        .map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+                }
+                writer
+                    .close()
+                    .map_err(|err| ScyllaPyError::CopyError(err.to_string()))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_row_group(
+    writer: &mut SerializedFileWriter<File>,
+    columns: &[ParquetColumn],
+    rows: &[Row],
+) -> parquet::errors::Result<()> {
+    let mut row_group = writer.next_row_group()?;
+    for (index, column) in columns.iter().enumerate() {
+        let Some(mut column_writer) = row_group.next_column()? else {
+            break;
+        };
+        column.write(
+            &mut column_writer,
+            rows.iter()
+                .map(|row| row.columns.get(index).and_then(Option::as_ref)),
+        )?;
+        column_writer.close()?;
+    }
+    row_group.close()?;
+    Ok(())
+}
+
+/// Split the token ring into ranges between tokens of nodes.
+///
+/// Each range is `(start, end]`, ranges cover the whole ring.
+fn token_ranges(session: &Session) -> Vec<(i64, i64)> {
+    let mut tokens = session
+        .get_cluster_data()
+        .replica_locator()
+        .ring()
+        .iter()
+        .map(|(token, _)| token.value)
+        .collect::<Vec<_>>();
+    tokens.push(i64::MIN);
+    tokens.push(i64::MAX);
+    tokens.sort_unstable();
+    tokens.dedup();
+    tokens
+        .windows(2)
+        .filter_map(|bounds| match bounds {
+            [start, end] => Some((*start, *end)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Find metadata of the table.
+///
+/// Table can be prefixed with keyspace, otherwise
+/// keyspace of the session is used.
+async fn table_info(session: &Session, table: &str) -> ScyllaPyResult<(String, String, Table)> {
+    let (keyspace, table) = match table.split_once('.') {
+        Some((keyspace, table)) => (keyspace.to_string(), table.to_string()),
+        None => (
+            session
+                .get_keyspace()
+                .ok_or_else(|| {
+                    ScyllaPyError::CopyError(format!(
+                        "Keyspace of the table {table} is not specified."
+                    ))
+                })?
+                .to_string(),
+            table.to_string(),
+        ),
+    };
+    // Table could be created after metadata was fetched.
+    session.refresh_metadata().await?;
+    let table_info = session
+        .get_cluster_data()
+        .get_keyspace_info()
+        .get(&keyspace)
+        .and_then(|keyspace_info| keyspace_info.tables.get(&table))
+        .cloned()
+        .ok_or_else(|| ScyllaPyError::CopyError(format!("Unknown table {keyspace}.{table}.")))?;
+    Ok((keyspace, table, table_info))
+}
+
+/// Build a query, which selects columns of the table within a token range.
+fn range_query(keyspace: &str, table: &str, table_info: &Table, columns: &str) -> String {
+    let partition_key = table_info
+        .partition_key
+        .iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "SELECT {columns} FROM {}.{} WHERE token({partition_key}) > ? AND token({partition_key}) <= ?",
+        quote_identifier(keyspace),
+        quote_identifier(table),
+    )
+}
+
+/// Send rows to the writer in chunks.
+///
+/// If the writer has failed, rows aren't read anymore,
+/// the error of the writer is returned instead.
+async fn send_rows(
+    mut rows: RowIterator,
+    sender: &tokio::sync::mpsc::Sender<(Arc<[ColumnSpec]>, Vec<Row>)>,
+) -> ScyllaPyResult<()> {
+    let specs: Arc<[ColumnSpec]> = rows.get_column_specs().into();
+    let mut chunk = Vec::with_capacity(COPY_CHUNK_SIZE);
+    while let Some(row) = rows.next().await {
+        chunk.push(row?);
+        if chunk.len() == COPY_CHUNK_SIZE
+            && sender
+                .send((specs.clone(), std::mem::take(&mut chunk)))
+                .await
+                .is_err()
+        {
+            return Ok(());
+        }
+    }
+    // Specs are always sent, so the file is created even for empty results.
+    sender.send((specs, chunk)).await.ok();
+    Ok(())
+}
+
+/// Unload rows of the table or a select statement into the file.
+///
+/// Tables are split by token ranges, which are scanned
+/// with at most `concurrency` queries in flight.
+/// Select statements are executed as is with paging.
+/// File is written in a separate thread.
+///
+/// Returns the number of written rows.
+///
+/// # Errors
+///
+/// If table is unknown, file cannot be written or any query fails.
+pub async fn copy_to(
+    session: &Session,
+    path: &Path,
+    table_or_select: &str,
+    format: FileFormat,
+    delimiter: u8,
+    concurrency: usize,
+    profile: Option<ExecutionProfileHandle>,
+) -> ScyllaPyResult<usize> {
+    let (sender, mut receiver) =
+        tokio::sync::mpsc::channel::<(Arc<[ColumnSpec]>, Vec<Row>)>(concurrency);
+    let file_path = path.to_path_buf();
+    let writer = tokio::task::spawn_blocking(move || {
+        let Some((specs, rows)) = receiver.blocking_recv() else {
+            return Ok(0);
+        };
+        let mut writer = RowWriter::create(&file_path, format, delimiter, &specs)?;
+        let mut written = rows.len();
+        writer.write(rows)?;
+        while let Some((_, rows)) = receiver.blocking_recv() {
+            written += rows.len();
+            writer.write(rows)?;
+        }
+        writer.finish()?;
+        Ok::<_, ScyllaPyError>(written)
+    });
+    let is_select = table_or_select
+        .trim_start()
+        .get(..6)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("select"));
+    let scanned = if is_select {
+        let mut query = Query::new(table_or_select);
+        query.set_execution_profile_handle(profile);
+        let rows = session.query_iter(query, ()).await?;
+        send_rows(rows, &sender).await
+    } else {
+        let (keyspace, table, table_info) = table_info(session, table_or_select).await?;
+        let mut prepared = session
+            .prepare(range_query(&keyspace, &table, &table_info, "*"))
+            .await?;
+        prepared.set_execution_profile_handle(profile);
+        let (prepared, sender) = (&prepared, &sender);
+        futures::stream::iter(token_ranges(session))
+            .map(|range| async move {
+                let rows = session.execute_iter(prepared.clone(), range).await?;
+                send_rows(rows, sender).await
+            })
+            .buffer_unordered(concurrency)
+            .try_for_each(|()| futures::future::ok(()))
+            .await
+    };
+    drop(sender);
+    let written = writer
+        .await
+        .map_err(|err| ScyllaPyError::CopyError(err.to_string()))??;
+    scanned?;
+    Ok(written)
+}
+
+/// Inserts of copied rows, which keep write timestamps and TTLs of cells.
+///
+/// Rows are selected with key columns first, then values
+/// and then `WRITETIME` and `TTL` of each timed value column.
+/// Cells of a row can be written at different times,
+/// so values are grouped by their timestamp and TTL,
+/// and each group is written with a separate insert.
+struct CopyInsert<'a> {
+    session: &'a Session,
+    table: String,
+    keys: Vec<String>,
+    values: Vec<String>,
+    // Position of `WRITETIME` of each value column in selected row,
+    // `TTL` goes right after it.
+    timed: Vec<Option<usize>>,
+    profile: Option<ExecutionProfileHandle>,
+    prepared: Mutex<HashMap<Vec<usize>, PreparedStatement>>,
+}
+
+impl CopyInsert<'_> {
+    /// Group values of the row by their timestamp and TTL.
+    ///
+    /// Non-frozen collections and user defined types have no single
+    /// timestamp, so they are written with the latest timestamp
+    /// of other cells of the row without TTL.
+    /// Rows without values are written with key columns only.
+    fn groups(&self, row: &Row) -> Vec<((Option<i64>, i32), Vec<usize>)> {
+        let mut groups = BTreeMap::<(Option<i64>, i32), Vec<usize>>::new();
+        let mut untimed = Vec::new();
+        for (index, timed) in self.timed.iter().enumerate() {
+            if !matches!(row.columns.get(self.keys.len() + index), Some(Some(_))) {
+                continue;
+            }
+            let Some(position) = *timed else {
+                untimed.push(index);
+                continue;
+            };
+            let cell = |position| row.columns.get(position).and_then(Option::as_ref);
+            let timestamp = cell(position).and_then(CqlValue::as_bigint);
+            let ttl = cell(position + 1).and_then(CqlValue::as_int).unwrap_or(0);
+            groups.entry((timestamp, ttl)).or_default().push(index);
+        }
+        if !untimed.is_empty() || groups.is_empty() {
+            let latest = groups.keys().filter_map(|(timestamp, _)| *timestamp).max();
+            groups.entry((latest, 0)).or_default().extend(untimed);
+        }
+        groups.into_iter().collect()
+    }
+
+    /// Insert of key columns and given value columns.
+    ///
+    /// Statements are prepared once for each set of columns.
+    async fn statement(&self, values: &[usize]) -> ScyllaPyResult<PreparedStatement> {
+        let cached = self
+            .prepared
+            .lock()
+            .ok()
+            .and_then(|prepared| prepared.get(values).cloned());
+        if let Some(prepared) = cached {
+            return Ok(prepared);
+        }
+        let columns = self
+            .keys
+            .iter()
+            .chain(values.iter().filter_map(|&index| self.values.get(index)))
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut prepared = self
+            .session
+            .prepare(format!(
+                "INSERT INTO {} ({}) VALUES ({}) USING TIMESTAMP ? AND TTL ?",
+                self.table,
+                columns.join(", "),
+                vec!["?"; columns.len()].join(", "),
+            ))
+            .await?;
+        prepared.set_execution_profile_handle(self.profile.clone());
+        if let Ok(mut cache) = self.prepared.lock() {
+            cache.insert(values.to_vec(), prepared.clone());
+        }
+        Ok(prepared)
+    }
+
+    /// Write rows of a single partition with unlogged batch.
+    async fn write_batch(&self, rows: Vec<Row>) -> ScyllaPyResult<usize> {
+        let mut batch = Batch::new(BatchType::Unlogged);
+        batch.set_execution_profile_handle(self.profile.clone());
+        let mut values = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let (keys, row_values) = row.columns.split_at(self.keys.len());
+            for ((timestamp, ttl), columns) in self.groups(row) {
+                batch.append_statement(self.statement(&columns).await?);
+                let mut serialized = LegacySerializedValues::with_capacity(0);
+                for value in keys
+                    .iter()
+                    .chain(columns.iter().filter_map(|&index| row_values.get(index)))
+                {
+                    serialized.add_value(value)?;
+                }
+                // Unset timestamp is assigned by the server.
+                serialized.add_value(&timestamp.map_or(MaybeUnset::Unset, MaybeUnset::Set))?;
+                serialized.add_value(&ttl)?;
+                values.push(serialized);
+            }
+        }
+        self.session.batch(&batch, values).await?;
+        Ok(rows.len())
+    }
+}
+
+/// Copy rows of a token range into another cluster.
+///
+/// Rows of a range are ordered by token, so rows of the same
+/// partition go one after another. They are grouped into
+/// batches of at most `COPY_BATCH_SIZE` rows.
+async fn copy_range(
+    source: &Session,
+    select: &PreparedStatement,
+    insert: &CopyInsert<'_>,
+    partition_key: usize,
+    range: (i64, i64),
+) -> ScyllaPyResult<usize> {
+    let same_partition = |left: &Row, right: &Row| {
+        left.columns.get(..partition_key) == right.columns.get(..partition_key)
+    };
+    let mut rows = source.execute_iter(select.clone(), range).await?;
+    let mut copied = 0;
+    let mut partition: Vec<Row> = Vec::with_capacity(COPY_BATCH_SIZE);
+    while let Some(row) = rows.next().await {
+        let row = row?;
+        if partition.len() == COPY_BATCH_SIZE
+            || partition
+                .last()
+                .is_some_and(|last| !same_partition(last, &row))
+        {
+            copied += insert.write_batch(std::mem::take(&mut partition)).await?;
+        }
+        partition.push(row);
+    }
+    if !partition.is_empty() {
+        copied += insert.write_batch(partition).await?;
+    }
+    Ok(copied)
+}
+
+/// Copy rows of the table from one cluster to another.
+///
+/// Source table is split by token ranges, which are
+/// scanned with at most `concurrency` queries in flight.
+/// Rows are written into the table with the same name
+/// in the destination cluster, so unqualified names are
+/// resolved with keyspaces of each session.
+/// Write timestamps and TTLs of cells are kept,
+/// so copies don't overwrite newer data in the destination.
+///
+/// Returns the number of copied rows.
+///
+/// # Errors
+///
+/// If table is unknown in the source cluster, has counter columns,
+/// which cannot be inserted, or any query fails.
+pub async fn copy_table(
+    source: &Session,
+    destination: &Session,
+    table: &str,
+    token_ranges: Option<Vec<(i64, i64)>>,
+    concurrency: usize,
+    read_profile: Option<ExecutionProfileHandle>,
+    write_profile: Option<ExecutionProfileHandle>,
+) -> ScyllaPyResult<usize> {
+    let (keyspace, table_name, table_info) = table_info(source, table).await?;
+    if table_info
+        .columns
+        .values()
+        .any(|column| matches!(column.type_, CqlType::Native(NativeType::Counter)))
+    {
+        return Err(ScyllaPyError::CopyError(format!(
+            "Table {keyspace}.{table_name} has counter columns, which cannot be copied."
+        )));
+    }
+    let keys = table_info
+        .partition_key
+        .iter()
+        .chain(&table_info.clustering_key)
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut values = table_info
+        .columns
+        .iter()
+        .filter(|(_, column)| {
+            !matches!(
+                column.kind,
+                ColumnKind::PartitionKey | ColumnKind::Clustering
+            )
+        })
+        .map(|(name, column)| (name.clone(), column))
+        .collect::<Vec<_>>();
+    values.sort_unstable_by(|(left, _), (right, _)| left.cmp(right));
+    let mut selected = keys
+        .iter()
+        .chain(values.iter().map(|(name, _)| name))
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>();
+    let mut timed = Vec::with_capacity(values.len());
+    for (name, column) in &values {
+        let has_timestamp = !matches!(
+            column.type_,
+            CqlType::Collection { frozen: false, .. }
+                | CqlType::UserDefinedType { frozen: false, .. }
+        );
+        if has_timestamp {
+            timed.push(Some(selected.len()));
+            let name = quote_identifier(name);
+            selected.push(format!("WRITETIME({name})"));
+            selected.push(format!("TTL({name})"));
+        } else {
+            timed.push(None);
+        }
+    }
+    let mut select = source
+        .prepare(range_query(
+            &keyspace,
+            &table_name,
+            &table_info,
+            &selected.join(", "),
+        ))
+        .await?;
+    select.set_execution_profile_handle(read_profile);
+    let insert = CopyInsert {
+        session: destination,
+        table: quote_table(table),
+        keys: keys.iter().map(|column| quote_identifier(column)).collect(),
+        values: values
+            .iter()
+            .map(|(name, _)| quote_identifier(name))
+            .collect(),
+        timed,
+        profile: write_profile,
+        prepared: Mutex::new(HashMap::new()),
+    };
+    let ranges = token_ranges.unwrap_or_else(|| self::token_ranges(source));
+    let partition_key = table_info.partition_key.len();
+    let (select, insert) = (&select, &insert);
+    futures::stream::iter(ranges)
+        .map(|range| copy_range(source, select, insert, partition_key, range))
+        .buffer_unordered(concurrency)
+        .try_fold(0, |copied, range_copied| async move {
+            Ok(copied + range_copied)
+        })
+        .await
+}
@@ -0,0 +1,217 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use pyo3::{types::PyDict, PyResult, Python};
+use scylla::QueryResult;
+
+use crate::{coalescing::ReadKey, utils::clone_query_result};
+
+#[derive(Debug)]
+struct CachedResult {
+    result: QueryResult,
+    expires_at: Instant,
+    size: usize,
+    last_used: u64,
+}
+
+/// Cached results in order of their use.
+#[derive(Debug, Default)]
+struct Entries {
+    results: HashMap<ReadKey, CachedResult>,
+    by_use: BTreeMap<u64, ReadKey>,
+    bytes: usize,
+    clock: u64,
+}
+
+impl Entries {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, key: &ReadKey) {
+        if let Some(cached) = self.results.remove(key) {
+            self.by_use.remove(&cached.last_used);
+            self.bytes -= cached.size;
+        }
+    }
+
+    /// Remove the least recently used result.
+    fn evict(&mut self) -> bool {
+        let Some((_, key)) = self.by_use.pop_first() else {
+            return false;
+        };
+        if let Some(cached) = self.results.remove(&key) {
+            self.bytes -= cached.size;
+        }
+        true
+    }
+}
+
+/// Cache of results of SELECT statements.
+///
+/// Results are keyed by statement and serialized values.
+/// Statements are cached for their own TTL, if it's set,
+/// otherwise for the default TTL. Statements without TTL
+/// aren't cached at all.
+///
+/// Size of a result is the size of its response frame.
+/// When the cache holds too many results or bytes,
+/// least recently used results are removed.
+/// Results larger than the whole cache aren't cached.
+#[derive(Debug)]
+pub struct ResultCache {
+    entries: Mutex<Entries>,
+    max_entries: NonZeroUsize,
+    max_bytes: Option<NonZeroUsize>,
+    default_ttl: Option<Duration>,
+    statement_ttls: RwLock<HashMap<String, Duration>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResultCache {
+    #[must_use]
+    pub fn new(
+        max_entries: NonZeroUsize,
+        max_bytes: Option<NonZeroUsize>,
+        default_ttl: Option<Duration>,
+    ) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            max_entries,
+            max_bytes,
+            default_ttl,
+            statement_ttls: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// How long results of the statement are cached.
+    #[must_use]
+    pub fn ttl(&self, statement: &str) -> Option<Duration> {
+        self.statement_ttls
+            .read()
+            .ok()
+            .and_then(|ttls| ttls.get(statement).copied())
+            .or(self.default_ttl)
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    /// Set TTL of the statement, or use the default one.
+    pub fn set_ttl(&self, statement: String, ttl: Option<Duration>) {
+        if let Ok(mut ttls) = self.statement_ttls.write() {
+            match ttl {
+                Some(ttl) => ttls.insert(statement, ttl),
+                None => ttls.remove(&statement),
+            };
+        }
+    }
+
+    /// Copy of the cached result, if it hasn't expired.
+    #[must_use]
+    pub fn get(&self, key: &ReadKey) -> Option<QueryResult> {
+        let result = self.entries.lock().ok().and_then(|mut entries| {
+            let expired = entries
+                .results
+                .get(key)
+                .map(|cached| cached.expires_at <= Instant::now())?;
+            if expired {
+                entries.remove(key);
+                return None;
+            }
+            let last_used = entries.tick();
+            let cached = entries.results.get_mut(key)?;
+            let previous = std::mem::replace(&mut cached.last_used, last_used);
+            let result = clone_query_result(&cached.result);
+            entries.by_use.remove(&previous);
+            entries.by_use.insert(last_used, key.clone());
+            Some(result)
+        });
+        let counter = if result.is_some() {
+            &self.hits
+        } else {
+            &self.misses
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    /// Store copy of the result.
+    pub fn insert(&self, key: ReadKey, result: &QueryResult, ttl: Duration) {
+        let size = result.serialized_size;
+        let max_bytes = self.max_bytes.map_or(usize::MAX, NonZeroUsize::get);
+        if size > max_bytes {
+            return;
+        }
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        entries.remove(&key);
+        while entries.results.len() >= self.max_entries.get() || entries.bytes + size > max_bytes {
+            if !entries.evict() {
+                break;
+            }
+        }
+        let last_used = entries.tick();
+        entries.by_use.insert(last_used, key.clone());
+        entries.bytes += size;
+        entries.results.insert(
+            key,
+            CachedResult {
+                result: clone_query_result(result),
+                expires_at: Instant::now() + ttl,
+                size,
+                last_used,
+            },
+        );
+    }
+
+    /// Remove cached results of the statement or all results.
+    pub fn invalidate(&self, statement: Option<&str>) {
+        if let Ok(mut entries) = self.entries.lock() {
+            match statement {
+                Some(statement) => {
+                    let keys = entries
+                        .results
+                        .keys()
+                        .filter(|key| key.statement() == statement)
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    for key in &keys {
+                        entries.remove(key);
+                    }
+                }
+                None => *entries = Entries::default(),
+            }
+        }
+    }
+
+    /// Counters of the cache as a dict.
+    ///
+    /// # Errors
+    ///
+    /// If dict cannot be created.
+    pub fn stats<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        let (size, bytes) = self
+            .entries
+            .lock()
+            .map_or((0, 0), |entries| (entries.results.len(), entries.bytes));
+        let stats = PyDict::new(py);
+        stats.set_item("hits", self.hits.load(Ordering::Relaxed))?;
+        stats.set_item("misses", self.misses.load(Ordering::Relaxed))?;
+        stats.set_item("size", size)?;
+        stats.set_item("max_size", self.max_entries.get())?;
+        stats.set_item("bytes", bytes)?;
+        stats.set_item("max_bytes", self.max_bytes.map(NonZeroUsize::get))?;
+        Ok(stats)
+    }
+}
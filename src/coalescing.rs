@@ -0,0 +1,236 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use scylla::{
+    frame::value::LegacySerializedValues,
+    prepared_statement::PreparedStatement,
+    query::Query,
+    statement::{Consistency, SerialConsistency},
+    transport::errors::QueryError,
+    QueryResult,
+};
+use tokio::sync::oneshot;
+
+use crate::utils::clone_query_result;
+
+/// What unqualified names of the statement are resolved in.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ReadScope {
+    /// Keyspace of the session, which queries use.
+    Keyspace(Option<Arc<String>>),
+    /// Id of the prepared statement, which depends on
+    /// the keyspace it was prepared in.
+    Prepared(Vec<u8>),
+}
+
+/// Statement, its serialized values and settings.
+///
+/// Reads with different consistency, timeout or page size
+/// can get different results, so they aren't identical.
+/// Settings of profiles are set to statements explicitly,
+/// so reads with different profiles differ by them too.
+/// The same text can read different tables in different
+/// keyspaces, so keyspace is part of the key as well.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ReadKey {
+    statement: String,
+    scope: ReadScope,
+    values: Vec<u8>,
+    consistency: u16,
+    serial_consistency: Option<i16>,
+    request_timeout: Option<Duration>,
+    page_size: Option<i32>,
+}
+
+impl ReadKey {
+    /// Text of the statement.
+    #[must_use]
+    pub fn statement(&self) -> &str {
+        &self.statement
+    }
+
+    /// Key of the query with keyspace of the session
+    /// and resolved consistency.
+    #[must_use]
+    pub fn of_query(
+        query: &Query,
+        keyspace: Option<Arc<String>>,
+        consistency: Consistency,
+        values: &LegacySerializedValues,
+    ) -> Self {
+        Self::new(
+            &query.contents,
+            ReadScope::Keyspace(keyspace),
+            values,
+            consistency,
+            query.get_serial_consistency(),
+            query.get_request_timeout(),
+            query.get_page_size(),
+        )
+    }
+
+    /// Key of the prepared statement with its resolved consistency.
+    #[must_use]
+    pub fn of_prepared(
+        prepared: &PreparedStatement,
+        consistency: Consistency,
+        values: &LegacySerializedValues,
+    ) -> Self {
+        Self::new(
+            prepared.get_statement(),
+            ReadScope::Prepared(prepared.get_id().to_vec()),
+            values,
+            consistency,
+            prepared.get_serial_consistency(),
+            prepared.get_request_timeout(),
+            prepared.get_page_size(),
+        )
+    }
+
+    fn new(
+        statement: &str,
+        scope: ReadScope,
+        values: &LegacySerializedValues,
+        consistency: Consistency,
+        serial_consistency: Option<SerialConsistency>,
+        request_timeout: Option<Duration>,
+        page_size: Option<i32>,
+    ) -> Self {
+        let mut buf = Vec::new();
+        values.write_to_request(&mut buf);
+        Self {
+            statement: statement.to_string(),
+            scope,
+            values: buf,
+            consistency: consistency as u16,
+            serial_consistency: serial_consistency.map(|consistency| consistency as i16),
+            request_timeout,
+            page_size,
+        }
+    }
+}
+
+type ReadWaiters = Vec<oneshot::Sender<Result<QueryResult, QueryError>>>;
+
+/// Deduplicator of concurrent identical reads.
+///
+/// The first read of the key is sent to the cluster,
+/// while identical reads, which start before it finishes,
+/// wait for its result.
+#[derive(Debug, Default)]
+pub struct ReadCoalescer {
+    in_flight: Mutex<HashMap<ReadKey, ReadWaiters>>,
+    coalesced: AtomicU64,
+}
+
+/// Removes the key of the first read, even if it was cancelled.
+///
+/// Waiters of the cancelled read send their own requests.
+struct ReadLeader<'a> {
+    coalescer: &'a ReadCoalescer,
+    key: Option<ReadKey>,
+}
+
+impl ReadLeader<'_> {
+    fn finish(mut self, res: &Result<QueryResult, QueryError>) {
+        for waiter in self.take_waiters() {
+            waiter
+                .send(res.as_ref().map(clone_query_result).map_err(Clone::clone))
+                .ok();
+        }
+    }
+
+    /// Remove the key, so next reads are sent to the cluster.
+    fn take_waiters(&mut self) -> ReadWaiters {
+        let Some(key) = self.key.take() else {
+            return Vec::new();
+        };
+        self.coalescer
+            .in_flight
+            .lock()
+            .ok()
+            .and_then(|mut in_flight| in_flight.remove(&key))
+            .unwrap_or_default()
+    }
+}
+
+impl Drop for ReadLeader<'_> {
+    fn drop(&mut self) {
+        self.take_waiters();
+    }
+}
+
+impl ReadCoalescer {
+    /// Number of reads, which got results of other reads.
+    #[must_use]
+    pub fn coalesced(&self) -> u64 {
+        self.coalesced.load(Ordering::Relaxed)
+    }
+
+    /// Run the read or wait for identical read in flight.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors of the read.
+    pub async fn read(
+        &self,
+        key: ReadKey,
+        read: impl Future<Output = Result<QueryResult, QueryError>>,
+    ) -> Result<QueryResult, QueryError> {
+        let waiter =
+            self.in_flight
+                .lock()
+                .ok()
+                .map(|mut in_flight| match in_flight.entry(key.clone()) {
+                    Entry::Occupied(mut entry) => {
+                        let (sender, receiver) = oneshot::channel();
+                        entry.get_mut().push(sender);
+                        Some(receiver)
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(Vec::new());
+                        None
+                    }
+                });
+        let Some(waiter) = waiter else {
+            return read.await;
+        };
+        if let Some(waiter) = waiter {
+            if let Ok(res) = waiter.await {
+                self.coalesced.fetch_add(1, Ordering::Relaxed);
+                return res;
+            }
+            // The first read was cancelled.
+            return read.await;
+        }
+        let leader = ReadLeader {
+            coalescer: self,
+            key: Some(key),
+        };
+        let res = read.await;
+        leader.finish(&res);
+        res
+    }
+}
+
+/// Run the read through the coalescer, if it's enabled.
+///
+/// # Errors
+///
+/// Proxies errors of the read.
+pub async fn coalesce(
+    coalescing: Option<(Arc<ReadCoalescer>, ReadKey)>,
+    read: impl Future<Output = Result<QueryResult, QueryError>>,
+) -> Result<QueryResult, QueryError> {
+    match coalescing {
+        Some((coalescer, key)) => coalescer.read(key, read).await,
+        None => read.await,
+    }
+}
@@ -1,11 +1,13 @@
-use pyo3::pyclass;
+use pyo3::{pyclass, pymethods, types::PyType, PyAny, PyResult, Python};
 use scylla::statement::{Consistency, SerialConsistency};
 
+use crate::utils::reduce_enum_variant;
+
 /// Consistency levels for queries.
 ///
 /// This class allows to run queries
 /// with specific consistency levels.
-#[pyclass(name = "Consistency")]
+#[pyclass(name = "Consistency", module = "scyllapy._internal")]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[allow(non_camel_case_types)]
 pub enum ScyllaPyConsistency {
@@ -22,7 +24,7 @@ pub enum ScyllaPyConsistency {
     LOCAL_SERIAL,
 }
 
-#[pyclass(name = "SerialConsistency")]
+#[pyclass(name = "SerialConsistency", module = "scyllapy._internal")]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 #[allow(non_camel_case_types)]
 pub enum ScyllaPySerialConsistency {
@@ -30,6 +32,30 @@ pub enum ScyllaPySerialConsistency {
     LOCAL_SERIAL,
 }
 
+#[pymethods]
+impl ScyllaPyConsistency {
+    /// Pickle consistency by its name.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if builtins cannot be imported.
+    pub fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        reduce_enum_variant::<Self>(py, format!("{self:?}"))
+    }
+}
+
+#[pymethods]
+impl ScyllaPySerialConsistency {
+    /// Pickle serial consistency by its name.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if builtins cannot be imported.
+    pub fn __reduce__<'a>(&self, py: Python<'a>) -> PyResult<(&'a PyAny, (&'a PyType, String))> {
+        reduce_enum_variant::<Self>(py, format!("{self:?}"))
+    }
+}
+
 /// Here we define how to convert our Consistency,
 /// to the type that is used by scylla library.
 impl From<ScyllaPyConsistency> for Consistency {
@@ -50,6 +76,26 @@ impl From<ScyllaPyConsistency> for Consistency {
     }
 }
 
+/// Conversion from scylla consistency
+/// to our python class.
+impl From<Consistency> for ScyllaPyConsistency {
+    fn from(value: Consistency) -> Self {
+        match value {
+            Consistency::Any => Self::ANY,
+            Consistency::One => Self::ONE,
+            Consistency::Two => Self::TWO,
+            Consistency::Three => Self::THREE,
+            Consistency::Quorum => Self::QUORUM,
+            Consistency::All => Self::ALL,
+            Consistency::LocalQuorum => Self::LOCAL_QUORUM,
+            Consistency::EachQuorum => Self::EACH_QUORUM,
+            Consistency::LocalOne => Self::LOCAL_ONE,
+            Consistency::Serial => Self::SERIAL,
+            Consistency::LocalSerial => Self::LOCAL_SERIAL,
+        }
+    }
+}
+
 /// Convertion between python serial consistency
 /// and scylla serial consistency.
 impl From<ScyllaPySerialConsistency> for SerialConsistency {
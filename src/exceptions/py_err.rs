@@ -1,4 +1,10 @@
-use pyo3::{create_exception, types::PyModule, PyResult, Python};
+use pyo3::{
+    create_exception, pyclass,
+    sync::GILOnceCell,
+    types::{PyDict, PyModule, PyTuple, PyType},
+    Py, PyResult, Python,
+};
+use scylla::transport::errors::DbError;
 
 create_exception!(
     scyllapy.exceptions,
@@ -8,12 +14,112 @@ create_exception!(
 create_exception!(scyllapy.exceptions, ScyllaPyBindingError, ScyllaPyBaseError);
 create_exception!(scyllapy.exceptions, ScyllaPyDBError, ScyllaPyBaseError);
 create_exception!(scyllapy.exceptions, ScyllaPySessionError, ScyllaPyDBError);
+create_exception!(scyllapy.exceptions, ScyllaPySyntaxError, ScyllaPyDBError);
 create_exception!(scyllapy.exceptions, ScyllaPyMappingError, ScyllaPyBaseError);
 create_exception!(
     scyllapy.exceptions,
     ScyllaPyQueryBuiderError,
     ScyllaPyBaseError
 );
+create_exception!(
+    scyllapy.exceptions,
+    ScyllaPyMigrationError,
+    ScyllaPyBaseError
+);
+create_exception!(
+    scyllapy.exceptions,
+    ScyllaPyServerWarning,
+    pyo3::exceptions::PyUserWarning
+);
+
+/// Error codes of CQL protocol.
+///
+/// Values are the same as in the protocol,
+/// except for `RATE_LIMIT_REACHED` which is a
+/// Scylla extension and `OTHER` for unknown codes.
+#[pyclass(name = "ErrorCode")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[allow(non_camel_case_types)]
+pub enum ScyllaPyErrorCode {
+    SERVER_ERROR = 0x0000,
+    PROTOCOL_ERROR = 0x000A,
+    AUTHENTICATION_ERROR = 0x0100,
+    UNAVAILABLE = 0x1000,
+    OVERLOADED = 0x1001,
+    IS_BOOTSTRAPPING = 0x1002,
+    TRUNCATE_ERROR = 0x1003,
+    WRITE_TIMEOUT = 0x1100,
+    READ_TIMEOUT = 0x1200,
+    READ_FAILURE = 0x1300,
+    FUNCTION_FAILURE = 0x1400,
+    WRITE_FAILURE = 0x1500,
+    SYNTAX_ERROR = 0x2000,
+    UNAUTHORIZED = 0x2100,
+    INVALID = 0x2200,
+    CONFIG_ERROR = 0x2300,
+    ALREADY_EXISTS = 0x2400,
+    UNPREPARED = 0x2500,
+    RATE_LIMIT_REACHED = 0xF000,
+    OTHER = -1,
+}
+
+impl From<&DbError> for ScyllaPyErrorCode {
+    fn from(value: &DbError) -> Self {
+        match value {
+            DbError::ServerError => Self::SERVER_ERROR,
+            DbError::ProtocolError => Self::PROTOCOL_ERROR,
+            DbError::AuthenticationError => Self::AUTHENTICATION_ERROR,
+            DbError::Unavailable { .. } => Self::UNAVAILABLE,
+            DbError::Overloaded => Self::OVERLOADED,
+            DbError::IsBootstrapping => Self::IS_BOOTSTRAPPING,
+            DbError::TruncateError => Self::TRUNCATE_ERROR,
+            DbError::WriteTimeout { .. } => Self::WRITE_TIMEOUT,
+            DbError::ReadTimeout { .. } => Self::READ_TIMEOUT,
+            DbError::ReadFailure { .. } => Self::READ_FAILURE,
+            DbError::FunctionFailure { .. } => Self::FUNCTION_FAILURE,
+            DbError::WriteFailure { .. } => Self::WRITE_FAILURE,
+            DbError::SyntaxError => Self::SYNTAX_ERROR,
+            DbError::Unauthorized => Self::UNAUTHORIZED,
+            DbError::Invalid => Self::INVALID,
+            DbError::ConfigError => Self::CONFIG_ERROR,
+            DbError::AlreadyExists { .. } => Self::ALREADY_EXISTS,
+            DbError::Unprepared { .. } => Self::UNPREPARED,
+            DbError::RateLimitReached { .. } => Self::RATE_LIMIT_REACHED,
+            DbError::Other(_) => Self::OTHER,
+        }
+    }
+}
+
+static TIMEOUT_ERROR: GILOnceCell<Py<PyType>> = GILOnceCell::new();
+
+/// Type of timeout exception.
+///
+/// Timeout exception is a subclass of both `ScyllaPyDBError`
+/// and `asyncio.TimeoutError`, so it cannot be created
+/// with `create_exception` macro.
+///
+/// # Errors
+///
+/// May return an error if the type cannot be created.
+pub fn timeout_error_type(py: Python<'_>) -> PyResult<&PyType> {
+    let error_type = TIMEOUT_ERROR.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+        let bases = PyTuple::new(
+            py,
+            [
+                py.get_type::<ScyllaPyDBError>().as_ref(),
+                py.import("asyncio")?.getattr("TimeoutError")?,
+            ],
+        );
+        let attrs = PyDict::new(py);
+        attrs.set_item("__module__", "scyllapy.exceptions")?;
+        attrs.set_item("__doc__", "Request to the database has timed out.")?;
+        let error_type = py
+            .get_type::<PyType>()
+            .call1(("ScyllaPyTimeoutError", bases, attrs))?;
+        Ok(error_type.downcast::<PyType>()?.into())
+    })?;
+    Ok(error_type.as_ref(py))
+}
 
 /// Create module with exceptions.
 ///
@@ -30,6 +136,13 @@ pub fn setup_module(py: Python<'_>, module: &PyModule) -> PyResult<()> {
         "ScyllaPySessionError",
         py.get_type::<ScyllaPySessionError>(),
     )?;
+    module.add("ScyllaPySyntaxError", py.get_type::<ScyllaPySyntaxError>())?;
+    module.add("ScyllaPyTimeoutError", timeout_error_type(py)?)?;
+    module.add(
+        "ScyllaPyServerWarning",
+        py.get_type::<ScyllaPyServerWarning>(),
+    )?;
+    module.add_class::<ScyllaPyErrorCode>()?;
     module.add(
         "ScyllaPyBindingError",
         py.get_type::<ScyllaPyBindingError>(),
@@ -42,5 +155,9 @@ pub fn setup_module(py: Python<'_>, module: &PyModule) -> PyResult<()> {
         "ScyllaPyQueryBuiderError",
         py.get_type::<ScyllaPyQueryBuiderError>(),
     )?;
+    module.add(
+        "ScyllaPyMigrationError",
+        py.get_type::<ScyllaPyMigrationError>(),
+    )?;
     Ok(())
 }
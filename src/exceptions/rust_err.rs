@@ -1,12 +1,53 @@
+use std::fmt::{self, Display};
+
+use pyo3::{IntoPy, PyObject, PyResult, Python};
+use scylla::transport::errors::{DbError, QueryError};
 use thiserror::Error;
 
+use crate::consistencies::ScyllaPyConsistency;
+
 use super::py_err::{
-    ScyllaPyBaseError, ScyllaPyBindingError, ScyllaPyDBError, ScyllaPyMappingError,
-    ScyllaPyQueryBuiderError, ScyllaPySessionError,
+    timeout_error_type, ScyllaPyBaseError, ScyllaPyBindingError, ScyllaPyDBError,
+    ScyllaPyErrorCode, ScyllaPyMappingError, ScyllaPyMigrationError, ScyllaPyQueryBuiderError,
+    ScyllaPySessionError, ScyllaPySyntaxError,
 };
 
 pub type ScyllaPyResult<T> = Result<T, ScyllaPyError>;
 
+/// Details of a failed query.
+#[derive(Debug, Clone, Default)]
+pub struct QueryErrorContext {
+    /// Text of the query, if enabled.
+    pub query: Option<String>,
+    /// Summary of parameters without values, if enabled.
+    pub params: Option<String>,
+    /// Number of attempts to execute the query.
+    pub attempts: usize,
+    /// Errors of failed attempts with addresses of nodes.
+    pub attempt_errors: Vec<String>,
+}
+
+impl Display for QueryErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(query) = &self.query {
+            write!(f, " Query: {query}.")?;
+        }
+        if let Some(params) = &self.params {
+            write!(f, " Parameters: {params}.")?;
+        }
+        // Single attempt doesn't add anything to the error itself.
+        if self.attempt_errors.len() > 1 {
+            write!(
+                f,
+                " Attempts: {}. Errors: {}.",
+                self.attempts,
+                self.attempt_errors.join("; ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
 /// Error type for internal use.
 ///
 /// Used only inside Rust application.
@@ -22,6 +63,11 @@ pub enum ScyllaPyError {
     QueryError(#[from] scylla::transport::errors::QueryError),
     #[error("{0}")]
     DBError(#[from] scylla::transport::errors::DbError),
+    #[error("{error}{context}")]
+    QueryContextError {
+        error: QueryError,
+        context: QueryErrorContext,
+    },
     #[error("Python exception: {0}.")]
     PyError(#[from] pyo3::PyErr),
     #[error("OpenSSL error: {0}.")]
@@ -51,20 +97,246 @@ pub enum ScyllaPyError {
     #[error("Query doesn't have columns.")]
     NoColumns,
 
+    // Batch errors
+    #[error("Batch is too large: {0}.")]
+    BatchSizeError(String),
+
     // QueryBuilder errors
     #[error("Query builder error: {0}.")]
     QueryBuilderError(&'static str),
+
+    // Migration errors
+    #[error("Migration error: {0}.")]
+    MigrationError(String),
+
+    // Bulk loading errors
+    #[error("Copy error: {0}.")]
+    CopyError(String),
+}
+
+/// Names of attributes with details of database errors.
+const DB_ERROR_ATTRS: [&str; 12] = [
+    "code",
+    "query",
+    "params",
+    "attempts",
+    "attempt_errors",
+    "consistency",
+    "required",
+    "alive",
+    "received",
+    "data_present",
+    "write_type",
+    "failures",
+];
+
+/// Code of the error and details of timeouts
+/// and unavailability errors.
+fn db_error_attrs(py: Python<'_>, error: &DbError) -> Vec<(&'static str, PyObject)> {
+    let code = ("code", ScyllaPyErrorCode::from(error).into_py(py));
+    let mut attrs = match error {
+        DbError::Unavailable {
+            consistency,
+            required,
+            alive,
+        } => vec![
+            (
+                "consistency",
+                ScyllaPyConsistency::from(*consistency).into_py(py),
+            ),
+            ("required", required.into_py(py)),
+            ("alive", alive.into_py(py)),
+        ],
+        DbError::ReadTimeout {
+            consistency,
+            received,
+            required,
+            data_present,
+        } => vec![
+            (
+                "consistency",
+                ScyllaPyConsistency::from(*consistency).into_py(py),
+            ),
+            ("received", received.into_py(py)),
+            ("required", required.into_py(py)),
+            ("data_present", data_present.into_py(py)),
+        ],
+        DbError::WriteTimeout {
+            consistency,
+            received,
+            required,
+            write_type,
+        } => vec![
+            (
+                "consistency",
+                ScyllaPyConsistency::from(*consistency).into_py(py),
+            ),
+            ("received", received.into_py(py)),
+            ("required", required.into_py(py)),
+            ("write_type", write_type.as_str().into_py(py)),
+        ],
+        DbError::ReadFailure {
+            consistency,
+            received,
+            required,
+            numfailures,
+            data_present,
+        } => vec![
+            (
+                "consistency",
+                ScyllaPyConsistency::from(*consistency).into_py(py),
+            ),
+            ("received", received.into_py(py)),
+            ("required", required.into_py(py)),
+            ("failures", numfailures.into_py(py)),
+            ("data_present", data_present.into_py(py)),
+        ],
+        DbError::WriteFailure {
+            consistency,
+            received,
+            required,
+            numfailures,
+            write_type,
+        } => vec![
+            (
+                "consistency",
+                ScyllaPyConsistency::from(*consistency).into_py(py),
+            ),
+            ("received", received.into_py(py)),
+            ("required", required.into_py(py)),
+            ("failures", numfailures.into_py(py)),
+            ("write_type", write_type.as_str().into_py(py)),
+        ],
+        _ => Vec::new(),
+    };
+    attrs.push(code);
+    attrs
+}
+
+/// Set details of database error as exception attributes.
+///
+/// Attributes which don't apply to the error are set to `None`.
+fn set_db_error_attrs(
+    err: &pyo3::PyErr,
+    error: Option<&DbError>,
+    context: Option<QueryErrorContext>,
+) -> PyResult<()> {
+    Python::with_gil(|py| {
+        let value = err.value(py);
+        for name in DB_ERROR_ATTRS {
+            value.setattr(name, py.None())?;
+        }
+        if let Some(context) = context {
+            value.setattr("query", context.query)?;
+            value.setattr("params", context.params)?;
+            value.setattr("attempts", context.attempts)?;
+            value.setattr("attempt_errors", context.attempt_errors)?;
+        }
+        if let Some(error) = error {
+            for (name, attr) in db_error_attrs(py, error) {
+                value.setattr(name, attr)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+impl ScyllaPyError {
+    /// Attach details of the failed query.
+    ///
+    /// Only errors of query execution get the context.
+    #[must_use]
+    pub fn with_query_context(self, context: QueryErrorContext) -> Self {
+        match self {
+            ScyllaPyError::QueryError(error) => ScyllaPyError::QueryContextError { error, context },
+            err => err,
+        }
+    }
+
+    /// Error returned by the database, if any.
+    fn db_error(&self) -> Option<&DbError> {
+        match self {
+            ScyllaPyError::QueryError(QueryError::DbError(error, _))
+            | ScyllaPyError::QueryContextError {
+                error: QueryError::DbError(error, _),
+                ..
+            }
+            | ScyllaPyError::DBError(error) => Some(error),
+            _ => None,
+        }
+    }
+
+    /// Check whether the request has timed out.
+    ///
+    /// Both client and server timeouts are counted.
+    fn is_timeout(&self) -> bool {
+        let query_error = match self {
+            ScyllaPyError::QueryError(error) | ScyllaPyError::QueryContextError { error, .. } => {
+                Some(error)
+            }
+            _ => None,
+        };
+        matches!(
+            query_error,
+            Some(QueryError::RequestTimeout(_) | QueryError::TimeoutError)
+        ) || matches!(
+            self.db_error(),
+            Some(DbError::ReadTimeout { .. } | DbError::WriteTimeout { .. })
+        )
+    }
+
+    /// Details of the query attached to the error.
+    fn query_context(&self) -> Option<QueryErrorContext> {
+        match self {
+            ScyllaPyError::QueryContextError { context, .. } => Some(context.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl From<ScyllaPyError> for pyo3::PyErr {
     fn from(error: ScyllaPyError) -> Self {
         let err_desc = error.to_string();
-        match error {
+        let db_error = error.db_error().cloned();
+        let context = error.query_context();
+        let is_timeout = error.is_timeout();
+        let is_db_error = matches!(
+            error,
+            ScyllaPyError::QueryError(_)
+                | ScyllaPyError::QueryContextError { .. }
+                | ScyllaPyError::DBError(_)
+                | ScyllaPyError::SessionError(_)
+                | ScyllaPyError::ScyllaSessionError(_)
+        );
+        let py_err = match error {
             ScyllaPyError::PyError(err) => err,
-            ScyllaPyError::SSLError(_) => ScyllaPyBaseError::new_err((err_desc,)),
-            ScyllaPyError::QueryError(_) | ScyllaPyError::DBError(_) => {
-                ScyllaPyDBError::new_err((err_desc,))
+            ScyllaPyError::SSLError(_)
+            | ScyllaPyError::BatchSizeError(_)
+            | ScyllaPyError::CopyError(_) => ScyllaPyBaseError::new_err((err_desc,)),
+            // Timeouts can be caught as `asyncio.TimeoutError`.
+            ScyllaPyError::QueryError(_)
+            | ScyllaPyError::QueryContextError { .. }
+            | ScyllaPyError::DBError(_)
+                if is_timeout =>
+            {
+                Python::with_gil(|py| {
+                    timeout_error_type(py).map_or_else(
+                        |err| err,
+                        |error_type| pyo3::PyErr::from_type(error_type, (err_desc,)),
+                    )
+                })
             }
+            // Errors in statements are separated from infrastructure problems.
+            ScyllaPyError::QueryError(_)
+            | ScyllaPyError::QueryContextError { .. }
+            | ScyllaPyError::DBError(_)
+                if matches!(db_error, Some(DbError::SyntaxError | DbError::Invalid)) =>
+            {
+                ScyllaPySyntaxError::new_err((err_desc,))
+            }
+            ScyllaPyError::QueryError(_)
+            | ScyllaPyError::QueryContextError { .. }
+            | ScyllaPyError::DBError(_) => ScyllaPyDBError::new_err((err_desc,)),
             ScyllaPyError::SessionError(_) | ScyllaPyError::ScyllaSessionError(_) => {
                 ScyllaPySessionError::new_err((err_desc,))
             }
@@ -79,6 +351,13 @@ impl From<ScyllaPyError> for pyo3::PyErr {
             | ScyllaPyError::NoReturnsError
             | ScyllaPyError::NoColumns => ScyllaPyMappingError::new_err((err_desc,)),
             ScyllaPyError::QueryBuilderError(_) => ScyllaPyQueryBuiderError::new_err((err_desc,)),
+            ScyllaPyError::MigrationError(_) => ScyllaPyMigrationError::new_err((err_desc,)),
+        };
+        if is_db_error {
+            if let Err(err) = set_db_error_attrs(&py_err, db_error.as_ref(), context) {
+                return err;
+            }
         }
+        py_err
     }
 }
@@ -1,17 +1,230 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use pyo3::{pyclass, pymethods};
-use scylla::{execution_profile::ExecutionProfileHandle, statement::SerialConsistency};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    pyclass, pymethods,
+    types::{PyDict, PyList, PyType},
+    PyResult, Python,
+};
+use scylla::{
+    execution_profile::{ExecutionProfileBuilder, ExecutionProfileHandle},
+    retry_policy::{DefaultRetryPolicy, FallthroughRetryPolicy, RetryPolicy},
+    speculative_execution::{
+        PercentileSpeculativeExecutionPolicy, SimpleSpeculativeExecutionPolicy,
+        SpeculativeExecutionPolicy,
+    },
+    statement::{Consistency, SerialConsistency},
+    transport::downgrading_consistency_retry_policy::DowngradingConsistencyRetryPolicy,
+};
 
 use crate::{
     consistencies::{ScyllaPyConsistency, ScyllaPySerialConsistency},
     load_balancing::ScyllaPyLoadBalancingPolicy,
 };
 
+/// Retry policies for execution profiles.
+///
+/// `DEFAULT` retries only when it's safe,
+/// `FALLTHROUGH` never retries and
+/// `DOWNGRADING_CONSISTENCY` retries with lower
+/// consistency if not enough replicas are alive.
+#[pyclass(name = "RetryPolicy")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[allow(non_camel_case_types)]
+pub enum ScyllaPyRetryPolicy {
+    DEFAULT,
+    FALLTHROUGH,
+    DOWNGRADING_CONSISTENCY,
+}
+
+impl From<ScyllaPyRetryPolicy> for Box<dyn RetryPolicy> {
+    fn from(value: ScyllaPyRetryPolicy) -> Self {
+        match value {
+            ScyllaPyRetryPolicy::DEFAULT => Box::new(DefaultRetryPolicy::new()),
+            ScyllaPyRetryPolicy::FALLTHROUGH => Box::new(FallthroughRetryPolicy::new()),
+            ScyllaPyRetryPolicy::DOWNGRADING_CONSISTENCY => {
+                Box::new(DowngradingConsistencyRetryPolicy::new())
+            }
+        }
+    }
+}
+
+/// Policy of speculative execution.
+///
+/// Speculative executions send the same request
+/// to other nodes, if the first one is slow.
+/// Only idempotent statements are executed speculatively.
+#[pyclass(name = "SpeculativeExecution")]
+#[derive(Clone, Debug)]
+pub struct ScyllaPySpeculativeExecution {
+    inner: Arc<dyn SpeculativeExecutionPolicy>,
+}
+
+#[pymethods]
+impl ScyllaPySpeculativeExecution {
+    /// Send speculative executions with constant delay.
+    ///
+    /// `retry_interval` is a delay between executions in milliseconds.
+    #[classmethod]
+    #[pyo3(signature = (*, max_retry_count, retry_interval))]
+    fn simple(_cls: &PyType, max_retry_count: usize, retry_interval: u64) -> Self {
+        Self {
+            inner: Arc::new(SimpleSpeculativeExecutionPolicy {
+                max_retry_count,
+                retry_interval: Duration::from_millis(retry_interval),
+            }),
+        }
+    }
+
+    /// Send speculative execution when request is slower
+    /// than the given percentile of latencies.
+    ///
+    /// # Errors
+    ///
+    /// If percentile is not in range (0, 100].
+    #[classmethod]
+    #[pyo3(signature = (*, max_retry_count, percentile))]
+    fn percentile(_cls: &PyType, max_retry_count: usize, percentile: f64) -> PyResult<Self> {
+        if percentile.is_nan() || percentile <= 0.0 || percentile > 100.0 {
+            return Err(PyValueError::new_err(
+                "Percentile should be in range (0, 100].",
+            ));
+        }
+        Ok(Self {
+            inner: Arc::new(PercentileSpeculativeExecutionPolicy {
+                max_retry_count,
+                percentile,
+            }),
+        })
+    }
+}
+
+/// Generators of timestamps for queries.
+///
+/// With `SERVER` timestamps are assigned by the database.
+/// With `MONOTONIC` timestamps are generated on the client,
+/// and each next timestamp is greater than the previous one.
+#[pyclass(name = "TimestampGenerator")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[allow(non_camel_case_types)]
+pub enum ScyllaPyTimestampGenerator {
+    #[default]
+    SERVER,
+    MONOTONIC,
+}
+
+impl ScyllaPyTimestampGenerator {
+    /// Generate timestamp for a statement.
+    ///
+    /// Returns `None` if timestamps are assigned by the server.
+    #[must_use]
+    pub fn generate(self) -> Option<i64> {
+        match self {
+            ScyllaPyTimestampGenerator::SERVER => None,
+            ScyllaPyTimestampGenerator::MONOTONIC => Some(monotonic_timestamp()),
+        }
+    }
+}
+
+/// Last timestamp generated by monotonic generator.
+static LAST_TIMESTAMP: AtomicI64 = AtomicI64::new(i64::MIN);
+
+/// Generate client timestamp in microseconds.
+///
+/// If clock goes backwards or multiple timestamps
+/// are generated within one microsecond,
+/// previous timestamp is incremented.
+fn monotonic_timestamp() -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|since_epoch| i64::try_from(since_epoch.as_micros()).ok())
+        .unwrap_or_default();
+    let mut last = LAST_TIMESTAMP.load(Ordering::Relaxed);
+    loop {
+        let next = now.max(last.saturating_add(1));
+        match LAST_TIMESTAMP.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed)
+        {
+            Ok(_) => return next,
+            Err(actual) => last = actual,
+        }
+    }
+}
+
 #[pyclass(name = "ExecutionProfile")]
 #[derive(Clone, Debug)]
 pub struct ScyllaPyExecutionProfile {
     inner: scylla::ExecutionProfile,
+    timestamp_generator: ScyllaPyTimestampGenerator,
+    /// Consistency of the profile.
+    /// Driver's profile doesn't expose its settings.
+    consistency: Consistency,
+    serial_consistency: Option<SerialConsistency>,
+    request_timeout: Option<Duration>,
+    /// Policy is kept to show its latency statistics.
+    load_balancing_policy: Option<ScyllaPyLoadBalancingPolicy>,
+}
+
+impl ScyllaPyExecutionProfile {
+    /// Generator of timestamps for statements with this profile.
+    ///
+    /// Driver's profile doesn't know about it,
+    /// so it should be applied to statements separately.
+    #[must_use]
+    pub fn timestamp_generator(&self) -> ScyllaPyTimestampGenerator {
+        self.timestamp_generator
+    }
+
+    /// Consistency of statements with this profile.
+    ///
+    /// It's `LOCAL_QUORUM` by default, as in the driver.
+    #[must_use]
+    pub fn consistency(&self) -> Consistency {
+        self.consistency
+    }
+
+    /// Serial consistency of statements with this profile.
+    #[must_use]
+    pub fn serial_consistency(&self) -> Option<SerialConsistency> {
+        self.serial_consistency
+    }
+
+    /// Client timeout of statements with this profile.
+    #[must_use]
+    pub fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
+    /// Generate timestamp for a query.
+    ///
+    /// Returns `None` if timestamps are assigned by the server.
+    #[must_use]
+    pub fn generate_timestamp(&self) -> Option<i64> {
+        self.timestamp_generator.generate()
+    }
+
+    /// Latencies of nodes measured by the load balancing policy.
+    ///
+    /// # Errors
+    ///
+    /// If statistics cannot be converted to python.
+    pub fn latency_stats<'a>(&self, py: Python<'a>) -> PyResult<Option<&'a PyList>> {
+        self.load_balancing_policy
+            .as_ref()
+            .map_or(Ok(None), |policy| policy.latency_stats(py))
+    }
+
+    /// Builder with all settings of the profile.
+    #[must_use]
+    pub fn to_builder(&self) -> ExecutionProfileBuilder {
+        self.inner.to_builder()
+    }
 }
 
 #[pymethods]
@@ -21,26 +234,155 @@ impl ScyllaPyExecutionProfile {
         consistency=None,
         serial_consistency=None,
         request_timeout=None,
-        load_balancing_policy = None
+        load_balancing_policy = None,
+        retry_policy = None,
+        speculative_execution = None,
+        timestamp_generator = ScyllaPyTimestampGenerator::SERVER,
     ))]
     fn py_new(
         consistency: Option<ScyllaPyConsistency>,
         serial_consistency: Option<ScyllaPySerialConsistency>,
         request_timeout: Option<u64>,
         load_balancing_policy: Option<ScyllaPyLoadBalancingPolicy>,
+        retry_policy: Option<ScyllaPyRetryPolicy>,
+        speculative_execution: Option<ScyllaPySpeculativeExecution>,
+        timestamp_generator: ScyllaPyTimestampGenerator,
     ) -> Self {
-        let mut profile_builder = scylla::ExecutionProfile::builder();
-        if let Some(consistency) = consistency {
-            profile_builder = profile_builder.consistency(consistency.into());
-        }
-        if let Some(load_balancing_policy) = load_balancing_policy {
+        let consistency = consistency.map_or(Consistency::LocalQuorum, Into::into);
+        let serial_consistency = serial_consistency.map(SerialConsistency::from);
+        let request_timeout = request_timeout.map(Duration::from_secs);
+        let mut profile_builder = scylla::ExecutionProfile::builder().consistency(consistency);
+        if let Some(load_balancing_policy) = load_balancing_policy.clone() {
             profile_builder = profile_builder.load_balancing_policy(load_balancing_policy.into());
         }
+        if let Some(retry_policy) = retry_policy {
+            profile_builder = profile_builder.retry_policy(retry_policy.into());
+        }
         profile_builder = profile_builder
-            .serial_consistency(serial_consistency.map(SerialConsistency::from))
-            .request_timeout(request_timeout.map(Duration::from_secs));
+            .speculative_execution_policy(speculative_execution.map(|policy| policy.inner))
+            .serial_consistency(serial_consistency)
+            .request_timeout(request_timeout);
         Self {
             inner: profile_builder.build(),
+            timestamp_generator,
+            consistency,
+            serial_consistency,
+            request_timeout,
+            load_balancing_policy,
+        }
+    }
+
+    /// Create a copy of the profile with some settings changed.
+    ///
+    /// Overrides have the same names as parameters of the constructor.
+    /// Passing `None` for optional settings resets them.
+    ///
+    /// # Errors
+    ///
+    /// If unknown parameter is passed,
+    /// or if value has wrong type.
+    #[pyo3(signature = (**overrides))]
+    fn derive(&self, overrides: Option<&PyDict>) -> PyResult<Self> {
+        let mut profile_builder = self.inner.to_builder();
+        let mut timestamp_generator = self.timestamp_generator;
+        let mut consistency = self.consistency;
+        let mut serial_consistency = self.serial_consistency;
+        let mut request_timeout = self.request_timeout;
+        let mut load_balancing_policy = self.load_balancing_policy.clone();
+        for (name, value) in overrides.into_iter().flatten() {
+            let name = name.extract::<&str>()?;
+            profile_builder = match name {
+                "consistency" => {
+                    consistency = value.extract::<ScyllaPyConsistency>()?.into();
+                    profile_builder.consistency(consistency)
+                }
+                "serial_consistency" => {
+                    serial_consistency = value
+                        .extract::<Option<ScyllaPySerialConsistency>>()?
+                        .map(SerialConsistency::from);
+                    profile_builder.serial_consistency(serial_consistency)
+                }
+                "request_timeout" => {
+                    request_timeout = value.extract::<Option<u64>>()?.map(Duration::from_secs);
+                    profile_builder.request_timeout(request_timeout)
+                }
+                "load_balancing_policy" => {
+                    let policy = value.extract::<ScyllaPyLoadBalancingPolicy>()?;
+                    load_balancing_policy = Some(policy.clone());
+                    profile_builder.load_balancing_policy(policy.into())
+                }
+                "retry_policy" => {
+                    profile_builder.retry_policy(value.extract::<ScyllaPyRetryPolicy>()?.into())
+                }
+                "speculative_execution" => profile_builder.speculative_execution_policy(
+                    value
+                        .extract::<Option<ScyllaPySpeculativeExecution>>()?
+                        .map(|policy| policy.inner),
+                ),
+                "timestamp_generator" => {
+                    timestamp_generator = value.extract::<ScyllaPyTimestampGenerator>()?;
+                    profile_builder
+                }
+                _ => {
+                    return Err(PyTypeError::new_err(format!(
+                        "Unknown profile parameter `{name}`."
+                    )))
+                }
+            };
+        }
+        Ok(Self {
+            inner: profile_builder.build(),
+            timestamp_generator,
+            consistency,
+            serial_consistency,
+            request_timeout,
+            load_balancing_policy,
+        })
+    }
+
+    #[must_use]
+    pub fn with_consistency(&self, consistency: ScyllaPyConsistency) -> Self {
+        let consistency = consistency.into();
+        Self {
+            inner: self.inner.to_builder().consistency(consistency).build(),
+            timestamp_generator: self.timestamp_generator,
+            consistency,
+            serial_consistency: self.serial_consistency,
+            request_timeout: self.request_timeout,
+            load_balancing_policy: self.load_balancing_policy.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_request_timeout(&self, request_timeout: Option<u64>) -> Self {
+        let request_timeout = request_timeout.map(Duration::from_secs);
+        Self {
+            inner: self
+                .inner
+                .to_builder()
+                .request_timeout(request_timeout)
+                .build(),
+            timestamp_generator: self.timestamp_generator,
+            consistency: self.consistency,
+            serial_consistency: self.serial_consistency,
+            request_timeout,
+            load_balancing_policy: self.load_balancing_policy.clone(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_load_balancing(&self, load_balancing_policy: ScyllaPyLoadBalancingPolicy) -> Self {
+        Self {
+            inner: self
+                .inner
+                .to_builder()
+                .load_balancing_policy(load_balancing_policy.clone().into())
+                .build(),
+            timestamp_generator: self.timestamp_generator,
+            consistency: self.consistency,
+            serial_consistency: self.serial_consistency,
+            request_timeout: self.request_timeout,
+            load_balancing_policy: Some(load_balancing_policy),
         }
     }
 }
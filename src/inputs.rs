@@ -1,11 +1,22 @@
-use pyo3::FromPyObject;
+use pyo3::{FromPyObject, PyAny};
 
 use crate::{
-    batches::{ScyllaPyBatch, ScyllaPyInlineBatch},
-    prepared_queries::ScyllaPyPreparedQuery,
+    batches::{BatchLimits, ScyllaPyBatch, ScyllaPyBatchRetryPolicy, ScyllaPyInlineBatch},
+    exceptions::rust_err::ScyllaPyResult,
+    prepared_queries::{
+        ScyllaPyBoundStatement, ScyllaPyPartialBoundStatement, ScyllaPyPreparedQuery,
+    },
     queries::ScyllaPyQuery,
+    query_builder::{delete::Delete, insert::Insert, update::Update},
+    utils::parse_python_query_params,
+};
+use scylla::{
+    batch::{Batch, BatchStatement},
+    frame::value::LegacySerializedValues,
+    query::Query,
+    transport::errors::QueryError,
+    Session,
 };
-use scylla::{batch::BatchStatement, query::Query};
 
 #[derive(Clone, FromPyObject)]
 pub enum ExecuteInput {
@@ -15,6 +26,10 @@ pub enum ExecuteInput {
     Query(ScyllaPyQuery),
     #[pyo3(transparent, annotation = "PreparedQuery")]
     PreparedQuery(ScyllaPyPreparedQuery),
+    #[pyo3(transparent, annotation = "BoundStatement")]
+    BoundStatement(ScyllaPyBoundStatement),
+    #[pyo3(transparent, annotation = "PartialBoundStatement")]
+    PartialBoundStatement(ScyllaPyPartialBoundStatement),
 }
 
 #[derive(Clone, FromPyObject)]
@@ -25,6 +40,8 @@ pub enum BatchQueryInput {
     Query(ScyllaPyQuery),
     #[pyo3(transparent, annotation = "PreparedQuery")]
     PreparedQuery(ScyllaPyPreparedQuery),
+    #[pyo3(transparent, annotation = "BoundStatement")]
+    BoundStatement(ScyllaPyBoundStatement),
 }
 
 impl From<BatchQueryInput> for BatchStatement {
@@ -33,10 +50,46 @@ impl From<BatchQueryInput> for BatchStatement {
             BatchQueryInput::Text(text) => Self::Query(text.into()),
             BatchQueryInput::Query(query) => Self::Query(query.into()),
             BatchQueryInput::PreparedQuery(prepared) => Self::PreparedStatement(prepared.into()),
+            BatchQueryInput::BoundStatement(bound) => {
+                Self::PreparedStatement(bound.prepared.into())
+            }
+        }
+    }
+}
+
+#[derive(Clone, FromPyObject)]
+pub enum BatchBuilderInput {
+    #[pyo3(transparent, annotation = "Insert")]
+    Insert(Insert),
+    #[pyo3(transparent, annotation = "Update")]
+    Update(Update),
+    #[pyo3(transparent, annotation = "Delete")]
+    Delete(Delete),
+}
+
+impl BatchBuilderInput {
+    /// Statements with their values for batches.
+    ///
+    /// # Errors
+    ///
+    /// If query cannot be built.
+    pub fn batch_statements(&self) -> ScyllaPyResult<Vec<(Query, LegacySerializedValues)>> {
+        match self {
+            BatchBuilderInput::Insert(insert) => insert.batch_statements(),
+            BatchBuilderInput::Update(update) => update.batch_statements(),
+            BatchBuilderInput::Delete(delete) => delete.batch_statements(),
         }
     }
 }
 
+#[derive(Clone, FromPyObject)]
+pub enum BatchStatementInput {
+    #[pyo3(transparent)]
+    Query(BatchQueryInput),
+    #[pyo3(transparent)]
+    Builder(BatchBuilderInput),
+}
+
 #[derive(Clone, FromPyObject)]
 pub enum PrepareInput {
     #[pyo3(transparent, annotation = "str")]
@@ -45,6 +98,29 @@ pub enum PrepareInput {
     Query(ScyllaPyQuery),
 }
 
+impl PrepareInput {
+    /// Prepare the statement.
+    ///
+    /// Timestamp and consistency of the query's profile
+    /// aren't fixed in the prepared statement,
+    /// they are applied on each execution instead.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors from the session.
+    pub async fn prepare(self, session: &Session) -> Result<ScyllaPyPreparedQuery, QueryError> {
+        let (query, profile) = match self {
+            PrepareInput::Text(text) => (Query::new(text), None),
+            PrepareInput::Query(mut query) => {
+                let profile = query.params.profile.take();
+                (Query::from(query), profile)
+            }
+        };
+        let prepared = ScyllaPyPreparedQuery::from(session.prepare(query).await?);
+        Ok(prepared.with_profile(profile))
+    }
+}
+
 impl From<PrepareInput> for Query {
     fn from(value: PrepareInput) -> Self {
         match value {
@@ -61,3 +137,56 @@ pub enum BatchInput {
     #[pyo3(transparent, annotation = "InlineBatch")]
     InlineBatch(ScyllaPyInlineBatch),
 }
+
+impl BatchInput {
+    /// Size limits of the batch.
+    #[must_use]
+    pub fn limits(&self) -> BatchLimits {
+        match self {
+            BatchInput::Batch(batch) => batch.limits(),
+            BatchInput::InlineBatch(inline) => inline.limits(),
+        }
+    }
+
+    /// Retry policy of the batch.
+    #[must_use]
+    pub fn retry_policy(&self) -> Option<ScyllaPyBatchRetryPolicy> {
+        match self {
+            BatchInput::Batch(batch) => batch.retry_policy(),
+            BatchInput::InlineBatch(inline) => inline.retry_policy(),
+        }
+    }
+
+    /// Convert input to batch with values.
+    ///
+    /// Parameters are used only for simple batches,
+    /// because inline batches already have values inside.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed.
+    pub fn into_batch(
+        self,
+        params: Option<Vec<&PyAny>>,
+    ) -> ScyllaPyResult<(Batch, Vec<LegacySerializedValues>)> {
+        let limits = self.limits();
+        let (batch, values) = match self {
+            BatchInput::Batch(batch) => {
+                let mut batch_params = Vec::new();
+                if let Some(passed_params) = params {
+                    for query_params in passed_params {
+                        batch_params.push(parse_python_query_params(
+                            Some(query_params),
+                            false,
+                            None,
+                        )?);
+                    }
+                }
+                batch.into_batch_with_values(batch_params)?
+            }
+            BatchInput::InlineBatch(inline) => inline.into(),
+        };
+        limits.check(&batch, &values)?;
+        Ok((batch, values))
+    }
+}
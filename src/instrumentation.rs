@@ -0,0 +1,1062 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{IpAddr, SocketAddr},
+    num::NonZeroU64,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use pyo3::{
+    exceptions::PyRuntimeError,
+    pyclass, pymethods,
+    sync::GILOnceCell,
+    types::{PyDict, PyList, PyTuple},
+    IntoPy, Py, PyAny, PyErr, PyResult, Python,
+};
+use scylla::{
+    batch::{Batch, BatchStatement},
+    statement::Consistency,
+    tracing::{TracingEvent, TracingInfo},
+};
+
+use crate::{
+    consistencies::ScyllaPyConsistency,
+    exceptions::rust_err::{QueryErrorContext, ScyllaPyResult},
+    utils::AttemptsListener,
+};
+
+/// Upper bounds of latency histogram buckets in milliseconds.
+const LATENCY_BUCKETS: [u32; 12] = [1, 2, 5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000];
+
+/// Maximum number of statements in statement statistics.
+///
+/// Statistics of new statements aren't collected after this limit.
+const MAX_TRACKED_STATEMENTS: usize = 1000;
+
+/// Number of the latest latencies kept for each statement
+/// to calculate quantiles.
+const STATEMENT_LATENCY_SAMPLES: usize = 1024;
+
+/// Quantiles of statement latencies in percents.
+const STATEMENT_QUANTILES: [usize; 3] = [50, 95, 99];
+
+/// Name of python logger for query logs.
+const QUERY_LOGGER: &str = "scyllapy.queries";
+
+/// Kind of the statement.
+///
+/// Statements are told apart by their first keyword.
+/// All statements except for selects and modifications
+/// of data, like schema changes, are `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatementKind {
+    Select,
+    Insert,
+    Update,
+    Delete,
+    Batch,
+    Other,
+}
+
+impl StatementKind {
+    /// Detect the kind of the statement by its first keyword.
+    #[must_use]
+    pub fn of(statement: &str) -> Self {
+        let keyword = statement.split_whitespace().next().unwrap_or_default();
+        [Self::Select, Self::Insert, Self::Update, Self::Delete]
+            .into_iter()
+            .find(|kind| keyword.eq_ignore_ascii_case(kind.name()))
+            .unwrap_or(Self::Other)
+    }
+
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Select => "SELECT",
+            Self::Insert => "INSERT",
+            Self::Update => "UPDATE",
+            Self::Delete => "DELETE",
+            Self::Batch => "BATCH",
+            Self::Other => "OTHER",
+        }
+    }
+
+    /// Whether the statement modifies data.
+    #[must_use]
+    pub fn is_write(self) -> bool {
+        matches!(
+            self,
+            Self::Insert | Self::Update | Self::Delete | Self::Batch
+        )
+    }
+}
+
+/// Normalize statement text.
+///
+/// Whitespaces are collapsed, string and numeric literals
+/// are replaced with `?`, so statements, which differ only
+/// in values, have the same normalized text.
+#[must_use]
+pub fn normalize_statement(statement: &str) -> String {
+    let mut normalized = String::with_capacity(statement.len());
+    let mut chars = statement.trim().chars().peekable();
+    while let Some(current) = chars.next() {
+        if current.is_whitespace() {
+            while chars.next_if(|next| next.is_whitespace()).is_some() {}
+            normalized.push(' ');
+        } else if current == '\'' {
+            // Quotes inside of string literals are escaped by doubling them.
+            while let Some(next) = chars.next() {
+                if next == '\'' && chars.next_if_eq(&'\'').is_none() {
+                    break;
+                }
+            }
+            normalized.push('?');
+        } else if current.is_ascii_digit() {
+            while chars
+                .next_if(|next| next.is_ascii_alphanumeric() || matches!(next, '.' | '-'))
+                .is_some()
+            {}
+            normalized.push('?');
+        } else if current.is_alphanumeric() || current == '_' {
+            // Identifiers are copied as is, even if they have digits.
+            normalized.push(current);
+            while let Some(next) = chars.next_if(|next| next.is_alphanumeric() || *next == '_') {
+                normalized.push(next);
+            }
+        } else {
+            normalized.push(current);
+        }
+    }
+    normalized
+}
+
+/// Hash of normalized statement.
+///
+/// It's 64-bit FNV-1a of the statement bytes, so it's the same
+/// between runs, platforms and versions of the library
+/// and can be used to search logs of the same statement.
+#[must_use]
+pub fn statement_hash(normalized: &str) -> String {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+    let hash = normalized.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Text of all statements in the batch.
+#[must_use]
+pub fn batch_statement(batch: &Batch) -> String {
+    batch
+        .statements
+        .iter()
+        .map(|statement| match statement {
+            BatchStatement::Query(query) => query.contents.as_str(),
+            BatchStatement::PreparedStatement(prepared) => prepared.get_statement(),
+        })
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Span of OpenTelemetry tracer for a single request.
+///
+/// Spans are created by python tracer, passed to the session.
+/// Errors of the tracer are only logged, so they
+/// never break requests.
+pub struct RequestSpan {
+    span: Py<PyAny>,
+}
+
+impl RequestSpan {
+    /// Start a client span for the request.
+    ///
+    /// Span is started in the current context,
+    /// so it becomes a child of the active span.
+    pub fn start(py: Python<'_>, tracer: &Py<PyAny>, kind: &str, statement: &str) -> Option<Self> {
+        let start = || -> PyResult<Self> {
+            let trace = py.import("opentelemetry.trace")?;
+            let attributes = PyDict::new(py);
+            attributes.set_item("db.system", "cassandra")?;
+            attributes.set_item("db.operation", kind)?;
+            attributes.set_item("db.statement", statement)?;
+            let kwargs = PyDict::new(py);
+            kwargs.set_item("kind", trace.getattr("SpanKind")?.getattr("CLIENT")?)?;
+            kwargs.set_item("attributes", attributes)?;
+            let span = tracer
+                .as_ref(py)
+                .call_method("start_span", (kind,), Some(kwargs))?;
+            Ok(Self { span: span.into() })
+        };
+        start()
+            .map_err(|err| log::warn!("Cannot start span: {err}"))
+            .ok()
+    }
+
+    /// Finish the span.
+    ///
+    /// Coordinator is the node, which has received the last attempt.
+    /// If error is passed, status of the span is set to error.
+    pub fn end(
+        self,
+        keyspace: Option<&str>,
+        coordinator: Option<SocketAddr>,
+        error: Option<String>,
+    ) {
+        Python::with_gil(|py| {
+            let end = || -> PyResult<()> {
+                let span = self.span.as_ref(py);
+                if let Some(keyspace) = keyspace {
+                    span.call_method1("set_attribute", ("db.name", keyspace))?;
+                }
+                if let Some(coordinator) = coordinator {
+                    span.call_method1(
+                        "set_attribute",
+                        ("net.peer.name", coordinator.ip().to_string()),
+                    )?;
+                    span.call_method1("set_attribute", ("net.peer.port", coordinator.port()))?;
+                }
+                if let Some(error) = error {
+                    let trace = py.import("opentelemetry.trace")?;
+                    let status = trace
+                        .getattr("Status")?
+                        .call1((trace.getattr("StatusCode")?.getattr("ERROR")?, error))?;
+                    span.call_method1("set_status", (status,))?;
+                }
+                span.call_method0("end")?;
+                Ok(())
+            };
+            if let Err(err) = end() {
+                log::warn!("Cannot end span: {err}");
+            }
+        });
+    }
+}
+
+/// Latency histogram of a single statement kind.
+#[derive(Debug, Default, Clone)]
+struct LatencyHistogram {
+    /// Number of requests in each bucket.
+    /// The last bucket is for requests slower than all bounds.
+    buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    fn record(&mut self, latency: Duration) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| latency <= Duration::from_millis(u64::from(*bound)))
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.total = self.total.saturating_add(latency);
+        self.max = self.max.max(latency);
+    }
+
+    fn to_dict<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        let bounds = LATENCY_BUCKETS
+            .iter()
+            .map(|bound| f64::from(*bound))
+            .chain([f64::INFINITY]);
+        let buckets = PyList::new(py, bounds.zip(self.buckets).collect::<Vec<_>>());
+        let histogram = PyDict::new(py);
+        histogram.set_item("count", self.count)?;
+        histogram.set_item("total", self.total.as_secs_f64() * 1000.0)?;
+        histogram.set_item("max", self.max.as_secs_f64() * 1000.0)?;
+        histogram.set_item("buckets", buckets)?;
+        Ok(histogram)
+    }
+}
+
+/// Latency histograms of requests split by statement kind.
+#[derive(Debug, Default)]
+pub struct LatencyHistograms {
+    kinds: Mutex<HashMap<&'static str, LatencyHistogram>>,
+}
+
+impl LatencyHistograms {
+    /// Record latency of a single request.
+    pub fn record(&self, kind: StatementKind, latency: Duration) {
+        let Ok(mut kinds) = self.kinds.lock() else {
+            return;
+        };
+        kinds.entry(kind.name()).or_default().record(latency);
+    }
+
+    /// Remove all recorded latencies.
+    pub fn reset(&self) {
+        if let Ok(mut kinds) = self.kinds.lock() {
+            kinds.clear();
+        }
+    }
+
+    /// Convert histograms to python dict.
+    ///
+    /// Keys are statement kinds and values are dicts
+    /// with number of requests, total and max latencies,
+    /// and buckets as pairs of upper bound and number of requests.
+    /// All latencies are in milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        let kinds = self
+            .kinds
+            .lock()
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let result = PyDict::new(py);
+        for (kind, histogram) in kinds.iter() {
+            result.set_item(*kind, histogram.to_dict(py)?)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Stage of request processing, which is timed by the profiler.
+#[derive(Debug, Clone, Copy)]
+pub enum ProfileStage {
+    /// Conversion of python parameters to CQL values.
+    Bind,
+    /// Execution of the request by the driver.
+    Network,
+    /// Preparation of result values without the GIL.
+    Decode,
+    /// Creation of python objects from result rows.
+    Conversion,
+}
+
+impl ProfileStage {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bind => "bind",
+            Self::Network => "network",
+            Self::Decode => "decode",
+            Self::Conversion => "conversion",
+        }
+    }
+}
+
+/// Timings of request processing stages.
+///
+/// It's enabled per session and shows where
+/// the time is spent on the rust side.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    stages: Mutex<HashMap<&'static str, LatencyHistogram>>,
+}
+
+impl Profiler {
+    /// Record duration of a single stage.
+    pub fn record(&self, stage: ProfileStage, duration: Duration) {
+        let Ok(mut stages) = self.stages.lock() else {
+            return;
+        };
+        stages.entry(stage.name()).or_default().record(duration);
+    }
+
+    /// Remove all recorded timings.
+    pub fn reset(&self) {
+        if let Ok(mut stages) = self.stages.lock() {
+            stages.clear();
+        }
+    }
+
+    /// Convert timings to python dict.
+    ///
+    /// Keys are names of stages and values are dicts
+    /// in the same format as latency histograms.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        let stages = self
+            .stages
+            .lock()
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let result = PyDict::new(py);
+        for (stage, histogram) in stages.iter() {
+            result.set_item(*stage, histogram.to_dict(py)?)?;
+        }
+        Ok(result)
+    }
+}
+
+/// Information about a request, passed to request listeners.
+#[pyclass(name = "RequestInfo", frozen)]
+#[derive(Clone, Debug)]
+pub struct ScyllaPyRequestInfo {
+    #[pyo3(get)]
+    kind: String,
+    #[pyo3(get)]
+    statement: String,
+    #[pyo3(get)]
+    coordinator: Option<String>,
+}
+
+impl ScyllaPyRequestInfo {
+    #[must_use]
+    pub fn new(kind: String, statement: String) -> Self {
+        Self {
+            kind,
+            statement,
+            coordinator: None,
+        }
+    }
+
+    #[must_use]
+    pub fn statement(&self) -> &str {
+        &self.statement
+    }
+
+    /// Copy of the info with the node, which has received the request.
+    #[must_use]
+    pub fn with_coordinator(&self, coordinator: Option<SocketAddr>) -> Self {
+        Self {
+            coordinator: coordinator.map(|node| node.to_string()),
+            ..self.clone()
+        }
+    }
+}
+
+#[pymethods]
+impl ScyllaPyRequestInfo {
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!(
+            "RequestInfo<kind={}, statement={:?}, coordinator={:?}>",
+            self.kind, self.statement, self.coordinator
+        )
+    }
+}
+
+/// Callbacks, which are called on each request.
+///
+/// Errors of callbacks are only logged, so they
+/// never break requests.
+#[derive(Debug, Default)]
+pub struct RequestListeners {
+    start: Mutex<Vec<Py<PyAny>>>,
+    end: Mutex<Vec<Py<PyAny>>>,
+    error: Mutex<Vec<Py<PyAny>>>,
+}
+
+impl RequestListeners {
+    fn add(listeners: &Mutex<Vec<Py<PyAny>>>, callback: Py<PyAny>) {
+        if let Ok(mut listeners) = listeners.lock() {
+            listeners.push(callback);
+        }
+    }
+
+    fn call(listeners: &Mutex<Vec<Py<PyAny>>>, args: impl IntoPy<Py<PyTuple>> + Clone) {
+        if listeners
+            .lock()
+            .map_or(true, |listeners| listeners.is_empty())
+        {
+            return;
+        }
+        Python::with_gil(|py| {
+            // Listeners are copied, so callbacks
+            // can register new listeners.
+            let Ok(callbacks) = listeners.lock().map(|listeners| {
+                listeners
+                    .iter()
+                    .map(|listener| listener.clone_ref(py))
+                    .collect::<Vec<_>>()
+            }) else {
+                return;
+            };
+            for callback in callbacks {
+                if let Err(err) = callback.call1(py, args.clone()) {
+                    log::warn!("Request listener has failed: {err}");
+                }
+            }
+        });
+    }
+
+    /// Whether no listeners are registered.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        [&self.start, &self.end, &self.error]
+            .into_iter()
+            .all(|listeners| {
+                listeners
+                    .lock()
+                    .map_or(true, |listeners| listeners.is_empty())
+            })
+    }
+
+    pub fn add_start(&self, callback: Py<PyAny>) {
+        Self::add(&self.start, callback);
+    }
+
+    pub fn add_end(&self, callback: Py<PyAny>) {
+        Self::add(&self.end, callback);
+    }
+
+    pub fn add_error(&self, callback: Py<PyAny>) {
+        Self::add(&self.error, callback);
+    }
+
+    /// Call start listeners with information about the request.
+    pub fn request_started(&self, info: &ScyllaPyRequestInfo) {
+        Self::call(&self.start, (info.clone(),));
+    }
+
+    /// Call end listeners with information about
+    /// the request and its duration in milliseconds.
+    pub fn request_ended(&self, info: &ScyllaPyRequestInfo, duration: Duration) {
+        Self::call(&self.end, (info.clone(), duration.as_secs_f64() * 1000.0));
+    }
+
+    /// Call error listeners with information about
+    /// the request and raised exception.
+    pub fn request_failed(&self, info: &ScyllaPyRequestInfo, error: &PyErr) {
+        Self::call(&self.error, (info.clone(), error));
+    }
+}
+
+/// Request, which is observed from its start.
+pub struct ObservedRequest {
+    kind: StatementKind,
+    info: Option<ScyllaPyRequestInfo>,
+    consistency: Consistency,
+    attempts: Arc<AttemptsListener>,
+}
+
+impl ObservedRequest {
+    /// Text of the statement.
+    ///
+    /// It's empty, if nothing uses texts of statements.
+    #[must_use]
+    pub fn statement(&self) -> &str {
+        self.info
+            .as_ref()
+            .map_or("", ScyllaPyRequestInfo::statement)
+    }
+
+    /// Node, which has received the last attempt.
+    #[must_use]
+    pub fn coordinator(&self) -> Option<SocketAddr> {
+        self.attempts.last_node()
+    }
+}
+
+/// Everything, which records finished requests.
+///
+/// Each way of sending requests reports them here,
+/// so histograms, statistics, query logs and listeners
+/// see the same requests.
+#[derive(Clone)]
+pub struct RequestObservers {
+    pub histograms: Option<Arc<LatencyHistograms>>,
+    pub statement_stats: Option<Arc<StatementStatistics>>,
+    pub query_logger: Option<Arc<QueryLogger>>,
+    pub profiler: Option<Arc<Profiler>>,
+    pub listeners: Arc<RequestListeners>,
+    /// Whether texts of statements are used.
+    pub tracks_requests: bool,
+}
+
+impl RequestObservers {
+    /// Start observing the request and call start listeners.
+    ///
+    /// Attempts listener must be set to the statement,
+    /// so logs and listeners know the coordinator.
+    #[must_use]
+    pub fn start(
+        &self,
+        kind: StatementKind,
+        statement: &str,
+        consistency: Consistency,
+        attempts: Arc<AttemptsListener>,
+    ) -> ObservedRequest {
+        // Listeners can be added after observers are created,
+        // e.g. while a writer is running.
+        let info = (self.tracks_requests || !self.listeners.is_empty())
+            .then(|| ScyllaPyRequestInfo::new(kind.name().into(), statement.to_string()));
+        if let Some(info) = &info {
+            self.listeners.request_started(info);
+        }
+        ObservedRequest {
+            kind,
+            info,
+            consistency,
+            attempts,
+        }
+    }
+
+    /// Record the finished request and call end or error listeners.
+    ///
+    /// Errors get the context with attempts of the request,
+    /// so listeners receive the same exception as the caller.
+    ///
+    /// # Errors
+    ///
+    /// If the request has failed.
+    pub fn finish<T>(
+        &self,
+        request: ObservedRequest,
+        duration: Duration,
+        mut context: QueryErrorContext,
+        res: ScyllaPyResult<T>,
+    ) -> Result<T, PyErr> {
+        if let Some(histograms) = &self.histograms {
+            histograms.record(request.kind, duration);
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.record(ProfileStage::Network, duration);
+        }
+        let node = request.coordinator();
+        if let Some(statement_stats) = &self.statement_stats {
+            statement_stats.record(request.statement(), duration, res.is_err());
+        }
+        if let Some(logger) = &self.query_logger {
+            logger.log(&QueryLogRecord {
+                statement: request.statement(),
+                duration,
+                node,
+                consistency: request.consistency,
+                error: res.as_ref().err().map(ToString::to_string),
+            });
+        }
+        let info = request.info.map(|info| info.with_coordinator(node));
+        match res {
+            Ok(res) => {
+                if let Some(info) = &info {
+                    self.listeners.request_ended(info, duration);
+                }
+                Ok(res)
+            }
+            Err(err) => {
+                (context.attempts, context.attempt_errors) = request.attempts.summary();
+                let err = PyErr::from(err.with_query_context(context));
+                if let Some(info) = &info {
+                    self.listeners.request_failed(info, &err);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// Counters of requests sent to a single node.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NodeCounters {
+    /// Attempts, which are waiting for response.
+    pub in_flight: u64,
+    /// Finished attempts, including failed ones.
+    pub requests: u64,
+    pub errors: u64,
+    pub total_latency: Duration,
+}
+
+impl NodeCounters {
+    /// Average latency of finished attempts.
+    #[must_use]
+    pub fn average_latency(&self) -> Option<Duration> {
+        self.total_latency
+            .checked_div(u32::try_from(self.requests).unwrap_or(u32::MAX))
+    }
+}
+
+/// Statistics of requests per node.
+///
+/// Attempts are recorded by query history listeners,
+/// so retries and speculative executions are counted
+/// for nodes that have received them.
+#[derive(Debug, Default)]
+pub struct NodeStatistics {
+    nodes: Mutex<HashMap<SocketAddr, NodeCounters>>,
+}
+
+impl NodeStatistics {
+    fn update(&self, node: SocketAddr, update: impl FnOnce(&mut NodeCounters)) {
+        if let Ok(mut nodes) = self.nodes.lock() {
+            update(nodes.entry(node).or_default());
+        }
+    }
+
+    pub fn attempt_started(&self, node: SocketAddr) {
+        self.update(node, |counters| counters.in_flight += 1);
+    }
+
+    pub fn attempt_succeeded(&self, node: SocketAddr, latency: Duration) {
+        self.update(node, |counters| {
+            counters.in_flight = counters.in_flight.saturating_sub(1);
+            counters.requests += 1;
+            counters.total_latency = counters.total_latency.saturating_add(latency);
+        });
+    }
+
+    pub fn attempt_failed(&self, node: SocketAddr, latency: Duration) {
+        self.update(node, |counters| {
+            counters.in_flight = counters.in_flight.saturating_sub(1);
+            counters.requests += 1;
+            counters.errors += 1;
+            counters.total_latency = counters.total_latency.saturating_add(latency);
+        });
+    }
+
+    /// Attempt, which has never received a response.
+    pub fn attempt_abandoned(&self, node: SocketAddr) {
+        self.update(node, |counters| {
+            counters.in_flight = counters.in_flight.saturating_sub(1);
+        });
+    }
+
+    /// Counters of the node.
+    #[must_use]
+    pub fn get(&self, node: SocketAddr) -> NodeCounters {
+        self.nodes
+            .lock()
+            .ok()
+            .and_then(|nodes| nodes.get(&node).copied())
+            .unwrap_or_default()
+    }
+}
+
+/// Counters of a single normalized statement.
+#[derive(Debug, Default)]
+struct StatementCounters {
+    calls: u64,
+    errors: u64,
+    total_latency: Duration,
+    /// The latest latencies to calculate quantiles.
+    samples: VecDeque<Duration>,
+}
+
+impl StatementCounters {
+    fn record(&mut self, latency: Duration, failed: bool) {
+        self.calls += 1;
+        if failed {
+            self.errors += 1;
+        }
+        self.total_latency = self.total_latency.saturating_add(latency);
+        if self.samples.len() == STATEMENT_LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+
+    fn to_dict<'a>(&self, py: Python<'a>, statement: &str) -> PyResult<&'a PyDict> {
+        let mut samples = self.samples.iter().copied().collect::<Vec<_>>();
+        samples.sort_unstable();
+        let stats = PyDict::new(py);
+        stats.set_item("statement", statement)?;
+        stats.set_item("calls", self.calls)?;
+        stats.set_item("errors", self.errors)?;
+        stats.set_item(
+            "error_rate",
+            f64::from(u32::try_from(self.errors).unwrap_or(u32::MAX))
+                / f64::from(u32::try_from(self.calls).unwrap_or(u32::MAX)),
+        )?;
+        stats.set_item("total_latency", self.total_latency.as_secs_f64() * 1000.0)?;
+        for quantile in STATEMENT_QUANTILES {
+            let index = (samples.len() * quantile / 100).min(samples.len().saturating_sub(1));
+            stats.set_item(
+                format!("p{quantile}"),
+                samples
+                    .get(index)
+                    .map(|latency| latency.as_secs_f64() * 1000.0),
+            )?;
+        }
+        Ok(stats)
+    }
+}
+
+/// Statistics of statements keyed by normalized text.
+///
+/// It's similar to `pg_stat_statements` of `PostgreSQL`.
+#[derive(Debug, Default)]
+pub struct StatementStatistics {
+    statements: Mutex<HashMap<String, StatementCounters>>,
+}
+
+impl StatementStatistics {
+    /// Record a single execution of the statement.
+    pub fn record(&self, statement: &str, latency: Duration, failed: bool) {
+        let statement = normalize_statement(statement);
+        let Ok(mut statements) = self.statements.lock() else {
+            return;
+        };
+        if !statements.contains_key(&statement) && statements.len() >= MAX_TRACKED_STATEMENTS {
+            return;
+        }
+        statements
+            .entry(statement)
+            .or_default()
+            .record(latency, failed);
+    }
+
+    /// Remove all statistics.
+    pub fn reset(&self) {
+        if let Ok(mut statements) = self.statements.lock() {
+            statements.clear();
+        }
+    }
+
+    /// Convert statistics to python list of dicts.
+    ///
+    /// Statements are sorted by total latency,
+    /// so the most expensive ones are first.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn to_list<'a>(&self, py: Python<'a>) -> PyResult<&'a PyList> {
+        let statements = self
+            .statements
+            .lock()
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let mut sorted = statements.iter().collect::<Vec<_>>();
+        sorted.sort_by(|(_, left), (_, right)| right.total_latency.cmp(&left.total_latency));
+        let mut result = Vec::with_capacity(sorted.len());
+        for (statement, counters) in sorted {
+            result.push(counters.to_dict(py, statement)?);
+        }
+        Ok(PyList::new(py, result))
+    }
+}
+
+/// Finished request for query logs.
+pub struct QueryLogRecord<'a> {
+    pub statement: &'a str,
+    pub duration: Duration,
+    pub node: Option<SocketAddr>,
+    /// Consistency, which the request was sent with.
+    pub consistency: Consistency,
+    pub error: Option<String>,
+}
+
+/// Logger of finished requests.
+///
+/// Records are written to `scyllapy.queries` python logger
+/// with structured fields in `extra`. Successful requests
+/// are logged with `DEBUG` level and sampled, failed requests
+/// are always logged with `WARNING` level.
+#[derive(Debug)]
+pub struct QueryLogger {
+    /// Only one of this number of successful requests is logged.
+    sampling: NonZeroU64,
+    successes: AtomicU64,
+}
+
+impl QueryLogger {
+    #[must_use]
+    pub fn new(sampling: NonZeroU64) -> Self {
+        Self {
+            sampling,
+            successes: AtomicU64::new(0),
+        }
+    }
+
+    pub fn log(&self, record: &QueryLogRecord<'_>) {
+        if record.error.is_none()
+            && self.successes.fetch_add(1, Ordering::Relaxed) % self.sampling.get() != 0
+        {
+            return;
+        }
+        Python::with_gil(|py| {
+            let log = || -> PyResult<()> {
+                let logging = py.import("logging")?;
+                let logger = logging.call_method1("getLogger", (QUERY_LOGGER,))?;
+                let level = logging.getattr(if record.error.is_some() {
+                    "WARNING"
+                } else {
+                    "DEBUG"
+                })?;
+                if !logger
+                    .call_method1("isEnabledFor", (level,))?
+                    .extract::<bool>()?
+                {
+                    return Ok(());
+                }
+                let statement = normalize_statement(record.statement);
+                let duration = record.duration.as_secs_f64() * 1000.0;
+                let extra = PyDict::new(py);
+                extra.set_item("statement_hash", statement_hash(&statement))?;
+                extra.set_item("statement", &statement)?;
+                extra.set_item("duration", duration)?;
+                extra.set_item("node", record.node.map(|node| node.to_string()))?;
+                extra.set_item(
+                    "consistency",
+                    format!("{:?}", ScyllaPyConsistency::from(record.consistency)),
+                )?;
+                extra.set_item("error", record.error.as_deref())?;
+                let message = match &record.error {
+                    Some(error) => format!("{statement} has failed: {error}"),
+                    None => format!("{statement} took {duration:.3}ms"),
+                };
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("extra", extra)?;
+                logger.call_method("log", (level, message), Some(kwargs))?;
+                Ok(())
+            };
+            if let Err(err) = log() {
+                log::warn!("Cannot log query: {err}");
+            }
+        });
+    }
+}
+
+fn trace_event<'a>(py: Python<'a>, event: &TracingEvent) -> PyResult<&'a PyDict> {
+    let info = PyDict::new(py);
+    info.set_item("node", event.source.map(|node| node.to_string()))?;
+    info.set_item("activity", event.activity.as_deref())?;
+    info.set_item("elapsed", event.source_elapsed)?;
+    info.set_item("thread", event.thread.as_deref())?;
+    Ok(info)
+}
+
+/// Timeline of a traced request.
+///
+/// Events are grouped by nodes, which have produced them,
+/// in order of their first appearance and sorted by elapsed
+/// microseconds on the node. All events are also returned
+/// in the order, they were recorded in.
+///
+/// # Errors
+///
+/// If dicts cannot be created.
+pub fn trace_timeline<'a>(
+    py: Python<'a>,
+    trace_id: &str,
+    tracing: &TracingInfo,
+) -> PyResult<&'a PyDict> {
+    let mut nodes: Vec<(Option<IpAddr>, Vec<&TracingEvent>)> = Vec::new();
+    for event in &tracing.events {
+        match nodes.iter_mut().find(|(node, _)| *node == event.source) {
+            Some((_, events)) => events.push(event),
+            None => nodes.push((event.source, vec![event])),
+        }
+    }
+    let mut timeline_nodes = Vec::with_capacity(nodes.len());
+    for (node, mut events) in nodes {
+        events.sort_by_key(|event| event.source_elapsed);
+        let node_info = PyDict::new(py);
+        node_info.set_item("node", node.map(|node| node.to_string()))?;
+        node_info.set_item(
+            "events",
+            events
+                .into_iter()
+                .map(|event| trace_event(py, event))
+                .collect::<PyResult<Vec<_>>>()?,
+        )?;
+        timeline_nodes.push(node_info);
+    }
+    let timeline = PyDict::new(py);
+    timeline.set_item("trace_id", trace_id)?;
+    timeline.set_item("request", tracing.request.as_deref())?;
+    timeline.set_item("command", tracing.command.as_deref())?;
+    timeline.set_item(
+        "coordinator",
+        tracing.coordinator.map(|node| node.to_string()),
+    )?;
+    timeline.set_item("client", tracing.client.map(|client| client.to_string()))?;
+    timeline.set_item(
+        "started_at",
+        tracing
+            .started_at
+            .and_then(|started| chrono::DateTime::from_timestamp_millis(started.0)),
+    )?;
+    timeline.set_item("duration", tracing.duration)?;
+    timeline.set_item("nodes", timeline_nodes)?;
+    timeline.set_item(
+        "events",
+        tracing
+            .events
+            .iter()
+            .map(|event| trace_event(py, event))
+            .collect::<PyResult<Vec<_>>>()?,
+    )?;
+    Ok(timeline)
+}
+
+static LAST_QUERY_VAR: GILOnceCell<Py<PyAny>> = GILOnceCell::new();
+
+/// Context variable with `LastQuery` of the current context.
+///
+/// # Errors
+///
+/// May return an error if the variable cannot be created.
+pub fn last_query_var(py: Python<'_>) -> PyResult<&PyAny> {
+    let var = LAST_QUERY_VAR.get_or_try_init(py, || -> PyResult<Py<PyAny>> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("default", py.None())?;
+        Ok(py
+            .import("contextvars")?
+            .getattr("ContextVar")?
+            .call(("scyllapy_last_query",), Some(kwargs))?
+            .into())
+    })?;
+    Ok(var.as_ref(py))
+}
+
+/// Tracing id and statement hash of the last finished query.
+///
+/// It's stored in `last_query` context variable,
+/// so it can be read by web middlewares.
+#[pyclass(name = "LastQuery")]
+#[derive(Clone, Debug, Default)]
+pub struct ScyllaPyLastQuery {
+    #[pyo3(get)]
+    trace_id: Option<String>,
+    #[pyo3(get)]
+    statement_hash: Option<String>,
+}
+
+impl ScyllaPyLastQuery {
+    /// Get `LastQuery` of the current context.
+    ///
+    /// If the context has no `LastQuery` yet, a new one is set.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if context variable cannot be accessed.
+    pub fn current(py: Python<'_>) -> PyResult<Py<Self>> {
+        let var = last_query_var(py)?;
+        if let Ok(last_query) = var.call_method0("get")?.extract::<Py<Self>>() {
+            return Ok(last_query);
+        }
+        let last_query = Py::new(py, Self::default())?;
+        var.call_method1("set", (last_query.clone_ref(py),))?;
+        Ok(last_query)
+    }
+
+    /// Store tracing id and hash of the finished statement.
+    pub fn update(last_query: &Py<Self>, trace_id: Option<uuid::Uuid>, statement: &str) {
+        let statement_hash = statement_hash(&normalize_statement(statement));
+        Python::with_gil(|py| {
+            if let Ok(mut last_query) = last_query.try_borrow_mut(py) {
+                last_query.trace_id = trace_id.map(|trace_id| trace_id.to_string());
+                last_query.statement_hash = Some(statement_hash);
+            }
+        });
+    }
+}
+
+#[pymethods]
+impl ScyllaPyLastQuery {
+    #[new]
+    #[must_use]
+    pub fn py_new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!(
+            "LastQuery<trace_id={:?}, statement_hash={:?}>",
+            self.trace_id, self.statement_hash
+        )
+    }
+}
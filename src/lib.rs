@@ -1,16 +1,29 @@
+pub mod auth;
+pub mod batch_writer;
 pub mod batches;
+pub mod bulk;
+pub mod cache;
+pub mod coalescing;
 pub mod consistencies;
 pub mod exceptions;
 pub mod execution_profiles;
 pub mod extra_types;
 pub mod inputs;
+pub mod instrumentation;
 pub mod load_balancing;
+pub mod lwt;
+pub mod migrations;
 pub mod prepared_queries;
 pub mod queries;
 pub mod query_builder;
 pub mod query_results;
+pub mod schema;
 pub mod scylla_cls;
+pub mod shadow;
+pub mod startup_retry;
+pub mod trio;
 pub mod utils;
+pub mod writer;
 
 use pyo3::{pymodule, types::PyModule, PyResult, Python};
 
@@ -26,11 +39,26 @@ fn _internal(py: Python<'_>, pymod: &PyModule) -> PyResult<()> {
     pymod.add_class::<consistencies::ScyllaPySerialConsistency>()?;
     pymod.add_class::<queries::ScyllaPyQuery>()?;
     pymod.add_class::<prepared_queries::ScyllaPyPreparedQuery>()?;
+    pymod.add_class::<prepared_queries::ScyllaPyBoundStatement>()?;
+    pymod.add_class::<prepared_queries::ScyllaPyPartialBoundStatement>()?;
     pymod.add_class::<batches::ScyllaPyBatch>()?;
     pymod.add_class::<batches::ScyllaPyBatchType>()?;
     pymod.add_class::<batches::ScyllaPyInlineBatch>()?;
+    pymod.add_class::<batches::ScyllaPyBatchRetryPolicy>()?;
+    pymod.add_class::<batch_writer::ScyllaPyBatchWriter>()?;
+    pymod.add_class::<writer::ScyllaPyWriter>()?;
+    pymod.add_class::<startup_retry::ScyllaPyStartupRetryPolicy>()?;
     pymod.add_class::<query_results::ScyllaPyQueryResult>()?;
+    pymod.add_class::<query_results::ScyllaPyBatchResult>()?;
+    pymod.add_class::<query_results::ScyllaPyLazyValue>()?;
     pymod.add_class::<execution_profiles::ScyllaPyExecutionProfile>()?;
+    pymod.add_class::<execution_profiles::ScyllaPyRetryPolicy>()?;
+    pymod.add_class::<execution_profiles::ScyllaPySpeculativeExecution>()?;
+    pymod.add_class::<execution_profiles::ScyllaPyTimestampGenerator>()?;
+    pymod.add_class::<instrumentation::ScyllaPyRequestInfo>()?;
+    pymod.add_class::<instrumentation::ScyllaPyLastQuery>()?;
+    pymod.add("last_query", instrumentation::last_query_var(py)?)?;
+    pymod.add_class::<migrations::ScyllaPyMigrations>()?;
     add_submodule(py, pymod, "extra_types", extra_types::setup_module)?;
     add_submodule(py, pymod, "query_builder", query_builder::setup_module)?;
     add_submodule(py, pymod, "exceptions", exceptions::py_err::setup_module)?;
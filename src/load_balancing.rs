@@ -1,19 +1,222 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use pyo3::{
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     pyclass, pymethods,
-    types::{PyModule, PyType},
-    PyAny, PyResult, Python,
+    types::{PyDict, PyList, PyModule, PyType},
+    IntoPy, Py, PyAny, PyResult, Python,
+};
+use scylla::{
+    load_balancing::{
+        DefaultPolicy, FallbackPlan, LatencyAwarenessBuilder, LoadBalancingPolicy, RoutingInfo,
+    },
+    routing::Token,
+    transport::{
+        errors::{DbError, QueryError},
+        topology::Strategy,
+        ClusterData, Node, NodeRef,
+    },
 };
-use scylla::load_balancing::{DefaultPolicy, LatencyAwarenessBuilder, LoadBalancingPolicy};
-use std::time::Duration;
+use uuid::Uuid;
 
-use crate::{exceptions::rust_err::ScyllaPyResult, utils::scyllapy_future};
+use crate::{
+    consistencies::ScyllaPyConsistency, exceptions::rust_err::ScyllaPyResult,
+    utils::scyllapy_future,
+};
 
 #[pyclass(name = "LoadBalancingPolicy")]
 #[derive(Clone, Debug)]
 pub struct ScyllaPyLoadBalancingPolicy {
     inner: Arc<dyn LoadBalancingPolicy>,
+    latency_stats: Option<Arc<LatencyStats>>,
+}
+
+/// Settings of latency awareness, which are used by the driver by default.
+const DEFAULT_MINIMUM_MEASUREMENTS: usize = 50;
+const DEFAULT_RETRY_PERIOD: Duration = Duration::from_secs(10);
+const DEFAULT_EXCLUSION_THRESHOLD: f64 = 2.0;
+const DEFAULT_SCALE: Duration = Duration::from_millis(100);
+
+/// Latency of a single node.
+#[derive(Debug)]
+struct NodeLatency {
+    address: String,
+    datacenter: Option<String>,
+    average: Duration,
+    measurements: usize,
+    updated_at: Instant,
+}
+
+/// Latencies of nodes per node.
+///
+/// Latency awareness of the driver doesn't expose its state,
+/// so the same measurements are collected with the same
+/// averaging and penalty rules to show them to users.
+#[derive(Debug)]
+struct LatencyStats {
+    settings: ScyllaPyLatencyAwareness,
+    nodes: Mutex<HashMap<Uuid, NodeLatency>>,
+}
+
+impl LatencyStats {
+    fn new(settings: ScyllaPyLatencyAwareness) -> Self {
+        Self {
+            settings,
+            nodes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Update average latency of the node.
+    ///
+    /// Older measurements lose their weight
+    /// with time, according to the scale.
+    fn record(&self, node: &Node, latency: Duration) {
+        if latency.is_zero() {
+            return;
+        }
+        let Ok(mut nodes) = self.nodes.lock() else {
+            return;
+        };
+        let now = Instant::now();
+        let Some(stats) = nodes.get_mut(&node.host_id) else {
+            nodes.insert(
+                node.host_id,
+                NodeLatency {
+                    address: node.address.to_string(),
+                    datacenter: node.datacenter.clone(),
+                    average: latency,
+                    measurements: 1,
+                    updated_at: now,
+                },
+            );
+            return;
+        };
+        let scaled_delay = now
+            .saturating_duration_since(stats.updated_at)
+            .as_secs_f64()
+            / self.settings.scale.as_secs_f64();
+        let previous_weight = if scaled_delay <= 0.0 {
+            1.0
+        } else {
+            (scaled_delay + 1.0).ln() / scaled_delay
+        };
+        stats.average = Duration::try_from_secs_f64(
+            (1.0 - previous_weight) * latency.as_secs_f64()
+                + previous_weight * stats.average.as_secs_f64(),
+        )
+        .unwrap_or(stats.average);
+        stats.measurements += 1;
+        stats.updated_at = now;
+    }
+
+    /// Latencies of nodes with their penalties.
+    ///
+    /// Node is penalized, if it has enough measurements,
+    /// it was measured within retry period and its average
+    /// is higher than the fastest one by exclusion threshold.
+    fn to_list<'a>(&self, py: Python<'a>) -> PyResult<&'a PyList> {
+        let nodes = self
+            .nodes
+            .lock()
+            .map_err(|err| PyRuntimeError::new_err(err.to_string()))?;
+        let settings = &self.settings;
+        let fastest = nodes
+            .values()
+            .filter(|node| node.measurements >= settings.minimum_measurements)
+            .map(|node| node.average)
+            .min();
+        let mut result = Vec::with_capacity(nodes.len());
+        for (host_id, node) in nodes.iter() {
+            let slowdown = fastest
+                .filter(|fastest| !fastest.is_zero())
+                .map(|fastest| node.average.as_secs_f64() / fastest.as_secs_f64());
+            let penalized = node.measurements >= settings.minimum_measurements
+                && node.updated_at.elapsed() < settings.retry_period
+                && slowdown.is_some_and(|slowdown| slowdown > settings.exclusion_threshold);
+            let info = PyDict::new(py);
+            info.set_item("host_id", host_id.to_string())?;
+            info.set_item("address", &node.address)?;
+            info.set_item("datacenter", node.datacenter.as_deref())?;
+            info.set_item("average_latency", node.average.as_secs_f64() * 1000.0)?;
+            info.set_item("measurements", node.measurements)?;
+            info.set_item("slowdown", slowdown)?;
+            info.set_item("penalized", penalized)?;
+            result.push(info);
+        }
+        Ok(PyList::new(py, result))
+    }
+}
+
+/// Policy that collects latencies of nodes.
+#[derive(Debug)]
+struct LatencyStatsPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    stats: Arc<LatencyStats>,
+}
+
+impl LoadBalancingPolicy for LatencyStatsPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        self.inner.pick(query, cluster)
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        self.inner.fallback(query, cluster)
+    }
+
+    fn on_query_success(&self, query: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.stats.record(node, latency);
+        self.inner.on_query_success(query, latency, node);
+    }
+
+    fn on_query_failure(
+        &self,
+        query: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &QueryError,
+    ) {
+        if reliable_latency_measure(error) {
+            self.stats.record(node, latency);
+        }
+        self.inner.on_query_failure(query, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+}
+
+/// Whether latency of the failed query tells how fast the node is.
+///
+/// Errors, which are returned right away, are not measured,
+/// the same way as in the driver's latency awareness.
+fn reliable_latency_measure(error: &QueryError) -> bool {
+    !matches!(
+        error,
+        QueryError::BadQuery(_)
+            | QueryError::TooManyOrphanedStreamIds(_)
+            | QueryError::UnableToAllocStreamId
+            | QueryError::TranslationError(_)
+            | QueryError::DbError(
+                DbError::IsBootstrapping
+                    | DbError::Unavailable { .. }
+                    | DbError::Unprepared { .. }
+                    | DbError::Overloaded
+                    | DbError::RateLimitReached { .. },
+                _,
+            )
+    )
 }
 
 #[pymethods]
@@ -27,8 +230,12 @@ impl ScyllaPyLoadBalancingPolicy {
         permit_dc_failover = None,
         shuffling_replicas = None,
         latency_awareness = None,
+        avoid_datacenters = None,
+        avoid_racks = None,
+        replica_ordering = None,
     )
     )]
+    #[allow(clippy::too_many_arguments)]
     fn build(
         cls: &PyType,
         token_aware: Option<bool>,
@@ -37,7 +244,18 @@ impl ScyllaPyLoadBalancingPolicy {
         permit_dc_failover: Option<bool>,
         shuffling_replicas: Option<bool>,
         latency_awareness: Option<ScyllaPyLatencyAwareness>,
+        avoid_datacenters: Option<Vec<String>>,
+        avoid_racks: Option<Vec<String>>,
+        replica_ordering: Option<ScyllaPyReplicaOrdering>,
     ) -> ScyllaPyResult<&PyAny> {
+        if replica_ordering == Some(ScyllaPyReplicaOrdering::RACK_LOCAL_FIRST)
+            && prefer_rack.is_none()
+        {
+            return Err(PyValueError::new_err(
+                "Rack-local-first ordering requires `prefer_rack` to be set.",
+            )
+            .into());
+        }
         scyllapy_future(cls.py(), async move {
             let mut policy_builer = DefaultPolicy::builder();
             if let Some(permit) = permit_dc_failover {
@@ -46,8 +264,8 @@ impl ScyllaPyLoadBalancingPolicy {
             if let Some(token) = token_aware {
                 policy_builer = policy_builer.token_aware(token);
             }
-            if let Some(dc) = prefer_datacenter {
-                if let Some(rack) = prefer_rack {
+            if let Some(dc) = prefer_datacenter.clone() {
+                if let Some(rack) = prefer_rack.clone() {
                     policy_builer = policy_builer.prefer_datacenter_and_rack(dc, rack);
                 } else {
                     policy_builer = policy_builer.prefer_datacenter(dc);
@@ -56,20 +274,511 @@ impl ScyllaPyLoadBalancingPolicy {
             if let Some(shufle) = shuffling_replicas {
                 policy_builer = policy_builer.enable_shuffling_replicas(shufle);
             }
+            if let Some(ordering) = replica_ordering {
+                policy_builer = policy_builer
+                    .enable_shuffling_replicas(ordering == ScyllaPyReplicaOrdering::SHUFFLED);
+            }
+            let mut latency_stats = None;
             if let Some(latency_awareness) = latency_awareness {
-                policy_builer = policy_builer.latency_awareness(latency_awareness.into());
+                policy_builer = policy_builer.latency_awareness(latency_awareness.inner.clone());
+                latency_stats = Some(Arc::new(LatencyStats::new(latency_awareness)));
+            }
+            let mut inner = policy_builer.build();
+            if let Some(ordering) = replica_ordering {
+                if ordering != ScyllaPyReplicaOrdering::SHUFFLED && token_aware != Some(false) {
+                    inner = Arc::new(ReplicaOrderingPolicy {
+                        inner,
+                        ordering,
+                        datacenter: prefer_datacenter,
+                        rack: prefer_rack,
+                    });
+                }
+            }
+            if let Some(stats) = &latency_stats {
+                inner = Arc::new(LatencyStatsPolicy {
+                    inner,
+                    stats: stats.clone(),
+                });
+            }
+            let avoid_datacenters = avoid_datacenters.unwrap_or_default();
+            let avoid_racks = avoid_racks.unwrap_or_default();
+            if !avoid_datacenters.is_empty() || !avoid_racks.is_empty() {
+                inner = Arc::new(FilteringPolicy {
+                    inner,
+                    avoid_datacenters,
+                    avoid_racks,
+                });
             }
             Ok(Self {
-                inner: policy_builer.build(),
+                inner,
+                latency_stats,
             })
         })
     }
+
+    /// Latencies of nodes measured by latency awareness.
+    ///
+    /// Returns a list of dicts with address, datacenter,
+    /// average latency in milliseconds, number of measurements,
+    /// slowdown compared to the fastest node and whether
+    /// the node is penalized for being slow.
+    /// If policy doesn't have latency awareness, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn latency_stats<'a>(&self, py: Python<'a>) -> PyResult<Option<&'a PyList>> {
+        self.latency_stats
+            .as_ref()
+            .map(|stats| stats.to_list(py))
+            .transpose()
+    }
+
+    /// Create round-robin policy.
+    ///
+    /// This policy isn't token-aware and sends
+    /// queries to all nodes of the cluster in turn.
+    #[classmethod]
+    fn round_robin(_cls: &PyType) -> Self {
+        Self {
+            inner: Arc::new(RoundRobinPolicy::default()),
+            latency_stats: None,
+        }
+    }
+
+    /// Create policy implemented in python.
+    ///
+    /// Policy should have `pick` method and
+    /// may have `fallback` method.
+    ///
+    /// # Errors
+    ///
+    /// If policy doesn't have `pick` method.
+    #[classmethod]
+    fn custom(_cls: &PyType, policy: &PyAny) -> PyResult<Self> {
+        if !policy.hasattr("pick")? {
+            return Err(PyTypeError::new_err("Policy should have `pick` method."));
+        }
+        Ok(Self {
+            inner: Arc::new(PythonPolicy {
+                policy: policy.into(),
+            }),
+            latency_stats: None,
+        })
+    }
+}
+
+/// Policy that sends queries to all nodes in turn.
+///
+/// It doesn't take tokens or datacenters into account,
+/// so it's useful when nodes are behind a proxy.
+#[derive(Debug, Default)]
+struct RoundRobinPolicy {
+    index: AtomicUsize,
+}
+
+impl LoadBalancingPolicy for RoundRobinPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        self.fallback(query, cluster)
+            .find(|node| node.is_enabled() && !node.is_down())
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        let nodes = cluster.get_nodes_info();
+        let start = self.index.fetch_add(1, Ordering::Relaxed);
+        Box::new(
+            nodes
+                .iter()
+                .cycle()
+                .skip(start % nodes.len().max(1))
+                .take(nodes.len()),
+        )
+    }
+
+    fn name(&self) -> String {
+        "RoundRobinPolicy".into()
+    }
+}
+
+/// Policy that sends queries only to one node.
+///
+/// It's used to reach every node of the cluster,
+/// for example to warm up connections.
+#[derive(Debug)]
+pub struct SingleNodePolicy {
+    host_id: Uuid,
+}
+
+impl SingleNodePolicy {
+    #[must_use]
+    pub fn new(host_id: Uuid) -> Self {
+        Self { host_id }
+    }
+}
+
+impl LoadBalancingPolicy for SingleNodePolicy {
+    fn pick<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> Option<NodeRef<'a>> {
+        cluster
+            .get_nodes_info()
+            .iter()
+            .find(|node| node.host_id == self.host_id)
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        Box::new(self.pick(query, cluster).into_iter())
+    }
+
+    fn name(&self) -> String {
+        "SingleNodePolicy".into()
+    }
+}
+
+/// Strategy for keyspaces, which are not known to the driver.
+static LOCAL_STRATEGY: Strategy = Strategy::LocalStrategy;
+
+/// Policy that sends queries to replicas of the token.
+///
+/// It's used for statements with explicit routing token,
+/// because the driver computes tokens only for prepared statements.
+/// If all replicas are down, other nodes are tried.
+#[derive(Debug)]
+pub struct TokenPolicy {
+    token: Token,
+    keyspace: Option<String>,
+}
+
+impl TokenPolicy {
+    /// Replication strategy of the keyspace is used to find replicas.
+    /// Without keyspace only the primary replica is known.
+    #[must_use]
+    pub fn new(token: Token, keyspace: Option<String>) -> Self {
+        Self { token, keyspace }
+    }
+
+    fn replicas<'a>(&'a self, cluster: &'a ClusterData) -> Vec<NodeRef<'a>> {
+        let strategy = self
+            .keyspace
+            .as_ref()
+            .and_then(|keyspace| cluster.get_keyspace_info().get(keyspace))
+            .map_or(&LOCAL_STRATEGY, |keyspace| &keyspace.strategy);
+        cluster
+            .replica_locator()
+            .replicas_for_token(self.token, strategy, None)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl LoadBalancingPolicy for TokenPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        self.fallback(query, cluster)
+            .find(|node| node.is_enabled() && !node.is_down())
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        _query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        let replicas = self.replicas(cluster);
+        let others = cluster
+            .get_nodes_info()
+            .iter()
+            .filter(|node| {
+                !replicas
+                    .iter()
+                    .any(|replica| replica.host_id == node.host_id)
+            })
+            .collect::<Vec<_>>();
+        Box::new(replicas.into_iter().chain(others))
+    }
+
+    fn name(&self) -> String {
+        "TokenPolicy".into()
+    }
+}
+
+/// Policy that excludes nodes from some datacenters and racks.
+///
+/// Nodes are filtered out from the plans of the inner policy.
+#[derive(Debug)]
+struct FilteringPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    avoid_datacenters: Vec<String>,
+    avoid_racks: Vec<String>,
+}
+
+impl FilteringPolicy {
+    fn is_allowed(&self, node: &Node) -> bool {
+        let avoided = |location: Option<&String>, avoid: &[String]| {
+            location.is_some_and(|location| avoid.contains(location))
+        };
+        !avoided(node.datacenter.as_ref(), &self.avoid_datacenters)
+            && !avoided(node.rack.as_ref(), &self.avoid_racks)
+    }
+}
+
+impl LoadBalancingPolicy for FilteringPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        match self.inner.pick(query, cluster) {
+            Some(node) if self.is_allowed(node) => Some(node),
+            _ => self.fallback(query, cluster).next(),
+        }
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        Box::new(
+            self.inner
+                .fallback(query, cluster)
+                .filter(|node| self.is_allowed(node)),
+        )
+    }
+
+    fn on_query_success(&self, query: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.inner.on_query_success(query, latency, node);
+    }
+
+    fn on_query_failure(
+        &self,
+        query: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &QueryError,
+    ) {
+        self.inner.on_query_failure(query, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        format!("FilteringPolicy({})", self.inner.name())
+    }
+}
+
+/// Order of replicas in token-aware plans.
+///
+/// `SHUFFLED` tries replicas in random order,
+/// `PRIMARY_FIRST` tries them in the order of the token ring,
+/// `RACK_LOCAL_FIRST` tries replicas from the preferred rack first
+/// and then the other ones in the order of the token ring.
+#[pyclass(name = "ReplicaOrdering")]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[allow(non_camel_case_types)]
+pub enum ScyllaPyReplicaOrdering {
+    SHUFFLED,
+    PRIMARY_FIRST,
+    RACK_LOCAL_FIRST,
+}
+
+/// Policy that tries replicas in the fixed order.
+///
+/// Alive replicas from the preferred datacenter go first,
+/// then all other nodes from the plan of the inner policy.
+#[derive(Debug)]
+struct ReplicaOrderingPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    ordering: ScyllaPyReplicaOrdering,
+    datacenter: Option<String>,
+    rack: Option<String>,
+}
+
+impl ReplicaOrderingPolicy {
+    /// Alive replicas for the token of the query in preferred order.
+    fn replicas<'a>(&self, query: &RoutingInfo, cluster: &'a ClusterData) -> Vec<NodeRef<'a>> {
+        let (Some(keyspace), Some(token)) = (query.keyspace, query.token) else {
+            return Vec::new();
+        };
+        let nodes = cluster.get_nodes_info();
+        let mut replicas = cluster
+            .get_token_endpoints(keyspace, token)
+            .iter()
+            .filter_map(|replica| nodes.iter().find(|node| node.host_id == replica.host_id))
+            .filter(|node| node.is_enabled() && !node.is_down())
+            .filter(|node| self.datacenter.is_none() || node.datacenter == self.datacenter)
+            .collect::<Vec<_>>();
+        if self.ordering == ScyllaPyReplicaOrdering::RACK_LOCAL_FIRST {
+            // Sort is stable, so ring order is kept inside groups.
+            replicas.sort_by_key(|node| node.rack != self.rack);
+        }
+        replicas
+    }
+}
+
+impl LoadBalancingPolicy for ReplicaOrderingPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        self.replicas(query, cluster)
+            .first()
+            .copied()
+            .or_else(|| self.inner.pick(query, cluster))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        let replicas = self.replicas(query, cluster);
+        let ordered = replicas.clone();
+        Box::new(
+            ordered.into_iter().chain(
+                self.inner
+                    .fallback(query, cluster)
+                    .filter(move |node| !replicas.iter().any(|replica| Arc::ptr_eq(replica, node))),
+            ),
+        )
+    }
+
+    fn on_query_success(&self, query: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.inner.on_query_success(query, latency, node);
+    }
+
+    fn on_query_failure(
+        &self,
+        query: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &QueryError,
+    ) {
+        self.inner.on_query_failure(query, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        format!("ReplicaOrderingPolicy({})", self.inner.name())
+    }
+}
+
+/// Policy implemented in python.
+///
+/// Python object should have `pick` method, which takes
+/// query information and list of nodes and returns
+/// index of the node, or `None`. Optional `fallback` method
+/// returns list of indices of nodes to try after the picked one.
+///
+/// If python callbacks fail, error is logged and
+/// all nodes are returned in their order.
+#[derive(Debug)]
+struct PythonPolicy {
+    policy: Py<PyAny>,
+}
+
+impl PythonPolicy {
+    /// Convert routing information to python dict.
+    fn routing_info<'a>(py: Python<'a>, query: &RoutingInfo) -> PyResult<&'a PyDict> {
+        let info = PyDict::new(py);
+        info.set_item(
+            "consistency",
+            ScyllaPyConsistency::from(query.consistency).into_py(py),
+        )?;
+        info.set_item("keyspace", query.keyspace)?;
+        info.set_item("token", query.token.map(|token| token.value))?;
+        info.set_item("is_confirmed_lwt", query.is_confirmed_lwt)?;
+        Ok(info)
+    }
+
+    /// Convert node metadata to python dict.
+    fn node_info<'a>(py: Python<'a>, node: &Node) -> PyResult<&'a PyDict> {
+        let info = PyDict::new(py);
+        info.set_item("host_id", node.host_id.to_string())?;
+        info.set_item("address", node.address.to_string())?;
+        info.set_item("datacenter", node.datacenter.as_deref())?;
+        info.set_item("rack", node.rack.as_deref())?;
+        info.set_item("is_down", node.is_down())?;
+        Ok(info)
+    }
+
+    /// Call method of python policy.
+    ///
+    /// Method is called with query information
+    /// and list of nodes as arguments.
+    fn call<'a>(
+        &self,
+        py: Python<'a>,
+        method: &str,
+        query: &RoutingInfo,
+        nodes: &[Arc<Node>],
+    ) -> PyResult<&'a PyAny> {
+        let nodes_info = nodes
+            .iter()
+            .map(|node| Self::node_info(py, node))
+            .collect::<PyResult<Vec<_>>>()?;
+        self.policy.as_ref(py).call_method1(
+            method,
+            (Self::routing_info(py, query)?, PyList::new(py, nodes_info)),
+        )
+    }
+}
+
+impl LoadBalancingPolicy for PythonPolicy {
+    fn pick<'a>(&'a self, query: &'a RoutingInfo, cluster: &'a ClusterData) -> Option<NodeRef<'a>> {
+        let nodes = cluster.get_nodes_info();
+        let picked = Python::with_gil(|py| {
+            self.call(py, "pick", query, nodes)?
+                .extract::<Option<usize>>()
+        });
+        match picked {
+            Ok(index) => index.and_then(|index| nodes.get(index)),
+            Err(err) => {
+                log::warn!("Cannot pick node using python policy: {err}");
+                None
+            }
+        }
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        query: &'a RoutingInfo,
+        cluster: &'a ClusterData,
+    ) -> FallbackPlan<'a> {
+        let nodes = cluster.get_nodes_info();
+        let plan = Python::with_gil(|py| {
+            if !self.policy.as_ref(py).hasattr("fallback")? {
+                return Ok(None);
+            }
+            self.call(py, "fallback", query, nodes)?
+                .extract::<Vec<usize>>()
+                .map(Some)
+        });
+        match plan {
+            Ok(Some(indices)) => Box::new(
+                indices
+                    .into_iter()
+                    .filter_map(move |index| nodes.get(index)),
+            ),
+            Ok(None) => Box::new(nodes.iter()),
+            Err(err) => {
+                log::warn!("Cannot build fallback plan using python policy: {err}");
+                Box::new(nodes.iter())
+            }
+        }
+    }
+
+    fn name(&self) -> String {
+        "PythonPolicy".into()
+    }
 }
 
 #[pyclass(name = "LatencyAwareness")]
 #[derive(Clone, Debug)]
 pub struct ScyllaPyLatencyAwareness {
     inner: LatencyAwarenessBuilder,
+    minimum_measurements: usize,
+    retry_period: Duration,
+    exclusion_threshold: f64,
+    scale: Duration,
 }
 
 #[pymethods]
@@ -90,23 +799,25 @@ impl ScyllaPyLatencyAwareness {
         update_rate: Option<u64>,
         scale: Option<u64>,
     ) -> Self {
-        let mut builder = LatencyAwarenessBuilder::new();
-        if let Some(minimum_measurements) = minimum_measurements {
-            builder = builder.minimum_measurements(minimum_measurements);
-        }
-        if let Some(retry_period) = retry_period {
-            builder = builder.retry_period(Duration::from_millis(retry_period));
-        }
-        if let Some(exclusion_threshold) = exclusion_threshold {
-            builder = builder.exclusion_threshold(exclusion_threshold);
-        }
+        let minimum_measurements = minimum_measurements.unwrap_or(DEFAULT_MINIMUM_MEASUREMENTS);
+        let retry_period = retry_period.map_or(DEFAULT_RETRY_PERIOD, Duration::from_millis);
+        let exclusion_threshold = exclusion_threshold.unwrap_or(DEFAULT_EXCLUSION_THRESHOLD);
+        let scale = scale.map_or(DEFAULT_SCALE, Duration::from_millis);
+        let mut builder = LatencyAwarenessBuilder::new()
+            .minimum_measurements(minimum_measurements)
+            .retry_period(retry_period)
+            .exclusion_threshold(exclusion_threshold)
+            .scale(scale);
         if let Some(update_rate) = update_rate {
             builder = builder.update_rate(Duration::from_millis(update_rate));
         }
-        if let Some(scale) = scale {
-            builder = builder.scale(Duration::from_millis(scale));
+        Self {
+            inner: builder,
+            minimum_measurements,
+            retry_period,
+            exclusion_threshold,
+            scale,
         }
-        Self { inner: builder }
     }
 }
 
@@ -124,7 +835,8 @@ impl From<ScyllaPyLoadBalancingPolicy> for Arc<dyn LoadBalancingPolicy> {
 
 /// Setup load balancing module.
 ///
-/// This function adds `LoadBalancingPolicy` and `LatencyAwareness` classes to the module.
+/// This function adds `LoadBalancingPolicy`, `LatencyAwareness`
+/// and `ReplicaOrdering` classes to the module.
 ///
 /// # Errors
 ///
@@ -132,5 +844,6 @@ impl From<ScyllaPyLoadBalancingPolicy> for Arc<dyn LoadBalancingPolicy> {
 pub fn setup_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
     module.add_class::<ScyllaPyLoadBalancingPolicy>()?;
     module.add_class::<ScyllaPyLatencyAwareness>()?;
+    module.add_class::<ScyllaPyReplicaOrdering>()?;
     Ok(())
 }
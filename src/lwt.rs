@@ -0,0 +1,220 @@
+use pyo3::{
+    types::{PyDict, PyList},
+    Py, PyAny, Python,
+};
+use scylla::{
+    frame::{
+        response::result::{ColumnSpec, CqlValue},
+        value::LegacySerializedValues,
+    },
+    transport::errors::{DbError, QueryError},
+    QueryResult,
+};
+
+use crate::{
+    batches::ScyllaPyBatchRetryPolicy,
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    utils::{cql_to_py, parse_python_query_params, quote_identifier, quote_table},
+};
+
+/// Name of the column, which tells whether conditional statement was applied.
+const APPLIED_COLUMN: &str = "[applied]";
+
+/// Backoff between attempts of conditional statements.
+///
+/// # Errors
+///
+/// Never fails for constant delays,
+/// but constructor of the policy returns a result.
+pub fn lwt_retry_policy(retries: u32) -> ScyllaPyResult<ScyllaPyBatchRetryPolicy> {
+    Ok(ScyllaPyBatchRetryPolicy::py_new(retries, 0.05, 1.0, true)?)
+}
+
+/// Whether conditional statement was rejected before it was processed.
+///
+/// Outcome of statements, which have failed with other errors,
+/// like timeouts, is unknown, so only these errors can be retried
+/// without reading the row.
+#[must_use]
+pub fn is_rejected(err: &QueryError) -> bool {
+    matches!(
+        err,
+        QueryError::DbError(DbError::Overloaded | DbError::IsBootstrapping, _)
+    )
+}
+
+/// Outcome of conditional update, which has timed out,
+/// found by reading the row at serial consistency.
+pub enum TimedOutUpdate {
+    /// Row has new values.
+    Applied,
+    /// Row still has expected values, so the update can be executed again.
+    NotApplied,
+    /// Row has other values, which are returned.
+    Changed(Py<PyDict>),
+}
+
+/// Names of columns in the dict.
+///
+/// # Errors
+///
+/// May return an error if keys are not strings.
+pub fn dict_columns(values: &PyDict) -> ScyllaPyResult<Vec<String>> {
+    values
+        .keys()
+        .iter()
+        .map(|key| Ok(key.extract::<String>()?))
+        .collect()
+}
+
+/// Build `UPDATE ... IF ...` statement.
+///
+/// # Errors
+///
+/// May return an error if some group of columns is empty.
+pub fn cas_query(
+    table: &str,
+    set_columns: &[String],
+    key_columns: &[String],
+    expected_columns: &[String],
+) -> ScyllaPyResult<String> {
+    if set_columns.is_empty() || key_columns.is_empty() || expected_columns.is_empty() {
+        return Err(ScyllaPyError::BindingError(
+            "Key, expected and new values should not be empty.".into(),
+        ));
+    }
+    Ok(format!(
+        "UPDATE {} SET {} WHERE {} IF {}",
+        quote_table(table),
+        bind_columns(set_columns, ", "),
+        bind_columns(key_columns, " AND "),
+        bind_columns(expected_columns, " AND "),
+    ))
+}
+
+/// Build `SELECT` statement, which reads updated and expected columns of the row.
+#[must_use]
+pub fn cas_read_query(
+    table: &str,
+    set_columns: &[String],
+    key_columns: &[String],
+    expected_columns: &[String],
+) -> String {
+    let mut columns = set_columns.to_vec();
+    for column in expected_columns {
+        if !columns.contains(column) {
+            columns.push(column.clone());
+        }
+    }
+    format!(
+        "SELECT {} FROM {} WHERE {}",
+        columns
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", "),
+        quote_table(table),
+        bind_columns(key_columns, " AND "),
+    )
+}
+
+/// Quoted columns with bind markers, joined with separator.
+fn bind_columns(columns: &[String], sep: &str) -> String {
+    columns
+        .iter()
+        .map(|column| format!("{} = ?", quote_identifier(column)))
+        .collect::<Vec<_>>()
+        .join(sep)
+}
+
+/// Serialize values of the update in the order of markers.
+///
+/// Missing values are bound as nulls.
+///
+/// # Errors
+///
+/// May return an error if values cannot be serialized.
+pub fn bind_cas_values(
+    py: Python<'_>,
+    col_specs: &[ColumnSpec],
+    groups: &[(&[String], &PyDict)],
+) -> ScyllaPyResult<LegacySerializedValues> {
+    let values = PyList::empty(py);
+    for &(columns, group) in groups {
+        for column in columns {
+            values.append(group.get_item(column.as_str())?)?;
+        }
+    }
+    parse_python_query_params(Some(values), false, Some(col_specs))
+}
+
+/// Whether conditional statement was applied and current values of the row.
+///
+/// If the row doesn't exist, only `[applied]` column is returned,
+/// so dict of current values is empty.
+///
+/// # Errors
+///
+/// May return an error if the statement
+/// returned no rows or values cannot be converted.
+pub fn lwt_outcome<'a>(py: Python<'a>, result: &QueryResult) -> ScyllaPyResult<(bool, &'a PyDict)> {
+    let row = result.rows.as_ref().and_then(|rows| rows.first()).ok_or(
+        ScyllaPyError::RowsDowncastError("Conditional statement returned no rows.".into()),
+    )?;
+    let current = PyDict::new(py);
+    let mut applied = false;
+    for (spec, value) in result.col_specs.iter().zip(&row.columns) {
+        if spec.name == APPLIED_COLUMN {
+            applied = matches!(value, Some(CqlValue::Boolean(true)));
+        } else {
+            let value: &PyAny = cql_to_py(py, &spec.name, &spec.typ, value.as_ref())?;
+            current.set_item(spec.name.as_str(), value)?;
+        }
+    }
+    Ok((applied, current))
+}
+
+/// Find out whether timed out update was applied
+/// from the row, which was read at serial consistency.
+///
+/// Serial read finishes Paxos rounds in progress,
+/// so the update cannot be applied after it.
+/// If the row has new values, the update is considered applied.
+///
+/// # Errors
+///
+/// May return an error if values cannot be converted or compared.
+pub fn timed_out_outcome(
+    py: Python<'_>,
+    result: &QueryResult,
+    new: (&[String], &PyDict),
+    expected: (&[String], &PyDict),
+) -> ScyllaPyResult<TimedOutUpdate> {
+    let current = PyDict::new(py);
+    if let Some(row) = result.rows.as_ref().and_then(|rows| rows.first()) {
+        for (spec, value) in result.col_specs.iter().zip(&row.columns) {
+            let value: &PyAny = cql_to_py(py, &spec.name, &spec.typ, value.as_ref())?;
+            current.set_item(spec.name.as_str(), value)?;
+        }
+    }
+    let has_values = |(columns, values): (&[String], &PyDict)| -> ScyllaPyResult<bool> {
+        for column in columns {
+            let current_value = current.get_item(column.as_str())?;
+            let value = values.get_item(column.as_str())?;
+            if !current_value
+                .unwrap_or_else(|| py.None().into_ref(py))
+                .eq(value)?
+            {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    };
+    if has_values(new)? {
+        return Ok(TimedOutUpdate::Applied);
+    }
+    if has_values(expected)? {
+        return Ok(TimedOutUpdate::NotApplied);
+    }
+    Ok(TimedOutUpdate::Changed(current.into()))
+}
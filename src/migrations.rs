@@ -0,0 +1,620 @@
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use pyo3::{pyclass, pymethods, types::PyDict, Py, PyAny, PyObject, PyResult, Python};
+use scylla::{
+    frame::{
+        response::result::CqlValue,
+        value::{CqlTimestamp, ValueList},
+    },
+    QueryResult, Session,
+};
+use tokio::sync::RwLock;
+
+use crate::{
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    scylla_cls::Scylla,
+    utils::scyllapy_future,
+};
+
+/// Default name of the table with applied migrations.
+pub const DEFAULT_MIGRATIONS_TABLE: &str = "schema_migrations";
+
+/// Statements of a migration.
+#[derive(Clone, Debug)]
+enum MigrationSource {
+    /// Statements from `.cql` file and
+    /// optional `.down.cql` file to roll it back.
+    Cql {
+        up: Vec<String>,
+        down: Option<Vec<String>>,
+    },
+    /// Python file with `up` and optional `down`
+    /// async functions, which accept the session.
+    Python(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+struct Migration {
+    version: i64,
+    name: String,
+    source: MigrationSource,
+}
+
+/// Migration, which is recorded in migrations table.
+///
+/// Migration is recorded, when it's started.
+/// It's applied only when `applied_at` is set.
+struct AppliedMigration {
+    version: i64,
+    name: Option<String>,
+    /// Number of statements of CQL migration,
+    /// which are already applied.
+    applied_statements: i32,
+    applied_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Split CQL file into statements.
+///
+/// Statements are separated by semicolons,
+/// which are not inside of quotes. Comments are removed.
+fn split_statements(text: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(symbol) = chars.next() {
+        match symbol {
+            '\'' | '"' => {
+                // Doubled quotes are parsed as two strings
+                // following each other, so they are kept as is.
+                current.push(symbol);
+                for next in chars.by_ref() {
+                    current.push(next);
+                    if next == symbol {
+                        break;
+                    }
+                }
+            }
+            '-' | '/' if chars.next_if_eq(&symbol).is_some() => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        current.push(next);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => {
+                while let Some(next) = chars.next() {
+                    if next == '*' && chars.next_if_eq(&'/').is_some() {
+                        break;
+                    }
+                }
+            }
+            ';' => {
+                statements.push(current.trim().to_string());
+                current.clear();
+            }
+            _ => current.push(symbol),
+        }
+    }
+    statements.push(current.trim().to_string());
+    statements.retain(|statement| !statement.is_empty());
+    statements
+}
+
+fn read_statements(path: &Path) -> ScyllaPyResult<Vec<String>> {
+    let text = fs::read_to_string(path).map_err(|err| {
+        ScyllaPyError::MigrationError(format!("Cannot read {}: {err}", path.display()))
+    })?;
+    Ok(split_statements(&text))
+}
+
+/// Load migrations from the directory.
+///
+/// Names of migration files start with version,
+/// followed by underscore and name of the migration.
+/// For example: `0001_create_users.cql`.
+/// Files which don't start with a version are ignored.
+fn load_migrations(path: &Path) -> ScyllaPyResult<Vec<Migration>> {
+    let entries = fs::read_dir(path).map_err(|err| {
+        ScyllaPyError::MigrationError(format!("Cannot read {}: {err}", path.display()))
+    })?;
+    // Files of each version: name, up file, down file.
+    let mut files: BTreeMap<i64, (String, Option<PathBuf>, Option<PathBuf>)> = BTreeMap::new();
+    for entry in entries {
+        let file_path = entry
+            .map_err(|err| ScyllaPyError::MigrationError(err.to_string()))?
+            .path();
+        let Some(file_name) = file_path.file_name().and_then(OsStr::to_str) else {
+            continue;
+        };
+        let (stem, is_down) = if let Some(stem) = file_name.strip_suffix(".down.cql") {
+            (stem, true)
+        } else if let Some(stem) = file_name
+            .strip_suffix(".cql")
+            .or_else(|| file_name.strip_suffix(".py"))
+        {
+            (stem, false)
+        } else {
+            continue;
+        };
+        let (version, name) = stem.split_once('_').unwrap_or((stem, ""));
+        let Ok(version) = version.parse::<i64>() else {
+            continue;
+        };
+        let (known_name, up, down) = files
+            .entry(version)
+            .or_insert_with(|| (name.to_string(), None, None));
+        let slot = if is_down { down } else { up };
+        if known_name.as_str() != name || slot.is_some() {
+            return Err(ScyllaPyError::MigrationError(format!(
+                "Multiple migrations have version {version}"
+            )));
+        }
+        *slot = Some(file_path);
+    }
+    let mut migrations = Vec::with_capacity(files.len());
+    for (version, (name, up, down)) in files {
+        let Some(up) = up else {
+            return Err(ScyllaPyError::MigrationError(format!(
+                "Migration {version} has only down file"
+            )));
+        };
+        let source = if up.extension() == Some(OsStr::new("py")) {
+            if down.is_some() {
+                return Err(ScyllaPyError::MigrationError(format!(
+                    "Python migration {version} cannot have down file, \
+                    use `down` function instead"
+                )));
+            }
+            MigrationSource::Python(up)
+        } else {
+            MigrationSource::Cql {
+                up: read_statements(&up)?,
+                down: down.as_deref().map(read_statements).transpose()?,
+            }
+        };
+        migrations.push(Migration {
+            version,
+            name,
+            source,
+        });
+    }
+    Ok(migrations)
+}
+
+/// Execute a query, holding the session only while it runs.
+///
+/// Python migrations use the same session, so
+/// it shouldn't be locked while they are running.
+async fn execute(
+    session: &RwLock<Option<Session>>,
+    query: &str,
+    values: impl ValueList,
+) -> ScyllaPyResult<QueryResult> {
+    let session_guard = session.read().await;
+    let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+        "Session is not initialized.".into(),
+    ))?;
+    Ok(session.query(query, values).await?)
+}
+
+/// Execute a conditional statement.
+///
+/// Returns whether the statement was applied.
+async fn execute_conditional(
+    session: &RwLock<Option<Session>>,
+    query: &str,
+    values: impl ValueList,
+) -> ScyllaPyResult<bool> {
+    let result = execute(session, query, values).await?;
+    // `[applied]` is always the first column of results.
+    Ok(matches!(
+        result
+            .rows
+            .as_ref()
+            .and_then(|rows| rows.first())
+            .and_then(|row| row.columns.first()),
+        Some(Some(CqlValue::Boolean(true)))
+    ))
+}
+
+async fn await_schema_agreement(session: &RwLock<Option<Session>>) -> ScyllaPyResult<()> {
+    let session_guard = session.read().await;
+    let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+        "Session is not initialized.".into(),
+    ))?;
+    session.await_schema_agreement().await?;
+    Ok(())
+}
+
+/// Call `up` or `down` function of python migration.
+async fn run_python(scylla: &Py<Scylla>, path: &Path, function: &str) -> ScyllaPyResult<()> {
+    let future = Python::with_gil(|py| -> ScyllaPyResult<_> {
+        let globals = py
+            .import("runpy")?
+            .call_method1("run_path", (path,))?
+            .downcast::<PyDict>()
+            .map_err(|err| ScyllaPyError::MigrationError(err.to_string()))?;
+        let Some(function) = globals.get_item(function)? else {
+            return Err(ScyllaPyError::MigrationError(format!(
+                "{} doesn't have `{function}` function",
+                path.display()
+            )));
+        };
+        let result = function.call1((scylla.clone_ref(py),))?;
+        Ok(pyo3_asyncio::tokio::into_future(result)?)
+    })?;
+    future.await?;
+    Ok(())
+}
+
+/// Run statements of CQL migration.
+///
+/// Schema agreement is awaited after each statement,
+/// so the next one sees changes of the previous.
+async fn run_cql(session: &RwLock<Option<Session>>, statements: &[String]) -> ScyllaPyResult<()> {
+    for statement in statements {
+        execute(session, statement, ()).await?;
+        await_schema_agreement(session).await?;
+    }
+    Ok(())
+}
+
+/// Run statements of CQL migration, starting from the given one.
+///
+/// Number of applied statements is recorded after each statement,
+/// so if migration fails, it's known which statements are applied.
+async fn run_recorded_cql(
+    session: &RwLock<Option<Session>>,
+    table: &str,
+    version: i64,
+    statements: &[String],
+    start: i32,
+) -> ScyllaPyResult<i32> {
+    let mut applied = start;
+    for statement in statements
+        .iter()
+        .skip(usize::try_from(start).unwrap_or_default())
+    {
+        execute(session, statement, ()).await?;
+        await_schema_agreement(session).await?;
+        let progress = execute_conditional(
+            session,
+            &format!(
+                "UPDATE {table} SET applied_statements = ? \
+                WHERE version = ? IF applied_statements = ?"
+            ),
+            (applied + 1, version, applied),
+        )
+        .await?;
+        if !progress {
+            return Err(ScyllaPyError::MigrationError(format!(
+                "Migration {version} is applied concurrently by another process"
+            )));
+        }
+        applied += 1;
+    }
+    Ok(applied)
+}
+
+/// Runner of schema migrations.
+///
+/// Applied migrations are recorded in a table,
+/// so each migration is applied only once.
+#[pyclass(name = "Migrations", frozen)]
+pub struct ScyllaPyMigrations {
+    scylla: Py<Scylla>,
+    session: Arc<RwLock<Option<Session>>>,
+    path: PathBuf,
+    table: String,
+}
+
+impl ScyllaPyMigrations {
+    /// Create a runner of migrations from the directory.
+    ///
+    /// # Errors
+    ///
+    /// If table name is not a valid identifier.
+    pub fn new(
+        scylla: Py<Scylla>,
+        session: Arc<RwLock<Option<Session>>>,
+        path: PathBuf,
+        table: String,
+    ) -> ScyllaPyResult<Self> {
+        let is_valid_table = !table.is_empty()
+            && table
+                .chars()
+                .all(|symbol| symbol.is_ascii_alphanumeric() || matches!(symbol, '_' | '.'));
+        if !is_valid_table {
+            return Err(ScyllaPyError::MigrationError(format!(
+                "Invalid migrations table name: {table}"
+            )));
+        }
+        Ok(Self {
+            scylla,
+            session,
+            path,
+            table,
+        })
+    }
+}
+
+/// Create migrations table and get applied migrations.
+async fn applied_migrations(
+    session: &RwLock<Option<Session>>,
+    table: &str,
+) -> ScyllaPyResult<Vec<AppliedMigration>> {
+    execute(
+        session,
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {table} \
+            (version BIGINT PRIMARY KEY, name TEXT, \
+            applied_statements INT, applied_at TIMESTAMP)"
+        ),
+        (),
+    )
+    .await?;
+    await_schema_agreement(session).await?;
+    let rows = execute(
+        session,
+        &format!("SELECT version, name, applied_statements, applied_at FROM {table}"),
+        (),
+    )
+    .await?
+    .rows_typed::<(i64, Option<String>, Option<i32>, Option<CqlTimestamp>)>()
+    .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+    let mut applied = Vec::new();
+    for row in rows {
+        let (version, name, applied_statements, applied_at) =
+            row.map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+        applied.push(AppliedMigration {
+            version,
+            name,
+            applied_statements: applied_statements.unwrap_or_default(),
+            applied_at: applied_at
+                .and_then(|timestamp| chrono::DateTime::from_timestamp_millis(timestamp.0)),
+        });
+    }
+    applied.sort_by_key(|migration| migration.version);
+    Ok(applied)
+}
+
+#[pymethods]
+impl ScyllaPyMigrations {
+    /// Apply migrations, which weren't applied yet.
+    ///
+    /// Migrations are applied in order of their versions.
+    /// If target is passed, only migrations with versions
+    /// up to the target are applied.
+    ///
+    /// Each migration is claimed with a conditional insert
+    /// before it's started, so concurrent runners don't apply
+    /// the same migration twice. Progress of CQL migrations
+    /// is recorded after each statement. Migrations, which were
+    /// started, but not finished, are continued only if `resume`
+    /// is set. CQL migrations are continued from the first
+    /// statement, which isn't applied, python migrations are
+    /// run again.
+    ///
+    /// Returns versions of applied migrations.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if migrations cannot be loaded,
+    /// session is not initialized, any migration fails
+    /// or it's applied by another process.
+    #[pyo3(signature = (target = None, *, resume = false))]
+    pub fn apply<'a>(
+        &'a self,
+        py: Python<'a>,
+        target: Option<i64>,
+        resume: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let migrations = load_migrations(&self.path)?;
+        let scylla = self.scylla.clone_ref(py);
+        let session = self.session.clone();
+        let table = self.table.clone();
+        scyllapy_future(py, async move {
+            let applied = applied_migrations(&session, &table).await?;
+            let mut versions = Vec::new();
+            for migration in migrations {
+                if target.is_some_and(|target| migration.version > target) {
+                    continue;
+                }
+                let recorded = applied
+                    .iter()
+                    .find(|applied| applied.version == migration.version);
+                let start = match recorded {
+                    Some(recorded) if recorded.applied_at.is_some() => continue,
+                    Some(recorded) if resume => recorded.applied_statements,
+                    Some(recorded) => {
+                        return Err(ScyllaPyError::MigrationError(format!(
+                            "Migration {} was started, but isn't finished, \
+                            {} statements are applied. It's applied by another \
+                            process or has failed, pass `resume=True` to continue it",
+                            migration.version, recorded.applied_statements
+                        )));
+                    }
+                    None => {
+                        let claimed = execute_conditional(
+                            &session,
+                            &format!(
+                                "INSERT INTO {table} (version, name, applied_statements) \
+                                VALUES (?, ?, 0) IF NOT EXISTS"
+                            ),
+                            (migration.version, &migration.name),
+                        )
+                        .await?;
+                        if !claimed {
+                            return Err(ScyllaPyError::MigrationError(format!(
+                                "Migration {} is applied concurrently by another process",
+                                migration.version
+                            )));
+                        }
+                        0
+                    }
+                };
+                let applied_statements = match &migration.source {
+                    MigrationSource::Cql { up, .. } => {
+                        run_recorded_cql(&session, &table, migration.version, up, start).await?
+                    }
+                    MigrationSource::Python(path) => {
+                        run_python(&scylla, path, "up").await?;
+                        start
+                    }
+                };
+                let finished = execute_conditional(
+                    &session,
+                    &format!(
+                        "UPDATE {table} SET applied_at = toTimestamp(now()) \
+                        WHERE version = ? IF applied_statements = ?"
+                    ),
+                    (migration.version, applied_statements),
+                )
+                .await?;
+                if !finished {
+                    return Err(ScyllaPyError::MigrationError(format!(
+                        "Migration {} is applied concurrently by another process",
+                        migration.version
+                    )));
+                }
+                versions.push(migration.version);
+            }
+            Ok(versions)
+        })
+    }
+
+    /// Roll back the latest applied migrations.
+    ///
+    /// Returns versions of rolled back migrations.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if migrations cannot be loaded,
+    /// session is not initialized, migration doesn't
+    /// have down statements or it fails.
+    #[pyo3(signature = (steps = 1))]
+    pub fn rollback<'a>(&'a self, py: Python<'a>, steps: usize) -> ScyllaPyResult<&'a PyAny> {
+        let migrations = load_migrations(&self.path)?;
+        let scylla = self.scylla.clone_ref(py);
+        let session = self.session.clone();
+        let table = self.table.clone();
+        scyllapy_future(py, async move {
+            let applied = applied_migrations(&session, &table).await?;
+            let mut versions = Vec::new();
+            let finished = applied
+                .iter()
+                .filter(|applied| applied.applied_at.is_some());
+            for applied in finished.rev().take(steps) {
+                let Some(migration) = migrations
+                    .iter()
+                    .find(|migration| migration.version == applied.version)
+                else {
+                    return Err(ScyllaPyError::MigrationError(format!(
+                        "Migration {} is applied, but its file is not found",
+                        applied.version
+                    )));
+                };
+                match &migration.source {
+                    MigrationSource::Cql {
+                        down: Some(down), ..
+                    } => {
+                        run_cql(&session, down).await?;
+                    }
+                    MigrationSource::Cql { down: None, .. } => {
+                        return Err(ScyllaPyError::MigrationError(format!(
+                            "Migration {} doesn't have down file",
+                            migration.version
+                        )));
+                    }
+                    MigrationSource::Python(path) => run_python(&scylla, path, "down").await?,
+                }
+                execute_conditional(
+                    &session,
+                    &format!("DELETE FROM {table} WHERE version = ? IF EXISTS"),
+                    (migration.version,),
+                )
+                .await?;
+                versions.push(migration.version);
+            }
+            Ok(versions)
+        })
+    }
+
+    /// Status of migrations.
+    ///
+    /// Returns a dict for each migration with its version,
+    /// name, whether it's applied and when, and number of
+    /// applied statements of started CQL migrations.
+    /// Recorded migrations without files are also returned.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if migrations cannot be loaded
+    /// or session is not initialized.
+    pub fn status<'a>(&'a self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let migrations = load_migrations(&self.path)?;
+        let session = self.session.clone();
+        let table = self.table.clone();
+        scyllapy_future(py, async move {
+            let applied = applied_migrations(&session, &table).await?;
+            let mut statuses = migrations
+                .into_iter()
+                .map(|migration| {
+                    let applied = applied
+                        .iter()
+                        .find(|applied| applied.version == migration.version);
+                    (
+                        migration.version,
+                        Some(migration.name),
+                        applied.map(|applied| applied.applied_statements),
+                        applied.and_then(|applied| applied.applied_at),
+                    )
+                })
+                .collect::<Vec<_>>();
+            for applied in applied {
+                if !statuses
+                    .iter()
+                    .any(|(version, ..)| *version == applied.version)
+                {
+                    statuses.push((
+                        applied.version,
+                        applied.name,
+                        Some(applied.applied_statements),
+                        applied.applied_at,
+                    ));
+                }
+            }
+            statuses.sort_by_key(|(version, ..)| *version);
+            let statuses = Python::with_gil(|py| -> PyResult<Vec<PyObject>> {
+                let mut result = Vec::with_capacity(statuses.len());
+                for (version, name, applied_statements, applied_at) in statuses {
+                    let status = PyDict::new(py);
+                    status.set_item("version", version)?;
+                    status.set_item("name", name)?;
+                    status.set_item("applied", applied_at.is_some())?;
+                    status.set_item("applied_at", applied_at)?;
+                    status.set_item("applied_statements", applied_statements)?;
+                    result.push(status.into());
+                }
+                Ok(result)
+            })?;
+            Ok(statuses)
+        })
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Migrations<path={:?}, table={}>",
+            self.path.display().to_string(),
+            self.table
+        )
+    }
+}
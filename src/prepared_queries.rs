@@ -1,20 +1,582 @@
-use pyo3::pyclass;
-use scylla::prepared_statement::PreparedStatement;
+use std::{
+    collections::HashMap,
+    hash::BuildHasherDefault,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyBytes, PyDict},
+    Py, PyAny, PyResult, Python,
+};
+use rustc_hash::FxHasher;
+use scylla::{
+    execution_profile::ExecutionProfileHandle,
+    frame::value::LegacySerializedValues,
+    prepared_statement::PreparedStatement,
+    routing::Token,
+    statement::{Consistency, SerialConsistency},
+    transport::{
+        errors::{DbError, QueryError},
+        iterator::RowIterator,
+    },
+    QueryResult, Session,
+};
+
+use crate::{
+    batches::named_to_positional,
+    consistencies::{ScyllaPyConsistency, ScyllaPySerialConsistency},
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    execution_profiles::{ScyllaPyExecutionProfile, ScyllaPyTimestampGenerator},
+    utils::{extract_positional_params, parse_python_query_params, serialize_params},
+};
+
+/// Positions of parameters by their names.
+///
+/// The same name can be used for several parameters.
+type ParamPositions = HashMap<String, Vec<usize>, BuildHasherDefault<FxHasher>>;
 
 #[pyclass(name = "PreparedQuery")]
 #[derive(Clone, Debug)]
 pub struct ScyllaPyPreparedQuery {
     pub inner: PreparedStatement,
+    /// Positions of named parameters are resolved once
+    /// and shared by all copies of the statement.
+    positions: Arc<ParamPositions>,
+    pub reprepared: RepreparedStatement,
+    /// Generator of the statement's profile.
+    /// Timestamps are generated on each execution.
+    pub timestamp_generator: ScyllaPyTimestampGenerator,
+    /// Consistency of the statement's profile.
+    /// It's used, if statement doesn't have its own consistency.
+    pub profile_consistency: Option<Consistency>,
+    /// Serial consistency and timeout of the statement's profile.
+    /// They're used, if statement doesn't have its own ones.
+    pub profile_serial_consistency: Option<SerialConsistency>,
+    pub profile_request_timeout: Option<Duration>,
+}
+
+/// Statement prepared again by the server.
+///
+/// It's shared by all copies of the prepared query,
+/// so the statement is prepared again only once.
+#[derive(Clone, Debug, Default)]
+pub struct RepreparedStatement(Arc<RwLock<Option<PreparedStatement>>>);
+
+impl RepreparedStatement {
+    /// Latest statement with settings of the given one.
+    #[must_use]
+    pub fn apply(&self, prepared: PreparedStatement) -> PreparedStatement {
+        let Some(mut reprepared) = self.0.read().ok().and_then(|stored| stored.clone()) else {
+            return prepared;
+        };
+        copy_settings(&prepared, &mut reprepared);
+        reprepared
+    }
+
+    fn store(&self, prepared: &PreparedStatement) {
+        if let Ok(mut stored) = self.0.write() {
+            *stored = Some(prepared.clone());
+        }
+    }
+}
+
+/// Prepared query with bound parameters.
+///
+/// Parameters are serialized only once,
+/// so statement can be executed many times
+/// without parsing them again.
+#[pyclass(name = "BoundStatement")]
+#[derive(Clone, Debug)]
+pub struct ScyllaPyBoundStatement {
+    pub prepared: ScyllaPyPreparedQuery,
+    pub values: LegacySerializedValues,
+}
+
+#[pymethods]
+impl ScyllaPyBoundStatement {
+    #[getter]
+    #[must_use]
+    pub fn query(&self) -> ScyllaPyPreparedQuery {
+        self.prepared.clone()
+    }
+}
+
+/// Prepared query with some of parameters bound.
+///
+/// Remaining parameters are passed on execution,
+/// either as a dict, or as a list in the order
+/// they appear in the query.
+#[pyclass(name = "PartialBoundStatement")]
+#[derive(Clone, Debug)]
+pub struct ScyllaPyPartialBoundStatement {
+    pub prepared: ScyllaPyPreparedQuery,
+    pub params: Py<PyDict>,
+}
+
+impl ScyllaPyPartialBoundStatement {
+    /// Merge bound parameters with the remaining ones.
+    ///
+    /// # Errors
+    ///
+    /// If parameter is bound twice, or if
+    /// the number of remaining parameters is wrong.
+    pub fn merge_params<'a>(
+        &self,
+        py: Python<'a>,
+        params: Option<&'a PyAny>,
+    ) -> ScyllaPyResult<&'a PyDict> {
+        let merged = self.params.as_ref(py).copy()?;
+        let Some(params) = params else {
+            return Ok(merged);
+        };
+        if let Ok(named_params) = params.downcast::<PyDict>() {
+            for (name, value) in named_params {
+                let name = name.extract::<&str>()?.to_lowercase();
+                if merged.contains(&name)? {
+                    return Err(ScyllaPyError::BindingError(format!(
+                        "Parameter `{name}` is already bound"
+                    )));
+                }
+                merged.set_item(name, value)?;
+            }
+            return Ok(merged);
+        }
+        let values = params.extract::<Vec<&PyAny>>()?;
+        let mut remaining = Vec::new();
+        for spec in &self.prepared.inner.get_prepared_metadata().col_specs {
+            let name = spec.name.as_str();
+            if !merged.contains(name)? && !remaining.contains(&name) {
+                remaining.push(name);
+            }
+        }
+        if remaining.len() != values.len() {
+            return Err(ScyllaPyError::BindingError(format!(
+                "Expected {} remaining parameters, got {}",
+                remaining.len(),
+                values.len()
+            )));
+        }
+        for (name, value) in remaining.into_iter().zip(values) {
+            merged.set_item(name, value)?;
+        }
+        Ok(merged)
+    }
+}
+
+#[pymethods]
+impl ScyllaPyPartialBoundStatement {
+    #[getter]
+    #[must_use]
+    pub fn query(&self) -> ScyllaPyPreparedQuery {
+        self.prepared.clone()
+    }
+
+    /// Bind remaining parameters.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed,
+    /// or if some of them are missing.
+    #[pyo3(signature = (params = None))]
+    pub fn bind(
+        &self,
+        py: Python<'_>,
+        params: Option<&PyAny>,
+    ) -> ScyllaPyResult<ScyllaPyBoundStatement> {
+        let merged = self.merge_params(py, params)?;
+        self.prepared.bind(py, Some(merged.as_ref()))
+    }
 }
 
 impl From<PreparedStatement> for ScyllaPyPreparedQuery {
     fn from(value: PreparedStatement) -> Self {
-        Self { inner: value }
+        let mut positions = ParamPositions::default();
+        for (index, spec) in value.get_prepared_metadata().col_specs.iter().enumerate() {
+            positions.entry(spec.name.clone()).or_default().push(index);
+        }
+        Self {
+            inner: value,
+            positions: Arc::new(positions),
+            reprepared: RepreparedStatement::default(),
+            timestamp_generator: ScyllaPyTimestampGenerator::default(),
+            profile_consistency: None,
+            profile_serial_consistency: None,
+            profile_request_timeout: None,
+        }
     }
 }
 
 impl From<ScyllaPyPreparedQuery> for PreparedStatement {
     fn from(value: ScyllaPyPreparedQuery) -> Self {
-        value.inner
+        let mut prepared = value.reprepared.apply(value.inner);
+        if prepared.get_timestamp().is_none() {
+            prepared.set_timestamp(value.timestamp_generator.generate());
+        }
+        if let Some(consistency) = value.profile_consistency {
+            if prepared.get_consistency().is_none() {
+                prepared.set_consistency(consistency);
+            }
+        }
+        if prepared.get_serial_consistency().is_none() {
+            if let Some(serial_consistency) = value.profile_serial_consistency {
+                prepared.set_serial_consistency(Some(serial_consistency));
+            }
+        }
+        if prepared.get_request_timeout().is_none() {
+            prepared.set_request_timeout(value.profile_request_timeout);
+        }
+        prepared
+    }
+}
+
+/// Copy all settings of one statement to another.
+fn copy_settings(from: &PreparedStatement, to: &mut PreparedStatement) {
+    if let Some(consistency) = from.get_consistency() {
+        to.set_consistency(consistency);
+    }
+    match from.get_page_size() {
+        Some(page_size) => to.set_page_size(page_size),
+        None => to.disable_paging(),
+    }
+    to.set_serial_consistency(from.get_serial_consistency());
+    to.set_is_idempotent(from.get_is_idempotent());
+    to.set_tracing(from.get_tracing());
+    to.set_timestamp(from.get_timestamp());
+    to.set_request_timeout(from.get_request_timeout());
+    to.set_retry_policy(from.get_retry_policy().cloned());
+    to.set_execution_profile_handle(from.get_execution_profile_handle().cloned());
+}
+
+/// Prepare statement again on all connections.
+///
+/// All settings of the statement are kept and the new
+/// statement is stored, so later executions use it.
+async fn reprepare(
+    session: &Session,
+    prepared: &PreparedStatement,
+    reprepared: Option<&RepreparedStatement>,
+) -> Result<PreparedStatement, QueryError> {
+    let mut statement = session
+        .prepare(prepared.get_statement().to_string())
+        .await?;
+    if let Some(reprepared) = reprepared {
+        reprepared.store(&statement);
+    }
+    copy_settings(prepared, &mut statement);
+    Ok(statement)
+}
+
+/// Execute prepared statement.
+///
+/// If server doesn't know the statement anymore,
+/// it's prepared again and executed once more.
+///
+/// # Errors
+///
+/// Proxies errors from the session.
+pub async fn execute_prepared(
+    session: &Session,
+    prepared: &PreparedStatement,
+    values: LegacySerializedValues,
+    reprepared: Option<&RepreparedStatement>,
+) -> Result<QueryResult, QueryError> {
+    match session.execute(prepared, &values).await {
+        Err(QueryError::DbError(DbError::Unprepared { .. }, _)) => {
+            let statement = reprepare(session, prepared, reprepared).await?;
+            session.execute(&statement, values).await
+        }
+        res => res,
+    }
+}
+
+/// Execute prepared statement with paging.
+///
+/// Statement is prepared again if needed,
+/// the same way as in `execute_prepared`.
+///
+/// # Errors
+///
+/// Proxies errors from the session.
+pub async fn execute_prepared_iter(
+    session: &Session,
+    prepared: PreparedStatement,
+    values: LegacySerializedValues,
+    reprepared: Option<&RepreparedStatement>,
+) -> Result<RowIterator, QueryError> {
+    match session.execute_iter(prepared.clone(), &values).await {
+        Err(QueryError::DbError(DbError::Unprepared { .. }, _)) => {
+            let statement = reprepare(session, &prepared, reprepared).await?;
+            session.execute_iter(statement, values).await
+        }
+        res => res,
+    }
+}
+
+impl ScyllaPyPreparedQuery {
+    /// Parse parameters as positional values.
+    ///
+    /// Named parameters are ordered according
+    /// to the metadata of the prepared statement.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed.
+    pub fn parse_values(
+        &self,
+        py: Python<'_>,
+        params: Option<&PyAny>,
+    ) -> ScyllaPyResult<LegacySerializedValues> {
+        let col_spec = self.inner.get_prepared_metadata().col_specs.as_slice();
+        let Some(named_params) = params.and_then(|vals| vals.downcast::<PyDict>().ok()) else {
+            return parse_python_query_params(params, false, Some(col_spec));
+        };
+        let mut values = vec![None; col_spec.len()];
+        for (name, value) in named_params {
+            let positions = name.extract::<&str>().ok().and_then(|name| {
+                self.positions
+                    .get(name)
+                    .or_else(|| self.positions.get(&name.to_lowercase()))
+            });
+            let Some(positions) = positions else {
+                return Err(self.names_error(py, named_params));
+            };
+            for position in positions {
+                values[*position] = Some(value);
+            }
+        }
+        let Some(values) = values.into_iter().collect::<Option<Vec<_>>>() else {
+            return Err(self.names_error(py, named_params));
+        };
+        serialize_params(py, &extract_positional_params(&values, Some(col_spec))?)
+    }
+
+    /// Error for named parameters, which don't match the query.
+    fn names_error(&self, py: Python<'_>, named_params: &PyDict) -> ScyllaPyError {
+        let col_spec = self.inner.get_prepared_metadata().col_specs.as_slice();
+        named_to_positional(py, named_params, col_spec)
+            .err()
+            .unwrap_or_else(|| {
+                ScyllaPyError::BindingError("Names of parameters don't match the query".into())
+            })
+    }
+
+    /// Calculate token of the partition key.
+    ///
+    /// Parameters can be passed as a list or as a dict.
+    /// Returns `None` if partition key cannot be found
+    /// in parameters of the query.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed.
+    pub fn token(&self, py: Python<'_>, params: Option<&PyAny>) -> ScyllaPyResult<Option<Token>> {
+        let values = self.parse_values(py, params)?;
+        Ok(self.inner.calculate_token(&values)?)
+    }
+}
+
+#[pymethods]
+impl ScyllaPyPreparedQuery {
+    /// Text of the prepared statement.
+    #[getter]
+    #[must_use]
+    pub fn query(&self) -> &str {
+        self.inner.get_statement()
+    }
+
+    /// Id of the statement assigned by the server.
+    #[getter]
+    #[must_use]
+    pub fn id<'a>(&self, py: Python<'a>) -> &'a PyBytes {
+        PyBytes::new(py, self.inner.get_id())
+    }
+
+    #[must_use]
+    pub fn __str__(&self) -> String {
+        self.inner.get_statement().to_string()
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        let id = self
+            .inner
+            .get_id()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        format!(
+            "PreparedQuery(id={id}, query={:?})",
+            self.inner.get_statement()
+        )
+    }
+
+    /// Calculate token for given parameters.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed.
+    #[pyo3(signature = (params = None))]
+    pub fn compute_token(
+        &self,
+        py: Python<'_>,
+        params: Option<&PyAny>,
+    ) -> ScyllaPyResult<Option<i64>> {
+        Ok(self.token(py, params)?.map(|token| token.value))
+    }
+
+    /// Bind parameters to the query.
+    ///
+    /// Parameters are serialized once and
+    /// can be reused for multiple executions.
+    ///
+    /// # Errors
+    ///
+    /// If parameters cannot be parsed.
+    #[pyo3(signature = (params = None))]
+    pub fn bind(
+        &self,
+        py: Python<'_>,
+        params: Option<&PyAny>,
+    ) -> ScyllaPyResult<ScyllaPyBoundStatement> {
+        Ok(ScyllaPyBoundStatement {
+            prepared: self.clone(),
+            values: self.parse_values(py, params)?,
+        })
+    }
+
+    /// Bind some of parameters to the query.
+    ///
+    /// Remaining parameters should be passed
+    /// on execution or to `bind` method of the result.
+    /// Names are case-insensitive, as in other named parameters.
+    ///
+    /// # Errors
+    ///
+    /// If query doesn't have passed parameters.
+    #[pyo3(signature = (**kwargs))]
+    pub fn bind_partial(
+        &self,
+        py: Python<'_>,
+        kwargs: Option<&PyDict>,
+    ) -> ScyllaPyResult<ScyllaPyPartialBoundStatement> {
+        let params = PyDict::new(py);
+        let col_spec = &self.inner.get_prepared_metadata().col_specs;
+        for (name, value) in kwargs.unwrap_or_else(|| PyDict::new(py)) {
+            let name = name.extract::<&str>()?;
+            let lowercase_name = name.to_lowercase();
+            if !col_spec.iter().any(|spec| spec.name == lowercase_name) {
+                return Err(ScyllaPyError::BindingError(format!(
+                    "Query doesn't have parameter `{name}`"
+                )));
+            }
+            params.set_item(lowercase_name, value)?;
+        }
+        Ok(ScyllaPyPartialBoundStatement {
+            prepared: self.clone(),
+            params: params.into(),
+        })
+    }
+
+    #[must_use]
+    pub fn with_consistency(&self, consistency: ScyllaPyConsistency) -> Self {
+        let mut query = self.clone();
+        query.inner.set_consistency(consistency.into());
+        query
+    }
+
+    #[must_use]
+    pub fn with_serial_consistency(
+        &self,
+        serial_consistency: Option<ScyllaPySerialConsistency>,
+    ) -> Self {
+        let mut query = self.clone();
+        query
+            .inner
+            .set_serial_consistency(serial_consistency.map(Into::into));
+        query
+    }
+
+    #[must_use]
+    pub fn with_request_timeout(&self, request_timeout: Option<u64>) -> Self {
+        let mut query = self.clone();
+        query
+            .inner
+            .set_request_timeout(request_timeout.map(Duration::from_secs));
+        query
+    }
+
+    #[must_use]
+    pub fn with_timestamp(&self, timestamp: Option<i64>) -> Self {
+        let mut query = self.clone();
+        query.inner.set_timestamp(timestamp);
+        query
+    }
+
+    #[must_use]
+    pub fn with_tracing(&self, tracing: bool) -> Self {
+        let mut query = self.clone();
+        query.inner.set_tracing(tracing);
+        query
+    }
+
+    #[must_use]
+    pub fn with_profile(&self, profile: Option<ScyllaPyExecutionProfile>) -> Self {
+        let mut query = self.clone();
+        query.timestamp_generator = profile
+            .as_ref()
+            .map(ScyllaPyExecutionProfile::timestamp_generator)
+            .unwrap_or_default();
+        query.profile_consistency = profile.as_ref().map(ScyllaPyExecutionProfile::consistency);
+        query.profile_serial_consistency = profile
+            .as_ref()
+            .and_then(ScyllaPyExecutionProfile::serial_consistency);
+        query.profile_request_timeout = profile
+            .as_ref()
+            .and_then(ScyllaPyExecutionProfile::request_timeout);
+        query
+            .inner
+            .set_execution_profile_handle(profile.map(ExecutionProfileHandle::from));
+        query
+    }
+
+    /// Mark statement as idempotent.
+    ///
+    /// Only idempotent statements are retried
+    /// and executed speculatively.
+    pub fn set_is_idempotent(&mut self, is_idempotent: bool) {
+        self.inner.set_is_idempotent(is_idempotent);
+    }
+
+    /// Set page size for paged queries.
+    ///
+    /// If `None` is passed, paging is disabled.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if page size is not positive.
+    pub fn set_page_size(&mut self, page_size: Option<i32>) -> PyResult<()> {
+        match page_size {
+            Some(page_size) if page_size <= 0 => {
+                return Err(PyValueError::new_err("Page size should be positive."));
+            }
+            Some(page_size) => self.inner.set_page_size(page_size),
+            None => self.inner.disable_paging(),
+        }
+        Ok(())
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn is_idempotent(&self) -> bool {
+        self.inner.get_is_idempotent()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn page_size(&self) -> Option<i32> {
+        self.inner.get_page_size()
     }
 }
@@ -5,8 +5,14 @@ use crate::{
     exceptions::rust_err::ScyllaPyResult,
     execution_profiles::ScyllaPyExecutionProfile,
 };
-use pyo3::{pyclass, pymethods, types::PyDict, FromPyObject, Python};
-use scylla::{batch::Batch, execution_profile::ExecutionProfileHandle, statement::query::Query};
+use pyo3::{
+    exceptions::PyTypeError, pyclass, pymethods, types::PyDict, FromPyObject, IntoPy, Python,
+};
+use scylla::{
+    batch::Batch,
+    execution_profile::ExecutionProfileHandle,
+    statement::{query::Query, Consistency, SerialConsistency},
+};
 
 #[derive(Clone, Debug, Default, FromPyObject)]
 pub struct ScyllaPyRequestParams {
@@ -17,13 +23,63 @@ pub struct ScyllaPyRequestParams {
     pub is_idempotent: Option<bool>,
     pub tracing: Option<bool>,
     pub profile: Option<ScyllaPyExecutionProfile>,
+    /// Token of the partition, which is used to route the statement.
+    pub routing_token: Option<i64>,
 }
 
 impl ScyllaPyRequestParams {
+    /// Timestamp of the request.
+    ///
+    /// If timestamp isn't set explicitly,
+    /// it's generated by the profile.
+    fn timestamp(&self) -> Option<i64> {
+        self.timestamp.or_else(|| {
+            self.profile
+                .as_ref()
+                .and_then(ScyllaPyExecutionProfile::generate_timestamp)
+        })
+    }
+
+    /// Consistency of the request.
+    ///
+    /// If consistency isn't set explicitly,
+    /// it's taken from the profile.
+    fn consistency(&self) -> Option<Consistency> {
+        self.consistency.map(Into::into).or_else(|| {
+            self.profile
+                .as_ref()
+                .map(ScyllaPyExecutionProfile::consistency)
+        })
+    }
+
+    /// Serial consistency of the request.
+    ///
+    /// If serial consistency isn't set explicitly,
+    /// it's taken from the profile.
+    fn serial_consistency(&self) -> Option<SerialConsistency> {
+        self.serial_consistency.map(Into::into).or_else(|| {
+            self.profile
+                .as_ref()
+                .and_then(ScyllaPyExecutionProfile::serial_consistency)
+        })
+    }
+
+    /// Client timeout of the request.
+    ///
+    /// If timeout isn't set explicitly,
+    /// it's taken from the profile.
+    fn request_timeout(&self) -> Option<Duration> {
+        self.request_timeout.map(Duration::from_secs).or_else(|| {
+            self.profile
+                .as_ref()
+                .and_then(ScyllaPyExecutionProfile::request_timeout)
+        })
+    }
+
     /// Apply parameters to scylla's query.
     pub fn apply_to_query(&self, query: &mut Query) {
-        if let Some(consistency) = self.consistency {
-            query.set_consistency(consistency.into());
+        if let Some(consistency) = self.consistency() {
+            query.set_consistency(consistency);
         }
         if let Some(is_idempotent) = self.is_idempotent {
             query.set_is_idempotent(is_idempotent);
@@ -32,14 +88,14 @@ impl ScyllaPyRequestParams {
             query.set_tracing(tracing);
         }
         query.set_execution_profile_handle(self.profile.as_ref().map(ExecutionProfileHandle::from));
-        query.set_timestamp(self.timestamp);
-        query.set_request_timeout(self.request_timeout.map(Duration::from_secs));
-        query.set_serial_consistency(self.serial_consistency.map(Into::into));
+        query.set_timestamp(self.timestamp());
+        query.set_request_timeout(self.request_timeout());
+        query.set_serial_consistency(self.serial_consistency());
     }
 
     pub fn apply_to_batch(&self, batch: &mut Batch) {
-        if let Some(consistency) = self.consistency {
-            batch.set_consistency(consistency.into());
+        if let Some(consistency) = self.consistency() {
+            batch.set_consistency(consistency);
         }
         if let Some(is_idempotent) = self.is_idempotent {
             batch.set_is_idempotent(is_idempotent);
@@ -47,8 +103,67 @@ impl ScyllaPyRequestParams {
         if let Some(tracing) = self.tracing {
             batch.set_tracing(tracing);
         }
-        batch.set_timestamp(self.timestamp);
-        batch.set_serial_consistency(self.serial_consistency.map(Into::into));
+        batch.set_execution_profile_handle(self.profile.as_ref().map(ExecutionProfileHandle::from));
+        batch.set_timestamp(self.timestamp());
+        batch.set_serial_consistency(self.serial_consistency());
+    }
+
+    /// Combine two sets of parameters.
+    ///
+    /// Parameters that are set in `other`
+    /// take precedence over current ones.
+    #[must_use]
+    pub fn merge(&self, other: Self) -> Self {
+        Self {
+            consistency: other.consistency.or(self.consistency),
+            serial_consistency: other.serial_consistency.or(self.serial_consistency),
+            request_timeout: other.request_timeout.or(self.request_timeout),
+            timestamp: other.timestamp.or(self.timestamp),
+            is_idempotent: other.is_idempotent.or(self.is_idempotent),
+            tracing: other.tracing.or(self.tracing),
+            profile: other.profile.or_else(|| self.profile.clone()),
+            routing_token: other.routing_token.or(self.routing_token),
+        }
+    }
+
+    /// Convert parameters back to dict.
+    ///
+    /// It's used for pickling, so only parameters
+    /// that were set are added to the dict.
+    ///
+    /// # Errors
+    ///
+    /// Execution profiles cannot be pickled,
+    /// so if profile is set, an error is returned.
+    pub fn to_dict<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyDict> {
+        if self.profile.is_some() {
+            return Err(
+                PyTypeError::new_err("Cannot pickle parameters with execution profile.").into(),
+            );
+        }
+        let params = PyDict::new(py);
+        if let Some(consistency) = self.consistency {
+            params.set_item("consistency", consistency.into_py(py))?;
+        }
+        if let Some(serial_consistency) = self.serial_consistency {
+            params.set_item("serial_consistency", serial_consistency.into_py(py))?;
+        }
+        if let Some(request_timeout) = self.request_timeout {
+            params.set_item("request_timeout", request_timeout)?;
+        }
+        if let Some(timestamp) = self.timestamp {
+            params.set_item("timestamp", timestamp)?;
+        }
+        if let Some(is_idempotent) = self.is_idempotent {
+            params.set_item("is_idempotent", is_idempotent)?;
+        }
+        if let Some(tracing) = self.tracing {
+            params.set_item("tracing", tracing)?;
+        }
+        if let Some(routing_token) = self.routing_token {
+            params.set_item("routing_token", routing_token)?;
+        }
+        Ok(params)
     }
 
     /// Parse dict to query parameters.
@@ -93,6 +208,10 @@ impl ScyllaPyRequestParams {
                 .get_item("profile")?
                 .map(pyo3::FromPyObject::extract)
                 .transpose()?,
+            routing_token: params
+                .get_item("routing_token")?
+                .map(pyo3::FromPyObject::extract)
+                .transpose()?,
         })
     }
 }
@@ -186,6 +305,18 @@ impl ScyllaPyQuery {
         query.params.profile = profile;
         query
     }
+
+    /// Route the query to replicas of the token.
+    ///
+    /// Tokens are computed by the driver only for
+    /// prepared queries, so it's useful for unprepared
+    /// queries when the token of the partition is known.
+    #[must_use]
+    pub fn with_routing_token(&self, routing_token: Option<i64>) -> Self {
+        let mut query = Self::from(self);
+        query.params.routing_token = routing_token;
+        query
+    }
 }
 
 impl From<ScyllaPyQuery> for Query {
@@ -0,0 +1,403 @@
+use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
+use scylla::query::Query;
+
+use super::utils::pretty_build;
+use crate::{
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    queries::ScyllaPyRequestParams,
+    scylla_cls::Scylla,
+    utils::ScyllaPyCQLDTO,
+};
+
+/// Prefix of create statements.
+fn create_prefix(or_replace: bool, entity: &str) -> String {
+    if or_replace {
+        format!("CREATE OR REPLACE {entity}")
+    } else {
+        format!("CREATE {entity}")
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct CreateFunction {
+    name_: String,
+    or_replace_: bool,
+    if_not_exists_: bool,
+    arguments_: Vec<(String, String)>,
+    called_on_null_input_: bool,
+    returns_: Option<String>,
+    language_: String,
+    body_: Option<String>,
+    request_params_: ScyllaPyRequestParams,
+}
+
+impl CreateFunction {
+    fn build_query(&self) -> ScyllaPyResult<String> {
+        let returns = self
+            .returns_
+            .as_ref()
+            .ok_or(ScyllaPyError::QueryBuilderError(
+                "Return type should be specified.",
+            ))?;
+        let body = self.body_.as_ref().ok_or(ScyllaPyError::QueryBuilderError(
+            "Body of the function should be specified.",
+        ))?;
+        let ifnexist = if self.if_not_exists_ {
+            "IF NOT EXISTS"
+        } else {
+            ""
+        };
+        let on_null = if self.called_on_null_input_ {
+            "CALLED ON NULL INPUT"
+        } else {
+            "RETURNS NULL ON NULL INPUT"
+        };
+        let arguments = self
+            .arguments_
+            .iter()
+            .map(|(name, cql_type)| format!("{name} {cql_type}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(pretty_build([
+            create_prefix(self.or_replace_, "FUNCTION").as_str(),
+            ifnexist,
+            format!("{}({arguments})", self.name_).as_str(),
+            on_null,
+            "RETURNS",
+            returns.as_str(),
+            "LANGUAGE",
+            self.language_.as_str(),
+            "AS",
+            format!("'{}'", body.replace('\'', "''")).as_str(),
+        ]))
+    }
+}
+
+#[pymethods]
+impl CreateFunction {
+    #[new]
+    #[must_use]
+    pub fn py_new(name: String) -> Self {
+        Self {
+            name_: name,
+            or_replace_: false,
+            if_not_exists_: false,
+            arguments_: Vec::new(),
+            called_on_null_input_: false,
+            returns_: None,
+            language_: String::from("lua"),
+            body_: None,
+            request_params_: ScyllaPyRequestParams::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn or_replace(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.or_replace_ = true;
+        slf
+    }
+
+    #[must_use]
+    pub fn if_not_exists(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.if_not_exists_ = true;
+        slf
+    }
+
+    /// Add argument to the function.
+    #[must_use]
+    pub fn argument(
+        mut slf: PyRefMut<'_, Self>,
+        name: String,
+        cql_type: String,
+    ) -> PyRefMut<'_, Self> {
+        slf.arguments_.push((name, cql_type));
+        slf
+    }
+
+    /// Call the function, even if some arguments are null.
+    ///
+    /// By default null is returned without calling the function.
+    #[must_use]
+    #[pyo3(signature = (called = true))]
+    pub fn called_on_null_input(mut slf: PyRefMut<'_, Self>, called: bool) -> PyRefMut<'_, Self> {
+        slf.called_on_null_input_ = called;
+        slf
+    }
+
+    #[must_use]
+    pub fn returns(mut slf: PyRefMut<'_, Self>, cql_type: String) -> PyRefMut<'_, Self> {
+        slf.returns_ = Some(cql_type);
+        slf
+    }
+
+    /// Set language of the function.
+    ///
+    /// Scylla supports `lua` and `wasm`, `lua` is used by default.
+    #[must_use]
+    pub fn language(mut slf: PyRefMut<'_, Self>, language: String) -> PyRefMut<'_, Self> {
+        slf.language_ = language;
+        slf
+    }
+
+    /// Set source code of the function.
+    #[must_use]
+    pub fn body(mut slf: PyRefMut<'_, Self>, body: String) -> PyRefMut<'_, Self> {
+        slf.body_ = Some(body);
+        slf
+    }
+
+    /// Add parameters to the request.
+    ///
+    /// These parameters are used by scylla.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if request parameters
+    /// cannot be built.
+    #[pyo3(signature = (**params))]
+    pub fn request_params<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        slf.request_params_ = ScyllaPyRequestParams::from_dict(params)?;
+        Ok(slf)
+    }
+
+    /// Execute a query.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if something goes wrong
+    /// during query building
+    /// or during query execution.
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(py, Some(query), None, Vec::<ScyllaPyCQLDTO>::new(), false)
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Convert query to string.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if something
+    /// goes wrong during query building.
+    pub fn __str__(&self) -> ScyllaPyResult<String> {
+        self.build_query()
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct CreateAggregate {
+    name_: String,
+    or_replace_: bool,
+    if_not_exists_: bool,
+    arguments_: Vec<String>,
+    state_function_: Option<String>,
+    state_type_: Option<String>,
+    final_function_: Option<String>,
+    reduce_function_: Option<String>,
+    initial_condition_: Option<String>,
+    request_params_: ScyllaPyRequestParams,
+}
+
+impl CreateAggregate {
+    fn build_query(&self) -> ScyllaPyResult<String> {
+        let state_function =
+            self.state_function_
+                .as_ref()
+                .ok_or(ScyllaPyError::QueryBuilderError(
+                    "State function should be specified.",
+                ))?;
+        let state_type = self
+            .state_type_
+            .as_ref()
+            .ok_or(ScyllaPyError::QueryBuilderError(
+                "State type should be specified.",
+            ))?;
+        let ifnexist = if self.if_not_exists_ {
+            "IF NOT EXISTS"
+        } else {
+            ""
+        };
+        let final_function = self
+            .final_function_
+            .as_ref()
+            .map_or(String::new(), |func| format!("FINALFUNC {func}"));
+        let reduce_function = self
+            .reduce_function_
+            .as_ref()
+            .map_or(String::new(), |func| format!("REDUCEFUNC {func}"));
+        let initial_condition = self
+            .initial_condition_
+            .as_ref()
+            .map_or(String::new(), |cond| format!("INITCOND {cond}"));
+        Ok(pretty_build([
+            create_prefix(self.or_replace_, "AGGREGATE").as_str(),
+            ifnexist,
+            format!("{}({})", self.name_, self.arguments_.join(", ")).as_str(),
+            "SFUNC",
+            state_function.as_str(),
+            "STYPE",
+            state_type.as_str(),
+            reduce_function.as_str(),
+            final_function.as_str(),
+            initial_condition.as_str(),
+        ]))
+    }
+}
+
+#[pymethods]
+impl CreateAggregate {
+    #[new]
+    #[must_use]
+    pub fn py_new(name: String) -> Self {
+        Self {
+            name_: name,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn or_replace(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.or_replace_ = true;
+        slf
+    }
+
+    #[must_use]
+    pub fn if_not_exists(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.if_not_exists_ = true;
+        slf
+    }
+
+    /// Add type of an argument.
+    #[must_use]
+    pub fn argument(mut slf: PyRefMut<'_, Self>, cql_type: String) -> PyRefMut<'_, Self> {
+        slf.arguments_.push(cql_type);
+        slf
+    }
+
+    /// Set function, which is called for each row with the current state.
+    #[must_use]
+    pub fn state_function(mut slf: PyRefMut<'_, Self>, name: String) -> PyRefMut<'_, Self> {
+        slf.state_function_ = Some(name);
+        slf
+    }
+
+    #[must_use]
+    pub fn state_type(mut slf: PyRefMut<'_, Self>, cql_type: String) -> PyRefMut<'_, Self> {
+        slf.state_type_ = Some(cql_type);
+        slf
+    }
+
+    /// Set function, which converts the final state to the result.
+    #[must_use]
+    pub fn final_function(mut slf: PyRefMut<'_, Self>, name: String) -> PyRefMut<'_, Self> {
+        slf.final_function_ = Some(name);
+        slf
+    }
+
+    /// Set function, which merges partial states.
+    ///
+    /// It allows Scylla to run the aggregate in parallel.
+    #[must_use]
+    pub fn reduce_function(mut slf: PyRefMut<'_, Self>, name: String) -> PyRefMut<'_, Self> {
+        slf.reduce_function_ = Some(name);
+        slf
+    }
+
+    /// Set initial state. It's a CQL literal, like `0` or `(0, 0)`.
+    #[must_use]
+    pub fn initial_condition(mut slf: PyRefMut<'_, Self>, literal: String) -> PyRefMut<'_, Self> {
+        slf.initial_condition_ = Some(literal);
+        slf
+    }
+
+    /// Add parameters to the request.
+    ///
+    /// These parameters are used by scylla.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if request parameters
+    /// cannot be built.
+    #[pyo3(signature = (**params))]
+    pub fn request_params<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        slf.request_params_ = ScyllaPyRequestParams::from_dict(params)?;
+        Ok(slf)
+    }
+
+    /// Execute a query.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if something goes wrong
+    /// during query building
+    /// or during query execution.
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(py, Some(query), None, Vec::<ScyllaPyCQLDTO>::new(), false)
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Convert query to string.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if something
+    /// goes wrong during query building.
+    pub fn __str__(&self) -> ScyllaPyResult<String> {
+        self.build_query()
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
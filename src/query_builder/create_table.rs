@@ -0,0 +1,192 @@
+use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
+use scylla::query::Query;
+
+use super::utils::pretty_build;
+use crate::{
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    queries::ScyllaPyRequestParams,
+    scylla_cls::Scylla,
+    utils::ScyllaPyCQLDTO,
+};
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct CreateTable {
+    name_: String,
+    if_not_exists_: bool,
+    columns_: Vec<(String, String)>,
+    partition_key_: Vec<String>,
+    clustering_key_: Vec<(String, bool)>,
+    request_params_: ScyllaPyRequestParams,
+}
+
+impl CreateTable {
+    fn build_query(&self) -> ScyllaPyResult<String> {
+        if self.columns_.is_empty() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "At least one column should be specified.",
+            ));
+        }
+        if self.partition_key_.is_empty() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Partition key should be specified.",
+            ));
+        }
+        let ifnexist = if self.if_not_exists_ {
+            "IF NOT EXISTS"
+        } else {
+            ""
+        };
+        let mut primary_key = vec![format!("({})", self.partition_key_.join(", "))];
+        primary_key.extend(self.clustering_key_.iter().map(|(name, _)| name.clone()));
+        let columns = self
+            .columns_
+            .iter()
+            .map(|(name, cql_type)| format!("{name} {cql_type}"))
+            .chain([format!("PRIMARY KEY ({})", primary_key.join(", "))])
+            .collect::<Vec<_>>()
+            .join(", ");
+        let order = if self.clustering_key_.iter().any(|(_, desc)| *desc) {
+            let ordered_cols = self
+                .clustering_key_
+                .iter()
+                .map(|(name, desc)| {
+                    if *desc {
+                        format!("{name} DESC")
+                    } else {
+                        format!("{name} ASC")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("WITH CLUSTERING ORDER BY ({ordered_cols})")
+        } else {
+            String::new()
+        };
+        Ok(pretty_build([
+            "CREATE TABLE",
+            ifnexist,
+            self.name_.as_str(),
+            format!("({columns})").as_str(),
+            order.as_str(),
+        ]))
+    }
+}
+
+#[pymethods]
+impl CreateTable {
+    #[new]
+    #[must_use]
+    pub fn py_new(name: String) -> Self {
+        Self {
+            name_: name,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn if_not_exists(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.if_not_exists_ = true;
+        slf
+    }
+
+    /// Add column to the table.
+    ///
+    /// Type of the column is a CQL type,
+    /// like `int`, `list<text>` or `frozen<some_type>`.
+    #[must_use]
+    pub fn column(
+        mut slf: PyRefMut<'_, Self>,
+        name: String,
+        cql_type: String,
+    ) -> PyRefMut<'_, Self> {
+        slf.columns_.push((name, cql_type));
+        slf
+    }
+
+    /// Set columns of the partition key.
+    #[must_use]
+    #[pyo3(signature = (*columns))]
+    pub fn partition_key(mut slf: PyRefMut<'_, Self>, columns: Vec<String>) -> PyRefMut<'_, Self> {
+        slf.partition_key_ = columns;
+        slf
+    }
+
+    /// Add a clustering column.
+    ///
+    /// Columns are added to the primary key
+    /// in the same order as this method is called.
+    #[must_use]
+    #[pyo3(signature = (column, desc = false))]
+    pub fn clustering_key(
+        mut slf: PyRefMut<'_, Self>,
+        column: String,
+        desc: bool,
+    ) -> PyRefMut<'_, Self> {
+        slf.clustering_key_.push((column, desc));
+        slf
+    }
+
+    /// Add parameters to the request.
+    ///
+    /// These parameters are used by scylla.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if request parameters
+    /// cannot be built.
+    #[pyo3(signature = (**params))]
+    pub fn request_params<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        slf.request_params_ = ScyllaPyRequestParams::from_dict(params)?;
+        Ok(slf)
+    }
+
+    /// Execute a query.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if something goes wrong
+    /// during query building
+    /// or during query execution.
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(py, Some(query), None, Vec::<ScyllaPyCQLDTO>::new(), false)
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Convert query to string.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if something
+    /// goes wrong during query building.
+    pub fn __str__(&self) -> ScyllaPyResult<String> {
+        self.build_query()
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
@@ -0,0 +1,148 @@
+use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
+use scylla::query::Query;
+
+use super::utils::pretty_build;
+use crate::{
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    queries::ScyllaPyRequestParams,
+    scylla_cls::Scylla,
+    utils::ScyllaPyCQLDTO,
+};
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct CreateType {
+    name_: String,
+    if_not_exists_: bool,
+    fields_: Vec<(String, String)>,
+    request_params_: ScyllaPyRequestParams,
+}
+
+impl CreateType {
+    fn build_query(&self) -> ScyllaPyResult<String> {
+        if self.fields_.is_empty() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "At least one field should be specified.",
+            ));
+        }
+        let ifnexist = if self.if_not_exists_ {
+            "IF NOT EXISTS"
+        } else {
+            ""
+        };
+        let fields = self
+            .fields_
+            .iter()
+            .map(|(name, cql_type)| format!("{name} {cql_type}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(pretty_build([
+            "CREATE TYPE",
+            ifnexist,
+            self.name_.as_str(),
+            format!("({fields})").as_str(),
+        ]))
+    }
+}
+
+#[pymethods]
+impl CreateType {
+    #[new]
+    #[must_use]
+    pub fn py_new(name: String) -> Self {
+        Self {
+            name_: name,
+            ..Default::default()
+        }
+    }
+
+    #[must_use]
+    pub fn if_not_exists(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.if_not_exists_ = true;
+        slf
+    }
+
+    /// Add field to the type.
+    ///
+    /// Type of the field is a CQL type,
+    /// like `int`, `text` or `frozen<other_type>`.
+    #[must_use]
+    pub fn field(
+        mut slf: PyRefMut<'_, Self>,
+        name: String,
+        cql_type: String,
+    ) -> PyRefMut<'_, Self> {
+        slf.fields_.push((name, cql_type));
+        slf
+    }
+
+    /// Add parameters to the request.
+    ///
+    /// These parameters are used by scylla.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if request parameters
+    /// cannot be built.
+    #[pyo3(signature = (**params))]
+    pub fn request_params<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        slf.request_params_ = ScyllaPyRequestParams::from_dict(params)?;
+        Ok(slf)
+    }
+
+    /// Execute a query.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if something goes wrong
+    /// during query building
+    /// or during query execution.
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(
+            py,
+            Some(query),
+            None,
+            Vec::<ScyllaPyCQLDTO>::new(),
+            false,
+            None,
+        )
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+
+    /// Convert query to string.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if something
+    /// goes wrong during query building.
+    pub fn __str__(&self) -> ScyllaPyResult<String> {
+        self.build_query()
+    }
+
+    #[must_use]
+    pub fn __copy__(&self) -> Self {
+        self.clone()
+    }
+
+    #[must_use]
+    pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+        self.clone()
+    }
+}
@@ -1,7 +1,7 @@
 use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
 use scylla::{frame::value::LegacySerializedValues, query::Query};
 
-use super::utils::{pretty_build, IfCluase, Timeout};
+use super::utils::{pretty_build, qualified_table, IfCluase, Timeout};
 use crate::{
     batches::ScyllaPyInlineBatch,
     exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
@@ -14,6 +14,7 @@ use crate::{
 #[derive(Clone, Debug, Default)]
 pub struct Delete {
     table_: String,
+    keyspace_: Option<String>,
     columns: Option<Vec<String>>,
     timeout_: Option<Timeout>,
     timestamp_: Option<u64>,
@@ -66,21 +67,49 @@ impl Delete {
             "DELETE",
             columns.as_str(),
             "FROM",
-            self.table_.as_str(),
+            qualified_table(self.keyspace_.as_deref(), &self.table_).as_str(),
             usings.as_str(),
             where_clause.as_str(),
             if_conditions.as_str(),
         ]))
     }
+
+    /// Get all values that should be bound to the statement.
+    fn bound_values(&self) -> Vec<ScyllaPyCQLDTO> {
+        if let Some(if_clause) = &self.if_clause_ {
+            if_clause.extend_values(self.values_.clone())
+        } else {
+            self.values_.clone()
+        }
+    }
+
+    /// Statements with their values for batches.
+    ///
+    /// # Errors
+    ///
+    /// May result into error if query cannot be build.
+    /// Or values cannot be serialized.
+    pub fn batch_statements(&self) -> ScyllaPyResult<Vec<(Query, LegacySerializedValues)>> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_.apply_to_query(&mut query);
+
+        let mut serialized = LegacySerializedValues::new();
+        for val in self.bound_values() {
+            serialized.add_value(&val)?;
+        }
+        Ok(vec![(query, serialized)])
+    }
 }
 
 #[pymethods]
 impl Delete {
     #[new]
     #[must_use]
-    pub fn py_new(table: String) -> Self {
+    #[pyo3(signature = (table, keyspace = None))]
+    pub fn py_new(table: String, keyspace: Option<String>) -> Self {
         Self {
             table_: table,
+            keyspace_: keyspace,
             ..Default::default()
         }
     }
@@ -191,16 +220,19 @@ impl Delete {
     /// May return an error, if something goes wrong
     /// during query building
     /// or during query execution.
-    pub fn execute<'a>(&'a self, py: Python<'a>, scylla: &'a Scylla) -> ScyllaPyResult<&'a PyAny> {
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
         let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
 
-        let values = if let Some(if_clause) = &self.if_clause_ {
-            if_clause.extend_values(self.values_.clone())
-        } else {
-            self.values_.clone()
-        };
-        scylla.native_execute(py, Some(query), None, values, false)
+        scylla.native_execute(py, Some(query), None, self.bound_values(), false, None)
     }
 
     /// Add to batch
@@ -212,22 +244,42 @@ impl Delete {
     /// May result into error if query cannot be build.
     /// Or values cannot be passed to batch.
     pub fn add_to_batch(&self, batch: &mut ScyllaPyInlineBatch) -> ScyllaPyResult<()> {
-        let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
-
-        let values = if let Some(if_clause) = &self.if_clause_ {
-            if_clause.extend_values(self.values_.clone())
-        } else {
-            self.values_.clone()
-        };
-        let mut serialized = LegacySerializedValues::new();
-        for val in values {
-            serialized.add_value(&val)?;
+        for (query, values) in self.batch_statements()? {
+            batch.add_query_inner(query, values)?;
         }
-        batch.add_query_inner(query, serialized);
         Ok(())
     }
 
+    #[getter]
+    #[must_use]
+    pub fn table(&self) -> String {
+        self.table_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn keyspace(&self) -> Option<String> {
+        self.keyspace_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn selected_columns(&self) -> Option<Vec<String>> {
+        self.columns.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn where_clauses(&self) -> Vec<String> {
+        self.where_clauses_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn bound_values_count(&self) -> usize {
+        self.bound_values().len()
+    }
+
     #[must_use]
     pub fn __repr__(&self) -> String {
         format!("{self:?}")
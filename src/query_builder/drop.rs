@@ -0,0 +1,116 @@
+use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
+use scylla::query::Query;
+
+use super::utils::pretty_build;
+use crate::{
+    exceptions::rust_err::ScyllaPyResult, queries::ScyllaPyRequestParams, scylla_cls::Scylla,
+    utils::ScyllaPyCQLDTO,
+};
+
+macro_rules! drop_builder {
+    ($name:ident, $entity:literal) => {
+        #[pyclass]
+        #[derive(Clone, Debug, Default)]
+        pub struct $name {
+            name_: String,
+            if_exists_: bool,
+            request_params_: ScyllaPyRequestParams,
+        }
+
+        impl $name {
+            #[must_use]
+            pub fn build_query(&self) -> String {
+                let ifexists = if self.if_exists_ { "IF EXISTS" } else { "" };
+                pretty_build([concat!("DROP ", $entity), ifexists, self.name_.as_str()])
+            }
+        }
+
+        #[pymethods]
+        impl $name {
+            #[new]
+            #[must_use]
+            pub fn py_new(name: String) -> Self {
+                Self {
+                    name_: name,
+                    ..Default::default()
+                }
+            }
+
+            #[must_use]
+            pub fn if_exists(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+                slf.if_exists_ = true;
+                slf
+            }
+
+            /// Add parameters to the request.
+            ///
+            /// These parameters are used by scylla.
+            ///
+            /// # Errors
+            ///
+            /// May return an error, if request parameters
+            /// cannot be built.
+            #[pyo3(signature = (**params))]
+            pub fn request_params<'a>(
+                mut slf: PyRefMut<'a, Self>,
+                params: Option<&'a PyDict>,
+            ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+                slf.request_params_ = ScyllaPyRequestParams::from_dict(params)?;
+                Ok(slf)
+            }
+
+            /// Execute a query.
+            ///
+            /// # Errors
+            ///
+            /// Proxies errors from `native_execute`.
+            #[pyo3(signature = (scylla, **params))]
+            pub fn execute<'a>(
+                &'a self,
+                py: Python<'a>,
+                scylla: &'a Scylla,
+                params: Option<&'a PyDict>,
+            ) -> ScyllaPyResult<&'a PyAny> {
+                let mut query = Query::new(self.build_query());
+                self.request_params_
+                    .merge(ScyllaPyRequestParams::from_dict(params)?)
+                    .apply_to_query(&mut query);
+                scylla.native_execute(
+                    py,
+                    Some(query),
+                    None,
+                    Vec::<ScyllaPyCQLDTO>::new(),
+                    false,
+                    None,
+                )
+            }
+
+            #[must_use]
+            pub fn __str__(&self) -> String {
+                self.build_query()
+            }
+
+            #[must_use]
+            pub fn __repr__(&self) -> String {
+                format!("{self:?}")
+            }
+
+            #[must_use]
+            pub fn __copy__(&self) -> Self {
+                self.clone()
+            }
+
+            #[must_use]
+            pub fn __deepcopy__(&self, _memo: &PyDict) -> Self {
+                self.clone()
+            }
+        }
+    };
+}
+
+drop_builder!(DropTable, "TABLE");
+drop_builder!(DropKeyspace, "KEYSPACE");
+drop_builder!(DropIndex, "INDEX");
+drop_builder!(DropType, "TYPE");
+drop_builder!(DropFunction, "FUNCTION");
+drop_builder!(DropAggregate, "AGGREGATE");
@@ -2,26 +2,27 @@ use pyo3::{pyclass, pymethods, types::PyDict, PyAny, PyRefMut, Python};
 use scylla::{frame::value::LegacySerializedValues, query::Query};
 
 use crate::{
-    batches::ScyllaPyInlineBatch,
+    batches::{ScyllaPyBatchType, ScyllaPyInlineBatch},
     exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
     queries::ScyllaPyRequestParams,
     scylla_cls::Scylla,
     utils::{py_to_value, ScyllaPyCQLDTO},
 };
 
-use super::utils::{pretty_build, Timeout};
+use super::utils::{pretty_build, qualified_table, Timeout, UsingParam};
 
 #[pyclass]
 #[derive(Clone, Debug, Default)]
 pub struct Insert {
     table_: String,
+    keyspace_: Option<String>,
     if_not_exists_: bool,
     names_: Vec<String>,
-    values_: Vec<ScyllaPyCQLDTO>,
+    rows_: Vec<Vec<ScyllaPyCQLDTO>>,
 
     timeout_: Option<Timeout>,
-    ttl_: Option<i32>,
-    timestamp_: Option<u64>,
+    ttl_: Option<UsingParam<i32>>,
+    timestamp_: Option<UsingParam<u64>>,
 
     request_params_: ScyllaPyRequestParams,
 }
@@ -52,8 +53,9 @@ impl Insert {
         };
         let params = [
             self.timestamp_
-                .map(|timestamp| format!("TIMESTAMP {timestamp}")),
-            self.ttl_.map(|ttl| format!("TTL {ttl}")),
+                .as_ref()
+                .map(|timestamp| timestamp.format("TIMESTAMP")),
+            self.ttl_.as_ref().map(|ttl| ttl.format("TTL")),
             self.timeout_.as_ref().map(|timeout| match timeout {
                 Timeout::Int(int) => format!("TIMEOUT {int}"),
                 Timeout::Str(string) => format!("TIMEOUT {string}"),
@@ -72,21 +74,83 @@ impl Insert {
 
         Ok(pretty_build([
             "INSERT INTO",
-            self.table_.as_str(),
+            qualified_table(self.keyspace_.as_deref(), &self.table_).as_str(),
             names_values.as_str(),
             ifnexist,
             usings.as_str(),
         ]))
     }
+
+    /// Get all values that should be bound to the statement.
+    ///
+    /// Values for `USING` markers go after column values,
+    /// because `USING` clause is placed after `VALUES`.
+    fn bound_values(&self, row: &[ScyllaPyCQLDTO]) -> Vec<ScyllaPyCQLDTO> {
+        let mut values = row.to_vec();
+        values.extend(self.timestamp_.as_ref().and_then(UsingParam::marker_value));
+        values.extend(self.ttl_.as_ref().and_then(UsingParam::marker_value));
+        values
+    }
+
+    /// Add value for a column to the single row of insert.
+    fn set_value(&mut self, name: String, value: ScyllaPyCQLDTO) -> ScyllaPyResult<()> {
+        if self.rows_.len() > 1 {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Values cannot be set for inserts with multiple rows",
+            ));
+        }
+        self.names_.push(name);
+        if let Some(row) = self.rows_.first_mut() {
+            row.push(value);
+        } else {
+            self.rows_.push(vec![value]);
+        }
+        Ok(())
+    }
+
+    /// Statements with their values for batches.
+    ///
+    /// # Errors
+    ///
+    /// May result into error if query cannot be build.
+    /// Or values cannot be serialized.
+    pub fn batch_statements(&self) -> ScyllaPyResult<Vec<(Query, LegacySerializedValues)>> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_.apply_to_query(&mut query);
+
+        let mut statements = Vec::with_capacity(self.rows_.len());
+        for row in &self.rows_ {
+            let mut serialized = LegacySerializedValues::new();
+            for val in self.bound_values(row) {
+                serialized.add_value(&val)?;
+            }
+            statements.push((query.clone(), serialized));
+        }
+        Ok(statements)
+    }
+}
+
+/// Convert python value to insert parameter.
+///
+/// Nones are converted to unset values,
+/// which is a small optimization to speedup inserts.
+fn insert_value(value: &PyAny) -> ScyllaPyResult<ScyllaPyCQLDTO> {
+    if value.is_none() {
+        Ok(ScyllaPyCQLDTO::Unset)
+    } else {
+        py_to_value(value, None)
+    }
 }
 
 #[pymethods]
 impl Insert {
     #[new]
     #[must_use]
-    pub fn py_new(table: String) -> Self {
+    #[pyo3(signature = (table, keyspace = None))]
+    pub fn py_new(table: String, keyspace: Option<String>) -> Self {
         Self {
             table_: table,
+            keyspace_: keyspace,
             ..Default::default()
         }
     }
@@ -102,19 +166,111 @@ impl Insert {
     /// # Errors
     ///
     /// If value cannot be translated
-    /// into `Rust` type.
+    /// into `Rust` type or if insert
+    /// already has multiple rows.
     pub fn set<'a>(
         mut slf: PyRefMut<'a, Self>,
         name: String,
         value: &'a PyAny,
     ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
-        slf.names_.push(name);
-        // Small optimization to speedup inserts.
-        if value.is_none() {
-            slf.values_.push(ScyllaPyCQLDTO::Unset);
-        } else {
-            slf.values_.push(py_to_value(value, None)?);
+        slf.set_value(name, insert_value(value)?)?;
+        Ok(slf)
+    }
+
+    /// Set values from model.
+    ///
+    /// Column values are taken from `__scylla_dump__` method,
+    /// which should return a dict. If model doesn't have such method,
+    /// but it's a dataclass, then dataclass fields are used.
+    ///
+    /// # Errors
+    ///
+    /// If values cannot be taken from model,
+    /// or cannot be translated into `Rust` types.
+    pub fn from_model<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        py: Python<'a>,
+        model: &'a PyAny,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        if model.hasattr("__scylla_dump__")? {
+            let dumped = model.call_method0("__scylla_dump__")?;
+            let dumped_dict = dumped.downcast::<PyDict>().map_err(|err| {
+                ScyllaPyError::BindingError(format!(
+                    "Cannot get model values. __scylla_dump__ has returned not a dict value. {err}"
+                ))
+            })?;
+            for (name, value) in dumped_dict {
+                slf.set_value(name.extract()?, insert_value(value)?)?;
+            }
+            return Ok(slf);
+        }
+        let dataclasses = py.import("dataclasses")?;
+        if dataclasses
+            .call_method1("is_dataclass", (model,))?
+            .is_true()?
+        {
+            for field in dataclasses.call_method1("fields", (model,))?.iter()? {
+                let name = field?.getattr("name")?.extract::<String>()?;
+                let value = insert_value(model.getattr(name.as_str())?)?;
+                slf.set_value(name, value)?;
+            }
+            return Ok(slf);
+        }
+        let type_name = model.get_type().name()?;
+        Err(ScyllaPyError::BindingError(format!(
+            "Cannot get values from model of type {type_name:?}. \
+             It should be a dataclass or define __scylla_dump__ method"
+        )))
+    }
+
+    /// Specify columns to insert.
+    ///
+    /// Values for these columns
+    /// should be passed using `values` method.
+    ///
+    /// # Errors
+    ///
+    /// If values were already added.
+    #[pyo3(signature = (*names))]
+    pub fn columns(
+        mut slf: PyRefMut<'_, Self>,
+        names: Vec<String>,
+    ) -> ScyllaPyResult<PyRefMut<'_, Self>> {
+        if !slf.rows_.is_empty() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Columns should be specified before values",
+            ));
         }
+        slf.names_ = names;
+        Ok(slf)
+    }
+
+    /// Add row of values.
+    ///
+    /// Values are matched with columns by position.
+    /// This method can be called multiple times,
+    /// but inserts with multiple rows
+    /// can only be added to batches.
+    ///
+    /// # Errors
+    ///
+    /// If number of values doesn't match number of columns,
+    /// or values cannot be translated into `Rust` types.
+    #[pyo3(signature = (*values))]
+    pub fn values<'a>(
+        mut slf: PyRefMut<'a, Self>,
+        values: Vec<&'a PyAny>,
+    ) -> ScyllaPyResult<PyRefMut<'a, Self>> {
+        if values.len() != slf.names_.len() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Number of values should match number of columns",
+            ));
+        }
+        let row = values
+            .into_iter()
+            .map(insert_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        slf.rows_.push(row);
         Ok(slf)
     }
 
@@ -126,13 +282,44 @@ impl Insert {
 
     #[must_use]
     pub fn timestamp(mut slf: PyRefMut<'_, Self>, timestamp: u64) -> PyRefMut<'_, Self> {
-        slf.timestamp_ = Some(timestamp);
+        slf.timestamp_ = Some(UsingParam::Inline(timestamp));
         slf
     }
 
     #[must_use]
     pub fn ttl(mut slf: PyRefMut<'_, Self>, ttl: i32) -> PyRefMut<'_, Self> {
-        slf.ttl_ = Some(ttl);
+        slf.ttl_ = Some(UsingParam::Inline(ttl));
+        slf
+    }
+
+    /// Use bind marker for TTL.
+    ///
+    /// Statement is built with `TTL ?`, so
+    /// the same statement text can be used
+    /// with different TTLs. If no value passed,
+    /// TTL is bound as unset.
+    #[must_use]
+    #[pyo3(signature = (ttl = None))]
+    pub fn using_ttl_marker(mut slf: PyRefMut<'_, Self>, ttl: Option<i32>) -> PyRefMut<'_, Self> {
+        slf.ttl_ = Some(UsingParam::Marker(
+            ttl.map_or(ScyllaPyCQLDTO::Unset, ScyllaPyCQLDTO::Int),
+        ));
+        slf
+    }
+
+    /// Use bind marker for timestamp.
+    ///
+    /// Statement is built with `TIMESTAMP ?`.
+    /// If no value passed, timestamp is bound as unset.
+    #[must_use]
+    #[pyo3(signature = (timestamp = None))]
+    pub fn using_timestamp_marker(
+        mut slf: PyRefMut<'_, Self>,
+        timestamp: Option<i64>,
+    ) -> PyRefMut<'_, Self> {
+        slf.timestamp_ = Some(UsingParam::Marker(
+            timestamp.map_or(ScyllaPyCQLDTO::Unset, ScyllaPyCQLDTO::BigInt),
+        ));
         slf
     }
 
@@ -159,34 +346,119 @@ impl Insert {
     ///
     /// # Errors
     ///
-    /// If query cannot be built.
+    /// If query cannot be built, or
+    /// insert has multiple rows.
     /// Also proxies errors from `native_execute`.
-    pub fn execute<'a>(&'a self, py: Python<'a>, scylla: &'a Scylla) -> ScyllaPyResult<&'a PyAny> {
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
         let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
-        scylla.native_execute(py, Some(query), None, self.values_.clone(), false)
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        let row: &[ScyllaPyCQLDTO] = match self.rows_.as_slice() {
+            [] => &[],
+            [row] => row,
+            _ => {
+                return Err(ScyllaPyError::QueryBuilderError(
+                    "Inserts with multiple rows can only be added to batch",
+                ))
+            }
+        };
+        scylla.native_execute(py, Some(query), None, self.bound_values(row), false, None)
+    }
+
+    /// Build batches for multiple rows.
+    ///
+    /// Each row is a dict of column values.
+    /// Other parameters of the insert, like TTL or `IF NOT EXISTS`,
+    /// are applied to every row. Rows are split into batches
+    /// of at most `chunk_size` statements.
+    ///
+    /// # Errors
+    ///
+    /// If values were already set on the insert,
+    /// chunk size is zero, or values cannot be translated
+    /// into `Rust` types.
+    #[pyo3(signature = (rows, *, chunk_size = 100, batch_type = ScyllaPyBatchType::UNLOGGED))]
+    pub fn many(
+        &self,
+        rows: Vec<&PyDict>,
+        chunk_size: usize,
+        batch_type: ScyllaPyBatchType,
+    ) -> ScyllaPyResult<Vec<ScyllaPyInlineBatch>> {
+        if !self.names_.is_empty() {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Columns and values should not be set when using `many`",
+            ));
+        }
+        if chunk_size == 0 {
+            return Err(ScyllaPyError::QueryBuilderError(
+                "Chunk size should be greater than zero",
+            ));
+        }
+        let mut batches = Vec::new();
+        for chunk in rows.chunks(chunk_size) {
+            let mut batch = ScyllaPyInlineBatch::py_new(batch_type, None, None, None, None, None)?;
+            for row in chunk {
+                let mut insert = self.clone();
+                for (name, value) in *row {
+                    insert.set_value(name.extract()?, insert_value(value)?)?;
+                }
+                insert.add_to_batch(&mut batch)?;
+            }
+            batches.push(batch);
+        }
+        Ok(batches)
     }
 
     /// Add to batch
     ///
     /// Adds current query to batch.
+    /// If insert has multiple rows,
+    /// separate statement is added for each row.
     ///
     /// # Errors
     ///
     /// May result into error if query cannot be build.
     /// Or values cannot be passed to batch.
     pub fn add_to_batch(&self, batch: &mut ScyllaPyInlineBatch) -> ScyllaPyResult<()> {
-        let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
-
-        let mut serialized = LegacySerializedValues::new();
-        for val in self.values_.clone() {
-            serialized.add_value(&val)?;
+        for (query, values) in self.batch_statements()? {
+            batch.add_query_inner(query, values)?;
         }
-        batch.add_query_inner(query, serialized);
         Ok(())
     }
 
+    #[getter]
+    #[must_use]
+    pub fn table(&self) -> String {
+        self.table_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn keyspace(&self) -> Option<String> {
+        self.keyspace_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn column_names(&self) -> Vec<String> {
+        self.names_.clone()
+    }
+
+    /// Number of values bound to a single statement.
+    #[getter]
+    #[must_use]
+    pub fn bound_values_count(&self) -> usize {
+        let row = self.rows_.first().map(Vec::as_slice).unwrap_or_default();
+        self.bound_values(row).len()
+    }
+
     #[must_use]
     pub fn __repr__(&self) -> String {
         format!("{self:?}")
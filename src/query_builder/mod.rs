@@ -1,12 +1,25 @@
 use pyo3::{types::PyModule, PyResult, Python};
 
-use self::{delete::Delete, insert::Insert, select::Select, update::Update};
+use self::{
+    create_function::{CreateAggregate, CreateFunction},
+    create_table::CreateTable,
+    create_type::CreateType,
+    delete::Delete,
+    drop::{DropAggregate, DropFunction, DropIndex, DropKeyspace, DropTable, DropType},
+    insert::Insert,
+    select::Select,
+    update::Update,
+};
 
+pub mod create_function;
+pub mod create_table;
+pub mod create_type;
 pub mod delete;
+pub mod drop;
 pub mod insert;
 pub mod select;
 pub mod update;
-mod utils;
+pub mod utils;
 
 /// Create `QueryBuilder` module.
 ///
@@ -22,5 +35,15 @@ pub fn setup_module(_py: Python<'_>, module: &PyModule) -> PyResult<()> {
     module.add_class::<Insert>()?;
     module.add_class::<Delete>()?;
     module.add_class::<Update>()?;
+    module.add_class::<CreateType>()?;
+    module.add_class::<CreateTable>()?;
+    module.add_class::<CreateFunction>()?;
+    module.add_class::<CreateAggregate>()?;
+    module.add_class::<DropTable>()?;
+    module.add_class::<DropKeyspace>()?;
+    module.add_class::<DropIndex>()?;
+    module.add_class::<DropType>()?;
+    module.add_class::<DropFunction>()?;
+    module.add_class::<DropAggregate>()?;
     Ok(())
 }
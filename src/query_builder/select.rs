@@ -1,7 +1,8 @@
 use pyo3::{
+    exceptions::PyValueError,
     pyclass, pymethods,
     types::{PyDict, PyTuple},
-    PyAny, PyRefMut, Python,
+    Py, PyAny, PyRefMut, Python,
 };
 use scylla::query::Query;
 
@@ -9,17 +10,19 @@ use crate::{
     batches::ScyllaPyInlineBatch,
     exceptions::rust_err::ScyllaPyResult,
     queries::ScyllaPyRequestParams,
+    query_results::ScyllaPyIterableQueryResult,
     scylla_cls::Scylla,
     utils::{py_to_value, ScyllaPyCQLDTO},
 };
 
-use super::utils::{pretty_build, Timeout};
+use super::utils::{pretty_build, qualified_table, Timeout};
 use scylla::frame::value::LegacySerializedValues;
 
 #[pyclass]
 #[derive(Clone, Debug, Default)]
 pub struct Select {
     table_: String,
+    keyspace_: Option<String>,
     distinct_: bool,
     allow_filtering_: bool,
     bypass_cache_: bool,
@@ -27,6 +30,7 @@ pub struct Select {
     limit_: Option<i32>,
     per_partition_limit_: Option<i32>,
     order_by_: Option<Vec<(String, bool)>>,
+    ann_: Option<(String, ScyllaPyCQLDTO)>,
     group_by_: Option<String>,
     columns_: Option<Vec<String>>,
     where_clauses_: Vec<String>,
@@ -36,6 +40,15 @@ pub struct Select {
 }
 
 impl Select {
+    /// Values of where clauses, followed by the query vector.
+    fn query_values(&self) -> Vec<ScyllaPyCQLDTO> {
+        self.values_
+            .iter()
+            .chain(self.ann_.as_ref().map(|(_, vector)| vector))
+            .cloned()
+            .collect()
+    }
+
     #[must_use]
     pub fn build_query(&self) -> String {
         let columns = self
@@ -51,20 +64,24 @@ impl Select {
         } else {
             format!("WHERE {}", self.where_clauses_.join(" AND "))
         };
-        let orders = self.order_by_.as_ref().map_or(String::new(), |ords| {
-            let ordered_cols = ords
-                .iter()
-                .map(|(col_name, desc)| {
-                    if *desc {
-                        format!("{col_name} DESC")
-                    } else {
-                        format!("{col_name} ASC")
-                    }
-                })
-                .collect::<Vec<_>>()
-                .join(", ");
-            format!("ORDER BY {ordered_cols}")
-        });
+        let orders = if let Some((column, _)) = &self.ann_ {
+            format!("ORDER BY {column} ANN OF ?")
+        } else {
+            self.order_by_.as_ref().map_or(String::new(), |ords| {
+                let ordered_cols = ords
+                    .iter()
+                    .map(|(col_name, desc)| {
+                        if *desc {
+                            format!("{col_name} DESC")
+                        } else {
+                            format!("{col_name} ASC")
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("ORDER BY {ordered_cols}")
+            })
+        };
         let per_part_limit = self.per_partition_limit_.map_or(String::new(), |pplimit| {
             format!("PER PARTITION LIMIT {pplimit}")
         });
@@ -94,7 +111,7 @@ impl Select {
             distinct,
             columns.as_str(),
             "FROM",
-            self.table_.as_str(),
+            qualified_table(self.keyspace_.as_deref(), &self.table_).as_str(),
             where_cls.as_str(),
             group_by.as_str(),
             orders.as_str(),
@@ -111,9 +128,11 @@ impl Select {
 impl Select {
     #[new]
     #[must_use]
-    pub fn py_new(table: String) -> Self {
+    #[pyo3(signature = (table, keyspace = None))]
+    pub fn py_new(table: String, keyspace: Option<String>) -> Self {
         Self {
             table_: table,
+            keyspace_: keyspace,
             ..Default::default()
         }
     }
@@ -176,6 +195,24 @@ impl Select {
         slf
     }
 
+    /// Order rows by similarity to the query vector.
+    ///
+    /// It builds `ORDER BY column ANN OF ?` clause,
+    /// vector is bound as `vector<float, n>`.
+    /// Other orderings are ignored, when it's set.
+    #[must_use]
+    pub fn order_by_ann(
+        mut slf: PyRefMut<'_, Self>,
+        column: String,
+        query_vector: Vec<f32>,
+        limit: i32,
+    ) -> PyRefMut<'_, Self> {
+        let vector = query_vector.into_iter().map(eq_float::F32).collect();
+        slf.ann_ = Some((column, ScyllaPyCQLDTO::FloatVector(vector)));
+        slf.limit_ = Some(limit);
+        slf
+    }
+
     #[must_use]
     pub fn per_partition_limit(
         mut slf: PyRefMut<'_, Self>,
@@ -239,16 +276,55 @@ impl Select {
     /// # Errors
     ///
     /// Proxies errors from `native_execute`.
-    #[pyo3(signature = (scylla, *, paged = false))]
+    #[pyo3(signature = (scylla, *, paged = false, **params))]
     pub fn execute<'a>(
         &'a self,
         py: Python<'a>,
         scylla: &'a Scylla,
         paged: bool,
+        params: Option<&'a PyDict>,
     ) -> ScyllaPyResult<&'a PyAny> {
         let mut query = Query::new(self.build_query());
-        self.request_params_.apply_to_query(&mut query);
-        scylla.native_execute(py, Some(query), None, self.values_.clone(), paged)
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(py, Some(query), None, self.query_values(), paged, None)
+    }
+
+    /// Execute a query and stream results.
+    ///
+    /// This function returns an async iterator
+    /// over rows, which are mapped using `as_class`
+    /// if it was passed. Rows are fetched in pages
+    /// of `page_size` rows.
+    ///
+    /// Query is sent to the database on the first iteration.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if request parameters
+    /// cannot be built, or page size is not positive.
+    #[pyo3(signature = (scylla, *, as_class = None, page_size = None, **params))]
+    pub fn execute_stream(
+        &self,
+        scylla: &Scylla,
+        as_class: Option<Py<PyAny>>,
+        page_size: Option<i32>,
+        params: Option<&PyDict>,
+    ) -> ScyllaPyResult<ScyllaPyIterableQueryResult> {
+        let mut query = Query::new(self.build_query());
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        if let Some(page_size) = page_size {
+            if page_size <= 0 {
+                return Err(PyValueError::new_err("Page size should be positive.").into());
+            }
+            query.set_page_size(page_size);
+        }
+        Ok(scylla
+            .native_execute_stream(query, self.query_values())
+            .with_mapper(as_class))
     }
 
     /// Add to batch
@@ -263,11 +339,40 @@ impl Select {
         self.request_params_.apply_to_query(&mut query);
 
         let mut serialized = LegacySerializedValues::new();
-        for val in self.values_.clone() {
+        for val in self.query_values() {
             serialized.add_value(&val)?;
         }
-        batch.add_query_inner(query, serialized);
-        Ok(())
+        batch.add_query_inner(query, serialized)
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn table(&self) -> String {
+        self.table_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn keyspace(&self) -> Option<String> {
+        self.keyspace_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn selected_columns(&self) -> Option<Vec<String>> {
+        self.columns_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn where_clauses(&self) -> Vec<String> {
+        self.where_clauses_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn bound_values_count(&self) -> usize {
+        self.values_.len() + usize::from(self.ann_.is_some())
     }
 
     #[must_use]
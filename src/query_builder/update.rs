@@ -11,7 +11,7 @@ use crate::{
     utils::{py_to_value, ScyllaPyCQLDTO},
 };
 
-use super::utils::{pretty_build, IfCluase, Timeout};
+use super::utils::{pretty_build, qualified_table, IfCluase, Timeout, UsingParam};
 #[derive(Clone, Debug)]
 enum UpdateAssignment {
     Simple(String),
@@ -19,6 +19,16 @@ enum UpdateAssignment {
     Dec(String, String),
 }
 
+impl UpdateAssignment {
+    fn column(&self) -> &str {
+        match self {
+            UpdateAssignment::Simple(name)
+            | UpdateAssignment::Inc(name, _)
+            | UpdateAssignment::Dec(name, _) => name,
+        }
+    }
+}
+
 impl Display for UpdateAssignment {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -33,6 +43,7 @@ impl Display for UpdateAssignment {
 #[derive(Clone, Debug, Default)]
 pub struct Update {
     table_: String,
+    keyspace_: Option<String>,
     assignments_: Vec<UpdateAssignment>,
     values_: Vec<ScyllaPyCQLDTO>,
 
@@ -40,8 +51,8 @@ pub struct Update {
     where_values_: Vec<ScyllaPyCQLDTO>,
 
     timeout_: Option<Timeout>,
-    ttl_: Option<i32>,
-    timestamp_: Option<u64>,
+    ttl_: Option<UsingParam<i32>>,
+    timestamp_: Option<UsingParam<u64>>,
     if_clause_: Option<IfCluase>,
 
     request_params_: ScyllaPyRequestParams,
@@ -61,8 +72,9 @@ impl Update {
         }
         let params = [
             self.timestamp_
-                .map(|timestamp| format!("TIMESTAMP {timestamp}")),
-            self.ttl_.map(|ttl| format!("TTL {ttl}")),
+                .as_ref()
+                .map(|timestamp| timestamp.format("TIMESTAMP")),
+            self.ttl_.as_ref().map(|ttl| ttl.format("TTL")),
             self.timeout_.as_ref().map(|timeout| match timeout {
                 Timeout::Int(int) => format!("TIMEOUT {int}"),
                 Timeout::Str(string) => format!("TIMEOUT {string}"),
@@ -99,22 +111,58 @@ impl Update {
 
         Ok(pretty_build([
             "UPDATE",
-            self.table_.as_str(),
+            qualified_table(self.keyspace_.as_deref(), &self.table_).as_str(),
             usings.as_str(),
             format!("SET {assigments}").as_str(),
             format!("WHERE {where_clauses}").as_str(),
             if_conditions.as_str(),
         ]))
     }
+
+    /// Get all values that should be bound to the statement.
+    ///
+    /// `USING` clause goes before assignments,
+    /// so values for its markers are placed first.
+    fn bound_values(&self) -> Vec<ScyllaPyCQLDTO> {
+        let mut values = Vec::new();
+        values.extend(self.timestamp_.as_ref().and_then(UsingParam::marker_value));
+        values.extend(self.ttl_.as_ref().and_then(UsingParam::marker_value));
+        values.extend(self.values_.iter().cloned());
+        values.extend(self.where_values_.iter().cloned());
+        if let Some(if_clause) = &self.if_clause_ {
+            if_clause.extend_values(values)
+        } else {
+            values
+        }
+    }
+
+    /// Statements with their values for batches.
+    ///
+    /// # Errors
+    ///
+    /// May result into error if query cannot be build.
+    /// Or values cannot be serialized.
+    pub fn batch_statements(&self) -> ScyllaPyResult<Vec<(Query, LegacySerializedValues)>> {
+        let mut query = Query::new(self.build_query()?);
+        self.request_params_.apply_to_query(&mut query);
+
+        let mut serialized = LegacySerializedValues::new();
+        for val in self.bound_values() {
+            serialized.add_value(&val)?;
+        }
+        Ok(vec![(query, serialized)])
+    }
 }
 
 #[pymethods]
 impl Update {
     #[new]
     #[must_use]
-    pub fn py_new(table: String) -> Self {
+    #[pyo3(signature = (table, keyspace = None))]
+    pub fn py_new(table: String, keyspace: Option<String>) -> Self {
         Self {
             table_: table,
+            keyspace_: keyspace,
             ..Default::default()
         }
     }
@@ -202,13 +250,44 @@ impl Update {
 
     #[must_use]
     pub fn timestamp(mut slf: PyRefMut<'_, Self>, timestamp: u64) -> PyRefMut<'_, Self> {
-        slf.timestamp_ = Some(timestamp);
+        slf.timestamp_ = Some(UsingParam::Inline(timestamp));
         slf
     }
 
     #[must_use]
     pub fn ttl(mut slf: PyRefMut<'_, Self>, ttl: i32) -> PyRefMut<'_, Self> {
-        slf.ttl_ = Some(ttl);
+        slf.ttl_ = Some(UsingParam::Inline(ttl));
+        slf
+    }
+
+    /// Use bind marker for TTL.
+    ///
+    /// Statement is built with `TTL ?`, so
+    /// the same statement text can be used
+    /// with different TTLs. If no value passed,
+    /// TTL is bound as unset.
+    #[must_use]
+    #[pyo3(signature = (ttl = None))]
+    pub fn using_ttl_marker(mut slf: PyRefMut<'_, Self>, ttl: Option<i32>) -> PyRefMut<'_, Self> {
+        slf.ttl_ = Some(UsingParam::Marker(
+            ttl.map_or(ScyllaPyCQLDTO::Unset, ScyllaPyCQLDTO::Int),
+        ));
+        slf
+    }
+
+    /// Use bind marker for timestamp.
+    ///
+    /// Statement is built with `TIMESTAMP ?`.
+    /// If no value passed, timestamp is bound as unset.
+    #[must_use]
+    #[pyo3(signature = (timestamp = None))]
+    pub fn using_timestamp_marker(
+        mut slf: PyRefMut<'_, Self>,
+        timestamp: Option<i64>,
+    ) -> PyRefMut<'_, Self> {
+        slf.timestamp_ = Some(UsingParam::Marker(
+            timestamp.map_or(ScyllaPyCQLDTO::Unset, ScyllaPyCQLDTO::BigInt),
+        ));
         slf
     }
 
@@ -276,17 +355,18 @@ impl Update {
     /// May return an error, if something goes wrong
     /// during query building
     /// or during query execution.
-    pub fn execute<'a>(&'a self, py: Python<'a>, scylla: &'a Scylla) -> ScyllaPyResult<&'a PyAny> {
+    #[pyo3(signature = (scylla, **params))]
+    pub fn execute<'a>(
+        &'a self,
+        py: Python<'a>,
+        scylla: &'a Scylla,
+        params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
         let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
-        let mut values = self.values_.clone();
-        values.extend(self.where_values_.clone());
-        let values = if let Some(if_clause) = &self.if_clause_ {
-            if_clause.extend_values(values)
-        } else {
-            values
-        };
-        scylla.native_execute(py, Some(query), None, values, false)
+        self.request_params_
+            .merge(ScyllaPyRequestParams::from_dict(params)?)
+            .apply_to_query(&mut query);
+        scylla.native_execute(py, Some(query), None, self.bound_values(), false, None)
     }
 
     /// Add to batch
@@ -298,22 +378,9 @@ impl Update {
     /// May result into error if query cannot be build.
     /// Or values cannot be passed to batch.
     pub fn add_to_batch(&self, batch: &mut ScyllaPyInlineBatch) -> ScyllaPyResult<()> {
-        let mut query = Query::new(self.build_query()?);
-        self.request_params_.apply_to_query(&mut query);
-
-        let mut values = self.values_.clone();
-        values.extend(self.where_values_.clone());
-        let values = if let Some(if_clause) = &self.if_clause_ {
-            if_clause.extend_values(values)
-        } else {
-            values
-        };
-
-        let mut serialized = LegacySerializedValues::new();
-        for val in values {
-            serialized.add_value(&val)?;
+        for (query, values) in self.batch_statements()? {
+            batch.add_query_inner(query, values)?;
         }
-        batch.add_query_inner(query, serialized);
         Ok(())
     }
 
@@ -326,6 +393,39 @@ impl Update {
         self.build_query()
     }
 
+    #[getter]
+    #[must_use]
+    pub fn table(&self) -> String {
+        self.table_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn keyspace(&self) -> Option<String> {
+        self.keyspace_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn column_names(&self) -> Vec<String> {
+        self.assignments_
+            .iter()
+            .map(|assignment| assignment.column().to_string())
+            .collect()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn where_clauses(&self) -> Vec<String> {
+        self.where_clauses_.clone()
+    }
+
+    #[getter]
+    #[must_use]
+    pub fn bound_values_count(&self) -> usize {
+        self.bound_values().len()
+    }
+
     #[must_use]
     pub fn __repr__(&self) -> String {
         format!("{self:?}")
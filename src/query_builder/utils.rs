@@ -1,6 +1,10 @@
-use pyo3::FromPyObject;
+use pyo3::{exceptions::PyValueError, FromPyObject};
 
-use crate::utils::ScyllaPyCQLDTO;
+use crate::{
+    batches::top_level_words,
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    utils::ScyllaPyCQLDTO,
+};
 
 #[derive(FromPyObject, Debug, Clone)]
 pub enum Timeout {
@@ -31,6 +35,94 @@ impl IfCluase {
     }
 }
 
+/// Name of the table, qualified with keyspace if it's set.
+///
+/// Driver cannot set keyspace of a single statement,
+/// so keyspace per statement is set by qualifying its table.
+/// It lets applications with keyspace per tenant avoid
+/// `USE` round trips and separate sessions.
+#[must_use]
+pub fn qualified_table(keyspace: Option<&str>, table: &str) -> String {
+    keyspace.map_or_else(
+        || table.to_string(),
+        |keyspace| format!("{keyspace}.{table}"),
+    )
+}
+
+/// Qualify table of the statement with keyspace, see `qualified_table`.
+///
+/// Tables of `SELECT`, `INSERT`, `UPDATE` and `DELETE`
+/// statements are qualified, unless they already have keyspace.
+///
+/// # Errors
+///
+/// Returns an error for other statements
+/// or if table of the statement cannot be found.
+pub fn qualify_statement(query: &str, keyspace: &str) -> ScyllaPyResult<String> {
+    let words = top_level_words(query);
+    let position = |name: &str| {
+        words
+            .iter()
+            .position(|(_, word)| word.eq_ignore_ascii_case(name))
+    };
+    let first = words.first().map(|(_, word)| word.to_ascii_uppercase());
+    let table_keyword = match first.as_deref() {
+        Some("SELECT" | "DELETE") => position("FROM"),
+        Some("INSERT") => position("INTO"),
+        Some("UPDATE") => Some(0),
+        _ => None,
+    };
+    let not_found = || {
+        PyValueError::new_err(format!(
+            "Cannot find table to qualify with keyspace in statement `{query}`."
+        ))
+    };
+    let (start, word) = table_keyword.map(|idx| words[idx]).ok_or_else(not_found)?;
+    let rest = &query[start + word.len()..];
+    let table_start = start + word.len() + rest.len() - rest.trim_start().len();
+    let bytes = query.as_bytes();
+    let mut table_end = table_start;
+    if bytes.get(table_start) == Some(&b'"') {
+        table_end += 1;
+        // Quotes are escaped by doubling them.
+        while table_end < bytes.len() {
+            if bytes[table_end] == b'"' {
+                if bytes.get(table_end + 1) != Some(&b'"') {
+                    break;
+                }
+                table_end += 1;
+            }
+            table_end += 1;
+        }
+        table_end += 1;
+    } else {
+        while table_end < bytes.len()
+            && (bytes[table_end].is_ascii_alphanumeric() || bytes[table_end] == b'_')
+        {
+            table_end += 1;
+        }
+    }
+    if table_end == table_start || table_end > bytes.len() {
+        return Err(not_found().into());
+    }
+    if query[table_end..].trim_start().starts_with('.') {
+        return Ok(query.to_string());
+    }
+    let (head, tail) = query.split_at(table_start);
+    Ok(format!("{head}{keyspace}.{tail}"))
+}
+
+/// Error for keyspace passed along with prepared statement,
+/// which text cannot be changed.
+#[must_use]
+pub fn prepared_keyspace_error(statement: &str) -> ScyllaPyError {
+    PyValueError::new_err(format!(
+        "Keyspace cannot be set for prepared statement `{statement}`. \
+        Prepare it with qualified table name instead."
+    ))
+    .into()
+}
+
 /// Function for building
 /// pretty queries.
 ///
@@ -43,3 +135,36 @@ pub fn pretty_build<'a>(query_parts: impl IntoIterator<Item = &'a str>) -> Strin
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+/// Parameter of the `USING` clause.
+///
+/// It can be either written directly
+/// in the statement text, or placed as a bind marker,
+/// so the same statement can be reused with different values.
+#[derive(Clone, Debug)]
+pub enum UsingParam<T> {
+    Inline(T),
+    Marker(ScyllaPyCQLDTO),
+}
+
+impl<T: std::fmt::Display> UsingParam<T> {
+    /// Format parameter with the given keyword.
+    ///
+    /// For example `TTL 10` or `TTL ?`.
+    #[must_use]
+    pub fn format(&self, keyword: &str) -> String {
+        match self {
+            UsingParam::Inline(value) => format!("{keyword} {value}"),
+            UsingParam::Marker(_) => format!("{keyword} ?"),
+        }
+    }
+
+    /// Get value, that should be bound to the marker.
+    #[must_use]
+    pub fn marker_value(&self) -> Option<ScyllaPyCQLDTO> {
+        match self {
+            UsingParam::Inline(_) => None,
+            UsingParam::Marker(value) => Some(value.clone()),
+        }
+    }
+}
@@ -1,18 +1,42 @@
-use std::{collections::HashMap, hash::BuildHasherDefault, sync::Arc};
+use std::{collections::VecDeque, future::Future, sync::Arc, time::Instant};
 
-use futures::StreamExt;
+use futures::{future::BoxFuture, StreamExt};
 use pyo3::{
-    exceptions::PyStopAsyncIteration, pyclass, pymethods, types::PyDict, IntoPy, Py, PyAny,
-    PyObject, PyRef, PyRefMut, Python, ToPyObject,
+    exceptions::PyStopAsyncIteration,
+    pyclass, pymethods,
+    types::{PyDict, PyList, PyString},
+    IntoPy, Py, PyAny, PyCell, PyObject, PyRef, PyRefMut, Python, ToPyObject,
 };
-use scylla::{transport::iterator::RowIterator, QueryResult};
-use tokio::sync::Mutex;
+use rayon::prelude::*;
+use scylla::{
+    frame::response::result::{ColumnSpec, ColumnType, CqlValue, Row},
+    transport::{errors::QueryError, iterator::RowIterator},
+    QueryResult,
+};
+use tokio::sync::{mpsc, Mutex};
 
 use crate::{
     exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
-    utils::{cql_to_py, map_rows, scyllapy_future},
+    instrumentation::{ProfileStage, Profiler},
+    utils::{cql_to_py, cql_to_text, has_text_form, map_rows, scyllapy_future, text_constructor},
 };
 
+const APPLIED_COLUMN: &str = "[applied]";
+
+/// How many rows are converted to python objects at once.
+///
+/// GIL is released between chunks, so other threads
+/// can run while big results are converted.
+const DECODE_CHUNK_SIZE: usize = 1000;
+
+/// Results with more values than this have text forms
+/// of uuids, dates, decimals and varints built on multiple threads.
+/// Values of other types are converted only while holding the GIL.
+const PARALLEL_DECODE_SIZE: usize = 10_000;
+
+/// How many rows are fetched ahead by iterable results.
+const PREFETCH_SIZE: usize = 1000;
+
 pub enum ScyllaPyQueryReturns {
     QueryResult(ScyllaPyQueryResult),
     IterableQueryResult(ScyllaPyIterableQueryResult),
@@ -27,50 +51,114 @@ impl IntoPy<Py<PyAny>> for ScyllaPyQueryReturns {
     }
 }
 
-#[pyclass(name = "QueryResult")]
+#[pyclass(name = "QueryResult", subclass)]
 pub struct ScyllaPyQueryResult {
     inner: QueryResult,
+    lazy_collections: bool,
+    profiler: Option<Arc<Profiler>>,
 }
 
 impl ScyllaPyQueryResult {
     pub fn new(results: QueryResult) -> Self {
-        Self { inner: results }
+        Self {
+            inner: results,
+            lazy_collections: false,
+            profiler: None,
+        }
+    }
+
+    /// Decode collections and UDTs only when they are accessed.
+    #[must_use]
+    pub fn with_lazy_collections(mut self, lazy_collections: bool) -> Self {
+        self.lazy_collections = lazy_collections;
+        self
+    }
+
+    /// Record timings of rows conversion.
+    #[must_use]
+    pub fn with_profiler(mut self, profiler: Option<Arc<Profiler>>) -> Self {
+        self.profiler = profiler;
+        self
+    }
+
+    #[must_use]
+    pub fn tracing_id(&self) -> Option<uuid::Uuid> {
+        self.inner.tracing_id
     }
     fn get_rows<'a>(
         &'a self,
         py: Python<'a>,
         limit: Option<usize>,
-    ) -> ScyllaPyResult<Option<Vec<rustc_hash::FxHashMap<&'a str, &'a PyAny>>>> {
+    ) -> ScyllaPyResult<Option<Vec<&'a PyDict>>> {
         let Some(rows) = &self.inner.rows else {
             return Ok(None);
         };
-        let specs = &self.inner.col_specs;
-        let mut dumped_rows = Vec::new();
+        let rows = &rows[..limit.unwrap_or(usize::MAX).min(rows.len())];
+        let specs = self.inner.col_specs.as_slice();
+        let columns = ResultColumns::new(py, specs, self.lazy_collections)?;
+        let started = Instant::now();
+        // Text forms of uuids, dates, decimals and varints
+        // of big results are built in parallel without the GIL,
+        // so only python objects are created from them while holding it.
+        let mut text_forms = (rows.len() * specs.len() >= PARALLEL_DECODE_SIZE
+            && columns.has_text_forms())
+        .then(|| {
+            let decoders = &columns.decoders;
+            py.allow_threads(|| prepare_text_forms(specs, decoders, rows))
+                .into_iter()
+        });
+        if let (Some(profiler), Some(_)) = (&self.profiler, &text_forms) {
+            profiler.record(ProfileStage::Decode, started.elapsed());
+        }
+        let started = Instant::now();
+        let mut dumped_rows = Vec::with_capacity(rows.len());
         for (row_index, row) in rows.iter().enumerate() {
-            let mut map = HashMap::with_capacity_and_hasher(
-                specs.len(),
-                BuildHasherDefault::<rustc_hash::FxHasher>::default(),
-            );
-            for (col_index, column) in row.columns.iter().enumerate() {
-                map.insert(
-                    specs[col_index].name.as_str(),
-                    cql_to_py(
-                        py,
-                        &specs[col_index].name,
-                        &specs[col_index].typ,
-                        column.as_ref(),
-                    )?,
-                );
-            }
-            dumped_rows.push(map);
-            if let Some(limit) = limit {
-                if row_index >= limit {
-                    break;
-                }
+            if row_index > 0 && row_index % DECODE_CHUNK_SIZE == 0 {
+                // Let other threads take the GIL.
+                py.allow_threads(|| ());
             }
+            let texts = text_forms.as_mut().and_then(Iterator::next);
+            dumped_rows.push(columns.row_to_dict(py, specs, row, texts)?);
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.record(ProfileStage::Conversion, started.elapsed());
         }
         Ok(Some(dumped_rows))
     }
+
+    /// Check whether conditional statement was applied.
+    ///
+    /// Results of conditional statements contain
+    /// `[applied]` column. If there's no such column,
+    /// the statement is considered as applied.
+    ///
+    /// # Errors
+    ///
+    /// If `[applied]` column is not a boolean.
+    pub fn is_applied(&self) -> ScyllaPyResult<bool> {
+        let Some(rows) = &self.inner.rows else {
+            return Ok(true);
+        };
+        let Some(applied_index) = self
+            .inner
+            .col_specs
+            .iter()
+            .position(|spec| spec.name == APPLIED_COLUMN)
+        else {
+            return Ok(true);
+        };
+        let Some(row) = rows.first() else {
+            return Ok(true);
+        };
+        row.columns
+            .get(applied_index)
+            .and_then(Option::as_ref)
+            .and_then(CqlValue::as_boolean)
+            .ok_or(ScyllaPyError::ValueDowncastError(
+                APPLIED_COLUMN.into(),
+                "Boolean",
+            ))
+    }
 }
 
 #[pymethods]
@@ -89,7 +177,7 @@ impl ScyllaPyQueryResult {
         let Some(rows) = self.get_rows(py, None)? else {
             return Err(ScyllaPyError::NoReturnsError);
         };
-        let py_rows = rows.to_object(py);
+        let py_rows: Py<PyAny> = PyList::new(py, rows).into();
         if let Some(as_class) = as_class {
             return Ok(map_rows(py, &py_rows, &as_class)?.to_object(py));
         }
@@ -117,7 +205,7 @@ impl ScyllaPyQueryResult {
             return Ok(None);
         }
         if let Some(as_class) = as_class {
-            let py_rows = rows.to_object(py);
+            let py_rows: Py<PyAny> = PyList::new(py, rows).into();
             return Ok(map_rows(py, &py_rows, &as_class)?
                 .first()
                 .map(|val| val.to_object(py)));
@@ -145,12 +233,13 @@ impl ScyllaPyQueryResult {
         let Some(col_name) = self.inner.col_specs.first() else {
             return Err(ScyllaPyError::NoColumns);
         };
-        Ok(Some(
-            rows.iter()
-                .filter_map(|row| row.get(col_name.name.as_str()))
-                .collect::<Vec<_>>()
-                .to_object(py),
-        ))
+        let mut values = Vec::with_capacity(rows.len());
+        for row in rows {
+            if let Some(value) = row.get_item(col_name.name.as_str())? {
+                values.push(value);
+            }
+        }
+        Ok(Some(values.to_object(py)))
     }
 
     /// Function to get first column of first row.
@@ -173,11 +262,23 @@ impl ScyllaPyQueryResult {
         let Some(col_name) = self.inner.col_specs.first() else {
             return Err(ScyllaPyError::NoColumns);
         };
-        Ok(Some(
-            rows.first()
-                .and_then(|row| row.get(col_name.name.as_str()))
-                .to_object(py),
-        ))
+        let Some(row) = rows.first() else {
+            return Ok(None);
+        };
+        Ok(row
+            .get_item(col_name.name.as_str())?
+            .map(|value| value.to_object(py)))
+    }
+
+    /// Names of columns in the result.
+    #[getter]
+    #[must_use]
+    pub fn columns(&self) -> Vec<&str> {
+        self.inner
+            .col_specs
+            .iter()
+            .map(|spec| spec.name.as_str())
+            .collect()
     }
 
     /// Get lenght of the result.
@@ -200,21 +301,460 @@ impl ScyllaPyQueryResult {
     }
 }
 
+/// State of the row iterator.
+///
+/// Iterator can be created lazily,
+/// in this case the query is sent to the database
+/// only when first row is requested.
+/// Ready iterator receives rows fetched in background
+/// and also holds converted rows, which weren't returned yet.
+enum RowIteratorState {
+    Pending(BoxFuture<'static, ScyllaPyResult<RowIterator>>),
+    Ready {
+        specs: Vec<ColumnSpec>,
+        /// Created with the GIL, when the first rows are converted.
+        columns: Option<ResultColumns>,
+        rows: mpsc::Receiver<Result<Row, QueryError>>,
+        decoded: VecDeque<ScyllaPyResult<PyObject>>,
+    },
+    Failed,
+}
+
+impl RowIteratorState {
+    /// Start fetching rows in background.
+    ///
+    /// Rows are fetched ahead into a bounded channel,
+    /// so next pages are requested while python
+    /// processes current rows. Fetching stops
+    /// when the iterator is dropped.
+    fn prefetch(mut row_iterator: RowIterator) -> Self {
+        // Specs are copied once for all pages,
+        // because the iterator is moved to the fetching task.
+        let specs = row_iterator.get_column_specs().to_vec();
+        let (sender, rows) = mpsc::channel(PREFETCH_SIZE);
+        pyo3_asyncio::tokio::get_runtime().spawn(async move {
+            while let Some(row) = row_iterator.next().await {
+                if sender.send(row).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Self::Ready {
+            specs,
+            columns: None,
+            rows,
+            decoded: VecDeque::new(),
+        }
+    }
+}
+
+/// Text forms of values in a row.
+///
+/// Values of types without text form are `None`.
+type TextForms = Vec<Option<ScyllaPyResult<String>>>;
+
+/// Build text forms of values for all rows in parallel.
+fn prepare_text_forms(
+    specs: &[ColumnSpec],
+    decoders: &[ColumnDecoder],
+    rows: &[Row],
+) -> Vec<TextForms> {
+    rows.par_iter()
+        .map(|row| {
+            specs
+                .iter()
+                .zip(decoders)
+                .zip(&row.columns)
+                .map(|((spec, decoder), value)| {
+                    value
+                        .as_ref()
+                        .filter(|_| matches!(decoder, ColumnDecoder::Text(_)))
+                        .map(|value| cql_to_text(&spec.name, &spec.typ, value))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// How values of a column are converted to python.
+///
+/// It's chosen once per result set, so types of columns
+/// aren't matched again for every value.
+enum ColumnDecoder {
+    /// Collection or UDT, which is wrapped in `LazyValue`.
+    Lazy,
+    /// Uuid, date, decimal or varint, which is passed
+    /// to the python constructor as a string.
+    Text(Py<PyAny>),
+    /// Any other value.
+    Value,
+}
+
+impl ColumnDecoder {
+    fn new(py: Python<'_>, spec: &ColumnSpec, lazy_collections: bool) -> ScyllaPyResult<Self> {
+        if lazy_collections && ScyllaPyLazyValue::is_lazy(&spec.typ) {
+            Ok(Self::Lazy)
+        } else if has_text_form(&spec.typ) {
+            Ok(Self::Text(text_constructor(py, &spec.typ)?.into()))
+        } else {
+            Ok(Self::Value)
+        }
+    }
+}
+
+/// Columns of a result set.
+///
+/// Python names of columns and their decoders are created
+/// once per result set and shared by all rows.
+struct ResultColumns {
+    names: Vec<Py<PyString>>,
+    decoders: Vec<ColumnDecoder>,
+}
+
+impl ResultColumns {
+    fn new(py: Python<'_>, specs: &[ColumnSpec], lazy_collections: bool) -> ScyllaPyResult<Self> {
+        Ok(Self {
+            names: specs
+                .iter()
+                .map(|spec| PyString::new(py, &spec.name).into())
+                .collect(),
+            decoders: specs
+                .iter()
+                .map(|spec| ColumnDecoder::new(py, spec, lazy_collections))
+                .collect::<ScyllaPyResult<_>>()?,
+        })
+    }
+
+    /// Whether any column has values with text form.
+    fn has_text_forms(&self) -> bool {
+        self.decoders
+            .iter()
+            .any(|decoder| matches!(decoder, ColumnDecoder::Text(_)))
+    }
+
+    /// Convert a value of the column to python.
+    ///
+    /// If text form of the value was prepared in advance,
+    /// python object is created right from it.
+    fn value_to_py<'a>(
+        py: Python<'a>,
+        decoder: &ColumnDecoder,
+        spec: &ColumnSpec,
+        value: Option<&CqlValue>,
+        text: Option<ScyllaPyResult<String>>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let Some(value) = value else {
+            return cql_to_py(py, &spec.name, &spec.typ, None);
+        };
+        match decoder {
+            ColumnDecoder::Lazy => Ok(Py::new(py, ScyllaPyLazyValue::new(spec, value.clone()))?
+                .into_ref(py)
+                .as_ref()),
+            ColumnDecoder::Text(constructor) => {
+                let text = match text {
+                    Some(text) => text?,
+                    None => cql_to_text(&spec.name, &spec.typ, value)?,
+                };
+                Ok(constructor.as_ref(py).call1((text,))?)
+            }
+            ColumnDecoder::Value => cql_to_py(py, &spec.name, &spec.typ, Some(value)),
+        }
+    }
+
+    /// Convert a row to dict.
+    fn row_to_dict<'a>(
+        &self,
+        py: Python<'a>,
+        specs: &[ColumnSpec],
+        row: &Row,
+        texts: Option<TextForms>,
+    ) -> ScyllaPyResult<&'a PyDict> {
+        let row_dict = PyDict::new(py);
+        let mut texts = texts.map(Vec::into_iter);
+        for (((spec, name), decoder), column) in specs
+            .iter()
+            .zip(&self.names)
+            .zip(&self.decoders)
+            .zip(&row.columns)
+        {
+            let text = texts.as_mut().and_then(Iterator::next).flatten();
+            let value = Self::value_to_py(py, decoder, spec, column.as_ref(), text)?;
+            row_dict.set_item(name.as_ref(py), value)?;
+        }
+        Ok(row_dict)
+    }
+
+    /// Convert a row of the iterator to python object.
+    ///
+    /// If `scalars` is set, only the first column is returned,
+    /// otherwise row is converted to a dict, which is passed
+    /// to the mapper as keyword arguments.
+    fn row_to_py(
+        &self,
+        py: Python<'_>,
+        specs: &[ColumnSpec],
+        row: &Row,
+        scalars: bool,
+        mapper: Option<&Py<PyAny>>,
+    ) -> ScyllaPyResult<PyObject> {
+        if scalars {
+            let spec = specs.first().ok_or(ScyllaPyError::NoColumns)?;
+            let decoder = self.decoders.first().ok_or(ScyllaPyError::NoColumns)?;
+            let value = row.columns.first().ok_or(ScyllaPyError::NoColumns)?;
+            return Ok(Self::value_to_py(py, decoder, spec, value.as_ref(), None)?.into_py(py));
+        }
+        let row_dict = self.row_to_dict(py, specs, row, None)?;
+        if let Some(mapper) = mapper {
+            Ok(mapper.call(py, (), Some(row_dict))?)
+        } else {
+            Ok(row_dict.into())
+        }
+    }
+}
+
+/// Collection or UDT value, which is decoded on first access.
+///
+/// Rows with big nested values are often filtered
+/// or used partially, so converting these values
+/// to python objects is deferred until they are used.
+/// Decoded value is cached.
+#[pyclass(name = "LazyValue")]
+pub struct ScyllaPyLazyValue {
+    name: String,
+    typ: ColumnType,
+    value: CqlValue,
+    decoded: Option<PyObject>,
+}
+
+impl ScyllaPyLazyValue {
+    #[must_use]
+    pub fn new(spec: &ColumnSpec, value: CqlValue) -> Self {
+        Self {
+            name: spec.name.clone(),
+            typ: spec.typ.clone(),
+            value,
+            decoded: None,
+        }
+    }
+
+    /// Whether values of the type are decoded lazily.
+    #[must_use]
+    pub fn is_lazy(typ: &ColumnType) -> bool {
+        matches!(
+            typ,
+            ColumnType::List(_)
+                | ColumnType::Set(_)
+                | ColumnType::Map(_, _)
+                | ColumnType::UserDefinedType { .. }
+        )
+    }
+
+    fn decoded<'a>(&mut self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        if let Some(decoded) = &self.decoded {
+            return Ok(decoded.clone_ref(py).into_ref(py));
+        }
+        let decoded: PyObject = cql_to_py(py, &self.name, &self.typ, Some(&self.value))?.into();
+        self.decoded = Some(decoded.clone_ref(py));
+        Ok(decoded.into_ref(py))
+    }
+}
+
+#[pymethods]
+impl ScyllaPyLazyValue {
+    /// Decode the value.
+    ///
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn load<'a>(&mut self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        self.decoded(py)
+    }
+
+    /// Number of items, which is known without decoding.
+    #[must_use]
+    pub fn __len__(&self) -> usize {
+        match &self.value {
+            CqlValue::List(items) | CqlValue::Set(items) => items.len(),
+            CqlValue::Map(items) => items.len(),
+            CqlValue::UserDefinedType { fields, .. } => fields.len(),
+            _ => 0,
+        }
+    }
+
+    /// Get attribute of the decoded value.
+    ///
+    /// It's called only for attributes, which `LazyValue`
+    /// doesn't have, so methods of lists, sets and dicts,
+    /// like `keys`, `items` or `get`, are available.
+    ///
+    /// # Errors
+    ///
+    /// If value cannot be converted to python
+    /// or it has no such attribute.
+    pub fn __getattr__<'a>(&mut self, py: Python<'a>, name: &str) -> ScyllaPyResult<&'a PyAny> {
+        Ok(self.decoded(py)?.getattr(name)?)
+    }
+
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn __iter__<'a>(&mut self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        Ok(self.decoded(py)?.iter()?.as_ref())
+    }
+
+    /// # Errors
+    ///
+    /// If value cannot be converted to python
+    /// or there's no such item.
+    pub fn __getitem__<'a>(&mut self, py: Python<'a>, key: &PyAny) -> ScyllaPyResult<&'a PyAny> {
+        Ok(self.decoded(py)?.get_item(key)?)
+    }
+
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn __contains__(&mut self, py: Python<'_>, item: &PyAny) -> ScyllaPyResult<bool> {
+        Ok(self.decoded(py)?.contains(item)?)
+    }
+
+    /// Compare decoded value with another object.
+    ///
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn __eq__(&mut self, py: Python<'_>, other: &PyAny) -> ScyllaPyResult<bool> {
+        let decoded = self.decoded(py)?;
+        match other.downcast::<PyCell<Self>>() {
+            // Value cannot be borrowed only if it's compared with itself.
+            Ok(other) => match other.try_borrow_mut() {
+                Ok(mut other) => Ok(decoded.eq(other.decoded(py)?)?),
+                Err(_) => Ok(true),
+            },
+            Err(_) => Ok(decoded.eq(other)?),
+        }
+    }
+
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn __ne__(&mut self, py: Python<'_>, other: &PyAny) -> ScyllaPyResult<bool> {
+        Ok(!self.__eq__(py, other)?)
+    }
+
+    /// # Errors
+    ///
+    /// If value cannot be converted to python.
+    pub fn __repr__(&mut self, py: Python<'_>) -> ScyllaPyResult<String> {
+        Ok(format!("LazyValue({})", self.decoded(py)?.repr()?))
+    }
+}
+
+/// Result of a batch execution.
+///
+/// It's the same as `QueryResult`, but also
+/// gives structured access to results of
+/// conditional statements.
+#[pyclass(name = "BatchResult", extends = ScyllaPyQueryResult)]
+pub struct ScyllaPyBatchResult {}
+
+impl ScyllaPyBatchResult {
+    /// Create new python object of batch result.
+    ///
+    /// # Errors
+    ///
+    /// If python object cannot be created.
+    pub fn new_py(py: Python<'_>, results: QueryResult) -> ScyllaPyResult<Py<Self>> {
+        Ok(Py::new(py, (Self {}, ScyllaPyQueryResult::new(results)))?)
+    }
+}
+
+#[pymethods]
+impl ScyllaPyBatchResult {
+    /// Whether the batch was applied.
+    ///
+    /// Batches without conditions are always applied.
+    ///
+    /// # Errors
+    ///
+    /// If `[applied]` column cannot be parsed.
+    #[getter]
+    pub fn applied(slf: PyRef<'_, Self>) -> ScyllaPyResult<bool> {
+        slf.as_ref().is_applied()
+    }
+
+    /// Current values of rows, that didn't match conditions.
+    ///
+    /// If batch wasn't applied, database returns
+    /// current values for every conditional statement.
+    /// Here we return these values without `[applied]` column.
+    ///
+    /// # Errors
+    ///
+    /// If values cannot be converted to python.
+    #[getter]
+    pub fn conditions(slf: PyRef<'_, Self>, py: Python<'_>) -> ScyllaPyResult<Py<PyAny>> {
+        let Some(rows) = slf.as_ref().get_rows(py, None)? else {
+            return Ok(Vec::<Py<PyAny>>::new().to_object(py));
+        };
+        for row in &rows {
+            if row.contains(APPLIED_COLUMN)? {
+                row.del_item(APPLIED_COLUMN)?;
+            }
+        }
+        Ok(rows.to_object(py))
+    }
+}
+
 #[pyclass(name = "IterableQueryResult")]
 pub struct ScyllaPyIterableQueryResult {
-    inner: Arc<Mutex<RowIterator>>,
+    inner: Arc<Mutex<RowIteratorState>>,
     mapper: Option<Py<PyAny>>,
     scalars: bool,
+    lazy_collections: bool,
+    profiler: Option<Arc<Profiler>>,
 }
 
 impl ScyllaPyIterableQueryResult {
     pub fn new(results: RowIterator) -> Self {
+        Self::lazy(futures::future::ready(Ok(results)))
+    }
+
+    /// Create iterable result from a future.
+    ///
+    /// The future is awaited when
+    /// the first row is requested.
+    #[must_use]
+    pub fn lazy(
+        results: impl Future<Output = ScyllaPyResult<RowIterator>> + Send + 'static,
+    ) -> Self {
         Self {
-            inner: Arc::new(Mutex::new(results)),
+            inner: Arc::new(Mutex::new(RowIteratorState::Pending(Box::pin(results)))),
             mapper: None,
             scalars: false,
+            lazy_collections: false,
+            profiler: None,
         }
     }
+
+    #[must_use]
+    pub fn with_mapper(mut self, mapper: Option<Py<PyAny>>) -> Self {
+        self.mapper = mapper;
+        self
+    }
+
+    /// Decode collections and UDTs only when they are accessed.
+    #[must_use]
+    pub fn with_lazy_collections(mut self, lazy_collections: bool) -> Self {
+        self.lazy_collections = lazy_collections;
+        self
+    }
+
+    /// Record timings of rows conversion.
+    #[must_use]
+    pub fn with_profiler(mut self, profiler: Option<Arc<Profiler>>) -> Self {
+        self.profiler = profiler;
+        self
+    }
 }
 
 #[pymethods]
@@ -251,46 +791,59 @@ impl ScyllaPyIterableQueryResult {
         let streamer = self.inner.clone();
         let map_function = self.mapper.clone();
         let scalars = self.scalars;
+        let lazy_collections = self.lazy_collections;
+        let profiler = self.profiler.clone();
         // Here we create our future that actually yields row.
         let future = scyllapy_future(py, async move {
-            let mut row_iterator = streamer.lock().await;
-            let row = row_iterator.next().await;
-            let col_spec = row_iterator.get_column_specs();
-            match row {
-                Some(val) => {
-                    let row_val = val?;
-                    // If user have chosen to iterate over scalars, we
-                    // just return first column of a row.
-                    if scalars {
-                        let spec = col_spec.first().ok_or(ScyllaPyError::NoColumns)?;
-                        let a = row_val.columns.first().ok_or(ScyllaPyError::NoColumns)?;
-                        return Python::with_gil(|gil| {
-                            Ok(cql_to_py(gil, &spec.name, &spec.typ, a.as_ref())?.into_py(gil))
-                        });
+            let mut state = streamer.lock().await;
+            // If query wasn't started yet, we start it here.
+            // In case of error, iterator is marked as failed,
+            // so it won't be started twice.
+            *state = match std::mem::replace(&mut *state, RowIteratorState::Failed) {
+                RowIteratorState::Pending(pending) => RowIteratorState::prefetch(pending.await?),
+                other => other,
+            };
+            let RowIteratorState::Ready {
+                specs,
+                columns,
+                rows: fetched,
+                decoded,
+            } = &mut *state
+            else {
+                return Err(PyStopAsyncIteration::new_err("No more rows").into());
+            };
+            if decoded.is_empty() {
+                // Rows, which are already fetched, are taken along
+                // with the next one, so they are converted
+                // with a single acquisition of the GIL.
+                let Some(row) = fetched.recv().await else {
+                    return Err(PyStopAsyncIteration::new_err("No more rows").into());
+                };
+                let mut rows = vec![row];
+                while rows.len() < DECODE_CHUNK_SIZE {
+                    match fetched.try_recv() {
+                        Ok(row) => rows.push(row),
+                        Err(_) => break,
                     }
-                    // Here we acquire GIL and map row to python object.
-                    Python::with_gil(move |gil| -> ScyllaPyResult<Py<PyAny>> {
-                        let row_dict = PyDict::new(gil);
-                        for (col_index, column) in row_val.columns.iter().enumerate() {
-                            row_dict.set_item(
-                                col_spec[col_index].name.as_str(),
-                                cql_to_py(
-                                    gil,
-                                    &col_spec[col_index].name,
-                                    &col_spec[col_index].typ,
-                                    column.as_ref(),
-                                )?,
-                            )?;
-                        }
-                        if let Some(mapper) = map_function {
-                            Ok(mapper.call(gil, (), Some(row_dict))?.into_py(gil))
-                        } else {
-                            Ok(row_dict.into())
-                        }
-                    })
                 }
-                None => Err(PyStopAsyncIteration::new_err("No more rows").into()),
+                let started = Instant::now();
+                Python::with_gil(|gil| -> ScyllaPyResult<()> {
+                    let columns = match columns {
+                        Some(columns) => columns,
+                        None => columns.insert(ResultColumns::new(gil, specs, lazy_collections)?),
+                    };
+                    decoded.extend(rows.into_iter().map(|row| {
+                        columns.row_to_py(gil, specs, &row?, scalars, map_function.as_ref())
+                    }));
+                    Ok(())
+                })?;
+                if let Some(profiler) = &profiler {
+                    profiler.record(ProfileStage::Conversion, started.elapsed());
+                }
             }
+            decoded
+                .pop_front()
+                .unwrap_or_else(|| Err(PyStopAsyncIteration::new_err("No more rows").into()))
         });
         Ok(Some(future?.into()))
     }
@@ -0,0 +1,797 @@
+use std::collections::HashMap;
+
+use pyo3::{
+    types::{IntoPyDict, PyDict, PyList},
+    PyResult, Python,
+};
+use scylla::{
+    transport::{
+        topology::{
+            CollectionType, Column, ColumnKind, CqlType, Keyspace, MaterializedView, Strategy,
+            Table, UserDefinedType,
+        },
+        ClusterData,
+    },
+    Session,
+};
+
+use crate::{
+    bulk::value_to_text,
+    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+};
+
+/// Options of tables and views, which can be set in `WITH`.
+///
+/// Other columns of `system_schema.tables` aren't options.
+/// Scylla options, like `cdc` and `tombstone_gc`, are stored
+/// in extensions, so they aren't described.
+const TABLE_OPTIONS: [&str; 14] = [
+    "bloom_filter_fp_chance",
+    "caching",
+    "comment",
+    "compaction",
+    "compression",
+    "crc_check_chance",
+    "dclocal_read_repair_chance",
+    "default_time_to_live",
+    "gc_grace_seconds",
+    "max_index_interval",
+    "memtable_flush_period_in_ms",
+    "min_index_interval",
+    "read_repair_chance",
+    "speculative_retry",
+];
+
+/// CQL name of the type, like `frozen<map<text, int>>`.
+#[must_use]
+pub fn cql_type_name(type_: &CqlType) -> String {
+    let (frozen, name) = match type_ {
+        CqlType::Native(native) => (false, format!("{native:?}").to_lowercase()),
+        CqlType::Collection { frozen, type_ } => (
+            *frozen,
+            match type_ {
+                CollectionType::List(item) => format!("list<{}>", cql_type_name(item)),
+                CollectionType::Set(item) => format!("set<{}>", cql_type_name(item)),
+                CollectionType::Map(key, value) => {
+                    format!("map<{}, {}>", cql_type_name(key), cql_type_name(value))
+                }
+            },
+        ),
+        CqlType::Tuple(items) => (
+            false,
+            format!(
+                "tuple<{}>",
+                items
+                    .iter()
+                    .map(cql_type_name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        CqlType::UserDefinedType { frozen, definition } => (
+            *frozen,
+            match definition {
+                Ok(udt) => cql_identifier(&udt.name),
+                Err(missing) => cql_identifier(&missing.name),
+            },
+        ),
+    };
+    if frozen {
+        format!("frozen<{name}>")
+    } else {
+        name
+    }
+}
+
+/// Identifier, which is quoted only if it's needed.
+#[must_use]
+pub fn cql_identifier(name: &str) -> String {
+    let mut chars = name.chars();
+    let plain = chars.next().is_some_and(|first| first.is_ascii_lowercase())
+        && chars.all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Name of the replication strategy and its options.
+#[must_use]
+pub fn replication(strategy: &Strategy) -> (String, Vec<(String, String)>) {
+    let mut options = match strategy {
+        Strategy::SimpleStrategy { replication_factor } => {
+            vec![(
+                "replication_factor".to_string(),
+                replication_factor.to_string(),
+            )]
+        }
+        Strategy::NetworkTopologyStrategy {
+            datacenter_repfactors,
+        } => datacenter_repfactors
+            .iter()
+            .map(|(datacenter, factor)| (datacenter.clone(), factor.to_string()))
+            .collect(),
+        Strategy::LocalStrategy => Vec::new(),
+        Strategy::Other { data, .. } => data
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    };
+    options.sort();
+    let class = match strategy {
+        Strategy::SimpleStrategy { .. } => "SimpleStrategy".to_string(),
+        Strategy::NetworkTopologyStrategy { .. } => "NetworkTopologyStrategy".to_string(),
+        Strategy::LocalStrategy => "LocalStrategy".to_string(),
+        Strategy::Other { name, .. } => name.clone(),
+    };
+    (class, options)
+}
+
+/// Columns of the table in the order of CQL `DESCRIBE`.
+///
+/// Partition key goes first, then clustering key,
+/// then other columns sorted by name.
+#[must_use]
+pub fn ordered_columns(table: &Table) -> Vec<(&str, &Column)> {
+    let mut other = table
+        .columns
+        .iter()
+        .filter(|(_, column)| {
+            !matches!(
+                column.kind,
+                ColumnKind::PartitionKey | ColumnKind::Clustering
+            )
+        })
+        .map(|(name, column)| (name.as_str(), column))
+        .collect::<Vec<_>>();
+    other.sort_by_key(|(name, _)| *name);
+    table
+        .partition_key
+        .iter()
+        .chain(&table.clustering_key)
+        .filter_map(|name| {
+            table
+                .columns
+                .get(name)
+                .map(|column| (name.as_str(), column))
+        })
+        .chain(other)
+        .collect()
+}
+
+fn sorted_names<T>(items: &HashMap<String, T>) -> Vec<&str> {
+    let mut names = items.keys().map(String::as_str).collect::<Vec<_>>();
+    names.sort_unstable();
+    names
+}
+
+/// Metadata of the keyspace.
+///
+/// # Errors
+///
+/// Returns an error if keyspace doesn't exist.
+pub fn find_keyspace<'a>(
+    cluster_data: &'a ClusterData,
+    keyspace: &str,
+) -> ScyllaPyResult<&'a Keyspace> {
+    cluster_data
+        .get_keyspace_info()
+        .get(keyspace)
+        .ok_or_else(|| ScyllaPyError::SessionError(format!("Unknown keyspace {keyspace}.")))
+}
+
+/// Metadata of the table.
+///
+/// # Errors
+///
+/// Returns an error if keyspace or table doesn't exist.
+pub fn find_table<'a>(
+    cluster_data: &'a ClusterData,
+    keyspace: &str,
+    table: &str,
+) -> ScyllaPyResult<&'a Table> {
+    find_keyspace(cluster_data, keyspace)?
+        .tables
+        .get(table)
+        .ok_or_else(|| ScyllaPyError::SessionError(format!("Unknown table {keyspace}.{table}.")))
+}
+
+/// Keyspace as a dict.
+///
+/// # Errors
+///
+/// If dict cannot be created.
+pub fn keyspace_to_dict<'a>(
+    py: Python<'a>,
+    name: &str,
+    keyspace: &Keyspace,
+) -> PyResult<&'a PyDict> {
+    let (class, options) = replication(&keyspace.strategy);
+    let info = PyDict::new(py);
+    info.set_item("name", name)?;
+    info.set_item("strategy", class)?;
+    info.set_item("replication", options.into_py_dict(py))?;
+    info.set_item("tables", sorted_names(&keyspace.tables))?;
+    info.set_item("views", sorted_names(&keyspace.views))?;
+    info.set_item("user_types", sorted_names(&keyspace.user_defined_types))?;
+    Ok(info)
+}
+
+/// Columns of the table as a list of dicts.
+///
+/// # Errors
+///
+/// If dicts cannot be created.
+pub fn columns_to_list<'a>(py: Python<'a>, table: &Table) -> PyResult<&'a PyList> {
+    let columns = PyList::empty(py);
+    for (name, column) in ordered_columns(table) {
+        let info = PyDict::new(py);
+        info.set_item("name", name)?;
+        info.set_item("type", cql_type_name(&column.type_))?;
+        info.set_item(
+            "kind",
+            match column.kind {
+                ColumnKind::PartitionKey => "partition_key",
+                ColumnKind::Clustering => "clustering",
+                ColumnKind::Static => "static",
+                ColumnKind::Regular => "regular",
+            },
+        )?;
+        columns.append(info)?;
+    }
+    Ok(columns)
+}
+
+/// Table as a dict.
+///
+/// # Errors
+///
+/// If dict cannot be created.
+pub fn table_to_dict<'a>(
+    py: Python<'a>,
+    keyspace: &str,
+    name: &str,
+    table: &Table,
+) -> PyResult<&'a PyDict> {
+    let info = PyDict::new(py);
+    info.set_item("keyspace", keyspace)?;
+    info.set_item("name", name)?;
+    info.set_item("partition_key", &table.partition_key)?;
+    info.set_item("clustering_key", &table.clustering_key)?;
+    info.set_item("partitioner", table.partitioner.as_deref())?;
+    info.set_item("columns", columns_to_list(py, table)?)?;
+    Ok(info)
+}
+
+/// User defined type as a dict.
+///
+/// # Errors
+///
+/// If dict cannot be created.
+pub fn user_type_to_dict<'a>(py: Python<'a>, udt: &UserDefinedType) -> PyResult<&'a PyDict> {
+    let fields = PyList::empty(py);
+    for (name, type_) in &udt.field_types {
+        let field = PyDict::new(py);
+        field.set_item("name", name)?;
+        field.set_item("type", cql_type_name(type_))?;
+        fields.append(field)?;
+    }
+    let info = PyDict::new(py);
+    info.set_item("keyspace", &udt.keyspace)?;
+    info.set_item("name", &udt.name)?;
+    info.set_item("fields", fields)?;
+    Ok(info)
+}
+
+/// Names of user defined types, which are used in the type.
+fn used_user_types<'a>(type_: &'a CqlType, names: &mut Vec<&'a str>) {
+    match type_ {
+        CqlType::Native(_) => {}
+        CqlType::Collection { type_, .. } => match type_ {
+            CollectionType::List(item) | CollectionType::Set(item) => {
+                used_user_types(item, names);
+            }
+            CollectionType::Map(key, value) => {
+                used_user_types(key, names);
+                used_user_types(value, names);
+            }
+        },
+        CqlType::Tuple(items) => {
+            for item in items {
+                used_user_types(item, names);
+            }
+        }
+        CqlType::UserDefinedType { definition, .. } => match definition {
+            Ok(udt) => names.push(&udt.name),
+            Err(missing) => names.push(&missing.name),
+        },
+    }
+}
+
+/// User defined types of the keyspace ordered by name,
+/// but each type goes after types, which it uses.
+fn ordered_user_types(keyspace: &Keyspace) -> Vec<&UserDefinedType> {
+    fn visit<'a>(keyspace: &'a Keyspace, name: &str, ordered: &mut Vec<&'a UserDefinedType>) {
+        let Some(udt) = keyspace.user_defined_types.get(name) else {
+            return;
+        };
+        if ordered.iter().any(|visited| visited.name == udt.name) {
+            return;
+        }
+        let mut used = Vec::new();
+        for (_, type_) in &udt.field_types {
+            used_user_types(type_, &mut used);
+        }
+        for used_name in used {
+            visit(keyspace, used_name, ordered);
+        }
+        ordered.push(udt);
+    }
+
+    let mut ordered = Vec::new();
+    for name in sorted_names(&keyspace.user_defined_types) {
+        visit(keyspace, name, &mut ordered);
+    }
+    ordered
+}
+
+/// `CREATE TYPE` statement of the user defined type.
+#[must_use]
+pub fn create_type_cql(udt: &UserDefinedType) -> String {
+    let fields = udt
+        .field_types
+        .iter()
+        .map(|(name, type_)| format!("    {} {}", cql_identifier(name), cql_type_name(type_)))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    format!(
+        "CREATE TYPE {}.{} (\n{fields}\n);",
+        cql_identifier(&udt.keyspace),
+        cql_identifier(&udt.name)
+    )
+}
+
+/// Clustering orders of columns of the table or the view.
+async fn clustering_orders(
+    session: &Session,
+    keyspace: &str,
+    name: &str,
+) -> ScyllaPyResult<HashMap<String, String>> {
+    session
+        .query(
+            "SELECT column_name, clustering_order FROM system_schema.columns \
+            WHERE keyspace_name = ? AND table_name = ?",
+            (keyspace, name),
+        )
+        .await?
+        .rows_typed::<(String, String)>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))
+}
+
+/// Options of the table or the view, like `comment = 'users'`.
+///
+/// Options are read from `system_schema.tables`
+/// or `system_schema.views` and sorted by name.
+async fn schema_options(
+    session: &Session,
+    query: &str,
+    keyspace: &str,
+    name: &str,
+) -> ScyllaPyResult<Vec<String>> {
+    let res = session.query(query, (keyspace, name)).await?;
+    let col_specs = res.col_specs.clone();
+    let mut options = res
+        .first_row()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?
+        .columns
+        .into_iter()
+        .zip(col_specs)
+        .filter(|(_, spec)| TABLE_OPTIONS.contains(&spec.name.as_str()))
+        .filter_map(|(value, spec)| {
+            value.map(|value| format!("{} = {}", spec.name, value_to_text(&value, true)))
+        })
+        .collect::<Vec<_>>();
+    options.sort();
+    Ok(options)
+}
+
+/// Definition of the primary key, like `PRIMARY KEY ((id, bucket), at)`.
+fn primary_key_cql(table: &Table) -> String {
+    let partition_key = table
+        .partition_key
+        .iter()
+        .map(|column| cql_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let partition_key = if table.partition_key.len() > 1 {
+        format!("({partition_key})")
+    } else {
+        partition_key
+    };
+    let primary_key = std::iter::once(partition_key)
+        .chain(
+            table
+                .clustering_key
+                .iter()
+                .map(|column| cql_identifier(column)),
+        )
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("PRIMARY KEY ({primary_key})")
+}
+
+/// `WITH` clause with clustering order and options.
+fn with_cql(table: &Table, orders: &HashMap<String, String>, options: Vec<String>) -> String {
+    let mut clauses = Vec::new();
+    if !table.clustering_key.is_empty() {
+        let ordering = table
+            .clustering_key
+            .iter()
+            .map(|column| {
+                let order = orders.get(column).map_or("ASC", |order| {
+                    if order.eq_ignore_ascii_case("desc") {
+                        "DESC"
+                    } else {
+                        "ASC"
+                    }
+                });
+                format!("{} {order}", cql_identifier(column))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        clauses.push(format!("CLUSTERING ORDER BY ({ordering})"));
+    }
+    clauses.extend(options);
+    if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WITH {}", clauses.join("\n    AND "))
+    }
+}
+
+/// `CREATE TABLE` statement of the table.
+///
+/// Clustering order isn't part of the driver's metadata,
+/// as well as table options, so they're read from `system_schema`.
+///
+/// # Errors
+///
+/// May return an error if schema cannot be read.
+pub async fn create_table_cql(
+    session: &Session,
+    keyspace: &str,
+    name: &str,
+    table: &Table,
+) -> ScyllaPyResult<String> {
+    let orders = clustering_orders(session, keyspace, name).await?;
+    let options = schema_options(
+        session,
+        "SELECT * FROM system_schema.tables WHERE keyspace_name = ? AND table_name = ?",
+        keyspace,
+        name,
+    )
+    .await?;
+    let mut lines = ordered_columns(table)
+        .into_iter()
+        .map(|(column_name, column)| {
+            let static_ = if matches!(column.kind, ColumnKind::Static) {
+                " static"
+            } else {
+                ""
+            };
+            format!(
+                "    {} {}{static_}",
+                cql_identifier(column_name),
+                cql_type_name(&column.type_)
+            )
+        })
+        .collect::<Vec<_>>();
+    lines.push(format!("    {}", primary_key_cql(table)));
+    Ok(format!(
+        "CREATE TABLE {}.{} (\n{}\n){};",
+        cql_identifier(keyspace),
+        cql_identifier(name),
+        lines.join(",\n"),
+        with_cql(table, &orders, options)
+    ))
+}
+
+/// `CREATE MATERIALIZED VIEW` statement of the view.
+///
+/// Filter of the view and whether it includes
+/// all columns are read from `system_schema.views`.
+///
+/// # Errors
+///
+/// May return an error if schema cannot be read.
+pub async fn create_view_cql(
+    session: &Session,
+    keyspace: &str,
+    name: &str,
+    view: &MaterializedView,
+) -> ScyllaPyResult<String> {
+    let (include_all_columns, where_clause) = session
+        .query(
+            "SELECT include_all_columns, where_clause FROM system_schema.views \
+            WHERE keyspace_name = ? AND view_name = ?",
+            (keyspace, name),
+        )
+        .await?
+        .single_row_typed::<(Option<bool>, Option<String>)>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+    let orders = clustering_orders(session, keyspace, name).await?;
+    let mut options = schema_options(
+        session,
+        "SELECT * FROM system_schema.views WHERE keyspace_name = ? AND view_name = ?",
+        keyspace,
+        name,
+    )
+    .await?;
+    // TTL of views is always the TTL of their base table.
+    options.retain(|option| !option.starts_with("default_time_to_live "));
+    let table = &view.view_metadata;
+    let columns = if include_all_columns.unwrap_or_default() {
+        "*".to_string()
+    } else {
+        ordered_columns(table)
+            .into_iter()
+            .map(|(column_name, _)| cql_identifier(column_name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let where_clause = where_clause
+        .filter(|where_clause| !where_clause.is_empty())
+        .map(|where_clause| format!("\n    WHERE {where_clause}"))
+        .unwrap_or_default();
+    let keyspace = cql_identifier(keyspace);
+    Ok(format!(
+        "CREATE MATERIALIZED VIEW {keyspace}.{} AS\n    \
+        SELECT {columns} FROM {keyspace}.{}{where_clause}\n    {}{};",
+        cql_identifier(name),
+        cql_identifier(&view.base_table_name),
+        primary_key_cql(table),
+        with_cql(table, &orders, options)
+    ))
+}
+
+/// Secondary index of the table.
+#[derive(Debug)]
+pub struct Index {
+    table: String,
+    name: String,
+    kind: String,
+    options: HashMap<String, String>,
+}
+
+impl Index {
+    /// Name of the materialized view, which Scylla creates for the index.
+    #[must_use]
+    pub fn view_name(&self) -> String {
+        format!("{}_index", self.name)
+    }
+
+    /// Indexed columns of the index.
+    ///
+    /// Targets of local indexes are stored as JSON,
+    /// like `{"pk":["id"],"ck":["name"]}`, and are turned
+    /// into `((id), name)`. Other targets are already CQL.
+    fn target_cql(&self) -> String {
+        let target = self.options.get("target").map_or("", String::as_str);
+        let Some(local) = target
+            .strip_prefix('{')
+            .and_then(|target| target.strip_suffix('}'))
+        else {
+            return target.to_string();
+        };
+        let names = |key: &str| -> Vec<String> {
+            local
+                .split_once(&format!("\"{key}\":["))
+                .and_then(|(_, rest)| rest.split_once(']'))
+                .map(|(names, _)| {
+                    names
+                        .split(',')
+                        .map(|name| cql_identifier(name.trim().trim_matches('"')))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let partition_key = format!("({})", names("pk").join(", "));
+        std::iter::once(partition_key)
+            .chain(names("ck"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// `CREATE INDEX` statement of the index.
+    #[must_use]
+    pub fn create_cql(&self, keyspace: &str) -> String {
+        let name = cql_identifier(&self.name);
+        let table = format!(
+            "{}.{}",
+            cql_identifier(keyspace),
+            cql_identifier(&self.table)
+        );
+        let target = self.target_cql();
+        if self.kind != "CUSTOM" {
+            return format!("CREATE INDEX {name} ON {table} ({target});");
+        }
+        let class_name = self.options.get("class_name").map_or("", String::as_str);
+        let mut options = self
+            .options
+            .iter()
+            .filter(|(key, _)| !matches!(key.as_str(), "target" | "class_name"))
+            .map(|(key, value)| {
+                format!(
+                    "'{}': '{}'",
+                    key.replace('\'', "''"),
+                    value.replace('\'', "''")
+                )
+            })
+            .collect::<Vec<_>>();
+        options.sort();
+        let options = if options.is_empty() {
+            String::new()
+        } else {
+            format!(" WITH OPTIONS = {{{}}}", options.join(", "))
+        };
+        format!(
+            "CREATE CUSTOM INDEX {name} ON {table} ({target}) USING '{}'{options};",
+            class_name.replace('\'', "''")
+        )
+    }
+}
+
+/// Secondary indexes of the keyspace ordered by table and name.
+///
+/// Indexes aren't part of the driver's metadata,
+/// so they're read from `system_schema.indexes`.
+///
+/// # Errors
+///
+/// May return an error if schema cannot be read.
+pub async fn keyspace_indexes(session: &Session, keyspace: &str) -> ScyllaPyResult<Vec<Index>> {
+    let mut indexes = session
+        .query(
+            "SELECT table_name, index_name, kind, options FROM system_schema.indexes \
+            WHERE keyspace_name = ?",
+            (keyspace,),
+        )
+        .await?
+        .rows_typed::<(String, String, String, Option<HashMap<String, String>>)>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?
+        .map(|row| {
+            row.map(|(table, name, kind, options)| Index {
+                table,
+                name,
+                kind,
+                options: options.unwrap_or_default(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+    indexes.sort_by(|left, right| (&left.table, &left.name).cmp(&(&right.table, &right.name)));
+    Ok(indexes)
+}
+
+/// `CREATE KEYSPACE` statement of the keyspace,
+/// followed by statements of its user defined types and tables.
+///
+/// Types are ordered, so each type is created
+/// after types which it uses. Each table is followed
+/// by its secondary indexes and materialized views.
+/// Views, which Scylla creates for indexes, are skipped.
+///
+/// # Errors
+///
+/// May return an error if schema cannot be read.
+pub async fn create_keyspace_cql(
+    session: &Session,
+    name: &str,
+    keyspace: &Keyspace,
+) -> ScyllaPyResult<String> {
+    let (replication, durable_writes) = session
+        .query(
+            "SELECT replication, durable_writes FROM system_schema.keyspaces \
+            WHERE keyspace_name = ?",
+            (name,),
+        )
+        .await?
+        .single_row_typed::<(HashMap<String, String>, bool)>()
+        .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+    let mut replication = replication.into_iter().collect::<Vec<_>>();
+    // Class goes first, as in cqlsh.
+    replication
+        .sort_by(|(left, _), (right, _)| (left != "class", left).cmp(&(right != "class", right)));
+    let replication = replication
+        .iter()
+        .map(|(key, value)| {
+            format!(
+                "'{}': '{}'",
+                key.replace('\'', "''"),
+                value.replace('\'', "''")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut statements = vec![format!(
+        "CREATE KEYSPACE {} WITH replication = {{{replication}}} \
+        AND durable_writes = {durable_writes};",
+        cql_identifier(name)
+    )];
+    statements.extend(
+        ordered_user_types(keyspace)
+            .into_iter()
+            .map(create_type_cql),
+    );
+    let indexes = keyspace_indexes(session, name).await?;
+    let index_views = indexes.iter().map(Index::view_name).collect::<Vec<_>>();
+    for table_name in sorted_names(&keyspace.tables) {
+        if let Some(table) = keyspace.tables.get(table_name) {
+            statements.push(create_table_cql(session, name, table_name, table).await?);
+        }
+        statements.extend(
+            indexes
+                .iter()
+                .filter(|index| index.table == table_name)
+                .map(|index| index.create_cql(name)),
+        );
+        for view_name in sorted_names(&keyspace.views) {
+            let Some(view) = keyspace.views.get(view_name) else {
+                continue;
+            };
+            if view.base_table_name == table_name
+                && !index_views.iter().any(|index_view| index_view == view_name)
+            {
+                statements.push(create_view_cql(session, name, view_name, view).await?);
+            }
+        }
+    }
+    Ok(statements.join("\n\n"))
+}
+
+/// Keyspace and name of the table, like `ks.users` or `"MyKs"."Users"`.
+///
+/// Names are parsed as in CQL: quoted names are case-sensitive,
+/// unquoted ones are lowercased.
+///
+/// # Errors
+///
+/// Returns an error if the name isn't a valid CQL name of a table.
+pub fn parse_table_name(name: &str) -> ScyllaPyResult<(Option<String>, String)> {
+    let invalid = || ScyllaPyError::SessionError(format!("Invalid table name {name}."));
+    let mut chars = name.trim().chars().peekable();
+    let mut parts = Vec::new();
+    loop {
+        let mut part = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') if chars.next_if_eq(&'"').is_some() => part.push('"'),
+                    Some('"') => break,
+                    Some(char) => part.push(char),
+                    None => return Err(invalid()),
+                }
+            }
+        } else {
+            while let Some(char) =
+                chars.next_if(|char| char.is_ascii_alphanumeric() || *char == '_')
+            {
+                part.push(char.to_ascii_lowercase());
+            }
+        }
+        if part.is_empty() {
+            return Err(invalid());
+        }
+        parts.push(part);
+        match chars.next() {
+            None => break,
+            Some('.') if parts.len() == 1 => {}
+            Some(_) => return Err(invalid()),
+        }
+    }
+    let table = parts.pop().ok_or_else(invalid)?;
+    Ok((parts.pop(), table))
+}
@@ -1,21 +1,125 @@
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 use crate::{
-    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    auth::CredentialsCallback,
+    batch_writer::{BatchWriterConfig, ScyllaPyBatchWriter},
+    batches::{
+        bound_values_error, is_timeout, qualify_batch, ScyllaPyBatchType, ScyllaPyInlineBatch,
+    },
+    bulk::{
+        copy_from, copy_table, copy_to, FileFormat, DEFAULT_COPY_CONCURRENCY,
+        DEFAULT_SCAN_CONCURRENCY,
+    },
+    cache::ResultCache,
+    coalescing::{coalesce, ReadCoalescer, ReadKey},
+    consistencies::ScyllaPySerialConsistency,
+    exceptions::{
+        py_err::timeout_error_type,
+        rust_err::{QueryErrorContext, ScyllaPyError, ScyllaPyResult},
+    },
     execution_profiles::ScyllaPyExecutionProfile,
-    inputs::{BatchInput, ExecuteInput, PrepareInput},
-    prepared_queries::ScyllaPyPreparedQuery,
-    query_results::{ScyllaPyIterableQueryResult, ScyllaPyQueryResult, ScyllaPyQueryReturns},
-    utils::{parse_python_query_params, scyllapy_future},
+    inputs::{BatchInput, BatchQueryInput, ExecuteInput, PrepareInput},
+    instrumentation::{
+        batch_statement, trace_timeline, LatencyHistograms, NodeStatistics, ObservedRequest,
+        ProfileStage, Profiler, QueryLogger, RequestListeners, RequestObservers, RequestSpan,
+        ScyllaPyLastQuery, StatementKind, StatementStatistics,
+    },
+    load_balancing::{SingleNodePolicy, TokenPolicy},
+    lwt::{
+        bind_cas_values, cas_query, cas_read_query, dict_columns, is_rejected, lwt_outcome,
+        lwt_retry_policy, timed_out_outcome, TimedOutUpdate,
+    },
+    migrations::{ScyllaPyMigrations, DEFAULT_MIGRATIONS_TABLE},
+    prepared_queries::{execute_prepared, execute_prepared_iter, ScyllaPyPreparedQuery},
+    queries::ScyllaPyRequestParams,
+    query_builder::utils::{prepared_keyspace_error, qualify_statement},
+    query_results::{
+        ScyllaPyBatchResult, ScyllaPyIterableQueryResult, ScyllaPyQueryResult, ScyllaPyQueryReturns,
+    },
+    schema::{
+        columns_to_list, create_keyspace_cql, create_table_cql, find_keyspace, find_table,
+        keyspace_to_dict, parse_table_name, table_to_dict, user_type_to_dict,
+    },
+    shadow::{MirrorSettings, ShadowTarget, DEFAULT_SHADOW_IN_FLIGHT},
+    startup_retry::ScyllaPyStartupRetryPolicy,
+    utils::{
+        cql_to_py, emit_server_warnings, parse_python_query_params, quote_identifier, quote_table,
+        scyllapy_future, AttemptsListener,
+    },
+    writer::{ScyllaPyWriter, WriterConfig, WriterContext},
 };
+use futures::StreamExt;
 use openssl::{
     pkey::PKey,
     rsa::Rsa,
     ssl::{SslContextBuilder, SslMethod, SslVerifyMode},
     x509::X509,
 };
-use pyo3::{pyclass, pymethods, PyAny, Python};
-use scylla::{frame::value::ValueList, prepared_statement::PreparedStatement, query::Query};
+use pyo3::{
+    exceptions::PyValueError,
+    pyclass, pymethods,
+    types::{PyDict, PyList},
+    FromPyObject, IntoPy, Py, PyAny, PyErr, PyObject, PyResult, Python,
+};
+use scylla::{
+    batch::{Batch, BatchStatement},
+    execution_profile::ExecutionProfileHandle,
+    frame::value::{LegacySerializedValues, ValueList},
+    prepared_statement::PreparedStatement,
+    query::Query,
+    routing::Token,
+    statement::{Consistency, SerialConsistency},
+    transport::{errors::QueryError, Compression},
+    QueryResult,
+};
+
+/// How many batches are executed concurrently by default.
+const DEFAULT_BATCHES_CONCURRENCY: usize = 10;
+/// How many statements are executed concurrently by `execute_many` by default.
+const DEFAULT_EXECUTE_MANY_CONCURRENCY: usize = 64;
+/// How many warmup requests are sent to each shard of a node.
+const WARMUP_REQUESTS_PER_SHARD: usize = 2;
+/// Cheap query, which is used to warm up connections.
+///
+/// It's prepared, so requests are sent to shards,
+/// which own tokens of keys.
+const WARMUP_QUERY: &str = "SELECT key FROM system.local WHERE key = ?";
+/// How many keys are tried to find keys for every shard.
+const WARMUP_KEYS: usize = 4096;
+/// Query, which returns version of the node.
+const VERSION_QUERY: &str = "SELECT release_version FROM system.local";
+/// How often the cluster is checked, while waiting for it to become healthy.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Name, argument names and types, return type, language,
+/// body and whether function is called on null input.
+type FunctionRow = (
+    String,
+    Option<Vec<String>>,
+    Option<Vec<String>>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<bool>,
+);
+/// Name, argument types, state function, state type,
+/// final function, initial condition and return type.
+type AggregateRow = (
+    String,
+    Option<Vec<String>>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+);
 
 /// SSL verification mode.
 #[pyclass(name = "SSLVerifyMode")]
@@ -40,12 +144,51 @@ impl From<ScyllaPySSLVerifyMode> for SslVerifyMode {
     }
 }
 
+/// Compression of CQL frames.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScyllaPyCompression {
+    Lz4,
+    Snappy,
+}
+
+impl ScyllaPyCompression {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lz4 => "lz4",
+            Self::Snappy => "snappy",
+        }
+    }
+}
+
+impl<'a> FromPyObject<'a> for ScyllaPyCompression {
+    fn extract(ob: &'a PyAny) -> PyResult<Self> {
+        match ob.extract::<&str>()? {
+            "lz4" => Ok(Self::Lz4),
+            "snappy" => Ok(Self::Snappy),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown compression: {other}. Supported compressions are lz4 and snappy."
+            ))),
+        }
+    }
+}
+
+impl From<ScyllaPyCompression> for Compression {
+    fn from(value: ScyllaPyCompression) -> Self {
+        match value {
+            ScyllaPyCompression::Lz4 => Self::Lz4,
+            ScyllaPyCompression::Snappy => Self::Snappy,
+        }
+    }
+}
+
 #[pyclass(frozen, weakref)]
 #[derive(Clone)]
 pub struct Scylla {
     contact_points: Vec<String>,
     username: Option<String>,
     password: Option<String>,
+    credentials: Option<Py<PyAny>>,
     keyspace: Option<String>,
     ssl_cert: Option<String>,
     ssl_key: Option<String>,
@@ -60,11 +203,439 @@ pub struct Scylla {
     keepalive_timeout: Option<u64>,
     tcp_keepalive_interval: Option<u64>,
     tcp_nodelay: Option<bool>,
+    compression: Option<ScyllaPyCompression>,
+    startup_retry_policy: Option<ScyllaPyStartupRetryPolicy>,
     default_execution_profile: Option<ScyllaPyExecutionProfile>,
+    read_profile: Option<ScyllaPyExecutionProfile>,
+    write_profile: Option<ScyllaPyExecutionProfile>,
+    query_context_in_errors: bool,
+    emit_server_warnings: bool,
+    tracer: Option<Py<PyAny>>,
+    latency_histograms: Option<Arc<LatencyHistograms>>,
+    listeners: Arc<RequestListeners>,
+    node_stats: Arc<NodeStatistics>,
+    statement_stats: Option<Arc<StatementStatistics>>,
+    query_logger: Option<Arc<QueryLogger>>,
+    track_last_query: bool,
+    lazy_collections: bool,
+    profiler: Option<Arc<Profiler>>,
+    read_coalescer: Option<Arc<ReadCoalescer>>,
+    result_cache: Option<Arc<ResultCache>>,
+    named_statements: Arc<RwLock<HashMap<String, ScyllaPyPreparedQuery>>>,
+    helper_statements: Arc<HelperStatements>,
+    shadow: Arc<RwLock<Option<ShadowTarget>>>,
     scylla_session: Arc<tokio::sync::RwLock<Option<scylla::Session>>>,
 }
 
+/// Profile that routes the statement to replicas of the token.
+///
+/// Other settings are taken from the profile of the statement,
+/// or from the default profile of the session.
+fn token_profile(
+    base: Option<&ExecutionProfileHandle>,
+    default: Option<&ScyllaPyExecutionProfile>,
+    token: i64,
+    keyspace: Option<String>,
+) -> ExecutionProfileHandle {
+    base.map(ExecutionProfileHandle::pointee_to_builder)
+        .or_else(|| default.map(ScyllaPyExecutionProfile::to_builder))
+        .unwrap_or_else(scylla::ExecutionProfile::builder)
+        .load_balancing_policy(Arc::new(TokenPolicy::new(Token { value: token }, keyspace)))
+        .build()
+        .into_handle()
+}
+
+/// Keyspace for schema lookups, keyspace of the session is used by default.
+fn schema_keyspace(session: &scylla::Session, keyspace: Option<String>) -> ScyllaPyResult<String> {
+    keyspace
+        .or_else(|| session.get_keyspace().map(|ks| (*ks).clone()))
+        .ok_or(ScyllaPyError::SessionError(
+            "Keyspace is not specified.".into(),
+        ))
+}
+
+/// Text of the statement and summary of its parameters.
+///
+/// Values of parameters are never included.
+/// For prepared statements names and types of
+/// parameters are used, otherwise only their number.
+fn query_context(
+    query: Option<&Query>,
+    prepared: Option<&PreparedStatement>,
+    values: &LegacySerializedValues,
+) -> (String, String) {
+    match (query, prepared) {
+        (_, Some(prepared)) => {
+            let params = prepared
+                .get_prepared_metadata()
+                .col_specs
+                .iter()
+                .map(|spec| format!("{}: {:?}", spec.name, spec.typ))
+                .collect::<Vec<_>>()
+                .join(", ");
+            (prepared.get_statement().to_string(), format!("[{params}]"))
+        }
+        (Some(query), None) => (query.contents.clone(), format!("{} values", values.len())),
+        (None, None) => (String::new(), String::new()),
+    }
+}
+
+/// Text of the batch and summary of parameters of its statements.
+fn batch_context(batch: &Batch, values: &[LegacySerializedValues]) -> (String, String) {
+    let params = batch
+        .statements
+        .iter()
+        .zip(values)
+        .map(|(statement, values)| match statement {
+            BatchStatement::Query(query) => query_context(Some(query), None, values).1,
+            BatchStatement::PreparedStatement(prepared) => {
+                query_context(None, Some(prepared), values).1
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    (batch_statement(batch), params)
+}
+
+/// Prepared statements of helpers by keyspace of the session and text.
+type HelperStatements = RwLock<HashMap<(Option<Arc<String>>, String), PreparedStatement>>;
+
+/// Statements sent by helpers, like `compare_and_set`.
+///
+/// Each execution is reported to observers as a separate request,
+/// the same way as statements executed by the user.
+struct HelperRequests {
+    statements: Arc<HelperStatements>,
+    observers: RequestObservers,
+    node_stats: Arc<NodeStatistics>,
+    query_context_in_errors: bool,
+    emit_warnings: bool,
+    consistency: Consistency,
+}
+
+/// Request of a helper, which has been started.
+struct HelperRequest {
+    request: ObservedRequest,
+    context: QueryErrorContext,
+    started: Instant,
+}
+
+impl HelperRequests {
+    /// Prepare the statement or take it from the cache.
+    ///
+    /// Statements are prepared once for each keyspace of the session,
+    /// because their tables can be qualified by the keyspace.
+    async fn prepare(
+        &self,
+        session: &scylla::Session,
+        query: String,
+    ) -> ScyllaPyResult<PreparedStatement> {
+        let key = (session.get_keyspace(), query);
+        let cached = self
+            .statements
+            .read()
+            .ok()
+            .and_then(|statements| statements.get(&key).cloned());
+        if let Some(prepared) = cached {
+            return Ok(prepared);
+        }
+        let prepared = session.prepare(key.1.as_str()).await?;
+        if let Ok(mut statements) = self.statements.write() {
+            statements.insert(key, prepared.clone());
+        }
+        Ok(prepared)
+    }
+
+    /// Start the request of the prepared statement.
+    ///
+    /// Attempts listener is set to the statement,
+    /// so it should be executed after the request is started.
+    fn start(
+        &self,
+        prepared: &mut PreparedStatement,
+        values: &LegacySerializedValues,
+    ) -> HelperRequest {
+        let attempts = Arc::new(AttemptsListener::new(self.node_stats.clone()));
+        prepared.set_history_listener(attempts.clone());
+        let mut context = QueryErrorContext::default();
+        if self.query_context_in_errors {
+            let (query, params) = query_context(None, Some(prepared), values);
+            context.query = Some(query);
+            context.params = Some(params);
+        }
+        let statement = prepared.get_statement();
+        let consistency = prepared.get_consistency().unwrap_or(self.consistency);
+        HelperRequest {
+            request: self.observers.start(
+                StatementKind::of(statement),
+                statement,
+                consistency,
+                attempts,
+            ),
+            context,
+            started: Instant::now(),
+        }
+    }
+
+    /// Finish the request and emit warnings of the server.
+    fn finish(
+        &self,
+        request: HelperRequest,
+        res: Result<QueryResult, QueryError>,
+    ) -> ScyllaPyResult<QueryResult> {
+        let res = self.observers.finish(
+            request.request,
+            request.started.elapsed(),
+            request.context,
+            res.map_err(Into::into),
+        )?;
+        if self.emit_warnings {
+            emit_server_warnings(&res.warnings)?;
+        }
+        Ok(res)
+    }
+
+    /// Execute the prepared statement as a separate request.
+    async fn execute(
+        &self,
+        session: &scylla::Session,
+        prepared: &PreparedStatement,
+        values: LegacySerializedValues,
+    ) -> ScyllaPyResult<QueryResult> {
+        let mut prepared = prepared.clone();
+        let request = self.start(&mut prepared, &values);
+        let res = execute_prepared(session, &prepared, values, None).await;
+        self.finish(request, res)
+    }
+}
+
 impl Scylla {
+    /// Default profile for the statement.
+    ///
+    /// Reads and writes can have different default profiles.
+    fn statement_profile(&self, statement: &str) -> Option<&ScyllaPyExecutionProfile> {
+        match StatementKind::of(statement) {
+            StatementKind::Select => self.read_profile.as_ref(),
+            kind if kind.is_write() => self.write_profile.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Timestamp generated by the profile of the statement.
+    ///
+    /// If statement doesn't have a profile,
+    /// the default profile of the session is used.
+    fn generate_timestamp(&self, profile: Option<&ScyllaPyExecutionProfile>) -> Option<i64> {
+        profile
+            .or(self.default_execution_profile.as_ref())
+            .and_then(ScyllaPyExecutionProfile::generate_timestamp)
+    }
+
+    /// Consistency of the profile of the statement.
+    ///
+    /// If statement doesn't have a profile,
+    /// the default profile of the session is used.
+    fn profile_consistency(&self, profile: Option<&ScyllaPyExecutionProfile>) -> Consistency {
+        profile.or(self.default_execution_profile.as_ref()).map_or(
+            Consistency::LocalQuorum,
+            ScyllaPyExecutionProfile::consistency,
+        )
+    }
+
+    /// Set default profile to query without profile.
+    ///
+    /// Consistency, serial consistency and timeout of the profile
+    /// are set explicitly, so logs show the consistency of the request
+    /// and identical reads are recognized by their settings.
+    fn apply_query_profile(&self, query: &mut Query) {
+        if query.get_execution_profile_handle().is_none() {
+            let profile = self.statement_profile(&query.contents);
+            query.set_execution_profile_handle(profile.map(ExecutionProfileHandle::from));
+            if query.get_timestamp().is_none() {
+                query.set_timestamp(self.generate_timestamp(profile));
+            }
+            if query.get_consistency().is_none() {
+                query.set_consistency(self.profile_consistency(profile));
+            }
+            let profile = profile.or(self.default_execution_profile.as_ref());
+            if query.get_serial_consistency().is_none() {
+                query.set_serial_consistency(
+                    profile.and_then(ScyllaPyExecutionProfile::serial_consistency),
+                );
+            }
+            if query.get_request_timeout().is_none() {
+                query.set_request_timeout(
+                    profile.and_then(ScyllaPyExecutionProfile::request_timeout),
+                );
+            }
+        }
+    }
+
+    /// Set default profile to prepared statement without profile.
+    fn apply_prepared_profile(&self, prepared: &mut PreparedStatement) {
+        if prepared.get_execution_profile_handle().is_none() {
+            let profile = self.statement_profile(prepared.get_statement());
+            prepared.set_execution_profile_handle(profile.map(ExecutionProfileHandle::from));
+            if prepared.get_timestamp().is_none() {
+                prepared.set_timestamp(self.generate_timestamp(profile));
+            }
+            if prepared.get_consistency().is_none() {
+                prepared.set_consistency(self.profile_consistency(profile));
+            }
+            let profile = profile.or(self.default_execution_profile.as_ref());
+            if prepared.get_serial_consistency().is_none() {
+                prepared.set_serial_consistency(
+                    profile.and_then(ScyllaPyExecutionProfile::serial_consistency),
+                );
+            }
+            if prepared.get_request_timeout().is_none() {
+                prepared.set_request_timeout(
+                    profile.and_then(ScyllaPyExecutionProfile::request_timeout),
+                );
+            }
+        }
+    }
+
+    /// Whether information about requests is used.
+    ///
+    /// Text of the statement is copied to the info only
+    /// for listeners, statistics, query logs and the last query.
+    fn tracks_requests(&self) -> bool {
+        !self.listeners.is_empty()
+            || self.statement_stats.is_some()
+            || self.query_logger.is_some()
+            || self.track_last_query
+    }
+
+    /// Set default write profile to batch without profile.
+    ///
+    /// Batches can contain only writes.
+    fn apply_batch_profile(&self, batch: &mut Batch) {
+        if batch.get_execution_profile_handle().is_none() {
+            let profile = self.write_profile.as_ref();
+            batch.set_execution_profile_handle(profile.map(ExecutionProfileHandle::from));
+            if batch.get_timestamp().is_none() {
+                batch.set_timestamp(self.generate_timestamp(profile));
+            }
+            if batch.get_consistency().is_none() {
+                batch.set_consistency(self.profile_consistency(profile));
+            }
+        }
+    }
+
+    /// Keyspace of the session, or `None` if the session is busy,
+    /// e.g. while it's started or its keyspace is changed.
+    fn session_keyspace(&self) -> Option<Option<Arc<String>>> {
+        self.scylla_session
+            .try_read()
+            .ok()
+            .map(|session| session.as_ref().and_then(scylla::Session::get_keyspace))
+    }
+
+    /// Observers, which record requests of the session.
+    fn observers(&self) -> RequestObservers {
+        RequestObservers {
+            histograms: self.latency_histograms.clone(),
+            statement_stats: self.statement_stats.clone(),
+            query_logger: self.query_logger.clone(),
+            profiler: self.profiler.clone(),
+            listeners: self.listeners.clone(),
+            tracks_requests: self.tracks_requests(),
+        }
+    }
+
+    /// Reporter of statements, which are sent by helpers.
+    fn helper_requests(&self) -> HelperRequests {
+        HelperRequests {
+            statements: self.helper_statements.clone(),
+            observers: self.observers(),
+            node_stats: self.node_stats.clone(),
+            query_context_in_errors: self.query_context_in_errors,
+            emit_warnings: self.emit_server_warnings,
+            consistency: self.profile_consistency(None),
+        }
+    }
+
+    /// Shadow cluster, if it's attached.
+    fn shadow_target(&self) -> Option<ShadowTarget> {
+        self.shadow.read().ok().and_then(|shadow| shadow.clone())
+    }
+
+    /// Record duration of the stage, if profiling is enabled.
+    fn profile(&self, stage: ProfileStage, started: Instant) {
+        if let Some(profiler) = &self.profiler {
+            profiler.record(stage, started.elapsed());
+        }
+    }
+
+    /// Select result of `function` for each column of the row.
+    ///
+    /// It's used for `TTL` and `WRITETIME` functions.
+    /// Names of the table and columns are quoted.
+    /// Returns a dict with values by column names
+    /// or `None` if the row doesn't exist.
+    fn select_cell_metadata<'a>(
+        &'a self,
+        py: Python<'a>,
+        function: &'static str,
+        table: &str,
+        key: &'a PyDict,
+        columns: Vec<String>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let key_columns = dict_columns(key)?;
+        if key_columns.is_empty() || columns.is_empty() {
+            return Err(ScyllaPyError::BindingError(
+                "Key and columns should not be empty.".into(),
+            ));
+        }
+        let selected = columns
+            .iter()
+            .map(|column| format!("{function}({})", quote_identifier(column)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let condition = key_columns
+            .iter()
+            .map(|column| format!("{} = ?", quote_identifier(column)))
+            .collect::<Vec<_>>()
+            .join(" AND ");
+        let query = format!(
+            "SELECT {selected} FROM {} WHERE {condition}",
+            quote_table(table)
+        );
+        let key_values: Py<PyList> = key.values().into();
+        let helper = self.helper_requests();
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let prepared = helper.prepare(session, query).await?;
+            let values = Python::with_gil(|py| {
+                parse_python_query_params(
+                    Some(key_values.as_ref(py)),
+                    false,
+                    Some(prepared.get_variable_col_specs()),
+                )
+            })?;
+            let res = helper.execute(session, &prepared, values).await?;
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let Some(row) = res.rows.as_ref().and_then(|rows| rows.first()) else {
+                    return Ok(py.None());
+                };
+                let result = PyDict::new(py);
+                for ((column, spec), value) in columns.iter().zip(&res.col_specs).zip(&row.columns)
+                {
+                    result.set_item(
+                        column,
+                        cql_to_py(py, &spec.name, &spec.typ, value.as_ref())?,
+                    )?;
+                }
+                Ok(result.into())
+            })
+        })
+    }
+
     /// Execute a query.
     ///
     /// This function is not exposed to python
@@ -81,61 +652,273 @@ impl Scylla {
         &'a self,
         py: Python<'a>,
         query: Option<impl Into<Query> + Send + 'static>,
-        prepared: Option<PreparedStatement>,
+        prepared: Option<ScyllaPyPreparedQuery>,
         values: impl ValueList + Send + 'static,
         paged: bool,
+        routing_token: Option<i64>,
     ) -> ScyllaPyResult<&'a PyAny> {
+        let attempts = Arc::new(AttemptsListener::new(self.node_stats.clone()));
+        let reprepared = prepared
+            .as_ref()
+            .map(|prepared| prepared.reprepared.clone());
+        let shadow = self.shadow_target();
+        let query = query.map(|query| {
+            let mut query: Query = query.into();
+            self.apply_query_profile(&mut query);
+            query.set_history_listener(attempts.clone());
+            query
+        });
+        let prepared = prepared.map(|prepared| {
+            let mut prepared = PreparedStatement::from(prepared);
+            self.apply_prepared_profile(&mut prepared);
+            prepared.set_history_listener(attempts.clone());
+            prepared
+        });
+        let values = match values.serialized() {
+            Ok(values) => values.into_owned(),
+            // Values are serialized before the request is started,
+            // but the error is raised when the result is awaited,
+            // as any other error of the execution.
+            Err(err) => {
+                return scyllapy_future(py, async move { Err::<(), _>(err.into()) });
+            }
+        };
+        let mut context = QueryErrorContext::default();
+        if self.query_context_in_errors {
+            let (query, params) = query_context(query.as_ref(), prepared.as_ref(), &values);
+            context.query = Some(query);
+            context.params = Some(params);
+        }
+        let statement = query
+            .as_ref()
+            .map(|query| query.contents.as_str())
+            .or_else(|| prepared.as_ref().map(PreparedStatement::get_statement))
+            .unwrap_or_default();
+        let kind = StatementKind::of(statement);
+        let cache_ttl = self
+            .result_cache
+            .as_ref()
+            .and_then(|cache| cache.ttl(statement));
+        let consistency = query
+            .as_ref()
+            .and_then(Query::get_consistency)
+            .or_else(|| {
+                prepared
+                    .as_ref()
+                    .and_then(PreparedStatement::get_consistency)
+            })
+            .unwrap_or_else(|| self.profile_consistency(None));
+        let identifies_reads = !paged
+            && kind == StatementKind::Select
+            && (self.read_coalescer.is_some() || cache_ttl.is_some());
+        let key = match (&query, &prepared) {
+            (Some(query), _) if identifies_reads => self
+                .session_keyspace()
+                .map(|keyspace| ReadKey::of_query(query, keyspace, consistency, &values)),
+            (_, Some(prepared)) if identifies_reads => {
+                Some(ReadKey::of_prepared(prepared, consistency, &values))
+            }
+            _ => None,
+        };
+        let coalescing = self.read_coalescer.clone().zip(key.clone());
+        let caching = self
+            .result_cache
+            .clone()
+            .zip(key)
+            .zip(cache_ttl)
+            .map(|((cache, key), ttl)| (cache, key, ttl));
+        // Cached results don't reach the cluster,
+        // so they aren't observed as requests.
+        if let Some(res) = caching.as_ref().and_then(|(cache, key, _)| cache.get(key)) {
+            let result = ScyllaPyQueryResult::new(res)
+                .with_lazy_collections(self.lazy_collections)
+                .with_profiler(self.profiler.clone());
+            return scyllapy_future(
+                py,
+                async move { Ok(ScyllaPyQueryReturns::QueryResult(result)) },
+            )
+            .map_err(Into::into);
+        }
+        let span = self
+            .tracer
+            .as_ref()
+            .and_then(|tracer| RequestSpan::start(py, tracer, kind.name(), statement));
+        let mirror = shadow.and_then(|shadow| {
+            let settings = match (&query, &prepared) {
+                (Some(query), _) => MirrorSettings::of_query(query, consistency),
+                (_, Some(prepared)) => MirrorSettings::of_prepared(prepared, consistency),
+                _ => return None,
+            };
+            shadow.request(kind, statement, &values, &settings)
+        });
+        let observers = self.observers();
+        let request = observers.start(kind, statement, consistency, attempts);
+        // Context variable is set right away, because
+        // the future doesn't run in the context of the caller.
+        let last_query = self
+            .track_last_query
+            .then(|| ScyllaPyLastQuery::current(py))
+            .transpose()?;
+        let emit_warnings = self.emit_server_warnings;
+        let lazy_collections = self.lazy_collections;
+        let result_profiler = self.profiler.clone();
+        let default_profile = self.default_execution_profile.clone();
         let session_arc = self.scylla_session.clone();
         scyllapy_future(py, async move {
             let session_guard = session_arc.read().await;
-            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
-                "Session is not initialized.".into(),
-            ))?;
-            // let res = session.query(query, values).await?;
-            if paged {
-                match (query, prepared) {
-                    (Some(query), None) => Ok(ScyllaPyQueryReturns::IterableQueryResult(
-                        ScyllaPyIterableQueryResult::new(
-                            session.query_iter(query, values.serialized()?).await?,
-                        ),
-                    )),
-                    (None, Some(prepared)) => Ok(ScyllaPyQueryReturns::IterableQueryResult(
-                        ScyllaPyIterableQueryResult::new(
-                            session.execute_iter(prepared, values.serialized()?).await?,
-                        ),
-                    )),
-                    _ => Err(ScyllaPyError::SessionError(
-                        "You should pass either query or prepared query.".into(),
-                    )),
+            let session = session_guard.as_ref();
+            // Request is timed after the session is acquired,
+            // so waiting for the lock isn't counted as network time.
+            let started = Instant::now();
+            let res: ScyllaPyResult<ScyllaPyQueryReturns> = async move {
+                let session = session.ok_or(ScyllaPyError::SessionError(
+                    "Session is not initialized.".into(),
+                ))?;
+                let (mut query, mut prepared) = (query, prepared);
+                if let Some(token) = routing_token {
+                    let keyspace = session.get_keyspace().map(|keyspace| (*keyspace).clone());
+                    if let Some(query) = &mut query {
+                        let profile = token_profile(
+                            query.get_execution_profile_handle(),
+                            default_profile.as_ref(),
+                            token,
+                            keyspace.clone(),
+                        );
+                        query.set_execution_profile_handle(Some(profile));
+                    }
+                    if let Some(prepared) = &mut prepared {
+                        let profile = token_profile(
+                            prepared.get_execution_profile_handle(),
+                            default_profile.as_ref(),
+                            token,
+                            keyspace,
+                        );
+                        prepared.set_execution_profile_handle(Some(profile));
+                    }
                 }
-            } else {
-                match (query, prepared) {
-                    (Some(query), None) => Ok(ScyllaPyQueryReturns::QueryResult(
-                        ScyllaPyQueryResult::new(session.query(query, values.serialized()?).await?),
-                    )),
-                    (None, Some(prepared)) => {
-                        Ok(ScyllaPyQueryReturns::QueryResult(ScyllaPyQueryResult::new(
-                            session.execute(&prepared, values.serialized()?).await?,
-                        )))
+                if paged {
+                    match (query, prepared) {
+                        (Some(query), None) => Ok(ScyllaPyQueryReturns::IterableQueryResult(
+                            ScyllaPyIterableQueryResult::new(
+                                session.query_iter(query, values).await?,
+                            )
+                            .with_lazy_collections(lazy_collections)
+                            .with_profiler(result_profiler),
+                        )),
+                        (None, Some(prepared)) => Ok(ScyllaPyQueryReturns::IterableQueryResult(
+                            ScyllaPyIterableQueryResult::new(
+                                execute_prepared_iter(
+                                    session,
+                                    prepared,
+                                    values,
+                                    reprepared.as_ref(),
+                                )
+                                .await?,
+                            )
+                            .with_lazy_collections(lazy_collections)
+                            .with_profiler(result_profiler),
+                        )),
+                        _ => Err(ScyllaPyError::SessionError(
+                            "You should pass either query or prepared query.".into(),
+                        )),
                     }
-                    _ => Err(ScyllaPyError::SessionError(
-                        "You should pass either query or prepared query.".into(),
-                    )),
+                } else {
+                    let res = match (query, prepared) {
+                        (Some(query), None) => {
+                            coalesce(coalescing, session.query(query, values)).await?
+                        }
+                        (None, Some(prepared)) => {
+                            coalesce(
+                                coalescing,
+                                execute_prepared(session, &prepared, values, reprepared.as_ref()),
+                            )
+                            .await?
+                        }
+                        _ => {
+                            return Err(ScyllaPyError::SessionError(
+                                "You should pass either query or prepared query.".into(),
+                            ))
+                        }
+                    };
+                    if let Some((cache, key, ttl)) = caching {
+                        cache.insert(key, &res, ttl);
+                    }
+                    if emit_warnings {
+                        emit_server_warnings(&res.warnings)?;
+                    }
+                    Ok(ScyllaPyQueryReturns::QueryResult(
+                        ScyllaPyQueryResult::new(res)
+                            .with_lazy_collections(lazy_collections)
+                            .with_profiler(result_profiler),
+                    ))
                 }
             }
+            .await;
+            let duration = started.elapsed();
+            if let (Some(mirror), Ok(_)) = (mirror, &res) {
+                mirror.send();
+            }
+            if let Some(span) = span {
+                let keyspace = session.and_then(scylla::Session::get_keyspace);
+                span.end(
+                    keyspace.as_deref().map(String::as_str),
+                    request.coordinator(),
+                    res.as_ref().err().map(ToString::to_string),
+                );
+            }
+            if let Some(last_query) = last_query {
+                let trace_id = match &res {
+                    Ok(ScyllaPyQueryReturns::QueryResult(result)) => result.tracing_id(),
+                    _ => None,
+                };
+                // Statement is set whenever the last query is tracked.
+                ScyllaPyLastQuery::update(&last_query, trace_id, request.statement());
+            }
+            Ok(observers.finish(request, duration, context, res)?)
         })
         .map_err(Into::into)
     }
+
+    /// Execute a query with paging lazily.
+    ///
+    /// This function is not exposed to python.
+    /// It returns iterable result right away, and
+    /// the query is sent to the database only when
+    /// the first row is requested.
+    #[must_use]
+    pub fn native_execute_stream(
+        &self,
+        mut query: Query,
+        values: impl ValueList + Send + 'static,
+    ) -> ScyllaPyIterableQueryResult {
+        self.apply_query_profile(&mut query);
+        let session_arc = self.scylla_session.clone();
+        ScyllaPyIterableQueryResult::lazy(async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            Ok(session.query_iter(query, values.serialized()?).await?)
+        })
+        .with_lazy_collections(self.lazy_collections)
+        .with_profiler(self.profiler.clone())
+    }
 }
 
 #[pymethods]
 impl Scylla {
+    /// Configure the session.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if TTL of the result cache is negative.
     #[new]
     #[pyo3(signature = (
         contact_points,
         *,
         username = None,
         password = None,
+        credentials = None,
         keyspace = None,
         ssl_cert = None,
         ssl_key  = None,
@@ -150,13 +933,32 @@ impl Scylla {
         tcp_keepalive_interval = None,
         tcp_nodelay = None,
         disallow_shard_aware_port = None,
+        compression = None,
+        startup_retry_policy = None,
         default_execution_profile = None,
+        default_read_profile = None,
+        default_write_profile = None,
+        query_context_in_errors = false,
+        emit_server_warnings = false,
+        tracer = None,
+        collect_latency_histograms = false,
+        collect_statement_stats = false,
+        log_queries = false,
+        log_sampling = NonZeroU64::MIN,
+        track_last_query = false,
+        lazy_collections = false,
+        profiling = false,
+        coalesce_reads = false,
+        result_cache_size = None,
+        result_cache_max_bytes = None,
+        result_cache_ttl = None,
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn py_new(
         contact_points: Vec<String>,
         username: Option<String>,
         password: Option<String>,
+        credentials: Option<Py<PyAny>>,
         keyspace: Option<String>,
         ssl_cert: Option<String>,
         ssl_key: Option<String>,
@@ -171,12 +973,35 @@ impl Scylla {
         tcp_keepalive_interval: Option<u64>,
         tcp_nodelay: Option<bool>,
         disallow_shard_aware_port: Option<bool>,
+        compression: Option<ScyllaPyCompression>,
+        startup_retry_policy: Option<ScyllaPyStartupRetryPolicy>,
         default_execution_profile: Option<ScyllaPyExecutionProfile>,
-    ) -> Self {
-        Scylla {
+        default_read_profile: Option<ScyllaPyExecutionProfile>,
+        default_write_profile: Option<ScyllaPyExecutionProfile>,
+        query_context_in_errors: bool,
+        emit_server_warnings: bool,
+        tracer: Option<Py<PyAny>>,
+        collect_latency_histograms: bool,
+        collect_statement_stats: bool,
+        log_queries: bool,
+        log_sampling: NonZeroU64,
+        track_last_query: bool,
+        lazy_collections: bool,
+        profiling: bool,
+        coalesce_reads: bool,
+        result_cache_size: Option<NonZeroUsize>,
+        result_cache_max_bytes: Option<NonZeroUsize>,
+        result_cache_ttl: Option<f64>,
+    ) -> ScyllaPyResult<Self> {
+        let result_cache_ttl = result_cache_ttl
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|_| PyValueError::new_err("Cache TTL should be a non-negative number."))?;
+        Ok(Scylla {
             contact_points,
             username,
             password,
+            credentials,
             ssl_cert,
             ssl_key,
             ssl_ca_file,
@@ -191,25 +1016,58 @@ impl Scylla {
             keepalive_timeout,
             tcp_keepalive_interval,
             tcp_nodelay,
+            compression,
+            startup_retry_policy,
             default_execution_profile,
+            read_profile: default_read_profile,
+            write_profile: default_write_profile,
+            query_context_in_errors,
+            emit_server_warnings,
+            tracer,
+            latency_histograms: collect_latency_histograms
+                .then(|| Arc::new(LatencyHistograms::default())),
+            listeners: Arc::new(RequestListeners::default()),
+            node_stats: Arc::new(NodeStatistics::default()),
+            statement_stats: collect_statement_stats
+                .then(|| Arc::new(StatementStatistics::default())),
+            query_logger: log_queries.then(|| Arc::new(QueryLogger::new(log_sampling))),
+            track_last_query,
+            lazy_collections,
+            profiler: profiling.then(|| Arc::new(Profiler::default())),
+            read_coalescer: coalesce_reads.then(|| Arc::new(ReadCoalescer::default())),
+            result_cache: result_cache_size.map(|size| {
+                Arc::new(ResultCache::new(
+                    size,
+                    result_cache_max_bytes,
+                    result_cache_ttl,
+                ))
+            }),
+            named_statements: Arc::new(RwLock::new(HashMap::new())),
+            helper_statements: Arc::new(RwLock::new(HashMap::new())),
+            shadow: Arc::new(RwLock::new(None)),
             scylla_session: Arc::new(tokio::sync::RwLock::new(None)),
-        }
+        })
     }
 
     /// Start the session.
     ///
     /// Here we create a new scylla session
     /// and save it in our structure.
+    /// If startup retry policy is set, attempts,
+    /// which failed because of connection errors, are retried.
     ///
     /// # Errors
     /// May return an error in several cases:
     /// * The session is already initialized;
     /// * Username passed without password and vice versa;
-    /// * Cannot connect to the database.
+    /// * Credentials callback passed with username or password;
+    /// * Cannot connect to the database,
+    ///   after all attempts of startup retry policy.
     pub fn startup<'a>(&'a self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
         let contact_points = self.contact_points.clone();
         let username = self.username.clone();
         let password = self.password.clone();
+        let credentials = self.credentials.clone();
         let mut ssl_context = None;
         if let Some(cert_data) = self.ssl_cert.clone() {
             let mut ssl_context_builder = SslContextBuilder::new(SslMethod::tls())?;
@@ -245,6 +1103,8 @@ impl Scylla {
         let keepalive_timeout = self.keepalive_timeout;
         let tcp_keepalive_interval = self.tcp_keepalive_interval;
         let tcp_nodelay = self.tcp_nodelay;
+        let compression = self.compression;
+        let startup_retry_policy = self.startup_retry_policy;
         let default_execution_profile = self.default_execution_profile.clone();
         scyllapy_future(py, async move {
             if scylla_session.read().await.is_some() {
@@ -254,7 +1114,8 @@ impl Scylla {
             }
             let mut session_builder = scylla::SessionBuilder::new()
                 .ssl_context(ssl_context)
-                .known_nodes(contact_points);
+                .known_nodes(contact_points)
+                .compression(compression.map(Into::into));
             if let Some(write_coalescing) = write_coalescing {
                 session_builder = session_builder.write_coalescing(write_coalescing);
             }
@@ -286,9 +1147,20 @@ impl Scylla {
             if let Some(tcp_nodelay) = tcp_nodelay {
                 session_builder = session_builder.tcp_nodelay(tcp_nodelay);
             }
-            match (username, password) {
-                (Some(user), Some(pass)) => session_builder = session_builder.user(user, pass),
-                (None, None) => {}
+            match (username, password, credentials) {
+                (Some(user), Some(pass), None) => {
+                    session_builder = session_builder.user(user, pass)
+                }
+                (None, None, Some(callback)) => {
+                    session_builder = session_builder
+                        .authenticator_provider(Arc::new(CredentialsCallback::new(callback)));
+                }
+                (None, None, None) => {}
+                (_, _, Some(_)) => {
+                    return Err(ScyllaPyError::SessionError(
+                        "Cannot use credentials callback with username and password.".into(),
+                    ));
+                }
                 _ => {
                     return Err(ScyllaPyError::SessionError(
                         "Cannot use username without a password and vice versa.".into(),
@@ -302,8 +1174,19 @@ impl Scylla {
                 session_builder =
                     session_builder.connection_timeout(Duration::from_secs(connection_timeout));
             }
+            // Session is built without the lock, so retries
+            // don't block requests and shutdown.
+            let session = match startup_retry_policy {
+                Some(policy) => policy.run(|| Box::pin(session_builder.build())).await?,
+                None => Box::pin(session_builder.build()).await?,
+            };
             let mut session_guard = scylla_session.write().await;
-            *session_guard = Some(Box::pin(session_builder.build()).await?);
+            if session_guard.is_some() {
+                return Err(ScyllaPyError::SessionError(
+                    "Session already initialized.".into(),
+                ));
+            }
+            *session_guard = Some(session);
             Ok(())
         })
     }
@@ -339,28 +1222,63 @@ impl Scylla {
     /// # Errors
     ///
     /// Can result in an error in any case, when something goes wrong.
-    #[pyo3(signature = (query, params = None, *, paged = false))]
+    #[pyo3(signature = (
+        query,
+        params = None,
+        *,
+        paged = false,
+        routing_token = None,
+        keyspace = None,
+    ))]
     pub fn execute<'a>(
         &'a self,
         py: Python<'a>,
         query: ExecuteInput,
         params: Option<&'a PyAny>,
         paged: bool,
+        routing_token: Option<i64>,
+        keyspace: Option<String>,
     ) -> ScyllaPyResult<&'a PyAny> {
-        let mut col_spec = None;
-        // We need to prepare parameter we're going to use
-        // in query.
-        if let ExecuteInput::PreparedQuery(prepared) = &query {
-            col_spec = Some(prepared.inner.get_prepared_metadata().col_specs.as_ref());
-        }
-        let query_params = parse_python_query_params(params, true, col_spec)?;
+        let bind_started = Instant::now();
+        let query_params = match &query {
+            // Bound statements already have serialized values.
+            ExecuteInput::BoundStatement(bound) => {
+                if params.is_some() {
+                    return Err(bound_values_error());
+                }
+                bound.values.clone()
+            }
+            ExecuteInput::PartialBoundStatement(partial) => {
+                let merged = partial.merge_params(py, params)?;
+                partial.prepared.parse_values(py, Some(merged.as_ref()))?
+            }
+            // Names of prepared statement's parameters are resolved once,
+            // so named values are converted to positional.
+            ExecuteInput::PreparedQuery(prepared) => prepared.parse_values(py, params)?,
+            _ => parse_python_query_params(params, true, None)?,
+        };
+        self.profile(ProfileStage::Bind, bind_started);
+        let routing_token = match &query {
+            ExecuteInput::Query(query) => routing_token.or(query.params.routing_token),
+            _ => routing_token,
+        };
         // We need this clone, to safely share the session between threads.
-        let (query, prepared) = match query {
+        let (mut query, prepared) = match query {
             ExecuteInput::Text(txt) => (Some(Query::new(txt)), None),
             ExecuteInput::Query(query) => (Some(Query::from(query)), None),
-            ExecuteInput::PreparedQuery(prep) => (None, Some(PreparedStatement::from(prep))),
+            ExecuteInput::PreparedQuery(prep) => (None, Some(prep)),
+            ExecuteInput::BoundStatement(bound) => (None, Some(bound.prepared)),
+            ExecuteInput::PartialBoundStatement(partial) => (None, Some(partial.prepared)),
         };
-        self.native_execute(py, query, prepared, query_params, paged)
+        if let Some(keyspace) = keyspace {
+            if let Some(prepared) = &prepared {
+                return Err(prepared_keyspace_error(prepared.inner.get_statement()));
+            }
+            if let Some(query) = &mut query {
+                query.contents = qualify_statement(&query.contents, &keyspace)?;
+            }
+        }
+        self.native_execute(py, query, prepared, query_params, paged, routing_token)
     }
 
     /// Execute a batch statement.
@@ -370,34 +1288,63 @@ impl Scylla {
     /// # Errors
     ///
     /// Can result in an error in any case, when something goes wrong.
-    #[pyo3(signature = (batch, params = None))]
+    #[pyo3(signature = (batch, params = None, *, keyspace = None))]
     pub fn batch<'a>(
         &'a self,
         py: Python<'a>,
         batch: BatchInput,
         params: Option<Vec<&'a PyAny>>,
+        keyspace: Option<String>,
     ) -> ScyllaPyResult<&'a PyAny> {
         // We need to prepare parameter we're going to use
         // in query.
         // If parameters were passed, we parse python values,
         // to corresponding CQL values.
-
-        let (batch, batch_params) = match batch {
-            BatchInput::Batch(batch) => {
-                let mut batch_params = Vec::new();
-                if let Some(passed_params) = params {
-                    for query_params in passed_params {
-                        batch_params.push(parse_python_query_params(
-                            Some(query_params),
-                            false,
-                            None,
-                        )?);
-                    }
-                }
-                (batch.into(), batch_params)
-            }
-            BatchInput::InlineBatch(inline) => inline.into(),
-        };
+        let retry_policy = batch.retry_policy();
+        let bind_started = Instant::now();
+        let (mut batch, batch_params) = batch.into_batch(params)?;
+        if let Some(keyspace) = keyspace {
+            qualify_batch(&mut batch, &keyspace)?;
+        }
+        self.profile(ProfileStage::Bind, bind_started);
+        self.apply_batch_profile(&mut batch);
+        let shadow = self.shadow_target();
+        let attempts = Arc::new(AttemptsListener::new(self.node_stats.clone()));
+        batch.set_history_listener(attempts.clone());
+        let mut context = QueryErrorContext::default();
+        if self.query_context_in_errors {
+            let (query, params) = batch_context(&batch, &batch_params);
+            context.query = Some(query);
+            context.params = Some(params);
+        }
+        let kind = StatementKind::Batch;
+        // Texts of statements are joined only if they are used.
+        let statement =
+            (self.tracer.is_some() || self.tracks_requests()).then(|| batch_statement(&batch));
+        let span = self
+            .tracer
+            .as_ref()
+            .zip(statement.as_deref())
+            .and_then(|(tracer, statement)| RequestSpan::start(py, tracer, kind.name(), statement));
+        let consistency = batch
+            .get_consistency()
+            .unwrap_or_else(|| self.profile_consistency(None));
+        let mirror = shadow.and_then(|shadow| {
+            let settings = MirrorSettings::of_batch(&batch, consistency);
+            shadow.batch_request(&batch, &batch_params, &settings)
+        });
+        let observers = self.observers();
+        let request = observers.start(
+            kind,
+            statement.as_deref().unwrap_or_default(),
+            consistency,
+            attempts,
+        );
+        let last_query = self
+            .track_last_query
+            .then(|| ScyllaPyLastQuery::current(py))
+            .transpose()?;
+        let emit_warnings = self.emit_server_warnings;
         // We need this clone, to safely share the session between threads.
         let session_arc = self.scylla_session.clone();
         scyllapy_future(py, async move {
@@ -405,67 +1352,1169 @@ impl Scylla {
             let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
                 "Session is not initialized.".into(),
             ))?;
-            let res = session.batch(&batch, batch_params).await?;
-            Ok(ScyllaPyQueryResult::new(res))
+            let started = Instant::now();
+            let res = match retry_policy {
+                Some(policy) => {
+                    policy
+                        .run(|| session.batch(&batch, batch_params.clone()))
+                        .await
+                }
+                None => session.batch(&batch, batch_params).await,
+            };
+            let duration = started.elapsed();
+            if let (Some(mirror), Ok(_)) = (mirror, &res) {
+                mirror.send();
+            }
+            if let Some(span) = span {
+                span.end(
+                    session.get_keyspace().as_deref().map(String::as_str),
+                    request.coordinator(),
+                    res.as_ref().err().map(ToString::to_string),
+                );
+            }
+            if let Some(last_query) = last_query {
+                let trace_id = res.as_ref().ok().and_then(|res| res.tracing_id);
+                ScyllaPyLastQuery::update(&last_query, trace_id, request.statement());
+            }
+            let res = observers.finish(request, duration, context, res.map_err(Into::into))?;
+            if emit_warnings {
+                emit_server_warnings(&res.warnings)?;
+            }
+            Python::with_gil(|py| ScyllaPyBatchResult::new_py(py, res))
         })
         .map_err(Into::into)
     }
 
-    /// Prepare a query.
+    /// Execute the same prepared query with many sets of parameters.
     ///
-    /// This function takes a query to prepare
-    /// and sends it to server.
+    /// All parameter sets are added to a single batch
+    /// and sent to the database in one request.
+    /// Additional keyword arguments are used as
+    /// request parameters of the batch.
     ///
-    /// After preparation it returns a prepared
-    /// query, that you can use later.
+    /// # Errors
+    ///
+    /// May return an error, if parameters cannot be parsed,
+    /// or if batch cannot be executed.
+    #[pyo3(signature = (
+        prepared,
+        params,
+        batch_type = ScyllaPyBatchType::UNLOGGED,
+        **request_params
+    ))]
+    pub fn execute_prepared_batch<'a>(
+        &'a self,
+        py: Python<'a>,
+        prepared: ScyllaPyPreparedQuery,
+        params: Vec<&'a PyAny>,
+        batch_type: ScyllaPyBatchType,
+        request_params: Option<&'a PyDict>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let mut batch =
+            ScyllaPyInlineBatch::py_new(batch_type, None, None, None, None, request_params)?;
+        for query_params in params {
+            let values = prepared.parse_values(py, Some(query_params))?;
+            batch.add_query_inner(PreparedStatement::from(prepared.clone()), values)?;
+        }
+        self.batch(py, BatchInput::InlineBatch(batch), None, None)
+    }
+
+    /// Execute multiple batches concurrently.
+    ///
+    /// Batches are sent to the database with at most
+    /// `concurrency` batches in flight at the same time.
+    ///
+    /// Returns a list with results in the same order as batches.
+    /// If a batch has failed, its place in the list
+    /// is taken by the exception, so one failed batch
+    /// doesn't affect others.
     ///
     /// # Errors
     ///
     /// May return an error, if session is not initialized.
-    pub fn prepare<'a>(
+    #[pyo3(signature = (batches, *, concurrency = None))]
+    pub fn execute_batches<'a>(
         &'a self,
-        python: Python<'a>,
-        query: PrepareInput,
+        py: Python<'a>,
+        batches: Vec<BatchInput>,
+        concurrency: Option<NonZeroUsize>,
     ) -> ScyllaPyResult<&'a PyAny> {
+        let shadow = self.shadow_target();
+        let observers = self.observers();
+        let prepared_batches = batches
+            .into_iter()
+            .map(|batch| {
+                let retry_policy = batch.retry_policy();
+                batch.into_batch(None).map(|(mut batch, values)| {
+                    self.apply_batch_profile(&mut batch);
+                    // Each batch is reported as a separate request.
+                    let attempts = Arc::new(AttemptsListener::new(self.node_stats.clone()));
+                    batch.set_history_listener(attempts.clone());
+                    let mut context = QueryErrorContext::default();
+                    if self.query_context_in_errors {
+                        let (query, params) = batch_context(&batch, &values);
+                        context.query = Some(query);
+                        context.params = Some(params);
+                    }
+                    let consistency = batch
+                        .get_consistency()
+                        .unwrap_or_else(|| self.profile_consistency(None));
+                    let mirror = shadow.as_ref().and_then(|shadow| {
+                        let settings = MirrorSettings::of_batch(&batch, consistency);
+                        shadow.batch_request(&batch, &values, &settings)
+                    });
+                    // Texts of statements are joined only if they are used.
+                    let statement = self.tracks_requests().then(|| batch_statement(&batch));
+                    let observed = (statement, consistency, attempts, context);
+                    (batch, values, retry_policy, mirror, observed)
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let concurrency = concurrency.map_or(DEFAULT_BATCHES_CONCURRENCY, NonZeroUsize::get);
+        let emit_warnings = self.emit_server_warnings;
         let session_arc = self.scylla_session.clone();
-        scyllapy_future(python, async move {
-            let cql_query = Query::from(query);
+        scyllapy_future(py, async move {
             let session_guard = session_arc.read().await;
             let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
                 "Session is not initialized.".into(),
             ))?;
-            let prepared = session.prepare(cql_query).await?;
-            Ok(ScyllaPyPreparedQuery::from(prepared))
+            let observers = &observers;
+            let results = futures::stream::iter(prepared_batches)
+                .map(
+                    |(batch, values, retry_policy, mirror, observed)| async move {
+                        let (statement, consistency, attempts, context) = observed;
+                        let request = observers.start(
+                            StatementKind::Batch,
+                            statement.as_deref().unwrap_or_default(),
+                            consistency,
+                            attempts,
+                        );
+                        let started = Instant::now();
+                        let res = match retry_policy {
+                            Some(policy) => {
+                                policy.run(|| session.batch(&batch, values.clone())).await
+                            }
+                            None => session.batch(&batch, values).await,
+                        };
+                        let duration = started.elapsed();
+                        if let (Some(mirror), Ok(_)) = (mirror, &res) {
+                            mirror.send();
+                        }
+                        let res = observers.finish(
+                            request,
+                            duration,
+                            context,
+                            res.map_err(Into::into),
+                        )?;
+                        if emit_warnings {
+                            emit_server_warnings(&res.warnings)?;
+                        }
+                        Ok::<_, PyErr>(res)
+                    },
+                )
+                .buffered(concurrency)
+                .collect::<Vec<_>>()
+                .await;
+            Python::with_gil(|py| {
+                results
+                    .into_iter()
+                    .map(|result| match result {
+                        Ok(res) => Ok(ScyllaPyBatchResult::new_py(py, res)?.into_py(py)),
+                        Err(err) => Ok(err.into_value(py).into_py(py)),
+                    })
+                    .collect::<ScyllaPyResult<Vec<PyObject>>>()
+            })
         })
     }
 
-    /// Set keyspace to all connections.
+    /// Execute one statement with many sets of parameters.
+    ///
+    /// All parameters are parsed before sending anything,
+    /// then statements are sent with at most `concurrency`
+    /// of them in flight at the same time.
+    ///
+    /// Returns a list with results in the same order as parameters.
+    /// If an execution has failed, its place in the list
+    /// is taken by the exception.
     ///
     /// # Errors
-    /// May return an error, if
-    /// sessions was not initialized.
-    pub fn use_keyspace<'a>(
+    ///
+    /// May return an error, if any parameters cannot be parsed
+    /// or session is not initialized.
+    #[pyo3(signature = (query, params, *, concurrency = None))]
+    pub fn execute_many<'a>(
         &'a self,
-        python: Python<'a>,
-        keyspace: String,
+        py: Python<'a>,
+        query: BatchQueryInput,
+        params: Vec<&'a PyAny>,
+        concurrency: Option<NonZeroUsize>,
     ) -> ScyllaPyResult<&'a PyAny> {
+        let bind_started = Instant::now();
+        let values = params
+            .into_iter()
+            .map(|params| match &query {
+                BatchQueryInput::PreparedQuery(prepared) => prepared.parse_values(py, Some(params)),
+                _ => parse_python_query_params(Some(params), true, None),
+            })
+            .collect::<ScyllaPyResult<Vec<_>>>()?;
+        self.profile(ProfileStage::Bind, bind_started);
+        let statement = match &query {
+            BatchQueryInput::Text(txt) => txt.clone(),
+            BatchQueryInput::Query(query) => query.query.clone(),
+            BatchQueryInput::PreparedQuery(prep) => prep.inner.get_statement().to_string(),
+        };
+        let kind = StatementKind::of(&statement);
+        let shadow = self.shadow_target();
+        let reprepared = match &query {
+            BatchQueryInput::PreparedQuery(prep) => Some(prep.reprepared.clone()),
+            _ => None,
+        };
+        let (query, prepared) = match query {
+            BatchQueryInput::Text(txt) => (Some(Query::new(txt)), None),
+            BatchQueryInput::Query(query) => (Some(Query::from(query)), None),
+            BatchQueryInput::PreparedQuery(prep) => (None, Some(PreparedStatement::from(prep))),
+        };
+        let query = query.map(|mut query| {
+            self.apply_query_profile(&mut query);
+            query
+        });
+        let prepared = prepared.map(|mut prepared| {
+            self.apply_prepared_profile(&mut prepared);
+            prepared
+        });
+        let concurrency = concurrency.map_or(DEFAULT_EXECUTE_MANY_CONCURRENCY, NonZeroUsize::get);
+        let consistency = query
+            .as_ref()
+            .and_then(Query::get_consistency)
+            .or_else(|| {
+                prepared
+                    .as_ref()
+                    .and_then(PreparedStatement::get_consistency)
+            })
+            .unwrap_or_else(|| self.profile_consistency(None));
+        let observers = self.observers();
+        let node_stats = self.node_stats.clone();
+        let query_context_in_errors = self.query_context_in_errors;
+        let lazy_collections = self.lazy_collections;
+        let profiler = self.profiler.clone();
         let session_arc = self.scylla_session.clone();
-        scyllapy_future(python, async move {
-            let guard = session_arc.write().await;
-            let session = guard.as_ref().ok_or(ScyllaPyError::SessionError(
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
                 "Session is not initialized.".into(),
             ))?;
-            session.use_keyspace(keyspace, true).await?;
-            Ok(())
+            let (query, prepared, statement) = (&query, &prepared, statement.as_str());
+            let (observers, node_stats, shadow) = (&observers, &node_stats, shadow.as_ref());
+            let reprepared = reprepared.as_ref();
+            let results = futures::stream::iter(values)
+                .map(|values| async move {
+                    let mut context = QueryErrorContext::default();
+                    if query_context_in_errors {
+                        let (query, params) =
+                            query_context(query.as_ref(), prepared.as_ref(), &values);
+                        context.query = Some(query);
+                        context.params = Some(params);
+                    }
+                    // Each execution is reported as a separate request.
+                    let attempts = Arc::new(AttemptsListener::new(node_stats.clone()));
+                    let request = observers.start(kind, statement, consistency, attempts.clone());
+                    let started = Instant::now();
+                    let mut mirror = None;
+                    let res = match (query, prepared) {
+                        (Some(query), None) => {
+                            let mut query = query.clone();
+                            query.set_history_listener(attempts);
+                            if let Some(shadow) = shadow {
+                                let settings = MirrorSettings::of_query(&query, consistency);
+                                mirror = shadow.request(kind, statement, &values, &settings);
+                            }
+                            session
+                                .query(query, values)
+                                .await
+                                .map_err(ScyllaPyError::from)
+                        }
+                        (None, Some(prepared)) => {
+                            let mut prepared = prepared.clone();
+                            prepared.set_history_listener(attempts);
+                            if let Some(shadow) = shadow {
+                                let settings = MirrorSettings::of_prepared(&prepared, consistency);
+                                mirror = shadow.request(kind, statement, &values, &settings);
+                            }
+                            execute_prepared(session, &prepared, values, reprepared)
+                                .await
+                                .map_err(ScyllaPyError::from)
+                        }
+                        _ => Err(ScyllaPyError::SessionError(
+                            "You should pass either query or prepared query.".into(),
+                        )),
+                    };
+                    if let (Some(mirror), Ok(_)) = (mirror, &res) {
+                        mirror.send();
+                    }
+                    observers.finish(request, started.elapsed(), context, res)
+                })
+                .buffered(concurrency)
+                .collect::<Vec<_>>()
+                .await;
+            Ok(Python::with_gil(|py| {
+                results
+                    .into_iter()
+                    .map(|result| match result {
+                        Ok(res) => ScyllaPyQueryResult::new(res)
+                            .with_lazy_collections(lazy_collections)
+                            .with_profiler(profiler.clone())
+                            .into_py(py),
+                        Err(err) => err.into_value(py).into_py(py),
+                    })
+                    .collect::<Vec<PyObject>>()
+            }))
         })
     }
 
-    /// Get current keyspace.
+    /// Execute a conditional statement.
+    ///
+    /// If the statement was rejected before it was processed,
+    /// because coordinator is overloaded or bootstrapping,
+    /// it's retried up to `retries` times with backoff.
+    /// Timeouts of LWT operations leave the outcome unknown,
+    /// so they're not retried. Read the row at serial consistency
+    /// to find out whether the statement was applied.
     ///
     /// # Errors
-    /// May return an error, if
-    /// sessions was not initialized.
-    pub fn get_keyspace<'a>(&'a self, python: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+    ///
+    /// May return an error, if parameters cannot be parsed,
+    /// session is not initialized or all attempts have failed.
+    #[pyo3(signature = (query, params = None, *, retries = 3))]
+    pub fn execute_lwt<'a>(
+        &'a self,
+        py: Python<'a>,
+        query: BatchQueryInput,
+        params: Option<&'a PyAny>,
+        retries: u32,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let values = match &query {
+            BatchQueryInput::PreparedQuery(prepared) => prepared.parse_values(py, params)?,
+            _ => parse_python_query_params(params, true, None)?,
+        };
+        let retry_policy = lwt_retry_policy(retries)?;
+        let reprepared = match &query {
+            BatchQueryInput::PreparedQuery(prep) => Some(prep.reprepared.clone()),
+            _ => None,
+        };
+        let (query, prepared) = match query {
+            BatchQueryInput::Text(txt) => (Some(Query::new(txt)), None),
+            BatchQueryInput::Query(query) => (Some(Query::from(query)), None),
+            BatchQueryInput::PreparedQuery(prep) => (None, Some(PreparedStatement::from(prep))),
+        };
+        // All retries are reported as attempts of a single request.
+        let attempts = Arc::new(AttemptsListener::new(self.node_stats.clone()));
+        let query = query.map(|mut query| {
+            self.apply_query_profile(&mut query);
+            query.set_history_listener(attempts.clone());
+            query
+        });
+        let prepared = prepared.map(|mut prepared| {
+            self.apply_prepared_profile(&mut prepared);
+            prepared.set_history_listener(attempts.clone());
+            prepared
+        });
+        let mut context = QueryErrorContext::default();
+        if self.query_context_in_errors {
+            let (query, params) = query_context(query.as_ref(), prepared.as_ref(), &values);
+            context.query = Some(query);
+            context.params = Some(params);
+        }
+        let statement = query
+            .as_ref()
+            .map(|query| query.contents.as_str())
+            .or_else(|| prepared.as_ref().map(PreparedStatement::get_statement))
+            .unwrap_or_default();
+        let consistency = query
+            .as_ref()
+            .and_then(Query::get_consistency)
+            .or_else(|| {
+                prepared
+                    .as_ref()
+                    .and_then(PreparedStatement::get_consistency)
+            })
+            .unwrap_or_else(|| self.profile_consistency(None));
+        let observers = self.observers();
+        let request = observers.start(
+            StatementKind::of(statement),
+            statement,
+            consistency,
+            attempts,
+        );
+        let emit_warnings = self.emit_server_warnings;
+        let lazy_collections = self.lazy_collections;
+        let profiler = self.profiler.clone();
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let started = Instant::now();
+            let res = match (query, prepared) {
+                (Some(query), None) => retry_policy
+                    .run_if(is_rejected, || session.query(query.clone(), values.clone()))
+                    .await
+                    .map_err(ScyllaPyError::from),
+                (None, Some(prepared)) => retry_policy
+                    .run_if(is_rejected, || {
+                        execute_prepared(session, &prepared, values.clone(), reprepared.as_ref())
+                    })
+                    .await
+                    .map_err(ScyllaPyError::from),
+                _ => Err(ScyllaPyError::SessionError(
+                    "You should pass either query or prepared query.".into(),
+                )),
+            };
+            let res = observers.finish(request, started.elapsed(), context, res)?;
+            if emit_warnings {
+                emit_server_warnings(&res.warnings)?;
+            }
+            Ok(ScyllaPyQueryResult::new(res)
+                .with_lazy_collections(lazy_collections)
+                .with_profiler(profiler))
+        })
+    }
+
+    /// Update a row, if its columns have expected values.
+    ///
+    /// `UPDATE ... IF ...` statement is prepared and executed.
+    /// If `new` is a callable, it's called with expected values
+    /// and returns values to set. When the update isn't applied,
+    /// current values of the row become expected ones and the
+    /// callable is called again, up to `retries` times with backoff.
+    ///
+    /// Timed out update may be applied, so it's never executed
+    /// again blindly. The row is read at serial consistency instead.
+    /// If it has new values, the update is considered applied.
+    /// If it still has expected values, the update is executed again.
+    /// Otherwise its values are handled as current ones.
+    /// Up to `timeout_retries` timeouts are handled this way.
+    ///
+    /// Statements use `serial_consistency`, which is
+    /// `LOCAL_SERIAL` by default, and `profile`,
+    /// or default write profile of the session.
+    ///
+    /// Returns whether the update was applied.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if values cannot be bound,
+    /// session is not initialized or the statement fails.
+    #[pyo3(signature = (
+        table,
+        key,
+        expected,
+        new,
+        *,
+        retries = 3,
+        timeout_retries = 3,
+        serial_consistency = None,
+        profile = None,
+    ))]
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+    pub fn compare_and_set<'a>(
+        &'a self,
+        py: Python<'a>,
+        table: String,
+        key: &'a PyDict,
+        expected: &'a PyDict,
+        new: &'a PyAny,
+        retries: u32,
+        timeout_retries: u32,
+        serial_consistency: Option<ScyllaPySerialConsistency>,
+        profile: Option<ScyllaPyExecutionProfile>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let update: Option<PyObject> = new.is_callable().then(|| new.into());
+        let new_values: &PyDict = match &update {
+            Some(update) => update.as_ref(py).call1((expected.copy()?,))?.extract()?,
+            None => new.downcast().map_err(|err| {
+                ScyllaPyError::BindingError(format!(
+                    "New values should be a dict or a callable. {err}"
+                ))
+            })?,
+        };
+        let set_columns = dict_columns(new_values)?;
+        let key_columns = dict_columns(key)?;
+        let expected_columns = dict_columns(expected)?;
+        let serial_consistency =
+            serial_consistency.map_or(SerialConsistency::LocalSerial, SerialConsistency::from);
+        let mut update_query = Query::new(cas_query(
+            &table,
+            &set_columns,
+            &key_columns,
+            &expected_columns,
+        )?);
+        if let Some(profile) = &profile {
+            update_query.set_execution_profile_handle(Some(profile.into()));
+            update_query.set_consistency(profile.consistency());
+        }
+        self.apply_query_profile(&mut update_query);
+        update_query.set_serial_consistency(Some(serial_consistency));
+        let mut read_query = Query::new(cas_read_query(
+            &table,
+            &set_columns,
+            &key_columns,
+            &expected_columns,
+        ));
+        read_query
+            .set_execution_profile_handle(update_query.get_execution_profile_handle().cloned());
+        read_query.set_consistency(match serial_consistency {
+            SerialConsistency::Serial => Consistency::Serial,
+            SerialConsistency::LocalSerial => Consistency::LocalSerial,
+        });
+        let retry_policy = lwt_retry_policy(retries)?;
+        let key: Py<PyDict> = key.into();
+        let mut expected: Py<PyDict> = expected.into();
+        let mut new_values: Py<PyDict> = new_values.into();
+        let helper = self.helper_requests();
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let prepared = session.prepare(update_query).await?;
+            let col_specs = prepared.get_variable_col_specs();
+            let mut read_prepared: Option<PreparedStatement> = None;
+            let mut attempt = 0;
+            let mut timeouts = 0;
+            loop {
+                let values = Python::with_gil(|py| {
+                    bind_cas_values(
+                        py,
+                        col_specs,
+                        &[
+                            (set_columns.as_slice(), new_values.as_ref(py)),
+                            (key_columns.as_slice(), key.as_ref(py)),
+                            (expected_columns.as_slice(), expected.as_ref(py)),
+                        ],
+                    )
+                })?;
+                let mut statement = prepared.clone();
+                let request = helper.start(&mut statement, &values);
+                let res = execute_prepared(session, &statement, values, None).await;
+                let timed_out = matches!(&res, Err(err) if is_timeout(err));
+                let (applied, current) = match helper.finish(request, res) {
+                    Ok(res) => Python::with_gil(|py| {
+                        lwt_outcome(py, &res).map(|(applied, current)| (applied, Py::from(current)))
+                    })?,
+                    Err(_) if timed_out && timeouts < timeout_retries => {
+                        tokio::time::sleep(retry_policy.delay(timeouts)).await;
+                        timeouts += 1;
+                        let read = match read_prepared.take() {
+                            Some(read) => read,
+                            None => session.prepare(read_query.clone()).await?,
+                        };
+                        let key_values = Python::with_gil(|py| {
+                            bind_cas_values(
+                                py,
+                                read.get_variable_col_specs(),
+                                &[(key_columns.as_slice(), key.as_ref(py))],
+                            )
+                        })?;
+                        let res = helper.execute(session, &read, key_values).await?;
+                        read_prepared = Some(read);
+                        let outcome = Python::with_gil(|py| {
+                            timed_out_outcome(
+                                py,
+                                &res,
+                                (set_columns.as_slice(), new_values.as_ref(py)),
+                                (expected_columns.as_slice(), expected.as_ref(py)),
+                            )
+                        })?;
+                        match outcome {
+                            TimedOutUpdate::Applied => return Ok(true),
+                            TimedOutUpdate::NotApplied => continue,
+                            TimedOutUpdate::Changed(current) => (false, current),
+                        }
+                    }
+                    Err(err) => return Err(err),
+                };
+                let next = Python::with_gil(|py| -> ScyllaPyResult<Option<_>> {
+                    let current = current.as_ref(py);
+                    let Some(update) = update.as_ref().filter(|_| !applied && attempt < retries)
+                    else {
+                        return Ok(None);
+                    };
+                    let current_expected = PyDict::new(py);
+                    for column in &expected_columns {
+                        current_expected.set_item(column, current.get_item(column)?)?;
+                    }
+                    let next_values: &PyDict = update
+                        .as_ref(py)
+                        .call1((current_expected.copy()?,))?
+                        .extract()?;
+                    let mut next_columns = dict_columns(next_values)?;
+                    next_columns.retain(|column| set_columns.contains(column));
+                    if next_columns.len() != set_columns.len()
+                        || next_values.len() != set_columns.len()
+                    {
+                        return Err(ScyllaPyError::BindingError(
+                            "Update function should return the same columns.".into(),
+                        ));
+                    }
+                    Ok(Some((Py::from(current_expected), Py::from(next_values))))
+                })?;
+                let Some((next_expected, next_values)) = next else {
+                    return Ok(applied);
+                };
+                expected = next_expected;
+                new_values = next_values;
+                tokio::time::sleep(retry_policy.delay(attempt)).await;
+                attempt += 1;
+            }
+        })
+    }
+
+    /// Load rows from CSV or parquet file into the table.
+    ///
+    /// Names of columns are taken from the header of CSV file
+    /// or from the schema of parquet file. Rows are inserted
+    /// with prepared statement, with at most `concurrency`
+    /// inserts in flight.
+    ///
+    /// Returns the number of inserted rows.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// file cannot be read or any insert fails.
+    #[pyo3(signature = (
+        path,
+        table,
+        *,
+        format = FileFormat::Csv,
+        delimiter = ',',
+        concurrency = None,
+    ))]
+    pub fn copy_from<'a>(
+        &'a self,
+        py: Python<'a>,
+        path: PathBuf,
+        table: String,
+        format: FileFormat,
+        delimiter: char,
+        concurrency: Option<NonZeroUsize>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let delimiter = u8::try_from(delimiter)
+            .map_err(|_| PyValueError::new_err("Delimiter must be an ASCII character."))?;
+        let concurrency = concurrency.map_or(DEFAULT_COPY_CONCURRENCY, NonZeroUsize::get);
+        let profile = self
+            .write_profile
+            .as_ref()
+            .map(ExecutionProfileHandle::from);
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            copy_from(
+                session,
+                &path,
+                &table,
+                format,
+                delimiter,
+                concurrency,
+                profile,
+            )
+            .await
+        })
+    }
+
+    /// Unload rows of the table or a select statement into CSV or parquet file.
+    ///
+    /// Tables are split by token ranges, which are scanned in parallel,
+    /// with at most `concurrency` ranges scanned at once.
+    /// Select statements are executed as is.
+    ///
+    /// Returns the number of written rows.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// table is unknown, file cannot be written or any query fails.
+    #[pyo3(signature = (
+        path,
+        table_or_select,
+        *,
+        format = FileFormat::Csv,
+        delimiter = ',',
+        concurrency = None,
+    ))]
+    pub fn copy_to<'a>(
+        &'a self,
+        py: Python<'a>,
+        path: PathBuf,
+        table_or_select: String,
+        format: FileFormat,
+        delimiter: char,
+        concurrency: Option<NonZeroUsize>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let delimiter = u8::try_from(delimiter)
+            .map_err(|_| PyValueError::new_err("Delimiter must be an ASCII character."))?;
+        let concurrency = concurrency.map_or(DEFAULT_SCAN_CONCURRENCY, NonZeroUsize::get);
+        let profile = self.read_profile.as_ref().map(ExecutionProfileHandle::from);
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            copy_to(
+                session,
+                &path,
+                &table_or_select,
+                format,
+                delimiter,
+                concurrency,
+                profile,
+            )
+            .await
+        })
+    }
+
+    /// Copy rows of the table into another cluster.
+    ///
+    /// The table is split by token ranges, which are scanned in parallel,
+    /// with at most `concurrency` ranges copied at once.
+    /// Rows of the same partition are written with unlogged batches
+    /// into the table with the same name in `destination`,
+    /// keeping write timestamps and TTLs of cells.
+    /// Only given `token_ranges` are copied, if specified.
+    ///
+    /// Returns the number of copied rows.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if any session is not initialized,
+    /// table is unknown, has counter columns or any query fails.
+    #[pyo3(signature = (
+        destination,
+        table,
+        *,
+        token_ranges = None,
+        concurrency = None,
+    ))]
+    pub fn copy_table<'a>(
+        &'a self,
+        py: Python<'a>,
+        destination: &'a Scylla,
+        table: String,
+        token_ranges: Option<Vec<(i64, i64)>>,
+        concurrency: Option<NonZeroUsize>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let concurrency = concurrency.map_or(DEFAULT_SCAN_CONCURRENCY, NonZeroUsize::get);
+        let read_profile = self.read_profile.as_ref().map(ExecutionProfileHandle::from);
+        let write_profile = destination
+            .write_profile
+            .as_ref()
+            .map(ExecutionProfileHandle::from);
+        let source_arc = self.scylla_session.clone();
+        let destination_arc = destination.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let source_guard = source_arc.read().await;
+            let source = source_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let destination_guard = destination_arc.read().await;
+            let destination = destination_guard
+                .as_ref()
+                .ok_or(ScyllaPyError::SessionError(
+                    "Destination session is not initialized.".into(),
+                ))?;
+            copy_table(
+                source,
+                destination,
+                &table,
+                token_ranges,
+                concurrency,
+                read_profile,
+                write_profile,
+            )
+            .await
+        })
+    }
+
+    /// Create a writer that groups statements into batches.
+    ///
+    /// Batches are sent when they reach `max_batch_size` statements,
+    /// or when `flush_interval` seconds pass since the last flush.
+    /// `buffer_size` limits the number of statements waiting to be sent.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if parameters are incorrect.
+    #[pyo3(signature = (
+        batch_type = ScyllaPyBatchType::UNLOGGED,
+        *,
+        max_batch_size = NonZeroUsize::new(100).unwrap(),
+        flush_interval = 1.0,
+        buffer_size = NonZeroUsize::new(1000).unwrap(),
+        **params
+    ))]
+    pub fn batch_writer(
+        &self,
+        batch_type: ScyllaPyBatchType,
+        max_batch_size: NonZeroUsize,
+        flush_interval: f64,
+        buffer_size: NonZeroUsize,
+        params: Option<&PyDict>,
+    ) -> ScyllaPyResult<ScyllaPyBatchWriter> {
+        if !flush_interval.is_finite() || flush_interval <= 0.0 {
+            return Err(
+                PyValueError::new_err("Flush interval should be a positive number.").into(),
+            );
+        }
+        let mut template = Batch::new(batch_type.into());
+        ScyllaPyRequestParams::from_dict(params)?.apply_to_batch(&mut template);
+        self.apply_batch_profile(&mut template);
+        Ok(ScyllaPyBatchWriter::start(
+            self.scylla_session.clone(),
+            template,
+            BatchWriterConfig {
+                max_batch_size,
+                flush_interval: Duration::from_secs_f64(flush_interval),
+                buffer_size,
+            },
+        ))
+    }
+
+    /// Create a writer that sends statements in background.
+    ///
+    /// At most `concurrency` statements are executed at once
+    /// and at most `writes_per_second` statements are sent per second,
+    /// if it's specified. `buffer_size` limits the number
+    /// of statements waiting in the queue.
+    /// Writes are reported to listeners and statistics
+    /// and mirrored to the shadow cluster like other requests.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if parameters are incorrect.
+    #[pyo3(signature = (
+        *,
+        writes_per_second = None,
+        concurrency = NonZeroUsize::new(64).unwrap(),
+        buffer_size = NonZeroUsize::new(1000).unwrap(),
+    ))]
+    pub fn writer(
+        &self,
+        writes_per_second: Option<f64>,
+        concurrency: NonZeroUsize,
+        buffer_size: NonZeroUsize,
+    ) -> ScyllaPyResult<ScyllaPyWriter> {
+        if writes_per_second.is_some_and(|rate| !rate.is_finite() || rate <= 0.0) {
+            return Err(
+                PyValueError::new_err("Writes per second should be a positive number.").into(),
+            );
+        }
+        Ok(ScyllaPyWriter::start(
+            WriterContext {
+                session: self.scylla_session.clone(),
+                observers: self.observers(),
+                node_stats: self.node_stats.clone(),
+                shadow: self.shadow.clone(),
+                profile: self
+                    .write_profile
+                    .as_ref()
+                    .map(ExecutionProfileHandle::from),
+                consistency: self.profile_consistency(self.write_profile.as_ref()),
+            },
+            WriterConfig {
+                writes_per_second,
+                concurrency,
+                buffer_size,
+            },
+        ))
+    }
+
+    /// Prepare a query.
+    ///
+    /// This function takes a query to prepare
+    /// and sends it to server.
+    ///
+    /// After preparation it returns a prepared
+    /// query, that you can use later.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized.
+    pub fn prepare<'a>(
+        &'a self,
+        python: Python<'a>,
+        query: PrepareInput,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(python, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            Ok(query.prepare(session).await?)
+        })
+    }
+
+    /// Prepare a statement and register it by name.
+    ///
+    /// Registered statements are executed with `execute_named`.
+    /// If statement with the same name was registered before,
+    /// it's replaced.
+    ///
+    /// Returns the prepared statement.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized
+    /// or the statement cannot be prepared.
+    pub fn register_statement<'a>(
+        &'a self,
+        py: Python<'a>,
+        name: String,
+        query: PrepareInput,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let named_statements = self.named_statements.clone();
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let prepared = query.prepare(session).await?;
+            if let Ok(mut statements) = named_statements.write() {
+                statements.insert(name, prepared.clone());
+            }
+            Ok(prepared)
+        })
+    }
+
+    /// Execute a statement, registered with `register_statement`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if statement isn't registered,
+    /// parameters cannot be parsed or query fails.
+    #[pyo3(signature = (name, params = None, *, paged = false))]
+    pub fn execute_named<'a>(
+        &'a self,
+        py: Python<'a>,
+        name: &str,
+        params: Option<&'a PyAny>,
+        paged: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let prepared = self
+            .named_statements
+            .read()
+            .ok()
+            .and_then(|statements| statements.get(name).cloned())
+            .ok_or_else(|| {
+                ScyllaPyError::SessionError(format!("Statement {name} is not registered."))
+            })?;
+        let bind_started = Instant::now();
+        let values = prepared.parse_values(py, params)?;
+        self.profile(ProfileStage::Bind, bind_started);
+        self.native_execute(py, None::<Query>, Some(prepared), values, paged, None)
+    }
+
+    /// Sorted names of registered statements.
+    #[must_use]
+    pub fn registered_statements(&self) -> Vec<String> {
+        let mut names = self
+            .named_statements
+            .read()
+            .map(|statements| statements.keys().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        names.sort();
+        names
+    }
+
+    /// Warm up the session.
+    ///
+    /// Metadata of the cluster is fetched, then every node
+    /// receives a few cheap requests for each of its shards,
+    /// so connections are used before real requests come.
+    /// Passed statements are prepared at the same time.
+    ///
+    /// Returns a list of prepared statements
+    /// in the same order as passed ones.
+    /// Failed warmup requests are only logged.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// metadata cannot be fetched or if any statement
+    /// cannot be prepared.
+    #[pyo3(signature = (statements = None))]
+    pub fn warmup<'a>(
+        &'a self,
+        py: Python<'a>,
+        statements: Option<Vec<PrepareInput>>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            session.refresh_metadata().await?;
+            let statement = session.prepare(WARMUP_QUERY).await?;
+            // Tokens of keys are the same for all nodes,
+            // only shards, which own them, differ.
+            let mut keys = Vec::with_capacity(WARMUP_KEYS);
+            for index in 0..WARMUP_KEYS {
+                let key = format!("warmup{index}");
+                if let Some(token) = statement.calculate_token(&(key.as_str(),))? {
+                    keys.push((token, key));
+                }
+            }
+            let mut requests = Vec::new();
+            for node in session.get_cluster_data().get_nodes_info() {
+                if !node.is_enabled() {
+                    continue;
+                }
+                let profile = scylla::ExecutionProfile::builder()
+                    .load_balancing_policy(Arc::new(SingleNodePolicy::new(node.host_id)))
+                    .build()
+                    .into_handle();
+                let mut node_statement = statement.clone();
+                node_statement.set_execution_profile_handle(Some(profile));
+                let Some(sharder) = node.sharder() else {
+                    requests.extend(
+                        keys.iter()
+                            .take(WARMUP_REQUESTS_PER_SHARD)
+                            .map(|(_, key)| (node_statement.clone(), key.as_str())),
+                    );
+                    continue;
+                };
+                let mut sent = vec![0; usize::from(sharder.nr_shards.get())];
+                for (token, key) in &keys {
+                    let Some(shard_sent) = usize::try_from(sharder.shard_of(*token))
+                        .ok()
+                        .and_then(|shard| sent.get_mut(shard))
+                    else {
+                        continue;
+                    };
+                    if *shard_sent < WARMUP_REQUESTS_PER_SHARD {
+                        *shard_sent += 1;
+                        requests.push((node_statement.clone(), key.as_str()));
+                    }
+                }
+            }
+            let warmup = futures::future::join_all(
+                requests
+                    .iter()
+                    .map(|(statement, key)| session.execute(statement, (*key,))),
+            );
+            let prepare = futures::future::try_join_all(
+                statements
+                    .into_iter()
+                    .flatten()
+                    .map(|query| query.prepare(session)),
+            );
+            let (warmed_up, prepared) = futures::join!(warmup, prepare);
+            for err in warmed_up.into_iter().filter_map(Result::err) {
+                log::warn!("Warmup request has failed: {err}");
+            }
+            Ok(prepared?)
+        })
+    }
+
+    /// Prepare multiple queries.
+    ///
+    /// All queries are prepared concurrently.
+    /// Returns a list of prepared queries
+    /// in the same order as passed queries.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized
+    /// or if any of queries cannot be prepared.
+    pub fn prepare_many<'a>(
+        &'a self,
+        python: Python<'a>,
+        queries: Vec<PrepareInput>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(python, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            Ok(futures::future::try_join_all(
+                queries.into_iter().map(|query| query.prepare(session)),
+            )
+            .await?)
+        })
+    }
+
+    /// Get replicas for given parameters of prepared query.
+    ///
+    /// Returns addresses of nodes, that own
+    /// the partition selected by parameters.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized
+    /// or if parameters cannot be parsed.
+    #[pyo3(signature = (query, params = None))]
+    pub fn get_replicas<'a>(
+        &'a self,
+        py: Python<'a>,
+        query: ScyllaPyPreparedQuery,
+        params: Option<&'a PyAny>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let token = query.token(py, params)?;
+        let keyspace = query.inner.get_keyspace_name().map(ToString::to_string);
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let (Some(token), Some(keyspace)) = (token, keyspace) else {
+                return Ok(Vec::new());
+            };
+            Ok(session
+                .get_cluster_data()
+                .get_token_endpoints(&keyspace, token)
+                .iter()
+                .map(|node| node.address.to_string())
+                .collect::<Vec<_>>())
+        })
+    }
+
+    /// Set keyspace to all connections.
+    ///
+    /// # Errors
+    /// May return an error, if
+    /// sessions was not initialized.
+    pub fn use_keyspace<'a>(
+        &'a self,
+        python: Python<'a>,
+        keyspace: String,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(python, async move {
+            let guard = session_arc.write().await;
+            let session = guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            session.use_keyspace(keyspace, true).await?;
+            Ok(())
+        })
+    }
+
+    /// Get current keyspace.
+    ///
+    /// # Errors
+    /// May return an error, if
+    /// sessions was not initialized.
+    pub fn get_keyspace<'a>(&'a self, python: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
         let session_arc = self.scylla_session.clone();
         scyllapy_future(python, async move {
             let guard = session_arc.write().await;
@@ -476,4 +2525,891 @@ impl Scylla {
             Ok(keyspace)
         })
     }
+
+    /// Get remaining TTL of columns in seconds.
+    ///
+    /// Row is found by values of primary key columns.
+    /// Returns a dict with TTL of each column,
+    /// TTL is `None` if the column doesn't expire or is null.
+    /// If the row doesn't exist, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if key or columns are empty,
+    /// session is not initialized or query fails.
+    pub fn get_ttl<'a>(
+        &'a self,
+        py: Python<'a>,
+        table: String,
+        key: &'a PyDict,
+        columns: Vec<String>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        self.select_cell_metadata(py, "TTL", &table, key, columns)
+    }
+
+    /// Get write time of columns in microseconds.
+    ///
+    /// Row is found by values of primary key columns.
+    /// Returns a dict with write time of each column,
+    /// it's `None` if the column is null.
+    /// If the row doesn't exist, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if key or columns are empty,
+    /// session is not initialized or query fails.
+    pub fn get_writetime<'a>(
+        &'a self,
+        py: Python<'a>,
+        table: String,
+        key: &'a PyDict,
+        columns: Vec<String>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        self.select_cell_metadata(py, "WRITETIME", &table, key, columns)
+    }
+
+    /// Get user defined functions of the keyspace.
+    ///
+    /// If keyspace isn't passed, keyspace of the session is used.
+    /// Functions are read from `system_schema.functions`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace is not specified or schema cannot be read.
+    #[pyo3(signature = (keyspace = None))]
+    pub fn get_functions<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            let rows = session
+                .query(
+                    "SELECT function_name, argument_names, argument_types, return_type, \
+                    language, body, called_on_null_input \
+                    FROM system_schema.functions WHERE keyspace_name = ?",
+                    (keyspace,),
+                )
+                .await?
+                .rows_typed::<FunctionRow>()
+                .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let functions = PyList::empty(py);
+                for (name, argument_names, argument_types, returns, language, body, on_null) in rows
+                {
+                    let function = PyDict::new(py);
+                    function.set_item("name", name)?;
+                    function.set_item("argument_names", argument_names.unwrap_or_default())?;
+                    function.set_item("argument_types", argument_types.unwrap_or_default())?;
+                    function.set_item("returns", returns)?;
+                    function.set_item("language", language)?;
+                    function.set_item("body", body)?;
+                    function.set_item("called_on_null_input", on_null.unwrap_or(false))?;
+                    functions.append(function)?;
+                }
+                Ok(functions.into())
+            })
+        })
+    }
+
+    /// Get user defined aggregates of the keyspace.
+    ///
+    /// If keyspace isn't passed, keyspace of the session is used.
+    /// Aggregates are read from `system_schema.aggregates`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace is not specified or schema cannot be read.
+    #[pyo3(signature = (keyspace = None))]
+    pub fn get_aggregates<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            let rows = session
+                .query(
+                    "SELECT aggregate_name, argument_types, state_func, state_type, \
+                    final_func, initcond, return_type \
+                    FROM system_schema.aggregates WHERE keyspace_name = ?",
+                    (keyspace,),
+                )
+                .await?
+                .rows_typed::<AggregateRow>()
+                .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| ScyllaPyError::RowsDowncastError(err.to_string()))?;
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let aggregates = PyList::empty(py);
+                for (name, argument_types, state_func, state_type, final_func, initcond, returns) in
+                    rows
+                {
+                    let aggregate = PyDict::new(py);
+                    aggregate.set_item("name", name)?;
+                    aggregate.set_item("argument_types", argument_types.unwrap_or_default())?;
+                    aggregate.set_item("state_function", state_func)?;
+                    aggregate.set_item("state_type", state_type)?;
+                    aggregate.set_item("final_function", final_func)?;
+                    aggregate.set_item("initial_condition", initcond)?;
+                    aggregate.set_item("returns", returns)?;
+                    aggregates.append(aggregate)?;
+                }
+                Ok(aggregates.into())
+            })
+        })
+    }
+
+    /// Get all keyspaces of the cluster.
+    ///
+    /// Keyspaces are taken from the cluster metadata of the driver,
+    /// which is refreshed periodically. If `refresh` is true,
+    /// metadata is refreshed before returning, which reads
+    /// the whole schema and topology of the cluster.
+    /// Each keyspace has its name, replication strategy
+    /// with options and names of tables, views and user types.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized
+    /// or metadata cannot be refreshed.
+    #[pyo3(signature = (*, refresh = false))]
+    pub fn get_keyspaces<'a>(&'a self, py: Python<'a>, refresh: bool) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let mut keyspaces = cluster_data.get_keyspace_info().iter().collect::<Vec<_>>();
+            keyspaces.sort_by_key(|(name, _)| *name);
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let result = PyList::empty(py);
+                for (name, keyspace) in keyspaces {
+                    result.append(keyspace_to_dict(py, name, keyspace)?)?;
+                }
+                Ok(result.into())
+            })
+        })
+    }
+
+    /// Get tables of the keyspace.
+    ///
+    /// If keyspace isn't passed, keyspace of the session is used.
+    /// Tables are taken from the cluster metadata,
+    /// which is refreshed before returning only if `refresh` is true.
+    /// Each table has its keyspace, name, partition and clustering keys,
+    /// partitioner and columns.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace is not specified or doesn't exist.
+    #[pyo3(signature = (keyspace = None, *, refresh = false))]
+    pub fn get_tables<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+        refresh: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let mut tables = find_keyspace(&cluster_data, &keyspace)?
+                .tables
+                .iter()
+                .collect::<Vec<_>>();
+            tables.sort_by_key(|(name, _)| *name);
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let result = PyList::empty(py);
+                for (name, table) in tables {
+                    result.append(table_to_dict(py, &keyspace, name, table)?)?;
+                }
+                Ok(result.into())
+            })
+        })
+    }
+
+    /// Get columns of the table.
+    ///
+    /// If keyspace is `None`, keyspace of the session is used.
+    /// Columns are taken from the cluster metadata,
+    /// which is refreshed before returning only if `refresh` is true.
+    /// Columns of partition key go first, then columns
+    /// of clustering key and then other columns sorted by name.
+    /// Each column has its name, CQL type and kind, which is one of
+    /// `partition_key`, `clustering`, `static` and `regular`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace is not specified or table doesn't exist.
+    #[pyo3(signature = (keyspace, table, *, refresh = false))]
+    pub fn get_columns<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+        table: String,
+        refresh: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let table = find_table(&cluster_data, &keyspace, &table)?;
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                Ok(columns_to_list(py, table)?.into())
+            })
+        })
+    }
+
+    /// Get user defined types of the keyspace.
+    ///
+    /// If keyspace isn't passed, keyspace of the session is used.
+    /// Types are taken from the cluster metadata,
+    /// which is refreshed before returning only if `refresh` is true.
+    /// Each type has its keyspace, name and fields
+    /// with their names and CQL types.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace is not specified or doesn't exist.
+    #[pyo3(signature = (keyspace = None, *, refresh = false))]
+    pub fn get_user_types<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+        refresh: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let mut user_types = find_keyspace(&cluster_data, &keyspace)?
+                .user_defined_types
+                .values()
+                .collect::<Vec<_>>();
+            user_types.sort_by_key(|udt| &udt.name);
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let result = PyList::empty(py);
+                for udt in user_types {
+                    result.append(user_type_to_dict(py, udt)?)?;
+                }
+                Ok(result.into())
+            })
+        })
+    }
+
+    /// Get `CREATE TABLE` statement of the table.
+    ///
+    /// Table can be prefixed with keyspace, otherwise
+    /// keyspace of the session is used. Names are parsed as in CQL,
+    /// so quoted names are case-sensitive.
+    /// Statement includes columns, primary key,
+    /// clustering order and options of the table.
+    /// Columns are taken from the cluster metadata,
+    /// which is refreshed before returning only if `refresh` is true.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// table doesn't exist or schema cannot be read.
+    #[pyo3(signature = (table, *, refresh = false))]
+    pub fn describe_table<'a>(
+        &'a self,
+        py: Python<'a>,
+        table: String,
+        refresh: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let (keyspace, table) = parse_table_name(&table)?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let table_info = find_table(&cluster_data, &keyspace, &table)?;
+            create_table_cql(session, &keyspace, &table, table_info).await
+        })
+    }
+
+    /// Get statements, which create the keyspace.
+    ///
+    /// If keyspace isn't passed, keyspace of the session is used.
+    /// `CREATE KEYSPACE` statement is followed by statements
+    /// of user defined types and tables of the keyspace.
+    /// Each table is followed by its secondary indexes
+    /// and materialized views. Tables and types are taken
+    /// from the cluster metadata, which is refreshed
+    /// before returning only if `refresh` is true.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// keyspace doesn't exist or schema cannot be read.
+    #[pyo3(signature = (keyspace = None, *, refresh = false))]
+    pub fn describe_keyspace<'a>(
+        &'a self,
+        py: Python<'a>,
+        keyspace: Option<String>,
+        refresh: bool,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let keyspace = schema_keyspace(session, keyspace)?;
+            if refresh {
+                session.refresh_metadata().await?;
+            }
+            let cluster_data = session.get_cluster_data();
+            let keyspace_info = find_keyspace(&cluster_data, &keyspace)?;
+            create_keyspace_cql(session, &keyspace, keyspace_info).await
+        })
+    }
+
+    /// Latency histograms of requests by statement kind.
+    ///
+    /// Keys are `SELECT`, `INSERT`, `UPDATE`, `DELETE`,
+    /// `BATCH` and `OTHER` for all other statements.
+    /// Only kinds with executed requests are returned.
+    /// Latencies are recorded only if histograms are enabled.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn latency_histograms<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        match &self.latency_histograms {
+            Some(histograms) => histograms.to_dict(py),
+            None => Ok(PyDict::new(py)),
+        }
+    }
+
+    /// Remove all recorded latencies.
+    pub fn reset_latency_histograms(&self) {
+        if let Some(histograms) = &self.latency_histograms {
+            histograms.reset();
+        }
+    }
+
+    /// Latencies of nodes measured by latency awareness.
+    ///
+    /// Statistics are taken from the load balancing
+    /// policy of the default execution profile.
+    /// Each dict shows average latency of the node,
+    /// how much slower it is than the fastest one
+    /// and whether it's penalized for being slow.
+    /// If policy doesn't have latency awareness, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If statistics cannot be converted to python.
+    pub fn latency_stats<'a>(&self, py: Python<'a>) -> PyResult<Option<&'a PyList>> {
+        self.default_execution_profile
+            .as_ref()
+            .map_or(Ok(None), |profile| profile.latency_stats(py))
+    }
+
+    /// Timings of request processing stages.
+    ///
+    /// Stages are `bind`, `network`, `decode` and `conversion`.
+    /// Timings are recorded only if profiling is enabled.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn profile_report<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        match &self.profiler {
+            Some(profiler) => profiler.to_dict(py),
+            None => Ok(PyDict::new(py)),
+        }
+    }
+
+    /// Remove all recorded timings of stages.
+    pub fn reset_profile_report(&self) {
+        if let Some(profiler) = &self.profiler {
+            profiler.reset();
+        }
+    }
+
+    /// Runner of migrations from the directory.
+    ///
+    /// Migration files are named with version and name,
+    /// for example `0001_create_users.cql`. CQL migrations
+    /// can be rolled back with `.down.cql` files, python migrations
+    /// have `up` and `down` async functions, which accept the session.
+    ///
+    /// # Errors
+    ///
+    /// If table name is not a valid identifier.
+    #[pyo3(signature = (path, table = DEFAULT_MIGRATIONS_TABLE.to_string()))]
+    pub fn migrations(
+        slf: Py<Self>,
+        py: Python<'_>,
+        path: PathBuf,
+        table: String,
+    ) -> ScyllaPyResult<ScyllaPyMigrations> {
+        let session = slf.get().scylla_session.clone();
+        ScyllaPyMigrations::new(slf.clone_ref(py), session, path, table)
+    }
+
+    /// Get timeline of a traced request.
+    ///
+    /// Tracing info is fetched from `system_traces` tables.
+    /// Timeline has events grouped by nodes with elapsed microseconds.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized,
+    /// trace id is not a valid uuid or tracing info cannot be fetched.
+    pub fn trace_timeline<'a>(
+        &'a self,
+        py: Python<'a>,
+        trace_id: String,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let tracing_id = uuid::Uuid::parse_str(&trace_id)
+            .map_err(|err| PyValueError::new_err(format!("Invalid trace id: {err}")))?;
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let tracing = session.get_tracing_info(&tracing_id).await?;
+            let timeline = Python::with_gil(|py| -> PyResult<PyObject> {
+                Ok(trace_timeline(py, &trace_id, &tracing)?.into())
+            })?;
+            Ok(timeline)
+        })
+    }
+
+    /// Statistics of statements.
+    ///
+    /// Statements are grouped by normalized text, where literals
+    /// are replaced with `?`. Each dict has number of calls,
+    /// errors, error rate, total latency and latency quantiles
+    /// in milliseconds. Statements with the highest total latency
+    /// are first. Statistics are collected only if they are enabled.
+    ///
+    /// # Errors
+    ///
+    /// If dicts cannot be created.
+    pub fn statement_stats<'a>(&self, py: Python<'a>) -> PyResult<&'a PyList> {
+        match &self.statement_stats {
+            Some(statement_stats) => statement_stats.to_list(py),
+            None => Ok(PyList::empty(py)),
+        }
+    }
+
+    /// Remove all statement statistics.
+    pub fn reset_statement_stats(&self) {
+        if let Some(statement_stats) = &self.statement_stats {
+            statement_stats.reset();
+        }
+    }
+
+    /// Statistics of nodes in the cluster.
+    ///
+    /// Returns a dict for each known node with its address,
+    /// datacenter, rack, state, configured size of the pool,
+    /// in-flight requests, finished requests, errors
+    /// and average latency in milliseconds.
+    ///
+    /// The driver doesn't expose opened connections, so the size
+    /// of the pool is calculated from pool settings and number
+    /// of shards of the node. It's zero, if node is down.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized.
+    pub fn node_stats<'a>(&'a self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let node_stats = self.node_stats.clone();
+        let pool_size_per_host = self.pool_size_per_host;
+        let pool_size_per_shard = self.pool_size_per_shard;
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let cluster_data = session.get_cluster_data();
+            let stats = Python::with_gil(|py| -> PyResult<Vec<PyObject>> {
+                let mut result = Vec::new();
+                for node in cluster_data.get_nodes_info() {
+                    let address = SocketAddr::new(node.address.ip(), node.address.port());
+                    let counters = node_stats.get(address);
+                    let is_up = node.is_enabled() && !node.is_down();
+                    let configured_pool_size = match (is_up, pool_size_per_host) {
+                        (false, _) => 0,
+                        (true, Some(per_host)) => per_host.get(),
+                        (true, None) => {
+                            let shards = node
+                                .sharder()
+                                .map_or(1, |sharder| usize::from(sharder.nr_shards.get()));
+                            pool_size_per_shard.map_or(1, NonZeroUsize::get) * shards
+                        }
+                    };
+                    let info = PyDict::new(py);
+                    info.set_item("address", node.address.to_string())?;
+                    info.set_item("host_id", node.host_id.to_string())?;
+                    info.set_item("datacenter", node.datacenter.as_deref())?;
+                    info.set_item("rack", node.rack.as_deref())?;
+                    info.set_item("is_up", is_up)?;
+                    info.set_item("configured_pool_size", configured_pool_size)?;
+                    info.set_item("in_flight", counters.in_flight)?;
+                    info.set_item("requests", counters.requests)?;
+                    info.set_item("errors", counters.errors)?;
+                    info.set_item(
+                        "average_latency",
+                        counters
+                            .average_latency()
+                            .map(|latency| latency.as_secs_f64() * 1000.0),
+                    )?;
+                    result.push(info.into());
+                }
+                Ok(result)
+            })?;
+            Ok(stats)
+        })
+    }
+
+    /// Features of connections to the cluster.
+    ///
+    /// Returns a dict with requested compression,
+    /// whether shard-aware port is used and info about nodes.
+    /// The driver doesn't expose what connections have negotiated,
+    /// so protocol version isn't reported and compression is the
+    /// requested one. Connections fall back to no compression,
+    /// if nodes don't support it.
+    /// Each node has its address, host id, datacenter, rack, state,
+    /// whether it's shard aware, number of shards and release version.
+    /// Release version is requested from each node which is up,
+    /// it's `None` if the request has failed.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if session is not initialized.
+    pub fn connection_info<'a>(&'a self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let shard_aware_port = !self.disallow_shard_aware_port.unwrap_or_default();
+        let compression = self.compression;
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let session_guard = session_arc.read().await;
+            let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+                "Session is not initialized.".into(),
+            ))?;
+            let cluster_data = session.get_cluster_data();
+            let nodes = cluster_data.get_nodes_info();
+            let versions = futures::future::join_all(nodes.iter().map(|node| async move {
+                if !node.is_enabled() || node.is_down() {
+                    return None;
+                }
+                let profile = scylla::ExecutionProfile::builder()
+                    .load_balancing_policy(Arc::new(SingleNodePolicy::new(node.host_id)))
+                    .build()
+                    .into_handle();
+                let mut query = Query::new(VERSION_QUERY);
+                query.set_execution_profile_handle(Some(profile));
+                let version = session
+                    .query(query, ())
+                    .await
+                    .map_err(|err| err.to_string())
+                    .and_then(|res| {
+                        res.single_row_typed::<(Option<String>,)>()
+                            .map_err(|err| err.to_string())
+                    });
+                match version {
+                    Ok((version,)) => version,
+                    Err(err) => {
+                        log::warn!("Cannot get version of the node {}: {err}", node.address);
+                        None
+                    }
+                }
+            }))
+            .await;
+            Python::with_gil(|py| -> ScyllaPyResult<PyObject> {
+                let nodes_info = PyList::empty(py);
+                for (node, version) in nodes.iter().zip(versions) {
+                    let sharder = node.sharder();
+                    let info = PyDict::new(py);
+                    info.set_item("address", node.address.to_string())?;
+                    info.set_item("host_id", node.host_id.to_string())?;
+                    info.set_item("datacenter", node.datacenter.as_deref())?;
+                    info.set_item("rack", node.rack.as_deref())?;
+                    info.set_item("is_up", node.is_enabled() && !node.is_down())?;
+                    info.set_item("shard_aware", sharder.is_some())?;
+                    info.set_item("shards", sharder.map(|sharder| sharder.nr_shards.get()))?;
+                    info.set_item("release_version", version)?;
+                    nodes_info.append(info)?;
+                }
+                let result = PyDict::new(py);
+                result.set_item(
+                    "requested_compression",
+                    compression.map(ScyllaPyCompression::as_str),
+                )?;
+                result.set_item("shard_aware_port", shard_aware_port)?;
+                result.set_item("nodes", nodes_info)?;
+                Ok(result.into())
+            })
+        })
+    }
+
+    /// Mirror requests to another cluster.
+    ///
+    /// Inserts, updates, deletes and batches are sent
+    /// to the shadow cluster as well. Selects are sent
+    /// with probability of `read_sampling`.
+    /// Other statements, like schema changes, aren't mirrored.
+    ///
+    /// Requests are mirrored only after they succeed on the main cluster,
+    /// with the same consistency, profile and timestamp.
+    /// Timestamps are generated by the configured generator,
+    /// with `SERVER` generator each cluster assigns its own timestamps.
+    ///
+    /// Mirrored requests are sent in background,
+    /// their errors are logged and counted in `shadow_stats`,
+    /// but never raised. At most `max_in_flight` of them are sent
+    /// at the same time, others are dropped and counted.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if sampling is not in range from 0 to 1
+    /// or `max_in_flight` is zero.
+    #[pyo3(signature = (shadow, *, read_sampling = 0.0, max_in_flight = DEFAULT_SHADOW_IN_FLIGHT))]
+    pub fn attach_shadow(
+        &self,
+        shadow: &Scylla,
+        read_sampling: f64,
+        max_in_flight: usize,
+    ) -> ScyllaPyResult<()> {
+        if !(0.0..=1.0).contains(&read_sampling) {
+            return Err(
+                PyValueError::new_err("Read sampling should be in range from 0 to 1.").into(),
+            );
+        }
+        if max_in_flight == 0 {
+            return Err(PyValueError::new_err("Max in flight should be positive.").into());
+        }
+        let target = ShadowTarget::new(shadow.scylla_session.clone(), read_sampling, max_in_flight);
+        if let Ok(mut current) = self.shadow.write() {
+            *current = Some(target);
+        }
+        Ok(())
+    }
+
+    /// Stop mirroring requests.
+    pub fn detach_shadow(&self) {
+        if let Ok(mut current) = self.shadow.write() {
+            *current = None;
+        }
+    }
+
+    /// Counters of mirrored requests.
+    ///
+    /// Returns a dict with number of mirrored writes and reads,
+    /// number of errors, dropped requests and the last error,
+    /// or `None` if shadow cluster isn't attached.
+    ///
+    /// # Errors
+    ///
+    /// If dict cannot be created.
+    pub fn shadow_stats<'a>(&self, py: Python<'a>) -> PyResult<Option<&'a PyDict>> {
+        self.shadow_target()
+            .map(|shadow| shadow.stats(py))
+            .transpose()
+    }
+
+    /// Number of reads, which got results of identical reads in flight.
+    ///
+    /// Returns `None` if reads aren't coalesced.
+    #[must_use]
+    pub fn coalesced_reads(&self) -> Option<u64> {
+        self.read_coalescer
+            .as_ref()
+            .map(|coalescer| coalescer.coalesced())
+    }
+
+    /// Set TTL of cached results of the statement in seconds.
+    ///
+    /// Zero TTL disables caching of the statement,
+    /// `None` makes it use the default TTL of the cache.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if the cache is disabled or TTL is negative.
+    pub fn set_cache_ttl(&self, statement: String, ttl: Option<f64>) -> ScyllaPyResult<()> {
+        let cache = self
+            .result_cache
+            .as_ref()
+            .ok_or(ScyllaPyError::SessionError(
+                "Result cache is disabled.".into(),
+            ))?;
+        let ttl = ttl
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|_| PyValueError::new_err("Cache TTL should be a non-negative number."))?;
+        cache.set_ttl(statement, ttl);
+        Ok(())
+    }
+
+    /// Remove cached results of the statement.
+    ///
+    /// If statement isn't specified, all results are removed.
+    #[pyo3(signature = (statement = None))]
+    pub fn invalidate_cache(&self, statement: Option<&str>) {
+        if let Some(cache) = &self.result_cache {
+            cache.invalidate(statement);
+        }
+    }
+
+    /// Counters of the result cache.
+    ///
+    /// Returns a dict with number of hits, misses,
+    /// current and maximum number of cached results and their bytes,
+    /// or `None` if the cache is disabled.
+    ///
+    /// # Errors
+    ///
+    /// If dict cannot be created.
+    pub fn cache_stats<'a>(&self, py: Python<'a>) -> PyResult<Option<&'a PyDict>> {
+        self.result_cache
+            .as_ref()
+            .map(|cache| cache.stats(py))
+            .transpose()
+    }
+
+    /// Wait until the cluster is ready to serve requests.
+    ///
+    /// Cluster is healthy, when the session is initialized,
+    /// metadata can be fetched through the control connection
+    /// and at least `min_nodes_up` nodes are up.
+    ///
+    /// # Errors
+    ///
+    /// May return an error, if timeout is incorrect
+    /// or cluster isn't healthy after `timeout` seconds.
+    #[pyo3(signature = (timeout, *, min_nodes_up = NonZeroUsize::MIN))]
+    pub fn await_healthy<'a>(
+        &'a self,
+        py: Python<'a>,
+        timeout: f64,
+        min_nodes_up: NonZeroUsize,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        if !timeout.is_finite() || timeout < 0.0 {
+            return Err(PyValueError::new_err("Timeout should be a non-negative number.").into());
+        }
+        let session_arc = self.scylla_session.clone();
+        scyllapy_future(py, async move {
+            let deadline = tokio::time::Instant::now() + Duration::from_secs_f64(timeout);
+            let mut state = String::from("Session is not initialized.");
+            loop {
+                if let Some(session) = session_arc.read().await.as_ref() {
+                    state =
+                        match tokio::time::timeout_at(deadline, session.refresh_metadata()).await {
+                            Ok(Ok(())) => {
+                                let nodes_up = session
+                                    .get_cluster_data()
+                                    .get_nodes_info()
+                                    .iter()
+                                    .filter(|node| node.is_enabled() && !node.is_down())
+                                    .count();
+                                if nodes_up >= min_nodes_up.get() {
+                                    return Ok(());
+                                }
+                                format!("{nodes_up} of {min_nodes_up} required nodes are up.")
+                            }
+                            Ok(Err(err)) => format!("Control connection is not established: {err}"),
+                            Err(_) => String::from("Metadata wasn't fetched in time."),
+                        };
+                }
+                if tokio::time::Instant::now() + HEALTH_CHECK_INTERVAL > deadline {
+                    break;
+                }
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            }
+            let message = format!("Cluster isn't healthy after {timeout} seconds. {state}");
+            Err(ScyllaPyError::PyError(Python::with_gil(|py| {
+                timeout_error_type(py).map_or_else(
+                    |err| err,
+                    |error_type| PyErr::from_type(error_type, (message,)),
+                )
+            })))
+        })
+    }
+
+    /// Register a callback, which is called before each request.
+    ///
+    /// Callback receives `RequestInfo` of the request.
+    /// The callback is returned, so this method can be used as a decorator.
+    pub fn on_request_start(&self, callback: Py<PyAny>) -> Py<PyAny> {
+        self.listeners.add_start(callback.clone());
+        callback
+    }
+
+    /// Register a callback, which is called after each successful request.
+    ///
+    /// Callback receives `RequestInfo` of the request
+    /// and its duration in milliseconds.
+    pub fn on_request_end(&self, callback: Py<PyAny>) -> Py<PyAny> {
+        self.listeners.add_end(callback.clone());
+        callback
+    }
+
+    /// Register a callback, which is called after each failed request.
+    ///
+    /// Callback receives `RequestInfo` of the request
+    /// and the exception, which is raised.
+    pub fn on_request_error(&self, callback: Py<PyAny>) -> Py<PyAny> {
+        self.listeners.add_error(callback.clone());
+        callback
+    }
 }
@@ -0,0 +1,265 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+
+use pyo3::{types::PyDict, PyResult, Python};
+use scylla::{
+    batch::Batch,
+    execution_profile::ExecutionProfileHandle,
+    frame::value::LegacySerializedValues,
+    prepared_statement::PreparedStatement,
+    query::Query,
+    statement::{Consistency, SerialConsistency},
+    transport::errors::QueryError,
+    Session,
+};
+use tokio::sync::Semaphore;
+
+use crate::{batches::statement_texts, instrumentation::StatementKind};
+
+/// How many mirrored requests can be in flight by default.
+pub const DEFAULT_SHADOW_IN_FLIGHT: usize = 1024;
+
+/// Counters of mirrored requests.
+#[derive(Debug, Default)]
+struct ShadowStats {
+    writes: AtomicU64,
+    reads: AtomicU64,
+    errors: AtomicU64,
+    dropped: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl ShadowStats {
+    fn record(&self, res: Result<(), QueryError>) {
+        if let Err(err) = res {
+            log::warn!("Shadow request has failed: {err}");
+            self.errors.fetch_add(1, Ordering::Relaxed);
+            if let Ok(mut last_error) = self.last_error.lock() {
+                *last_error = Some(err.to_string());
+            }
+        }
+    }
+}
+
+/// Settings of the primary request, which are copied to its mirror.
+///
+/// Mirror gets the same client timestamp, consistency and profile,
+/// so both clusters resolve conflicting writes the same way,
+/// if timestamps are generated on the client.
+#[derive(Clone, Debug)]
+pub struct MirrorSettings {
+    profile: Option<ExecutionProfileHandle>,
+    consistency: Consistency,
+    serial_consistency: Option<SerialConsistency>,
+    timestamp: Option<i64>,
+}
+
+impl MirrorSettings {
+    #[must_use]
+    pub fn of_query(query: &Query, consistency: Consistency) -> Self {
+        Self {
+            profile: query.get_execution_profile_handle().cloned(),
+            consistency,
+            serial_consistency: query.get_serial_consistency(),
+            timestamp: query.get_timestamp(),
+        }
+    }
+
+    #[must_use]
+    pub fn of_prepared(prepared: &PreparedStatement, consistency: Consistency) -> Self {
+        Self {
+            profile: prepared.get_execution_profile_handle().cloned(),
+            consistency,
+            serial_consistency: prepared.get_serial_consistency(),
+            timestamp: prepared.get_timestamp(),
+        }
+    }
+
+    #[must_use]
+    pub fn of_batch(batch: &Batch, consistency: Consistency) -> Self {
+        Self {
+            profile: batch.get_execution_profile_handle().cloned(),
+            consistency,
+            serial_consistency: batch.get_serial_consistency(),
+            timestamp: batch.get_timestamp(),
+        }
+    }
+
+    fn apply_to_query(&self, query: &mut Query) {
+        query.set_execution_profile_handle(self.profile.clone());
+        query.set_consistency(self.consistency);
+        if self.serial_consistency.is_some() {
+            query.set_serial_consistency(self.serial_consistency);
+        }
+        query.set_timestamp(self.timestamp);
+    }
+
+    fn apply_to_batch(&self, batch: &mut Batch) {
+        batch.set_execution_profile_handle(self.profile.clone());
+        batch.set_consistency(self.consistency);
+        if self.serial_consistency.is_some() {
+            batch.set_serial_consistency(self.serial_consistency);
+        }
+        batch.set_timestamp(self.timestamp);
+    }
+}
+
+/// Statement of mirrored request with its values.
+enum MirroredStatement {
+    Query(Query, LegacySerializedValues),
+    Batch(Batch, Vec<LegacySerializedValues>),
+}
+
+/// Copy of the request, which is sent to the shadow cluster
+/// only after the primary request has succeeded.
+pub struct ShadowRequest {
+    target: ShadowTarget,
+    kind: StatementKind,
+    statement: MirroredStatement,
+}
+
+impl ShadowRequest {
+    /// Send the request in background.
+    ///
+    /// If too many mirrored requests are in flight,
+    /// the request is dropped and counted.
+    pub fn send(self) {
+        let stats = self.target.stats.clone();
+        let Ok(permit) = self.target.in_flight.clone().try_acquire_owned() else {
+            stats.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        };
+        if self.kind.is_write() {
+            stats.writes.fetch_add(1, Ordering::Relaxed);
+        } else {
+            stats.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        let session = self.target.session.clone();
+        let statement = self.statement;
+        pyo3_asyncio::tokio::get_runtime().spawn(async move {
+            let _permit = permit;
+            let session_guard = session.read().await;
+            let Some(session) = session_guard.as_ref() else {
+                return;
+            };
+            let res = match statement {
+                MirroredStatement::Query(query, values) => {
+                    session.query(query, values).await.map(|_| ())
+                }
+                MirroredStatement::Batch(batch, values) => {
+                    session.batch(&batch, values).await.map(|_| ())
+                }
+            };
+            stats.record(res);
+        });
+    }
+}
+
+/// Secondary cluster, which receives copies of requests.
+///
+/// Writes are always mirrored, reads are mirrored
+/// with probability of `read_sampling`.
+/// Requests are sent in background with at most
+/// `max_in_flight` of them at the same time.
+/// Their errors are only counted,
+/// so they never affect the primary cluster.
+#[derive(Clone, Debug)]
+pub struct ShadowTarget {
+    session: Arc<tokio::sync::RwLock<Option<Session>>>,
+    read_sampling: f64,
+    in_flight: Arc<Semaphore>,
+    stats: Arc<ShadowStats>,
+}
+
+impl ShadowTarget {
+    #[must_use]
+    pub fn new(
+        session: Arc<tokio::sync::RwLock<Option<Session>>>,
+        read_sampling: f64,
+        max_in_flight: usize,
+    ) -> Self {
+        Self {
+            session,
+            read_sampling,
+            in_flight: Arc::new(Semaphore::new(max_in_flight)),
+            stats: Arc::new(ShadowStats::default()),
+        }
+    }
+
+    /// Whether statement of the kind should be mirrored.
+    fn sample(&self, kind: StatementKind) -> bool {
+        kind.is_write()
+            || (kind == StatementKind::Select && rand::random::<f64>() < self.read_sampling)
+    }
+
+    /// Copy of the statement for the shadow cluster,
+    /// or `None` if it shouldn't be mirrored.
+    ///
+    /// Prepared statements have different ids in another cluster,
+    /// so their text is sent with already serialized values.
+    #[must_use]
+    pub fn request(
+        &self,
+        kind: StatementKind,
+        statement: &str,
+        values: &LegacySerializedValues,
+        settings: &MirrorSettings,
+    ) -> Option<ShadowRequest> {
+        if !self.sample(kind) {
+            return None;
+        }
+        let mut query = Query::new(statement);
+        settings.apply_to_query(&mut query);
+        Some(ShadowRequest {
+            target: self.clone(),
+            kind,
+            statement: MirroredStatement::Query(query, values.clone()),
+        })
+    }
+
+    /// Copy of the batch for the shadow cluster.
+    #[must_use]
+    pub fn batch_request(
+        &self,
+        batch: &Batch,
+        values: &[LegacySerializedValues],
+        settings: &MirrorSettings,
+    ) -> Option<ShadowRequest> {
+        if !self.sample(StatementKind::Batch) {
+            return None;
+        }
+        let mut shadow_batch = Batch::new(batch.get_type());
+        for statement in statement_texts(batch) {
+            shadow_batch.append_statement(Query::new(statement));
+        }
+        settings.apply_to_batch(&mut shadow_batch);
+        Some(ShadowRequest {
+            target: self.clone(),
+            kind: StatementKind::Batch,
+            statement: MirroredStatement::Batch(shadow_batch, values.to_vec()),
+        })
+    }
+
+    /// Counters of mirrored requests as a dict.
+    ///
+    /// # Errors
+    ///
+    /// If dict cannot be created.
+    pub fn stats<'a>(&self, py: Python<'a>) -> PyResult<&'a PyDict> {
+        let stats = PyDict::new(py);
+        stats.set_item("writes", self.stats.writes.load(Ordering::Relaxed))?;
+        stats.set_item("reads", self.stats.reads.load(Ordering::Relaxed))?;
+        stats.set_item("errors", self.stats.errors.load(Ordering::Relaxed))?;
+        stats.set_item("dropped", self.stats.dropped.load(Ordering::Relaxed))?;
+        let last_error = self
+            .stats
+            .last_error
+            .lock()
+            .ok()
+            .and_then(|last_error| last_error.clone());
+        stats.set_item("last_error", last_error)?;
+        Ok(stats)
+    }
+}
@@ -0,0 +1,131 @@
+use std::{future::Future, time::Duration};
+
+use pyo3::{exceptions::PyValueError, pyclass, pymethods, types::PyType, PyResult, Python};
+use scylla::transport::errors::{DbError, NewSessionError};
+
+/// Policy of connecting to the cluster, which isn't reachable yet.
+///
+/// It's used only by `startup`. Connections, which are lost later,
+/// are restored by the driver itself.
+///
+/// Attempts, which failed because of connection errors,
+/// are retried with exponential backoff,
+/// starting from `initial_delay` and capped by `max_delay`.
+/// If `max_attempts` is not set, attempts never stop.
+#[pyclass(name = "StartupRetryPolicy", module = "scyllapy._internal")]
+#[derive(Clone, Copy, Debug)]
+pub struct ScyllaPyStartupRetryPolicy {
+    #[pyo3(get)]
+    initial_delay: f64,
+    #[pyo3(get)]
+    max_delay: f64,
+    #[pyo3(get)]
+    max_attempts: Option<u32>,
+}
+
+impl ScyllaPyStartupRetryPolicy {
+    /// Delay after the failed attempt with the given number.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self.initial_delay * 2f64.powf(f64::from(attempt));
+        Duration::from_secs_f64(delay.min(self.max_delay))
+    }
+
+    /// Whether the error can go away, once the cluster is reachable.
+    ///
+    /// Other errors, like bad credentials or unknown keyspace,
+    /// are configuration errors, so they aren't retried.
+    fn is_transient(err: &NewSessionError) -> bool {
+        matches!(
+            err,
+            NewSessionError::FailedToResolveAnyHostname(_)
+                | NewSessionError::IoError(_)
+                | NewSessionError::TimeoutError
+                | NewSessionError::RequestTimeout(_)
+                | NewSessionError::TooManyOrphanedStreamIds(_)
+                | NewSessionError::UnableToAllocStreamId
+                | NewSessionError::DbError(
+                    DbError::Overloaded | DbError::IsBootstrapping | DbError::Unavailable { .. },
+                    _,
+                )
+        )
+    }
+
+    /// Create the session, retrying attempts,
+    /// which failed because of connection errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns the last error, if all attempts failed.
+    pub async fn run<T, F, Fut>(&self, mut connect: F) -> Result<T, NewSessionError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, NewSessionError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match connect().await {
+                Err(err)
+                    if Self::is_transient(&err)
+                        && self.max_attempts.map_or(true, |max| attempt + 1 < max) =>
+                {
+                    let delay = self.delay(attempt);
+                    log::warn!(
+                        "Cannot connect to the cluster: {err}. Retrying in {:.2}s.",
+                        delay.as_secs_f64()
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl ScyllaPyStartupRetryPolicy {
+    /// Create new startup retry policy.
+    ///
+    /// Delays are in seconds.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if delays are negative
+    /// or number of attempts is zero.
+    #[new]
+    #[pyo3(signature = (initial_delay = 0.5, max_delay = 10.0, max_attempts = Some(10)))]
+    pub fn py_new(initial_delay: f64, max_delay: f64, max_attempts: Option<u32>) -> PyResult<Self> {
+        for delay in [initial_delay, max_delay] {
+            if !delay.is_finite() || delay < 0.0 {
+                return Err(PyValueError::new_err(
+                    "Delays should be non-negative numbers.",
+                ));
+            }
+        }
+        if max_attempts == Some(0) {
+            return Err(PyValueError::new_err(
+                "At least one attempt should be allowed.",
+            ));
+        }
+        Ok(Self {
+            initial_delay,
+            max_delay,
+            max_attempts,
+        })
+    }
+
+    /// Pickle startup retry policy.
+    #[must_use]
+    pub fn __reduce__<'a>(&self, py: Python<'a>) -> (&'a PyType, (f64, f64, Option<u32>)) {
+        (
+            py.get_type::<Self>(),
+            (self.initial_delay, self.max_delay, self.max_attempts),
+        )
+    }
+
+    #[must_use]
+    pub fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
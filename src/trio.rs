@@ -0,0 +1,95 @@
+use std::{future::Future, pin::Pin};
+
+use pyo3::{pyclass, pymethods, types::PyCFunction, PyAny, PyObject, Python};
+
+use crate::exceptions::rust_err::{ScyllaPyError, ScyllaPyResult};
+
+type BoxedFuture = Pin<Box<dyn Future<Output = ScyllaPyResult<PyObject>> + Send>>;
+
+/// Check whether the current thread runs trio event loop.
+///
+/// Trio is checked only if it was imported,
+/// so applications without trio don't pay for it.
+#[must_use]
+pub fn in_trio(py: Python<'_>) -> bool {
+    let Ok(modules) = py.import("sys").and_then(|sys| sys.getattr("modules")) else {
+        return false;
+    };
+    if !modules.contains("trio").unwrap_or(false) {
+        return false;
+    }
+    py.import("trio.lowlevel")
+        .and_then(|lowlevel| lowlevel.call_method0("current_trio_token"))
+        .is_ok()
+}
+
+/// Awaitable for trio event loop.
+///
+/// Rust future is spawned on tokio runtime when the object
+/// is awaited. When it's done, the waiting task is rescheduled
+/// by trio's token, which is safe to call from other threads.
+///
+/// Cancellation doesn't interrupt the request,
+/// trio cancels the task after the request is completed.
+#[pyclass(name = "TrioFuture")]
+pub struct ScyllaPyTrioFuture {
+    fut: Option<BoxedFuture>,
+}
+
+impl ScyllaPyTrioFuture {
+    pub fn new<F>(fut: F) -> Self
+    where
+        F: Future<Output = ScyllaPyResult<PyObject>> + Send + 'static,
+    {
+        Self {
+            fut: Some(Box::pin(fut)),
+        }
+    }
+}
+
+#[pymethods]
+impl ScyllaPyTrioFuture {
+    /// Start the future and wait until the task is rescheduled.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if the future was already awaited,
+    /// or if it's awaited outside of trio.
+    pub fn __await__<'a>(&mut self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let fut = self.fut.take().ok_or(ScyllaPyError::SessionError(
+            "Future was already awaited.".into(),
+        ))?;
+        let lowlevel = py.import("trio.lowlevel")?;
+        let outcome = py.import("outcome")?;
+        let token: PyObject = lowlevel.call_method0("current_trio_token")?.into();
+        let task: PyObject = lowlevel.call_method0("current_task")?.into();
+        let reschedule: PyObject = lowlevel.getattr("reschedule")?.into();
+        let value_cls: PyObject = outcome.getattr("Value")?.into();
+        let error_cls: PyObject = outcome.getattr("Error")?.into();
+        let abort_failed: PyObject = lowlevel.getattr("Abort")?.getattr("FAILED")?.into();
+
+        pyo3_asyncio::tokio::get_runtime().spawn(async move {
+            let res = fut.await;
+            Python::with_gil(|py| {
+                let result = match res {
+                    Ok(value) => value_cls.call1(py, (value,)),
+                    Err(err) => error_cls.call1(py, (pyo3::PyErr::from(err).value(py),)),
+                }
+                .and_then(|result| {
+                    token.call_method1(py, "run_sync_soon", (reschedule, task, result))
+                });
+                // The loop may be already closed, nothing can be rescheduled then.
+                if let Err(err) = result {
+                    log::warn!("Cannot reschedule trio task: {err}");
+                }
+            });
+        });
+
+        let abort_fn = PyCFunction::new_closure(py, None, None, move |args, _kwargs| {
+            abort_failed.clone_ref(args.py())
+        })?;
+        Ok(lowlevel
+            .call_method1("wait_task_rescheduled", (abort_fn,))?
+            .call_method0("__await__")?)
+    }
+}
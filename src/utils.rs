@@ -1,24 +1,206 @@
-use std::{collections::HashMap, future::Future, hash::BuildHasherDefault, str::FromStr};
+use std::{
+    collections::HashMap,
+    future::Future,
+    hash::BuildHasherDefault,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use pyo3::{
-    types::{PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyModule, PySet, PyString, PyTuple},
-    IntoPy, Py, PyAny, PyObject, PyResult, Python, ToPyObject,
+    types::{
+        PyBool, PyBytes, PyDict, PyFloat, PyInt, PyList, PyModule, PySet, PyString, PyTuple, PyType,
+    },
+    IntoPy, Py, PyAny, PyCell, PyErr, PyObject, PyResult, PyTypeInfo, Python, ToPyObject,
 };
 use scylla::{
     frame::{
         response::result::{ColumnSpec, ColumnType, CqlValue},
         value::{CqlDuration, LegacySerializedValues, Value},
     },
-    BufMut,
+    history::{AttemptId, HistoryListener, QueryId, SpeculativeId},
+    retry_policy::RetryDecision,
+    transport::errors::QueryError,
+    BufMut, QueryResult,
 };
 
 use std::net::IpAddr;
 
 use crate::{
-    exceptions::rust_err::{ScyllaPyError, ScyllaPyResult},
+    exceptions::{
+        py_err::ScyllaPyServerWarning,
+        rust_err::{ScyllaPyError, ScyllaPyResult},
+    },
     extra_types::{BigInt, Counter, Double, ScyllaPyUnset, SmallInt, TinyInt},
+    instrumentation::NodeStatistics,
+    query_results::ScyllaPyLazyValue,
+    trio::{in_trio, ScyllaPyTrioFuture},
 };
 
+/// Emit warnings returned by the server as python warnings.
+///
+/// # Errors
+///
+/// If warnings are turned into errors by python warning filters.
+pub fn emit_server_warnings(warnings: &[String]) -> ScyllaPyResult<()> {
+    if warnings.is_empty() {
+        return Ok(());
+    }
+    Python::with_gil(|py| {
+        let category = py.get_type::<ScyllaPyServerWarning>();
+        for warning in warnings {
+            PyErr::warn(py, category, warning, 1)?;
+        }
+        Ok(())
+    })
+}
+
+/// Quote identifier, so names are used as they are in schema.
+#[must_use]
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Quote name of the table, which can be prefixed with keyspace.
+#[must_use]
+pub fn quote_table(table: &str) -> String {
+    match table.split_once('.') {
+        Some((keyspace, table)) => {
+            format!("{}.{}", quote_identifier(keyspace), quote_identifier(table))
+        }
+        None => quote_identifier(table),
+    }
+}
+
+/// Single attempt of a query.
+#[derive(Debug)]
+struct Attempt {
+    node: SocketAddr,
+    started: Instant,
+    error: Option<String>,
+    finished: bool,
+}
+
+/// Listener that keeps errors of query attempts.
+///
+/// Attempts are counted including retries
+/// and speculative executions. All attempts
+/// are also recorded in statistics of nodes.
+#[derive(Debug)]
+pub struct AttemptsListener {
+    attempts: Mutex<Vec<Attempt>>,
+    node_stats: Arc<NodeStatistics>,
+}
+
+impl AttemptsListener {
+    #[must_use]
+    pub fn new(node_stats: Arc<NodeStatistics>) -> Self {
+        Self {
+            attempts: Mutex::default(),
+            node_stats,
+        }
+    }
+
+    /// Number of attempts and errors of failed ones.
+    #[must_use]
+    pub fn summary(&self) -> (usize, Vec<String>) {
+        let Ok(attempts) = self.attempts.lock() else {
+            return (0, Vec::new());
+        };
+        let errors = attempts
+            .iter()
+            .filter_map(|attempt| {
+                let node = attempt.node;
+                attempt
+                    .error
+                    .as_ref()
+                    .map(|error| format!("{node}: {error}"))
+            })
+            .collect();
+        (attempts.len(), errors)
+    }
+
+    /// Address of the node, which has received the last attempt.
+    #[must_use]
+    pub fn last_node(&self) -> Option<SocketAddr> {
+        let attempts = self.attempts.lock().ok()?;
+        attempts.last().map(|attempt| attempt.node)
+    }
+}
+
+impl HistoryListener for AttemptsListener {
+    fn log_query_start(&self) -> QueryId {
+        QueryId(0)
+    }
+
+    fn log_query_success(&self, _query_id: QueryId) {}
+
+    fn log_query_error(&self, _query_id: QueryId, _error: &QueryError) {}
+
+    fn log_new_speculative_fiber(&self, _query_id: QueryId) -> SpeculativeId {
+        SpeculativeId(0)
+    }
+
+    fn log_attempt_start(
+        &self,
+        _query_id: QueryId,
+        _speculative_id: Option<SpeculativeId>,
+        node_addr: SocketAddr,
+    ) -> AttemptId {
+        let Ok(mut attempts) = self.attempts.lock() else {
+            return AttemptId(0);
+        };
+        self.node_stats.attempt_started(node_addr);
+        attempts.push(Attempt {
+            node: node_addr,
+            started: Instant::now(),
+            error: None,
+            finished: false,
+        });
+        AttemptId(attempts.len() - 1)
+    }
+
+    fn log_attempt_success(&self, attempt_id: AttemptId) {
+        if let Ok(mut attempts) = self.attempts.lock() {
+            if let Some(attempt) = attempts.get_mut(attempt_id.0) {
+                attempt.finished = true;
+                self.node_stats
+                    .attempt_succeeded(attempt.node, attempt.started.elapsed());
+            }
+        }
+    }
+
+    fn log_attempt_error(
+        &self,
+        attempt_id: AttemptId,
+        error: &QueryError,
+        _retry_decision: &RetryDecision,
+    ) {
+        if let Ok(mut attempts) = self.attempts.lock() {
+            if let Some(attempt) = attempts.get_mut(attempt_id.0) {
+                attempt.error = Some(error.to_string());
+                attempt.finished = true;
+                self.node_stats
+                    .attempt_failed(attempt.node, attempt.started.elapsed());
+            }
+        }
+    }
+}
+
+impl Drop for AttemptsListener {
+    /// Attempts can be left unfinished, for example if
+    /// request has timed out or speculative execution has won.
+    /// They shouldn't stay in-flight forever.
+    fn drop(&mut self) {
+        if let Ok(attempts) = self.attempts.get_mut() {
+            for attempt in attempts.iter().filter(|attempt| !attempt.finished) {
+                self.node_stats.attempt_abandoned(attempt.node);
+            }
+        }
+    }
+}
+
 const DATE_FORMAT: &[::time::format_description::FormatItem<'static>] =
     ::time::macros::format_description!(version = 2, "[year]-[month]-[day]");
 
@@ -54,6 +236,24 @@ pub fn add_submodule(
     Ok(())
 }
 
+/// Pickle support for enums.
+///
+/// Enum variants are restored by taking
+/// an attribute with the variant's name from its class.
+///
+/// # Errors
+///
+/// May return an error if builtins cannot be imported.
+pub fn reduce_enum_variant<T: PyTypeInfo>(
+    py: Python<'_>,
+    variant: String,
+) -> PyResult<(&PyAny, (&PyType, String))> {
+    Ok((
+        py.import("builtins")?.getattr("getattr")?,
+        (py.get_type::<T>(), variant),
+    ))
+}
+
 /// Small function to integrate custom result type
 /// and `pyo3_asyncio`.
 ///
@@ -61,6 +261,9 @@ pub fn add_submodule(
 /// but it expects future to return `ScyllaPyResult` type, rather
 /// than `PyResult` from `pyo3`. It's useful for using `?` operators all over the place.
 ///
+/// If there's no running asyncio loop, but the function
+/// is called inside of trio, trio-compatible awaitable is returned.
+///
 /// # Errors
 ///
 /// If result of a future was unsuccessful, it propagates the error.
@@ -69,8 +272,23 @@ where
     F: Future<Output = ScyllaPyResult<T>> + Send + 'static,
     T: IntoPy<PyObject>,
 {
-    let res = pyo3_asyncio::tokio::future_into_py(py, async { fut.await.map_err(Into::into) })
-        .map(Into::into)?;
+    let locals = match pyo3_asyncio::tokio::get_current_locals(py) {
+        Ok(locals) => locals,
+        Err(err) => {
+            if !in_trio(py) {
+                return Err(err.into());
+            }
+            let fut = ScyllaPyTrioFuture::new(async move {
+                let res = fut.await?;
+                Ok(Python::with_gil(|py| res.into_py(py)))
+            });
+            return Ok(Py::new(py, fut)?.into_ref(py).as_ref());
+        }
+    };
+    let res = pyo3_asyncio::tokio::future_into_py_with_locals(py, locals, async {
+        fut.await.map_err(Into::into)
+    })
+    .map(Into::into)?;
     Ok(res)
 }
 
@@ -107,8 +325,10 @@ pub enum ScyllaPyCQLDTO {
     Inet(IpAddr),
     List(Vec<ScyllaPyCQLDTO>),
     Map(Vec<(ScyllaPyCQLDTO, ScyllaPyCQLDTO)>),
-    // UDT holds serialized bytes according to the protocol.
-    Udt(Vec<u8>),
+    // UDT holds values of its fields in the order of definition.
+    Udt(Vec<ScyllaPyCQLDTO>),
+    // Vector of floats, which is used for vector search.
+    FloatVector(Vec<eq_float::F32>),
 }
 
 impl Value for ScyllaPyCQLDTO {
@@ -138,8 +358,32 @@ impl Value for ScyllaPyCQLDTO {
                 scylla::frame::value::CqlTimestamp::from(*timestamp).serialize(buf)
             }
             ScyllaPyCQLDTO::Null => Option::<bool>::None.serialize(buf),
-            ScyllaPyCQLDTO::Udt(udt) => {
-                buf.extend(udt);
+            ScyllaPyCQLDTO::Udt(fields) => {
+                // Here we put the size of UDT value.
+                // Now it's zero, but we will replace it after serialization.
+                let start = buf.len();
+                buf.put_i32(0);
+                for field in fields {
+                    field.serialize(buf)?;
+                }
+                // Size buffer itself isn't counted.
+                let size = i32::try_from(buf.len() - start - 4)
+                    .map_err(|_| scylla::_macro_internal::ValueTooBig)?;
+                buf[start..start + 4].copy_from_slice(&size.to_be_bytes());
+                Ok(())
+            }
+            ScyllaPyCQLDTO::FloatVector(floats) => {
+                // Elements of vectors have fixed size,
+                // so they are written without their own sizes.
+                let size = floats
+                    .len()
+                    .checked_mul(4)
+                    .and_then(|size| i32::try_from(size).ok())
+                    .ok_or(scylla::_macro_internal::ValueTooBig)?;
+                buf.put_i32(size);
+                for float in floats {
+                    buf.put_f32(float.0);
+                }
                 Ok(())
             }
             ScyllaPyCQLDTO::Decimal(decimal) => decimal.serialize(buf),
@@ -158,6 +402,26 @@ impl Value for ScyllaPyCQLDTO {
     }
 }
 
+impl ScyllaPyCQLDTO {
+    /// Approximate size of the serialized value in bytes.
+    #[must_use]
+    pub fn approximate_size(&self) -> usize {
+        match self {
+            ScyllaPyCQLDTO::String(string) => string.len(),
+            ScyllaPyCQLDTO::Bytes(bytes) => bytes.len(),
+            ScyllaPyCQLDTO::FloatVector(floats) => floats.len() * 4,
+            ScyllaPyCQLDTO::List(items) | ScyllaPyCQLDTO::Udt(items) => {
+                items.iter().map(Self::approximate_size).sum()
+            }
+            ScyllaPyCQLDTO::Map(items) => items
+                .iter()
+                .map(|(key, value)| key.approximate_size() + value.approximate_size())
+                .sum(),
+            _ => 8,
+        }
+    }
+}
+
 /// Convert Python type to CQL parameter value.
 ///
 /// It converts python object to another type,
@@ -179,6 +443,10 @@ pub fn py_to_value(
         Ok(ScyllaPyCQLDTO::String(item.extract::<String>()?))
     } else if item.is_instance_of::<ScyllaPyUnset>() {
         Ok(ScyllaPyCQLDTO::Unset)
+    } else if let Ok(lazy) = item.downcast::<PyCell<ScyllaPyLazyValue>>() {
+        // Values from results can be passed back as they are.
+        let mut lazy = lazy.try_borrow_mut().map_err(PyErr::from)?;
+        py_to_value(lazy.load(item.py())?, column_type)
     } else if item.is_instance_of::<PyBool>() {
         Ok(ScyllaPyCQLDTO::Bool(item.extract::<bool>()?))
     } else if item.is_instance_of::<PyInt>() {
@@ -225,29 +493,12 @@ pub fn py_to_value(
                 "Cannot get UDT values. __dump_udt__ has returned not a list value. {err}"
             ))
         })?;
-        let mut buf = Vec::new();
-        // Here we put the size of UDT value.
-        // Now it's zero, but we will replace it after serialization.
-        buf.put_i32(0);
-        for val in dumped_py {
-            // Here we serialize all fields.
-            py_to_value(val, None)?
-                .serialize(buf.as_mut())
-                .map_err(|err| {
-                    ScyllaPyError::BindingError(format!(
-                        "Cannot serialize UDT field because of {err}"
-                    ))
-                })?;
-        }
-        // Then we calculate the size of the UDT value, cast it to i32
-        // and put it in the beginning of the buffer.
-        let buf_len: i32 = buf.len().try_into().map_err(|_| {
-            ScyllaPyError::BindingError("Cannot serialize. UDT value is too big.".into())
-        })?;
-        // Here we also subtract 4 bytes, because we don't want to count
-        // size buffer itself.
-        buf[0..4].copy_from_slice(&(buf_len - 4).to_be_bytes()[..]);
-        Ok(ScyllaPyCQLDTO::Udt(buf))
+        // Fields are serialized later, without holding the GIL.
+        let fields = dumped_py
+            .iter()
+            .map(|val| py_to_value(val, None))
+            .collect::<ScyllaPyResult<Vec<_>>>()?;
+        Ok(ScyllaPyCQLDTO::Udt(fields))
     } else if item.get_type().name()? == "UUID" {
         Ok(ScyllaPyCQLDTO::Uuid(uuid::Uuid::parse_str(
             item.str()?.extract::<&str>()?,
@@ -436,26 +687,15 @@ pub fn cql_to_py<'a>(
                 "TinyInt",
             ))
             .map(|val| val.to_object(py).into_ref(py)),
-        ColumnType::Uuid => {
-            let uuid_str = unwrapped_value
-                .as_uuid()
-                .ok_or(ScyllaPyError::ValueDowncastError(col_name.into(), "Uuid"))?
-                .simple()
-                .to_string();
-            Ok(py.import("uuid")?.getattr("UUID")?.call1((uuid_str,))?)
-        }
-        ColumnType::Timeuuid => {
-            let uuid_str = unwrapped_value
-                .as_timeuuid()
-                .ok_or(ScyllaPyError::ValueDowncastError(
-                    col_name.into(),
-                    "Timeuuid",
-                ))?
-                .as_ref()
-                .as_simple()
-                .to_string();
-            Ok(py.import("uuid")?.getattr("UUID")?.call1((uuid_str,))?)
-        }
+        ColumnType::Uuid
+        | ColumnType::Timeuuid
+        | ColumnType::Date
+        | ColumnType::Decimal
+        | ColumnType::Varint => text_to_py(
+            py,
+            cql_type,
+            cql_to_text(col_name, cql_type, unwrapped_value)?,
+        ),
         ColumnType::Duration => {
             // We loose some perscision on converting it to
             // python datetime, because in scylla,
@@ -526,18 +766,6 @@ pub fn cql_to_py<'a>(
             .ok_or(ScyllaPyError::ValueDowncastError(col_name.into(), "Inet"))?
             .to_object(py)
             .into_ref(py)),
-        ColumnType::Date => {
-            let formatted_date = unwrapped_value
-                .as_date()
-                .ok_or(ScyllaPyError::ValueDowncastError(col_name.into(), "Date"))?
-                .format(DATE_FORMAT)
-                .map_err(|_| ScyllaPyError::ValueDowncastError(col_name.into(), "Date"))?
-                .to_string();
-            Ok(py
-                .import("datetime")?
-                .getattr("date")?
-                .call_method1("fromisoformat", (formatted_date,))?)
-        }
         ColumnType::Tuple(types) => {
             if let CqlValue::Tuple(data) = unwrapped_value {
                 let mut dumped_elemets = Vec::new();
@@ -609,10 +837,65 @@ pub fn cql_to_py<'a>(
             }
             Ok(res_map)
         }
+        ColumnType::Custom(_) => Err(ScyllaPyError::ValueDowncastError(
+            col_name.into(),
+            "Unknown",
+        )),
+    }
+}
+
+/// Whether values of the type are converted to python from their text form.
+#[must_use]
+pub fn has_text_form(cql_type: &ColumnType) -> bool {
+    matches!(
+        cql_type,
+        ColumnType::Uuid
+            | ColumnType::Timeuuid
+            | ColumnType::Date
+            | ColumnType::Decimal
+            | ColumnType::Varint
+    )
+}
+
+/// Build text form of a value.
+///
+/// Uuids, dates, decimals and varints are passed
+/// to python constructors as strings. These strings
+/// don't need the GIL, so they can be built in advance.
+///
+/// # Errors
+///
+/// If value doesn't match the type, or the type has no text form.
+pub fn cql_to_text(
+    col_name: &str,
+    cql_type: &ColumnType,
+    cql_value: &CqlValue,
+) -> ScyllaPyResult<String> {
+    match cql_type {
+        ColumnType::Uuid => Ok(cql_value
+            .as_uuid()
+            .ok_or(ScyllaPyError::ValueDowncastError(col_name.into(), "Uuid"))?
+            .simple()
+            .to_string()),
+        ColumnType::Timeuuid => Ok(cql_value
+            .as_timeuuid()
+            .ok_or(ScyllaPyError::ValueDowncastError(
+                col_name.into(),
+                "Timeuuid",
+            ))?
+            .as_ref()
+            .as_simple()
+            .to_string()),
+        ColumnType::Date => Ok(cql_value
+            .as_date()
+            .ok_or(ScyllaPyError::ValueDowncastError(col_name.into(), "Date"))?
+            .format(DATE_FORMAT)
+            .map_err(|_| ScyllaPyError::ValueDowncastError(col_name.into(), "Date"))?
+            .to_string()),
         ColumnType::Decimal => {
             // Because the `as_decimal` method is not implemented for `CqlValue`,
             // will make a PR.
-            let decimal: bigdecimal_04::BigDecimal = match unwrapped_value {
+            let decimal: bigdecimal_04::BigDecimal = match cql_value {
                 CqlValue::Decimal(inner) => inner.clone().into(),
                 _ => {
                     return Err(ScyllaPyError::ValueDowncastError(
@@ -621,81 +904,279 @@ pub fn cql_to_py<'a>(
                     ))
                 }
             };
-            Ok(py
-                .import("decimal")?
-                .getattr("Decimal")?
-                .call1((decimal.to_scientific_notation(),))?)
+            Ok(decimal.to_scientific_notation())
         }
         ColumnType::Varint => {
-            let bigint: bigdecimal_04::num_bigint::BigInt = match unwrapped_value {
+            let bigint: bigdecimal_04::num_bigint::BigInt = match cql_value {
                 CqlValue::Varint(inner) => inner.clone().into(),
                 _ => return Err(ScyllaPyError::ValueDowncastError(col_name.into(), "Varint")),
             };
-            Ok(py
-                .import("builtins")?
-                .getattr("int")?
-                .call1((bigint.to_string(),))?)
+            Ok(bigint.to_string())
         }
-        ColumnType::Custom(_) => Err(ScyllaPyError::ValueDowncastError(
+        _ => Err(ScyllaPyError::ValueDowncastError(
             col_name.into(),
             "Unknown",
         )),
     }
 }
 
-/// Parse python type to `LegacySerializedValues`.
+/// Python callable, which creates objects of the type from their text form.
 ///
-/// Serialized values are used for
-/// parameter binding. We parse python types
-/// into our own types that are capable
-/// of being bound to query and add parsed
-/// results to `LegacySerializedValues`.
+/// # Errors
+///
+/// If python module cannot be imported.
+pub fn text_constructor<'a>(py: Python<'a>, cql_type: &ColumnType) -> ScyllaPyResult<&'a PyAny> {
+    Ok(match cql_type {
+        ColumnType::Date => py
+            .import("datetime")?
+            .getattr("date")?
+            .getattr("fromisoformat")?,
+        ColumnType::Decimal => py.import("decimal")?.getattr("Decimal")?,
+        ColumnType::Varint => py.import("builtins")?.getattr("int")?,
+        _ => py.import("uuid")?.getattr("UUID")?,
+    })
+}
+
+/// Create python object from the text form of a value.
+///
+/// # Errors
+///
+/// If python constructor fails.
+pub fn text_to_py<'a>(
+    py: Python<'a>,
+    cql_type: &ColumnType,
+    text: String,
+) -> ScyllaPyResult<&'a PyAny> {
+    Ok(text_constructor(py, cql_type)?.call1((text,))?)
+}
+
+/// Check named parameters against prepared metadata.
+///
+/// Returns an error listing all missing
+/// and unknown parameter names at once.
+///
+/// # Errors
+///
+/// If names don't match bind markers of the query.
+pub fn check_named_params<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    col_spec: &[ColumnSpec],
+) -> ScyllaPyResult<()> {
+    let names = names.into_iter().collect::<Vec<_>>();
+    let mut missing = Vec::new();
+    for spec in col_spec {
+        let name = spec.name.as_str();
+        if !names.contains(&name) && !missing.contains(&name) {
+            missing.push(name);
+        }
+    }
+    let unknown = names
+        .iter()
+        .filter(|name| !col_spec.iter().any(|spec| spec.name == **name))
+        .copied()
+        .collect::<Vec<_>>();
+    let mut problems = Vec::new();
+    if !missing.is_empty() {
+        problems.push(format!("missing parameters: {}", missing.join(", ")));
+    }
+    if !unknown.is_empty() {
+        problems.push(format!("unknown parameters: {}", unknown.join(", ")));
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(ScyllaPyError::BindingError(format!(
+        "Parameters don't match the query, {}",
+        problems.join("; ")
+    )))
+}
+
+/// Convert a single query parameter.
+///
+/// Errors describe the parameter, the type
+/// of passed value and the expected CQL type.
+/// The description of the parameter is built
+/// only if the value cannot be converted.
+///
+/// # Errors
+///
+/// If value cannot be converted.
+fn param_to_value(
+    param: &PyAny,
+    description: impl FnOnce() -> String,
+    column_type: Option<&ColumnType>,
+) -> ScyllaPyResult<ScyllaPyCQLDTO> {
+    py_to_value(param, column_type).map_err(|err| {
+        let description = description();
+        let type_name = param.get_type().name().unwrap_or("unknown");
+        let expected = column_type
+            .map(|typ| format!(", expected CQL type {typ:?}"))
+            .unwrap_or_default();
+        let cause = match err {
+            ScyllaPyError::BindingError(cause) => cause,
+            err => err.to_string(),
+        };
+        ScyllaPyError::BindingError(format!(
+            "Cannot bind parameter {description} of type {type_name}{expected}. {cause}"
+        ))
+    })
+}
+
+/// Parameters below this size are serialized without releasing the GIL,
+/// because releasing it costs more than the serialization.
+const SERIALIZE_WITHOUT_GIL_SIZE: usize = 16 * 1024;
+
+/// Query parameters extracted from python objects.
+///
+/// They don't reference python objects,
+/// so they can be serialized without holding the GIL.
+#[derive(Debug, Default)]
+pub enum ExtractedParams {
+    #[default]
+    Empty,
+    Positional(Vec<ScyllaPyCQLDTO>),
+    Named(Vec<(String, ScyllaPyCQLDTO)>),
+}
+
+impl ExtractedParams {
+    /// Approximate size of serialized parameters in bytes.
+    #[must_use]
+    pub fn approximate_size(&self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Positional(values) => values.iter().map(ScyllaPyCQLDTO::approximate_size).sum(),
+            Self::Named(values) => values
+                .iter()
+                .map(|(_, value)| value.approximate_size())
+                .sum(),
+        }
+    }
+
+    /// Expected size of the buffer with serialized parameters.
+    ///
+    /// Every value is prefixed with its length
+    /// and named values are also prefixed with names.
+    /// Lengths of items of collections aren't counted,
+    /// so it's a lower bound for them.
+    #[must_use]
+    pub fn capacity_hint(&self) -> usize {
+        match self {
+            Self::Empty => 0,
+            Self::Positional(values) => values
+                .iter()
+                .map(|value| 4 + value.approximate_size())
+                .sum(),
+            Self::Named(values) => values
+                .iter()
+                .map(|(name, value)| 2 + name.len() + 4 + value.approximate_size())
+                .sum(),
+        }
+    }
+
+    /// Serialize parameters for binding.
+    ///
+    /// Buffer is preallocated with the expected size
+    /// of all parameters, so usually it doesn't grow
+    /// while values are added. Buffers aren't pooled,
+    /// each call allocates a new one.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if any value is too big.
+    pub fn serialize(&self) -> ScyllaPyResult<LegacySerializedValues> {
+        let mut values = LegacySerializedValues::with_capacity(self.capacity_hint());
+        match self {
+            Self::Empty => {}
+            Self::Positional(positional) => {
+                for value in positional {
+                    values.add_value(value)?;
+                }
+            }
+            Self::Named(named) => {
+                for (name, value) in named {
+                    values.add_named_value(name, value)?;
+                }
+            }
+        }
+        Ok(values)
+    }
+}
+
+/// Extract positional query parameters from python objects.
+///
+/// # Errors
+///
+/// May result in error if any of parameters cannot be parsed.
+pub fn extract_positional_params(
+    params: &[&PyAny],
+    col_spec: Option<&[ColumnSpec]>,
+) -> ScyllaPyResult<ExtractedParams> {
+    let mut values = Vec::with_capacity(params.len());
+    for (index, param) in params.iter().enumerate() {
+        let spec = col_spec.and_then(|specs| specs.get(index));
+        let description = || match spec {
+            Some(spec) => format!("{index} (`{}`)", spec.name),
+            None => index.to_string(),
+        };
+        values.push(param_to_value(
+            param,
+            description,
+            spec.map(|spec| &spec.typ),
+        )?);
+    }
+    Ok(ExtractedParams::Positional(values))
+}
+
+/// Extract query parameters from python objects.
+///
+/// All python objects are converted here,
+/// so parameters can be serialized later without the GIL.
 ///
 /// # Errors
 ///
 /// May result in error if any of parameters cannot
-/// be parsed.
-pub fn parse_python_query_params(
+/// be parsed, or if dict keys don't match
+/// parameters of prepared query.
+pub fn extract_python_query_params(
     params: Option<&PyAny>,
     allow_dicts: bool,
     col_spec: Option<&[ColumnSpec]>,
-) -> ScyllaPyResult<LegacySerializedValues> {
-    let mut values = LegacySerializedValues::new();
-
+) -> ScyllaPyResult<ExtractedParams> {
     let Some(params) = params else {
-        return Ok(values);
+        return Ok(ExtractedParams::Empty);
     };
 
     // If list was passed, we construct only unnamed parameters.
     // Otherwise it parses dict to named parameters.
     if params.is_instance_of::<PyList>() || params.is_instance_of::<PyTuple>() {
-        let params = params.extract::<Vec<&PyAny>>()?;
-        for (index, param) in params.iter().enumerate() {
-            let coltype = col_spec.and_then(|specs| specs.get(index)).map(|f| &f.typ);
-            let py_dto = py_to_value(param, coltype)?;
-            values.add_value(&py_dto)?;
-        }
-        return Ok(values);
+        return extract_positional_params(&params.extract::<Vec<&PyAny>>()?, col_spec);
     } else if params.is_instance_of::<PyDict>() {
         if allow_dicts {
             let types_map = col_spec
                 .map(|specs| {
                     specs
                         .iter()
-                        .map(|spec| (spec.name.as_str(), spec.typ.clone()))
+                        .map(|spec| (spec.name.as_str(), &spec.typ))
                         .collect::<HashMap<_, _, BuildHasherDefault<rustc_hash::FxHasher>>>()
                 })
                 .unwrap_or_default();
-            // let map = HashMap::with_capacity_and_hasher(, hasher)
             let dict = params
                 .extract::<HashMap<&str, &PyAny, BuildHasherDefault<rustc_hash::FxHasher>>>()?;
+            if let Some(specs) = col_spec {
+                let names = dict
+                    .keys()
+                    .map(|name| name.to_lowercase())
+                    .collect::<Vec<_>>();
+                check_named_params(names.iter().map(String::as_str), specs)?;
+            }
+            let mut values = Vec::with_capacity(dict.len());
             for (name, value) in dict {
-                values.add_named_value(
-                    name.to_lowercase().as_str(),
-                    &py_to_value(value, types_map.get(name))?,
-                )?;
+                let lowercase = name.to_lowercase();
+                let typ = types_map.get(lowercase.as_str()).copied();
+                let value = param_to_value(value, || format!("`{name}`"), typ)?;
+                values.push((lowercase, value));
             }
-            return Ok(values);
+            return Ok(ExtractedParams::Named(values));
         }
         return Err(ScyllaPyError::BindingError(
             "Dicts are not allowed here.".into(),
@@ -707,6 +1188,70 @@ pub fn parse_python_query_params(
     )))
 }
 
+/// Parse python type to `LegacySerializedValues`.
+///
+/// Serialized values are used for
+/// parameter binding. We parse python types
+/// into our own types that are capable
+/// of being bound to query and add parsed
+/// results to `LegacySerializedValues`.
+///
+/// Big parameters are serialized with released GIL,
+/// so other threads can run python code meanwhile.
+///
+/// # Errors
+///
+/// May result in error if any of parameters cannot
+/// be parsed, or if dict keys don't match
+/// parameters of prepared query.
+pub fn parse_python_query_params(
+    params: Option<&PyAny>,
+    allow_dicts: bool,
+    col_spec: Option<&[ColumnSpec]>,
+) -> ScyllaPyResult<LegacySerializedValues> {
+    let extracted = extract_python_query_params(params, allow_dicts, col_spec)?;
+    match params {
+        Some(params) => serialize_params(params.py(), &extracted),
+        None => extracted.serialize(),
+    }
+}
+
+/// Serialize extracted parameters.
+///
+/// Big parameters are serialized with released GIL.
+///
+/// # Errors
+///
+/// May return an error if any value is too big.
+pub fn serialize_params(
+    py: Python<'_>,
+    extracted: &ExtractedParams,
+) -> ScyllaPyResult<LegacySerializedValues> {
+    if extracted.approximate_size() >= SERIALIZE_WITHOUT_GIL_SIZE {
+        py.allow_threads(|| extracted.serialize())
+    } else {
+        extracted.serialize()
+    }
+}
+
+/// Copy of the query result.
+///
+/// Results of the driver aren't cloneable,
+/// but all their fields are.
+#[must_use]
+#[allow(clippy::field_reassign_with_default)]
+pub fn clone_query_result(result: &QueryResult) -> QueryResult {
+    // Result is non-exhaustive, so it cannot be built with a literal.
+    let mut cloned = QueryResult::default();
+    cloned.rows = result.rows.clone();
+    cloned.warnings = result.warnings.clone();
+    cloned.tracing_id = result.tracing_id;
+    cloned.paging_state = result.paging_state.clone();
+    cloned.col_specs = result.col_specs.clone();
+    cloned.serialized_size = result.serialized_size;
+    cloned
+}
+
 /// Map rows, using some python callable.
 ///
 /// This function casts every row to dictionary
@@ -0,0 +1,414 @@
+use std::{
+    future::Future,
+    num::NonZeroUsize,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use pyo3::{pyclass, pymethods, Py, PyAny, Python};
+use scylla::{
+    batch::BatchStatement, execution_profile::ExecutionProfileHandle,
+    frame::value::LegacySerializedValues, statement::Consistency, Session,
+};
+use tokio::{
+    sync::{mpsc, oneshot, Mutex},
+    task::{JoinError, JoinHandle, JoinSet},
+    time::MissedTickBehavior,
+};
+
+use crate::{
+    batches::parse_batch_values,
+    exceptions::rust_err::{QueryErrorContext, ScyllaPyError, ScyllaPyResult},
+    inputs::BatchQueryInput,
+    instrumentation::{NodeStatistics, RequestObservers, StatementKind},
+    prepared_queries::execute_prepared,
+    shadow::{MirrorSettings, ShadowRequest, ShadowTarget},
+    utils::{scyllapy_future, AttemptsListener},
+};
+
+/// Command of a background writer.
+pub enum WriterCommand<T> {
+    Add(T),
+    Flush(oneshot::Sender<ScyllaPyResult<()>>),
+}
+
+/// Queue and task of a background writer.
+///
+/// Writers differ only in the way they send queued items,
+/// so adding items, flushing and closing are shared.
+pub struct WriterHandle<T> {
+    name: &'static str,
+    sender: Arc<Mutex<Option<mpsc::Sender<WriterCommand<T>>>>>,
+    task: Arc<Mutex<Option<JoinHandle<ScyllaPyResult<()>>>>>,
+}
+
+fn writer_closed(name: &str) -> ScyllaPyError {
+    ScyllaPyError::SessionError(format!("{name} is closed"))
+}
+
+impl<T: Send + 'static> WriterHandle<T> {
+    /// Start the main loop of the writer.
+    ///
+    /// The loop receives queued commands and stops,
+    /// when the writer is closed.
+    pub fn spawn<F>(
+        name: &'static str,
+        buffer_size: NonZeroUsize,
+        run: impl FnOnce(mpsc::Receiver<WriterCommand<T>>) -> F,
+    ) -> Self
+    where
+        F: Future<Output = ScyllaPyResult<()>> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(buffer_size.get());
+        let task = pyo3_asyncio::tokio::get_runtime().spawn(run(receiver));
+        Self {
+            name,
+            sender: Arc::new(Mutex::new(Some(sender))),
+            task: Arc::new(Mutex::new(Some(task))),
+        }
+    }
+
+    /// Queue the item.
+    ///
+    /// If the queue is full, it waits until previous items are sent.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if writer is closed.
+    pub fn add<'a>(&self, py: Python<'a>, item: T) -> ScyllaPyResult<&'a PyAny> {
+        let (name, sender) = (self.name, self.sender.clone());
+        scyllapy_future(py, async move {
+            let sender = sender
+                .lock()
+                .await
+                .clone()
+                .ok_or_else(|| writer_closed(name))?;
+            sender
+                .send(WriterCommand::Add(item))
+                .await
+                .map_err(|_| writer_closed(name))
+        })
+    }
+
+    /// Wait until the main loop sends queued items.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writer is closed,
+    /// or if any item sent in background has failed.
+    pub fn flush<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let (name, sender) = (self.name, self.sender.clone());
+        scyllapy_future(py, async move {
+            let sender = sender
+                .lock()
+                .await
+                .clone()
+                .ok_or_else(|| writer_closed(name))?;
+            let (reply, response) = oneshot::channel();
+            sender
+                .send(WriterCommand::Flush(reply))
+                .await
+                .map_err(|_| writer_closed(name))?;
+            response.await.map_err(|_| writer_closed(name))?
+        })
+    }
+
+    /// Stop receiving items and wait for the main loop to finish.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any item sent in background has failed.
+    pub fn close<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        let sender = self.sender.clone();
+        let task = self.task.clone();
+        scyllapy_future(py, async move {
+            sender.lock().await.take();
+            let Some(handle) = task.lock().await.take() else {
+                return Ok(());
+            };
+            handle
+                .await
+                .map_err(|err| ScyllaPyError::SessionError(err.to_string()))?
+        })
+    }
+}
+
+/// Settings of the background writer.
+#[derive(Clone, Copy, Debug)]
+pub struct WriterConfig {
+    pub writes_per_second: Option<f64>,
+    pub concurrency: NonZeroUsize,
+    pub buffer_size: NonZeroUsize,
+}
+
+/// Session of the writer and its instrumentation.
+///
+/// Writes are reported to the same histograms, statistics,
+/// query logs and listeners as other requests of the session,
+/// and are mirrored to its shadow cluster, if it's attached.
+#[derive(Clone)]
+pub struct WriterContext {
+    pub session: Arc<tokio::sync::RwLock<Option<Session>>>,
+    pub observers: RequestObservers,
+    pub node_stats: Arc<NodeStatistics>,
+    pub shadow: Arc<RwLock<Option<ShadowTarget>>>,
+    pub profile: Option<ExecutionProfileHandle>,
+    pub consistency: Consistency,
+}
+
+/// Statement queued in the writer.
+pub struct Write {
+    statement: BatchStatement,
+    values: LegacySerializedValues,
+    kind: StatementKind,
+    consistency: Consistency,
+    mirror: Option<ShadowRequest>,
+}
+
+/// Writer that sends statements in background.
+///
+/// Statements are queued and sent one by one,
+/// with at most `concurrency` statements in flight
+/// and at most `writes_per_second` statements sent per second.
+///
+/// If the queue is full, adding new statements
+/// waits until previous statements are sent.
+#[pyclass(name = "Writer")]
+pub struct ScyllaPyWriter {
+    handle: WriterHandle<Write>,
+    context: WriterContext,
+}
+
+impl ScyllaPyWriter {
+    /// Start new writer.
+    ///
+    /// Statements without execution profile
+    /// are executed with the profile of the context.
+    #[must_use]
+    pub fn start(context: WriterContext, config: WriterConfig) -> Self {
+        let handle = WriterHandle::spawn("Writer", config.buffer_size, |receiver| {
+            run_writer(context.clone(), config, receiver)
+        });
+        Self { handle, context }
+    }
+}
+
+/// Execute a single statement.
+///
+/// Shadow request is sent only if the statement succeeds.
+async fn write(context: WriterContext, write: Write) -> ScyllaPyResult<()> {
+    let session_guard = context.session.read().await;
+    let session = session_guard.as_ref().ok_or(ScyllaPyError::SessionError(
+        "Session is not initialized.".into(),
+    ))?;
+    let Write {
+        mut statement,
+        values,
+        kind,
+        consistency,
+        mirror,
+    } = write;
+    let attempts = Arc::new(AttemptsListener::new(context.node_stats.clone()));
+    let text = match &mut statement {
+        BatchStatement::Query(query) => {
+            query.set_history_listener(attempts.clone());
+            query.contents.as_str()
+        }
+        BatchStatement::PreparedStatement(prepared) => {
+            prepared.set_history_listener(attempts.clone());
+            prepared.get_statement()
+        }
+    };
+    let request = context.observers.start(kind, text, consistency, attempts);
+    let started = Instant::now();
+    let res = match statement {
+        BatchStatement::Query(query) => session.query(query, values).await,
+        BatchStatement::PreparedStatement(prepared) => {
+            execute_prepared(session, &prepared, values, None).await
+        }
+    }
+    .map(|_| ())
+    .map_err(ScyllaPyError::from);
+    if let (Some(mirror), Ok(())) = (mirror, &res) {
+        mirror.send();
+    }
+    context.observers.finish(
+        request,
+        started.elapsed(),
+        QueryErrorContext::default(),
+        res,
+    )?;
+    Ok(())
+}
+
+/// Keep the first error of finished writes.
+fn record_error(
+    error: &mut Option<ScyllaPyError>,
+    finished: Option<Result<ScyllaPyResult<()>, JoinError>>,
+) {
+    match finished {
+        Some(Ok(Err(err))) => {
+            error.get_or_insert(err);
+        }
+        Some(Err(err)) => {
+            error.get_or_insert(ScyllaPyError::SessionError(err.to_string()));
+        }
+        Some(Ok(Ok(()))) | None => {}
+    }
+}
+
+/// Main loop of the writer.
+///
+/// Errors of statements sent in background are kept
+/// and returned on the next flush or when writer stops.
+async fn run_writer(
+    context: WriterContext,
+    config: WriterConfig,
+    mut receiver: mpsc::Receiver<WriterCommand<Write>>,
+) -> ScyllaPyResult<()> {
+    let mut rate = config.writes_per_second.map(|writes_per_second| {
+        let mut interval = tokio::time::interval(Duration::from_secs_f64(1.0 / writes_per_second));
+        // Idle time shouldn't allow bursts of writes.
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    });
+    let mut in_flight = JoinSet::new();
+    let mut error = None;
+    while let Some(command) = receiver.recv().await {
+        match command {
+            WriterCommand::Add(statement) => {
+                if let Some(rate) = rate.as_mut() {
+                    rate.tick().await;
+                }
+                while in_flight.len() >= config.concurrency.get() {
+                    record_error(&mut error, in_flight.join_next().await);
+                }
+                in_flight.spawn(write(context.clone(), statement));
+            }
+            WriterCommand::Flush(reply) => {
+                while !in_flight.is_empty() {
+                    record_error(&mut error, in_flight.join_next().await);
+                }
+                reply.send(error.take().map_or(Ok(()), Err)).ok();
+            }
+        }
+    }
+    // All senders are dropped, so writer is closed.
+    while !in_flight.is_empty() {
+        record_error(&mut error, in_flight.join_next().await);
+    }
+    error.map_or(Ok(()), Err)
+}
+
+#[pymethods]
+impl ScyllaPyWriter {
+    /// Add statement to the queue.
+    ///
+    /// Values are parsed the same way
+    /// as in `InlineBatch.add_query`.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if values are incorrect,
+    /// or if writer is closed.
+    #[pyo3(signature = (query, values = None))]
+    pub fn add<'a>(
+        &self,
+        py: Python<'a>,
+        query: BatchQueryInput,
+        values: Option<&'a PyAny>,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        let values = parse_batch_values(py, &query, values)?;
+        let mut statement = BatchStatement::from(query);
+        let shadow = self
+            .context
+            .shadow
+            .read()
+            .ok()
+            .and_then(|shadow| shadow.clone());
+        let (kind, consistency, mirror) = match &mut statement {
+            BatchStatement::Query(query) => {
+                if query.get_execution_profile_handle().is_none() {
+                    query.set_execution_profile_handle(self.context.profile.clone());
+                }
+                let kind = StatementKind::of(&query.contents);
+                let consistency = query.get_consistency().unwrap_or(self.context.consistency);
+                let mirror = shadow.and_then(|shadow| {
+                    query.set_timestamp(ShadowTarget::timestamp(kind, query.get_timestamp()));
+                    let settings = MirrorSettings::of_query(query, consistency);
+                    shadow.request(kind, &query.contents, &values, &settings)
+                });
+                (kind, consistency, mirror)
+            }
+            BatchStatement::PreparedStatement(prepared) => {
+                if prepared.get_execution_profile_handle().is_none() {
+                    prepared.set_execution_profile_handle(self.context.profile.clone());
+                }
+                let kind = StatementKind::of(prepared.get_statement());
+                let consistency = prepared
+                    .get_consistency()
+                    .unwrap_or(self.context.consistency);
+                let mirror = shadow.and_then(|shadow| {
+                    prepared.set_timestamp(ShadowTarget::timestamp(kind, prepared.get_timestamp()));
+                    let settings = MirrorSettings::of_prepared(prepared, consistency);
+                    shadow.request(kind, prepared.get_statement(), &values, &settings)
+                });
+                (kind, consistency, mirror)
+            }
+        };
+        self.handle.add(
+            py,
+            Write {
+                statement,
+                values,
+                kind,
+                consistency,
+                mirror,
+            },
+        )
+    }
+
+    /// Wait until all queued statements are sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any statement
+    /// sent in background has failed.
+    pub fn flush<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        self.handle.flush(py)
+    }
+
+    /// Send all queued statements and stop the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any statement
+    /// sent in background has failed.
+    pub fn close<'a>(&self, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        self.handle.close(py)
+    }
+
+    /// Start using writer as a context manager.
+    ///
+    /// # Errors
+    ///
+    /// May return an error if future cannot be created.
+    pub fn __aenter__<'a>(slf: Py<Self>, py: Python<'a>) -> ScyllaPyResult<&'a PyAny> {
+        scyllapy_future(py, async move { Ok(slf) })
+    }
+
+    /// Close writer when leaving context manager.
+    ///
+    /// # Errors
+    ///
+    /// Proxies errors from `close`.
+    pub fn __aexit__<'a>(
+        &self,
+        py: Python<'a>,
+        _exc_type: &PyAny,
+        _exc: &PyAny,
+        _traceback: &PyAny,
+    ) -> ScyllaPyResult<&'a PyAny> {
+        self.close(py)
+    }
+}